@@ -0,0 +1,49 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Translation3};
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::detection::{AabbGrowthMonitor, AabbGrowthEvent};
+
+#[test]
+fn runaway_bodies_are_reported() {
+    let mut world = World::new();
+    let reported  = Rc::new(RefCell::new(Vec::new()));
+    let sink      = reported.clone();
+
+    let monitor = AabbGrowthMonitor::new(4.0, move |event: &AabbGrowthEvent<f32>| {
+        sink.borrow_mut().push(event.ratio);
+        event.body.borrow_mut().set_lin_vel(na::zero());
+    });
+    let _ = world.set_aabb_growth_monitor(Some(monitor));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.enable_speculative_contacts();
+    let calm = world.add_rigid_body(rb);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Translation3::new(10.0, 0.0, 0.0));
+    rb.enable_speculative_contacts();
+    let runaway = world.add_rigid_body(rb);
+
+    calm.borrow_mut().set_lin_vel(Vector3::new(1.0, 0.0, 0.0));
+    runaway.borrow_mut().set_lin_vel(Vector3::new(1000.0, 0.0, 0.0));
+
+    world.step(0.016);
+    world.step(0.016);
+
+    // Only the runaway body is reported, and it was stopped by the handler.
+    assert_eq!(reported.borrow().len(), 1);
+    assert!(reported.borrow()[0] > 4.0);
+    assert_eq!(runaway.borrow().lin_vel(), na::zero());
+
+    // Non-finite positions are reported too.
+    runaway.borrow_mut().set_lin_vel(Vector3::new(std::f32::NAN, 0.0, 0.0));
+    world.step(0.016);
+    assert_eq!(reported.borrow().len(), 2);
+}
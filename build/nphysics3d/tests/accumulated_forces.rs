@@ -0,0 +1,50 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3};
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+fn add_ball(world: &mut World<f32>) -> RigidBodyHandle<f32> {
+    world.add_rigid_body(RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6))
+}
+
+#[test]
+fn accumulated_forces_apply_during_one_step() {
+    let mut world = World::new();
+    let rb   = add_ball(&mut world);
+    let mass = rb.borrow().mass().unwrap();
+
+    rb.borrow_mut().accumulate_force(Vector3::x() * mass);
+    rb.borrow_mut().accumulate_force(Vector3::x() * mass);
+    world.step(0.5);
+
+    assert!((rb.borrow().lin_vel() - Vector3::x()).norm() < 1.0e-5);
+    assert_eq!(rb.borrow().accumulated_force(), na::zero());
+    assert_eq!(rb.borrow().lin_acc(), na::zero());
+
+    world.step(0.5);
+    assert!((rb.borrow().lin_vel() - Vector3::x()).norm() < 1.0e-5);
+}
+
+#[test]
+fn accumulated_force_at_point_applies_a_torque() {
+    let mut world = World::new();
+    let rb = add_ball(&mut world);
+
+    // Let the ball fall asleep, the accumulated forces wake it up.
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+    assert!(!rb.borrow().is_active());
+
+    rb.borrow_mut().accumulate_force_at_point(Vector3::z(), &Point3::new(1.0, 0.0, 0.0));
+    assert_eq!(rb.borrow().accumulated_torque(), -Vector3::y());
+    world.step(0.016);
+
+    assert!(rb.borrow().lin_vel().z > 0.0);
+    assert!(rb.borrow().ang_vel().y < 0.0);
+    assert_eq!(rb.borrow().accumulated_torque(), na::zero());
+}
@@ -0,0 +1,78 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+fn resting_ball_world() -> (World<f32>, RigidBodyHandle<f32>) {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    ball.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    (world, ball)
+}
+
+// The number of steps before `ball` falls asleep.
+fn steps_to_sleep(world: &mut World<f32>, ball: &RigidBodyHandle<f32>) -> usize {
+    for i in 0 .. 1000 {
+        world.step(1.0 / 60.0);
+
+        if !ball.borrow().is_active() {
+            return i;
+        }
+    }
+
+    panic!("The ball did not fall asleep.")
+}
+
+#[test]
+fn deactivation_delay() {
+    let (mut world, ball) = resting_ball_world();
+    let immediate         = steps_to_sleep(&mut world, &ball);
+
+    let (mut world, ball) = resting_ball_world();
+    world.activation_manager().set_deactivation_delay(50);
+    assert_eq!(world.activation_manager().deactivation_delay(), 50);
+
+    assert_eq!(steps_to_sleep(&mut world, &ball), immediate + 50);
+
+    world.wake_all();
+    assert!(ball.borrow().is_active());
+}
+
+#[test]
+fn energy_threshold_override() {
+    let mut world = World::new();
+
+    // A ball drifting in the void.
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    ball.set_lin_vel(Vector3::new(1.0, 0.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    for _ in 0 .. 500 {
+        world.step(1.0 / 60.0);
+    }
+
+    assert!(ball.borrow().is_active());
+
+    world.activation_manager().set_energy_threshold(Some(100.0));
+    let _ = steps_to_sleep(&mut world, &ball);
+
+    world.activation_manager().set_energy_threshold(None);
+    world.wake_all();
+    ball.borrow_mut().set_lin_vel(Vector3::new(1.0, 0.0, 0.0));
+
+    for _ in 0 .. 500 {
+        world.step(1.0 / 60.0);
+    }
+
+    assert!(ball.borrow().is_active());
+}
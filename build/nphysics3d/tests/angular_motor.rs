@@ -0,0 +1,66 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, AngularMotor, BallInSocket};
+
+// A platform held at its center by a ball-in-socket.
+fn platform(world: &mut World<f32>) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(2.0f32, 0.1, 2.0)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 1.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let _ = world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Point3::new(0.0, 1.0, 0.0)),
+                                                       Anchor::new(Some(rb.clone()), Point3::origin())));
+
+    rb
+}
+
+#[test]
+fn angular_motor_spins_a_platform() {
+    let mut world = World::new();
+    let rb = platform(&mut world);
+
+    let motor = world.add_angular_motor(AngularMotor::new(Anchor::new(None, na::one()),
+                                                          Anchor::new(Some(rb.clone()), na::one()),
+                                                          Vector3::y(), 3.0, 10000.0));
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+    }
+
+    let w = rb.borrow().ang_vel();
+    assert!((w.y - 3.0).abs() < 1.0e-3, "Unexpected velocity: {}", w);
+    assert!(w.x.abs() < 1.0e-3 && w.z.abs() < 1.0e-3);
+    assert_eq!(world.summary().num_angular_motors, 1);
+
+    // The motor does not prevent the other rotations.
+    rb.borrow_mut().set_ang_vel(Vector3::new(1.0, 3.0, 0.0));
+    motor.borrow_mut().set_target_velocity(-1.0);
+    world.step(0.016);
+
+    let w = rb.borrow().ang_vel();
+    assert!((w.x - 1.0).abs() < 1.0e-2, "Unexpected velocity: {}", w);
+    assert!((w.y + 1.0).abs() < 1.0e-3, "Unexpected velocity: {}", w);
+}
+
+#[test]
+fn angular_motor_torque_is_limited() {
+    let mut world = World::new();
+    let rb = platform(&mut world);
+    let _  = world.add_angular_motor(AngularMotor::new(Anchor::new(None, na::one()),
+                                                       Anchor::new(Some(rb.clone()), na::one()),
+                                                       Vector3::y(), 100.0, 1.0));
+
+    world.step(0.1);
+
+    // The angular velocity increases by the torque impulse divided by the inertia.
+    let mass    = 4.0 * 0.2 * 4.0;
+    let inertia = mass * (4.0 * 4.0 + 4.0 * 4.0) / 12.0;
+    let w       = rb.borrow().ang_vel().y;
+    assert!((w - 0.1 / inertia).abs() < 1.0e-3, "Unexpected velocity: {}", w);
+}
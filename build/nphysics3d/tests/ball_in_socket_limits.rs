@@ -0,0 +1,93 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Joint, BallInSocket, BallInSocketLimits};
+
+// A horizontal rod along `x` attached by its end to (0, 5, 0).
+fn limb(world: &mut World<f32>, limits: BallInSocketLimits<f32>)
+        -> (RigidBodyHandle<f32>, Rc<RefCell<BallInSocket<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 0.1, 0.1)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(1.0, 5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let mut joint = BallInSocket::new(Anchor::new(None, Point3::new(0.0, 5.0, 0.0)),
+                                      Anchor::new(Some(rb.clone()), Point3::new(-1.0, 0.0, 0.0)));
+    joint.set_angular_limits(Some(limits));
+
+    (rb, world.add_ball_in_socket(joint))
+}
+
+#[test]
+fn swing_stops_at_the_cone() {
+    let mut world = World::new();
+    let limits    = BallInSocketLimits::new(na::one(), na::one(), 0.5, -3.0, 3.0);
+    let (rb, joint) = limb(&mut world, limits);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+
+        let (swing, _) = joint.borrow().swing_twist().unwrap();
+        assert!(swing < 0.6, "Swing limit exceeded: {}", swing);
+    }
+
+    // The rod hangs on the border of the cone, without leaving its anchor.
+    let (swing, _) = joint.borrow().swing_twist().unwrap();
+    assert!((swing - 0.5).abs() < 0.05, "Unexpected swing: {}", swing);
+
+    let joint = joint.borrow();
+    assert!(rb.borrow().position().translation.vector.y < 5.0);
+    assert!((joint.anchor1_pos() - joint.anchor2_pos()).norm() < 0.05);
+}
+
+#[test]
+fn twist_stops_at_its_limits() {
+    let mut world = World::new();
+    let limits    = BallInSocketLimits::new(na::one(), na::one(), 3.0, -0.3, 0.3);
+    let (rb, joint) = limb(&mut world, limits);
+
+    // Spin the rod around its own axis.
+    world.set_gravity(na::zero());
+    rb.borrow_mut().set_ang_vel(Vector3::new(5.0, 0.0, 0.0));
+
+    let mut max_twist = 0.0f32;
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+
+        let (_, twist) = joint.borrow().swing_twist().unwrap();
+        assert!(twist.abs() < 0.4, "Twist limit exceeded: {}", twist);
+        max_twist = max_twist.max(twist);
+    }
+
+    assert!(max_twist > 0.25);
+}
+
+#[test]
+fn joint_without_limits() {
+    let mut world = World::<f32>::new();
+    let limits    = BallInSocketLimits::new(na::one(), na::one(), 0.5, -0.3, 0.3);
+    let (_, joint) = limb(&mut world, limits);
+
+    joint.borrow_mut().set_angular_limits(None);
+    assert!(joint.borrow().swing_twist().is_none());
+
+    let rb = joint.borrow().anchor2().body.clone().unwrap();
+    let mut min_y = 5.0f32;
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+        min_y = min_y.min(rb.borrow().position().translation.vector.y);
+    }
+
+    // The rod swings down to the vertical.
+    assert!(min_y < 4.1, "Unexpected lowest position: {}", min_y);
+}
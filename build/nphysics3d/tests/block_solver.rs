@@ -0,0 +1,98 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::f32;
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::resolution::{self, VelocityConstraint, Velocities, FrictionModel};
+
+// A contact at `point` relative to the center of mass of a body of unit mass and inertia,
+// with a normal along `y` and the opposite of the normal velocity `objective`.
+fn contact(point: Vector3<f32>, objective: f32) -> VelocityConstraint<f32> {
+    let mut c = VelocityConstraint::new();
+
+    c.normal             = Vector3::y();
+    c.normal2            = Vector3::y();
+    c.weighted_normal2   = Vector3::y();
+    c.rot_axis2          = point.cross(&Vector3::y());
+    c.weighted_rot_axis2 = c.rot_axis2;
+    c.inv_projected_mass = 1.0 / (1.0 + na::norm_squared(&c.rot_axis2));
+    c.hibound            = f32::MAX;
+    c.objective          = objective;
+    c.id2                = 0;
+
+    c
+}
+
+fn solve(contacts: &mut [VelocityConstraint<f32>], blocks: &[(usize, usize)]) -> Velocities<f32> {
+    let mut result = [ Velocities::new() ];
+    resolution::projected_gauss_seidel_block_solve(contacts, &mut [][..], blocks, FrictionModel::Pyramid,
+                                                   &mut result[..], 1, 1, true);
+
+    result[0].clone()
+}
+
+#[test]
+fn blocks_are_solved_in_one_iteration() {
+    let mut contacts = [ contact(Vector3::new(1.0, 0.0, 0.0), 1.0), contact(Vector3::new(-0.5, 0.0, 0.0), 1.0) ];
+    let result = solve(&mut contacts[..], &[ (0, 2) ]);
+
+    for c in contacts.iter() {
+        assert!(resolution::velocity_residual(c, &[ result.clone() ]) < 1.0e-5);
+    }
+
+    // One Gauss-Seidel iteration without block leaves an error on the first contact.
+    let mut contacts = [ contact(Vector3::new(1.0, 0.0, 0.0), 1.0), contact(Vector3::new(-0.5, 0.0, 0.0), 1.0) ];
+    let result = solve(&mut contacts[..], &[]);
+
+    assert!(resolution::velocity_residual(&contacts[0], &[ result ]) > 0.1);
+}
+
+#[test]
+fn separating_contacts_of_a_block_apply_no_impulse() {
+    let mut contacts = [ contact(Vector3::new(1.0, 0.0, 0.0), 1.0), contact(Vector3::new(-0.5, 0.0, 0.0), -2.0) ];
+    let result = solve(&mut contacts[..], &[ (0, 2) ]);
+
+    assert_eq!(contacts[1].impulse, 0.0);
+    assert!((contacts[0].impulse - 0.5).abs() < 1.0e-5);
+    assert!(resolution::velocity_residual(&contacts[0], &[ result ]) < 1.0e-5);
+}
+
+// The largest contact error left by the solver on a frictionless box resting on the ground.
+fn resting_box_residual(block_solver: bool) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.constraints_solver().set_num_second_order_iter(1);
+
+    if !block_solver {
+        world.constraints_solver().disable_block_solver();
+    }
+
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(2.0f32, 0.1, 0.5)), 1.0, 0.0, 0.0);
+    rb.set_translation(Translation3::new(0.0, 0.15, 0.0));
+    let _ = world.add_rigid_body(rb);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    world.constraints_solver().max_residual()
+}
+
+#[test]
+fn resting_contacts_are_solved_in_one_iteration() {
+    let mut world = World::<f32>::new();
+
+    assert!(world.constraints_solver().block_solver_enabled());
+
+    let block      = resting_box_residual(true);
+    let sequential = resting_box_residual(false);
+
+    assert!(block < 1.0e-4, "Unexpected residual: {}", block);
+    assert!(sequential > 1.0e-2, "Unexpected residual: {}", sequential);
+}
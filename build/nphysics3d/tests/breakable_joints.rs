@@ -0,0 +1,124 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::constraint::Constraint;
+use nphysics3d::detection::joint::{Anchor, Joint, Fixed, Hinge, BrokenJoint};
+
+// A box of mass 1 hanging from a fixed joint at (0, 5, 0), 1 unit above its center.
+fn hanging_box(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Fixed<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 4.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), Isometry3::new(Vector3::new(0.0, 1.0, 0.0), na::zero()));
+    let joint   = world.add_fixed(Fixed::new(anchor1, anchor2));
+
+    (rb, joint)
+}
+
+fn record_breaks(world: &mut World<f32>) -> Rc<RefCell<Vec<BrokenJoint<f32>>>> {
+    let breaks  = Rc::new(RefCell::new(Vec::new()));
+    let handler = breaks.clone();
+
+    world.register_joint_break_handler("breaks", move |b: &BrokenJoint<f32>| handler.borrow_mut().push(b.clone()));
+
+    breaks
+}
+
+#[test]
+fn joint_holds_below_its_breaking_force() {
+    let mut world = World::new();
+    let (rb, joint) = hanging_box(&mut world);
+    let breaks = record_breaks(&mut world);
+
+    // The reaction force reaches about 25 while the joint catches the box at the first step.
+    joint.borrow_mut().set_breaking_forces(Some((30.0, 30.0)));
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    assert!(breaks.borrow().is_empty());
+    assert_eq!(world.summary().num_fixed_joints, 1);
+    assert!((rb.borrow().position().translation.vector.y - 4.0).abs() < 0.05);
+}
+
+#[test]
+fn joint_breaks_beyond_its_breaking_force() {
+    let mut world = World::new();
+    let (rb, joint) = hanging_box(&mut world);
+    let breaks = record_breaks(&mut world);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    // The joint can no longer bear the weight of the box.
+    joint.borrow_mut().set_breaking_forces(Some((5.0, 20.0)));
+    assert_eq!(joint.borrow().breaking_forces(), Some((5.0, 20.0)));
+
+    world.step(0.016);
+
+    {
+        let breaks = breaks.borrow();
+        assert_eq!(breaks.len(), 1);
+
+        match breaks[0].joint {
+            Constraint::Fixed(ref f) => assert!(&**f as *const _ == &*joint as *const _),
+            _                        => panic!("The wrong joint broke.")
+        }
+
+        // The joint held the box against gravity during the step.
+        let force = breaks[0].linear_impulse / 0.016;
+        assert!((force - Vector3::y() * 9.81).norm() < 0.1, "Unexpected reaction force: {}", force);
+    }
+
+    assert_eq!(world.summary().num_fixed_joints, 0);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    // The box fell, and the joint broke only once.
+    assert_eq!(breaks.borrow().len(), 1);
+    assert!(rb.borrow().position().translation.vector.y < 1.0);
+}
+
+#[test]
+fn joint_breaks_beyond_its_breaking_torque() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    let breaks = record_breaks(&mut world);
+
+    // A horizontal bar of mass 1 along `x`, hinged at its end (0, 5, 0) around its own axis. The
+    // joint bears the torque of its weight around `z`.
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 0.1, 0.1)), 1.0 / 0.08, 0.3, 0.6);
+    rb.set_translation(Translation3::new(1.0, 5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), Isometry3::new(Vector3::new(-1.0, 0.0, 0.0), na::zero()));
+    let hinge   = world.add_hinge(Hinge::new(anchor1, anchor2, Vector3::x()));
+
+    hinge.borrow_mut().set_breaking_forces(Some((100.0, 5.0)));
+
+    world.step(0.016);
+
+    // The torque of the weight around the anchor is 9.81.
+    let breaks = breaks.borrow();
+    assert_eq!(breaks.len(), 1);
+    assert!(breaks[0].angular_impulse.norm() > 5.0 * 0.016);
+    assert!(breaks[0].linear_impulse.norm() < 100.0 * 0.016);
+    assert_eq!(world.summary().num_hinges, 0);
+}
+
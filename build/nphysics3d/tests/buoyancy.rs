@@ -0,0 +1,91 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Cuboid;
+use ncollide::bounding_volume::AABB;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::integration::Buoyancy;
+
+fn add_box(world: &mut World<f32>, density: f32, y: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), density, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, y, 0.0));
+    rb.set_deactivation_threshold(None);
+
+    world.add_rigid_body(rb)
+}
+
+fn water(world: &mut World<f32>) -> Buoyancy<f32> {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    Buoyancy::new(world.gravity(), Point3::origin(), 1.0)
+}
+
+#[test]
+fn light_box_floats_at_its_equilibrium_depth() {
+    let mut world = World::new();
+    let mut fluid = water(&mut world);
+    fluid.set_drag(5.0, 5.0);
+    world.register_force_generator("water", fluid);
+
+    let rb = add_box(&mut world, 0.3, -2.0);
+
+    for _ in 0 .. 500 {
+        world.step(0.016);
+    }
+
+    // 30% of the box is submerged.
+    let fraction = water(&mut world).submerged_fraction(&*rb.borrow()).0;
+    assert!((fraction - 0.3).abs() < 1.0e-3, "Unexpected fraction: {}", fraction);
+    assert!(rb.borrow().lin_vel().norm() < 1.0e-2);
+}
+
+#[test]
+fn heavy_box_sinks_slowly() {
+    let mut world = World::new();
+    let fluid = water(&mut world);
+    world.register_force_generator("water", fluid);
+
+    let rb = add_box(&mut world, 2.0, -2.0);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    // The buoyancy halves the gravity, and the drag slows the box down further.
+    let v = rb.borrow().lin_vel().y;
+    assert!(v < 0.0 && v > -9.81 * 0.5 * 1.6 + 1.0, "Unexpected velocity: {}", v);
+}
+
+#[test]
+fn flow_and_bounds_of_the_fluid() {
+    let mut world = World::new();
+    let mut fluid = water(&mut world);
+    fluid.set_flow_velocity(Vector3::x());
+    // A pool between x = -10 and x = 1.
+    fluid.set_bounds(Some(AABB::new(Point3::new(-10.0, -10.0, -10.0), Point3::new(1.0, 10.0, 10.0))));
+
+    let rb   = add_box(&mut world, 0.3, 0.0);
+    let half = fluid.submerged_fraction(&*rb.borrow()).0;
+    assert!((half - 0.5).abs() < 1.0e-5, "Unexpected fraction: {}", half);
+
+    world.register_force_generator("water", fluid);
+
+    for _ in 0 .. 60 {
+        world.step(0.016);
+    }
+
+    // The flow carries the floating box along.
+    let pos = rb.borrow().position().translation.vector;
+    assert!(pos.x > 0.1 && pos.y > -0.5, "Unexpected position: {}", pos);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    // The box falls once out of the pool.
+    let pos = rb.borrow().position().translation.vector;
+    assert!(pos.x > 1.0 && pos.y < -2.0, "Unexpected position: {}", pos);
+}
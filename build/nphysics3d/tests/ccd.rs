@@ -0,0 +1,32 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn ccd_stops_thin_spinning_box() {
+    let mut world = World::new();
+
+    let wall = RigidBody::new_static(Cuboid::new(Vector3::new(0.05, 2.0, 2.0)), 0.0, 0.5);
+    world.add_rigid_body(wall);
+
+    let mut plate = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.02, 0.3, 0.3)), 1.0, 0.0, 0.5);
+    plate.append_translation(&Translation3::new(-5.0, 0.0, 0.0));
+    plate.set_lin_vel(Vector3::new(200.0, 0.0, 0.0));
+    plate.set_ang_vel(Vector3::new(0.0, 0.0, 0.5));
+    plate.enable_ccd(0.01);
+    let plate = world.add_rigid_body(plate);
+
+    for _ in 0 .. 60 {
+        world.step(1.0 / 60.0);
+    }
+
+    let plate = plate.borrow();
+    assert!(plate.position().translation.vector.x < 0.0);
+    // The impact was handed to the solver.
+    assert!(plate.lin_vel().x < 200.0);
+}
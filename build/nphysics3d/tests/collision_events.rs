@@ -0,0 +1,119 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::query::Proximity;
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle, Sensor, WorldObject};
+use nphysics3d::detection::CollisionEvent;
+
+fn is_body(object: &WorldObject<f32>, body: &RigidBodyHandle<f32>) -> bool {
+    match *object {
+        WorldObject::RigidBody(ref rb) => &**rb as *const _ == &**body as *const _,
+        WorldObject::Sensor(_)         => false
+    }
+}
+
+fn falling_ball(world: &mut World<f32>) -> RigidBodyHandle<f32> {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    ball.set_translation(Translation3::new(0.0, 1.0, 0.0));
+    world.add_rigid_body(ball)
+}
+
+#[test]
+fn queue_collects_contact_and_activation_events() {
+    let mut world = World::new();
+    let ball = falling_ball(&mut world);
+
+    world.enable_collision_event_queue();
+    assert!(world.collision_event_queue_enabled());
+
+    let mut events = Vec::new();
+
+    for _ in 0 .. 500 {
+        world.step(0.016);
+
+        // The bodies can be borrowed while processing the events.
+        for event in world.drain_collision_events() {
+            if let CollisionEvent::BodyDeactivated(ref rb) = event {
+                assert!(!rb.borrow().is_active());
+            }
+
+            events.push(event);
+        }
+    }
+
+    assert!(world.drain_collision_events().is_empty());
+
+    let started = events.iter().position(|e| match *e {
+        CollisionEvent::ContactStarted(ref o1, ref o2) => is_body(o1, &ball) || is_body(o2, &ball),
+        _                                              => false
+    });
+    let deactivated = events.iter().position(|e| match *e {
+        CollisionEvent::BodyDeactivated(ref rb) => &**rb as *const _ == &*ball as *const _,
+        _                                       => false
+    });
+
+    // The ball touches the ground, then falls asleep.
+    assert!(started.is_some() && deactivated.is_some());
+    assert!(started < deactivated);
+}
+
+#[test]
+fn queue_collects_proximity_events() {
+    let mut world = World::new();
+    let ball = falling_ball(&mut world);
+
+    let mut sensor = Sensor::new(Cuboid::new(Vector3::new(1.0, 0.1, 1.0)), None);
+    sensor.set_relative_position(na::Isometry3::new(Vector3::new(0.0, 0.3, 0.0), na::zero()));
+    let _ = world.add_sensor(sensor);
+
+    world.enable_collision_event_queue();
+
+    let mut entered = false;
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+
+        for event in world.drain_collision_events() {
+            if let CollisionEvent::ProximityChanged(ref o1, ref o2, _, Proximity::Intersecting) = event {
+                entered = entered || is_body(o1, &ball) || is_body(o2, &ball);
+            }
+        }
+    }
+
+    assert!(entered);
+}
+
+#[test]
+fn disabled_queue_collects_nothing() {
+    let mut world = World::new();
+    let _ = falling_ball(&mut world);
+
+    assert!(!world.collision_event_queue_enabled());
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    assert!(world.drain_collision_events().is_empty());
+
+    // Disabling discards the events not drained yet.
+    let mut world = World::new();
+    let _ = falling_ball(&mut world);
+
+    world.enable_collision_event_queue();
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    world.disable_collision_event_queue();
+    assert!(world.drain_collision_events().is_empty());
+}
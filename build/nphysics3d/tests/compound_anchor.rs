@@ -0,0 +1,33 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Isometry3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Compound, ShapeHandle};
+use nphysics3d::object::RigidBody;
+use nphysics3d::detection::joint::{Anchor, BallInSocket, Joint};
+
+#[test]
+fn anchor_relative_to_compound_child() {
+    let chassis = ShapeHandle::new(Cuboid::new(Vector3::new(1.0f32, 0.2, 0.5)));
+    let hitch   = ShapeHandle::new(Ball::new(0.1f32));
+    let shapes  = vec![
+        (Isometry3::identity(), chassis),
+        (Isometry3::new(Vector3::new(-1.2, 0.0, 0.0), na::zero()), hitch)
+    ];
+
+    let mut truck = RigidBody::new_dynamic(Compound::new(shapes), 1.0, 0.3, 0.6);
+    truck.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+    let truck = std::rc::Rc::new(std::cell::RefCell::new(truck));
+
+    let anchor = Anchor::new_on_child(truck.clone(), 1, Point3::new(-0.1, 0.0, 0.0));
+    assert!((anchor.local_position() - Point3::new(-1.3, 0.0, 0.0)).norm() < 1.0e-5);
+    assert!((anchor.global_position() - Point3::new(-1.3, 2.0, 0.0)).norm() < 1.0e-5);
+
+    // An out-of-bounds child falls back to the body local space.
+    let detached = Anchor::new_on_child(truck.clone(), 2, Point3::new(-0.1, 0.0, 0.0));
+    assert!((detached.global_position() - Point3::new(-0.1, 2.0, 0.0)).norm() < 1.0e-5);
+
+    let joint = BallInSocket::new(anchor, Anchor::new(None, Point3::new(-1.3, 2.0, 0.0)));
+    assert!(na::distance(&joint.anchor1_pos(), &joint.anchor2_pos()) < 1.0e-5);
+}
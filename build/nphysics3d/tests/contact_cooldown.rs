@@ -0,0 +1,63 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::Cell;
+use na::{Point3, Isometry3, Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use ncollide::narrow_phase::{ContactHandler, ContactAlgorithm};
+use nphysics3d::world::{World, WorldCollisionObject};
+use nphysics3d::object::{RigidBody, WorldObject};
+
+struct Counter {
+    started: Rc<Cell<usize>>,
+    stopped: Rc<Cell<usize>>
+}
+
+impl ContactHandler<Point3<f32>, Isometry3<f32>, WorldObject<f32>> for Counter {
+    fn handle_contact_started(&mut self,
+                              _: &WorldCollisionObject<f32>,
+                              _: &WorldCollisionObject<f32>,
+                              _: &ContactAlgorithm<Point3<f32>, Isometry3<f32>>) {
+        self.started.set(self.started.get() + 1)
+    }
+
+    fn handle_contact_stopped(&mut self, _: &WorldCollisionObject<f32>, _: &WorldCollisionObject<f32>) {
+        self.stopped.set(self.stopped.get() + 1)
+    }
+}
+
+fn counter() -> (Counter, Rc<Cell<usize>>, Rc<Cell<usize>>) {
+    let started = Rc::new(Cell::new(0));
+    let stopped = Rc::new(Cell::new(0));
+
+    (Counter { started: started.clone(), stopped: stopped.clone() }, started, stopped)
+}
+
+#[test]
+fn contact_started_events_are_rate_limited() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 1.0, 0.6));
+
+    // A bouncing ball.
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.9, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 1.0, 0.0));
+    world.add_rigid_body(rb);
+
+    let (all, all_started, all_stopped)     = counter();
+    let (limited, lim_started, lim_stopped)   = counter();
+    world.register_contact_handler("all", all);
+    world.register_contact_handler_with_cooldown("limited", limited, 100.0);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    assert!((world.time() - 4.8).abs() < 1.0e-3);
+    assert!(all_started.get() > 1);
+    assert!(all_stopped.get() > 0);
+    assert_eq!(lim_started.get(), 1);
+    assert!(lim_stopped.get() <= 1);
+}
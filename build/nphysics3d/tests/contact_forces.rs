@@ -0,0 +1,83 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// A static scale, and two boxes of mass 1 and 2 stacked on it.
+fn scale(world: &mut World<f32>) -> (RigidBodyHandle<f32>, RigidBodyHandle<f32>, RigidBodyHandle<f32>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let plate = world.add_rigid_body(RigidBody::new_static(Cuboid::new(Vector3::new(5.0f32, 0.5, 5.0)), 0.3, 0.6));
+
+    let mut rb1 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+    rb1.set_translation(Translation3::new(0.0, 1.0, 0.0));
+    let rb1 = world.add_rigid_body(rb1);
+
+    let mut rb2 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 2.0, 0.0, 0.6);
+    rb2.set_translation(Translation3::new(0.0, 2.0, 0.0));
+    let rb2 = world.add_rigid_body(rb2);
+
+    (plate, rb1, rb2)
+}
+
+#[test]
+fn scale_reads_the_weight_of_the_stack() {
+    let mut world = World::new();
+    let (plate, rb1, rb2) = scale(&mut world);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    // The stack weighs (1 + 2) * 9.81 = 29.43 on the scale.
+    let on_plate = world.contact_force(&plate);
+    assert!((on_plate + Vector3::y() * 29.43).norm() < 0.5, "Unexpected force: {}", on_plate);
+
+    // The bottom box is pushed up by the scale and down by the top box.
+    let on_rb1 = world.contact_force(&rb1);
+    assert!((on_rb1 - Vector3::y() * 9.81).norm() < 0.5, "Unexpected force: {}", on_rb1);
+
+    let on_rb2 = world.contact_force(&rb2);
+    assert!((on_rb2 - Vector3::y() * 19.62).norm() < 0.5, "Unexpected force: {}", on_rb2);
+}
+
+#[test]
+fn sleeping_stack_keeps_its_weight() {
+    let mut world = World::new();
+    let (plate, rb1, _) = scale(&mut world);
+
+    world.activation_manager().set_energy_threshold(Some(0.5));
+
+    for _ in 0 .. 1000 {
+        world.step(0.016);
+    }
+
+    assert!(!rb1.borrow().is_active());
+
+    let on_plate = world.contact_force(&plate);
+    assert!((on_plate + Vector3::y() * 29.43).norm() < 0.5, "Unexpected force: {}", on_plate);
+}
+
+#[test]
+fn removed_bodies_no_longer_weigh() {
+    let mut world = World::new();
+    let (plate, _, rb2) = scale(&mut world);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    world.remove_rigid_body(&rb2);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    let on_plate = world.contact_force(&plate);
+    assert!((on_plate + Vector3::y() * 9.81).norm() < 0.5, "Unexpected force: {}", on_plate);
+    assert_eq!(world.contact_force(&rb2), na::zero());
+}
@@ -0,0 +1,82 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use ncollide::query::Contact;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::detection::ContactJitter;
+
+// A box resting on the ground, with the contacts perturbed by the given jitter.
+fn resting_box(jitter: Option<ContactJitter<f32>>) -> World<f32> {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    let _ = world.set_contact_jitter(jitter);
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+    rb.set_translation(Translation3::new(0.0, 0.5, 0.0));
+    let _ = world.add_rigid_body(rb);
+
+    world
+}
+
+fn box_position(world: &mut World<f32>) -> Vector3<f32> {
+    for _ in 0 .. 60 {
+        world.step(0.016);
+    }
+
+    let rb = world.rigid_bodies().filter(|rb| rb.borrow().can_move()).next().unwrap();
+    let p  = rb.borrow().position().translation.vector;
+
+    p
+}
+
+#[test]
+fn perturbations_are_bounded_and_keep_the_depth() {
+    let mut jitter = ContactJitter::new(42, 0.01f32, 0.001);
+
+    for _ in 0 .. 100 {
+        let mut c = Contact::new(Point3::new(1.0, 0.1, 2.0), Point3::new(1.0, -0.1, 2.0), Vector3::y(), 0.2);
+        jitter.perturb(&mut c);
+
+        assert!((c.normal.norm() - 1.0).abs() < 1.0e-5);
+        assert!((c.normal - Vector3::y()).norm() < 0.02);
+        assert!((c.world1 - Point3::new(1.0, 0.1, 2.0)).norm() <= 0.001 * 3.0f32.sqrt() + 1.0e-6);
+        assert!(((c.world1 - c.world2) - Vector3::new(0.0, 0.2, 0.0)).norm() < 1.0e-5);
+        assert_eq!(c.depth, 0.2);
+    }
+}
+
+#[test]
+fn the_seed_determines_the_perturbations() {
+    let contact = Contact::new(Point3::origin(), Point3::origin(), Vector3::y(), 0.0);
+
+    let perturbed = |seed| {
+        let mut jitter = ContactJitter::new(seed, 0.01f32, 0.001);
+        let mut c      = contact.clone();
+        jitter.perturb(&mut c);
+        (c.normal, c.world1)
+    };
+
+    assert_eq!(perturbed(1), perturbed(1));
+    assert!(perturbed(1) != perturbed(2));
+}
+
+#[test]
+fn jittered_simulations_are_reproducible() {
+    let jitter = || Some(ContactJitter::new(7, 0.01f32, 0.001));
+    let p1     = box_position(&mut resting_box(jitter()));
+    let p2     = box_position(&mut resting_box(jitter()));
+    let p3     = box_position(&mut resting_box(Some(ContactJitter::new(8, 0.01f32, 0.001))));
+    let p4     = box_position(&mut resting_box(None));
+
+    assert_eq!(p1, p2);
+    assert!(p1 != p3);
+    assert!(p1 != p4);
+
+    // A box resting on the ground is not a knife-edge configuration.
+    assert!((p1 - p4).norm() < 0.01, "{:?} {:?}", p1, p4);
+}
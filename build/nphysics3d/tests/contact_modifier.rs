@@ -0,0 +1,124 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use ncollide::query::Contact;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::detection::constraint::ContactFlags;
+
+fn slide(ice: bool) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    if ice {
+        // An ice panel covering the floor beyond x = 1.
+        world.register_contact_modifier("ice", |_: &RigidBody<f32>, _: &RigidBody<f32>,
+                                                c: &mut Contact<Point3<f32>>, flags: &mut ContactFlags| {
+            if c.world1.x > 1.0 {
+                flags.friction = false;
+            }
+        });
+    }
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 1.0));
+
+    let mut block = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 1.0);
+    block.append_translation(&Translation3::new(2.0, 0.5, 0.0));
+    block.set_lin_vel(Vector3::new(2.0, 0.0, 0.0));
+    block.set_deactivation_threshold(None);
+    let block = world.add_rigid_body(block);
+
+    for _ in 0 .. 60 {
+        world.step(1.0 / 60.0);
+    }
+
+    let vel = block.borrow().lin_vel().x;
+
+    vel
+}
+
+fn bounce(restitution: bool) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.register_contact_modifier("dead mat", move |_: &RigidBody<f32>, _: &RigidBody<f32>,
+                                                      _: &mut Contact<Point3<f32>>, flags: &mut ContactFlags| {
+        flags.restitution = restitution;
+    });
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 1.0, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 1.0, 0.6);
+    ball.append_translation(&Translation3::new(0.0, 3.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    let mut max_after_bounce = 0.0f32;
+    let mut bounced          = false;
+
+    for _ in 0 .. 180 {
+        world.step(1.0 / 60.0);
+
+        let rb = ball.borrow();
+        bounced = bounced || rb.lin_vel().y > 0.0;
+
+        if bounced {
+            max_after_bounce = max_after_bounce.max(rb.position().translation.vector.y);
+        }
+    }
+
+    max_after_bounce
+}
+
+#[test]
+fn disable_friction_per_contact() {
+    assert!(slide(false) < 0.1);
+    assert!((slide(true) - 2.0).abs() < 0.1);
+}
+
+#[test]
+fn disable_restitution_per_contact() {
+    assert!(bounce(true) > 2.0);
+    assert!(bounce(false) < 1.0);
+}
+
+// The normal impulse of the contacts of a block resting on the floor at the beginning of a step,
+// before any solver iteration.
+fn warm_start_impulse(friction: f32, modified: bool) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    if modified {
+        world.register_contact_modifier("ice", |_: &RigidBody<f32>, _: &RigidBody<f32>,
+                                                _: &mut Contact<Point3<f32>>, flags: &mut ContactFlags| {
+            flags.friction = false;
+        });
+    }
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 1.0));
+
+    let mut block = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, friction);
+    block.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    block.set_deactivation_threshold(None);
+    let _ = world.add_rigid_body(block);
+
+    for _ in 0 .. 60 {
+        world.step(1.0 / 60.0);
+    }
+
+    world.constraints_solver().set_num_first_order_iter(0);
+    world.constraints_solver().set_num_second_order_iter(0);
+    world.step(1.0 / 60.0);
+
+    world.constraints_solver().contact_impulses().iter().map(|c| c.1.y).sum()
+}
+
+#[test]
+fn frictionless_contacts_are_warm_started() {
+    // The solver keeps 85% of the impulse of the last step, which supports the weight of 1.
+    let expected = 0.85 * 9.81 / 60.0;
+
+    assert!((warm_start_impulse(0.0, false) - expected).abs() < 5.0e-3);
+    assert!((warm_start_impulse(1.0, true) - expected).abs() < 5.0e-3);
+}
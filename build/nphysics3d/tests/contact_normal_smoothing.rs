@@ -0,0 +1,36 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3};
+use ncollide::query::Contact;
+use nphysics3d::detection::ContactNormalSmoothing;
+
+fn contact(x: f32, normal: Vector3<f32>) -> Contact<Point3<f32>> {
+    Contact::new(Point3::new(x, 0.0, 0.0), Point3::new(x, -0.01, 0.0), na::normalize(&normal), 0.01)
+}
+
+#[test]
+fn persistent_contact_normals_are_blended() {
+    let mut smoothing = ContactNormalSmoothing::new(0.5, 0.3, 0.1);
+
+    let mut c = contact(0.0, Vector3::y());
+    smoothing.smooth(1, 2, &mut c);
+    smoothing.end_frame();
+
+    // A small change is halved.
+    let mut c = contact(0.05, Vector3::new(0.2, 1.0, 0.0));
+    smoothing.smooth(1, 2, &mut c);
+    smoothing.end_frame();
+    assert!(c.normal.x > 0.0 && c.normal.x < na::normalize(&Vector3::new(0.2f32, 1.0, 0.0)).x * 0.6);
+    assert!((na::norm(&c.normal) - 1.0).abs() < 1.0e-5);
+
+    // Large changes and far contacts are left unchanged.
+    let mut c = contact(0.05, Vector3::x());
+    smoothing.smooth(1, 2, &mut c);
+    assert_eq!(c.normal, Vector3::x());
+
+    let mut c = contact(1.0, Vector3::new(0.2, 1.0, 0.0));
+    smoothing.smooth(1, 2, &mut c);
+    assert_eq!(c.normal, na::normalize(&Vector3::new(0.2, 1.0, 0.0)));
+}
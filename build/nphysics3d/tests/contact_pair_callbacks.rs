@@ -0,0 +1,39 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn contact_pairs_expose_the_body_dynamics() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    let ball = world.add_rigid_body(rb);
+
+    world.step(0.016);
+
+    // Bounce everything that touches the ground.
+    let mut num_pairs = 0;
+    world.for_each_contact_pair_mut(|mut rb1, mut rb2, contacts| {
+        assert!(!contacts.is_empty());
+        num_pairs += 1;
+
+        if rb1.can_move() {
+            rb1.set_lin_vel(Vector3::new(0.0, 5.0, 0.0));
+        }
+
+        if rb2.can_move() {
+            rb2.set_lin_vel(Vector3::new(0.0, 5.0, 0.0));
+        }
+    });
+
+    assert_eq!(num_pairs, 1);
+    assert_eq!(ball.borrow().lin_vel(), Vector3::new(0.0, 5.0, 0.0));
+}
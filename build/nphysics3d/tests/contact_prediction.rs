@@ -0,0 +1,35 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, WorldObject};
+
+#[test]
+fn per_body_prediction_generates_contacts_early() {
+    let mut world = World::new();
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    // Floating 0.5 above the ground, without gravity.
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 1.0, 0.0));
+    rb.set_deactivation_threshold(None);
+    let ball = world.add_rigid_body(rb);
+
+    world.step(0.016);
+    assert_eq!(world.stats().contacts, 0);
+
+    // The change is taken into account at the next step.
+    ball.borrow_mut().set_prediction(Some(1.0));
+    world.step(0.016);
+    assert_eq!(world.stats().contacts, 1);
+    let limit = world.collision_world().collision_object(WorldObject::rigid_body_uid(&ball))
+                     .map(|co| co.query_type.query_limit());
+    assert_eq!(limit, Some(1.0 + ball.borrow().margin()));
+
+    ball.borrow_mut().set_prediction(None);
+    world.step(0.016);
+    assert_eq!(world.stats().contacts, 0);
+}
@@ -0,0 +1,44 @@
+extern crate nalgebra as na;
+extern crate nphysics3d;
+
+use na::Point3;
+use nphysics3d::resolution::ImpulseCache;
+
+#[test]
+fn moving_contacts_keep_their_impulses() {
+    let mut cache = ImpulseCache::new(0.1f32, 3);
+
+    // The contact crosses a multiple of the matching distance between the two frames.
+    cache.insert(0, 1, 2, Point3::new(0.09, 0.0, 0.0));
+    cache.push_impulsions().copy_from_slice(&[1.0, 2.0, 3.0]);
+    cache.contacts_mut()[0].2 = 3;
+    cache.swap();
+
+    cache.insert(0, 1, 2, Point3::new(0.11, 0.0, 0.0));
+
+    let offset = cache.contacts()[0].2;
+    assert_eq!(cache.impulsions_at(offset), &[1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn each_impulse_warm_starts_the_closest_contact_of_its_pair() {
+    let mut cache = ImpulseCache::new(0.1f32, 1);
+
+    cache.insert(0, 1, 2, Point3::new(0.0, 0.0, 0.0));
+    cache.insert(1, 1, 2, Point3::new(1.0, 0.0, 0.0));
+    cache.push_impulsions()[0] = 1.0;
+    cache.push_impulsions()[0] = 2.0;
+    cache.contacts_mut()[0].2 = 1;
+    cache.contacts_mut()[1].2 = 2;
+    cache.swap();
+
+    cache.insert(0, 1, 2, Point3::new(0.98, 0.0, 0.0));
+    cache.insert(1, 1, 2, Point3::new(1.03, 0.0, 0.0));
+    cache.insert(2, 1, 3, Point3::new(0.0, 0.0, 0.0));
+    cache.insert(3, 1, 2, Point3::new(0.5, 0.0, 0.0));
+
+    let impulses: Vec<f32> = cache.contacts().iter().map(|c| cache.impulsions_at(c.2)[0]).collect();
+
+    // The second contact is not the closest, and the last two ones have no match.
+    assert_eq!(impulses, vec![2.0, 0.0, 0.0, 0.0]);
+}
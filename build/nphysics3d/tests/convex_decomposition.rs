@@ -0,0 +1,109 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::collections::HashMap;
+
+use na::{Point3, Vector3, Translation3};
+use ncollide::procedural::{TriMesh, IndexBuffer};
+use ncollide::shape::Plane;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, convex_decomposition};
+use nphysics3d::volumetric::Volumetric;
+
+// The surface of an L-shaped prism, the union of [0, 2]x[0, 1] and [0, 1]x[0, 2] extruded by 1
+// along `z`, tessellated into squares of side `1 / RES`.
+fn l_prism() -> TriMesh<Point3<f32>> {
+    const RES: i32 = 4;
+
+    let filled = |i: i32, j: i32, k: i32| {
+        i >= 0 && j >= 0 && k >= 0 && i < 2 * RES && j < 2 * RES && k < RES && (i < RES || j < RES)
+    };
+
+    let mut coords  = Vec::new();
+    let mut ids     = HashMap::new();
+    let mut indices = Vec::new();
+
+    {
+        let mut vertex = |p: [i32; 3]| *ids.entry(p).or_insert_with(|| {
+            coords.push(Point3::new(p[0] as f32, p[1] as f32, p[2] as f32) / RES as f32);
+            coords.len() as u32 - 1
+        });
+
+        for i in 0 .. 2 * RES {
+            for j in 0 .. 2 * RES {
+                for k in 0 .. RES {
+                    if !filled(i, j, k) {
+                        continue;
+                    }
+
+                    // One square per face of the cell not shared with another cell.
+                    for axis in 0 .. 3 {
+                        for &side in [-1, 1].iter() {
+                            let mut n = [i, j, k];
+                            n[axis] += side;
+
+                            if filled(n[0], n[1], n[2]) {
+                                continue;
+                            }
+
+                            let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+                            let mut corner = |du: i32, dv: i32| {
+                                let mut p = [i, j, k];
+                                p[axis] += (side + 1) / 2;
+                                p[u] += du;
+                                p[v] += dv;
+                                vertex(p)
+                            };
+
+                            let (a, b, c, d) = (corner(0, 0), corner(1, 0), corner(1, 1), corner(0, 1));
+
+                            // Counterclockwise when seen from outside.
+                            if side > 0 {
+                                indices.push(Point3::new(a, b, c));
+                                indices.push(Point3::new(a, c, d));
+                            }
+                            else {
+                                indices.push(Point3::new(a, c, b));
+                                indices.push(Point3::new(a, d, c));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    TriMesh::new(coords, None, None, Some(IndexBuffer::Unified(indices)))
+}
+
+#[test]
+fn decompose_concave_mesh() {
+    let compound = convex_decomposition(&l_prism(), 0.01);
+
+    // The mesh has a volume of 3, its convex hull a volume of 3.5. The pieces are approximate
+    // and may overlap a bit.
+    let volume = compound.volume();
+    assert!(compound.shapes().len() >= 2);
+    assert!(volume > 2.9 && volume < 3.45, "Unexpected volume: {}", volume);
+}
+
+#[test]
+fn concave_mesh_as_dynamic_body() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let mut body = RigidBody::new_dynamic_concave(&l_prism(), 0.01, 1.0, 0.0, 0.5);
+    body.append_translation(&Translation3::new(0.0, 1.0, 0.0));
+    let body = world.add_rigid_body(body);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    // Resting on its bottom face.
+    let y = body.borrow().position().translation.vector.y;
+    assert!(y > -0.1 && y < 0.2, "Unexpected height: {}", y);
+}
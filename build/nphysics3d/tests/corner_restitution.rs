@@ -0,0 +1,50 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Translation3, UnitQuaternion};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// The vertical velocity of the corner `local` of a body.
+fn corner_velocity(rb: &RigidBodyHandle<f32>, local: &Point3<f32>) -> f32 {
+    let rb = rb.borrow();
+    let r  = rb.position() * local - *rb.center_of_mass();
+
+    (rb.lin_vel() + rb.ang_vel().cross(&r)).y
+}
+
+#[test]
+fn tumbling_box_corner_bounces_with_its_restitution() {
+    let mut world = World::new();
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 1.0, 0.0));
+
+    // A tilted box falling without spinning: only its lowest corner hits the ground.
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 0.2, 0.2)), 1.0, 0.5, 0.0);
+    rb.set_rotation(UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.5));
+    rb.set_translation(Translation3::new(0.0, 1.5, 0.0));
+    rb.set_lin_vel(Vector3::new(0.0, -5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let corner = Point3::new(-1.0, -0.2, 0.2);
+
+    let mut before = corner_velocity(&rb, &corner);
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+
+        let after = corner_velocity(&rb, &corner);
+
+        if after > 0.0 {
+            // The restitution of the contact is 0.5 · 1.0.
+            assert!(before < -4.9);
+            assert!((after + 0.5 * before).abs() < 0.1, "{} after {}", after, before);
+            return;
+        }
+
+        before = after;
+    }
+
+    panic!("The corner of the box did not bounce.")
+}
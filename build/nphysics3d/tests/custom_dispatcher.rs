@@ -0,0 +1,164 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Isometry3, Translation3};
+use ncollide::bounding_volume::AABB;
+use ncollide::shape::{Shape, ShapeHandle, Ball, Plane};
+use ncollide::query::Contact;
+use ncollide::narrow_phase::{ContactDispatcher, ContactAlgorithm, ContactGenerator};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::volumetric::Volumetric;
+
+// A user-defined shape unknown to the default narrow phase.
+struct Marker {
+    radius: f32
+}
+
+impl Shape<Point3<f32>, Isometry3<f32>> for Marker {
+    fn aabb(&self, m: &Isometry3<f32>) -> AABB<Point3<f32>> {
+        let center = Point3::from_coordinates(m.translation.vector);
+        let extent = Vector3::new(self.radius, self.radius, self.radius);
+
+        AABB::new(center - extent, center + extent)
+    }
+}
+
+struct MarkerPlaneContactGenerator {
+    // Whether the plane is the first object of the pair.
+    flip:    bool,
+    contact: Option<Contact<Point3<f32>>>
+}
+
+impl ContactGenerator<Point3<f32>, Isometry3<f32>> for MarkerPlaneContactGenerator {
+    fn update(&mut self,
+              _:          &ContactDispatcher<Point3<f32>, Isometry3<f32>>,
+              ma:         &Isometry3<f32>,
+              a:          &Shape<Point3<f32>, Isometry3<f32>>,
+              mb:         &Isometry3<f32>,
+              b:          &Shape<Point3<f32>, Isometry3<f32>>,
+              prediction: f32)
+              -> bool {
+        if self.flip {
+            let res = self.update_marker_plane(mb, b, ma, a, prediction);
+
+            if let Some(ref mut c) = self.contact {
+                c.flip();
+            }
+
+            res
+        }
+        else {
+            self.update_marker_plane(ma, a, mb, b, prediction)
+        }
+    }
+
+    fn num_contacts(&self) -> usize {
+        if self.contact.is_some() { 1 } else { 0 }
+    }
+
+    fn contacts(&self, out: &mut Vec<Contact<Point3<f32>>>) {
+        out.extend(self.contact.iter().cloned())
+    }
+}
+
+impl MarkerPlaneContactGenerator {
+    fn update_marker_plane(&mut self,
+                           ma:         &Isometry3<f32>,
+                           a:          &Shape<Point3<f32>, Isometry3<f32>>,
+                           mb:         &Isometry3<f32>,
+                           b:          &Shape<Point3<f32>, Isometry3<f32>>,
+                           prediction: f32)
+                           -> bool {
+        match (a.as_shape::<Marker>(), b.as_shape::<Plane<Vector3<f32>>>()) {
+            (Some(marker), Some(plane)) => {
+                let normal = mb.rotation * *plane.normal();
+                let center = Point3::from_coordinates(ma.translation.vector);
+                let dist   = na::dot(&(center - Point3::from_coordinates(mb.translation.vector)), &normal);
+                let depth  = marker.radius - dist;
+
+                self.contact = if depth >= -prediction {
+                    Some(Contact::new(center - normal * marker.radius, center - normal * dist, -normal, depth))
+                }
+                else {
+                    None
+                };
+
+                true
+            },
+            _ => false
+        }
+    }
+}
+
+struct MarkerDispatcher;
+
+impl ContactDispatcher<Point3<f32>, Isometry3<f32>> for MarkerDispatcher {
+    fn get_contact_algorithm(&self, a: &Shape<Point3<f32>, Isometry3<f32>>, b: &Shape<Point3<f32>, Isometry3<f32>>)
+                             -> Option<ContactAlgorithm<Point3<f32>, Isometry3<f32>>> {
+        if a.is_shape::<Marker>() && b.is_shape::<Plane<Vector3<f32>>>() {
+            Some(Box::new(MarkerPlaneContactGenerator { flip: false, contact: None }))
+        }
+        else if a.is_shape::<Plane<Vector3<f32>>>() && b.is_shape::<Marker>() {
+            Some(Box::new(MarkerPlaneContactGenerator { flip: true, contact: None }))
+        }
+        else {
+            None
+        }
+    }
+}
+
+fn drop_marker(custom_dispatcher: bool) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    if custom_dispatcher {
+        world.register_contact_dispatcher("marker", MarkerDispatcher);
+    }
+
+    let props      = Ball::new(0.5f32).mass_properties(1.0);
+    let mut marker = RigidBody::new(ShapeHandle::new(Marker { radius: 0.5 }), Some(props), 0.0, 0.6);
+    marker.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+    let marker: RigidBodyHandle<f32> = world.add_rigid_body(marker);
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    for _ in 0 .. 120 {
+        world.step(1.0 / 60.0);
+    }
+
+    let y = marker.borrow().position().translation.vector.y;
+
+    y
+}
+
+#[test]
+fn custom_contact_dispatcher() {
+    // The default narrow phase ignores the marker.
+    assert!(drop_marker(false) < -5.0);
+
+    let y = drop_marker(true);
+    // The marker rests on the plane, up to the body margins.
+    assert!((y - 0.5).abs() < 0.1, "{}", y);
+}
+
+#[test]
+fn predicted_trajectory_uses_the_custom_dispatchers() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.register_contact_dispatcher("marker", MarkerDispatcher);
+
+    let props      = Ball::new(0.5f32).mass_properties(1.0);
+    let mut marker = RigidBody::new(ShapeHandle::new(Marker { radius: 0.5 }), Some(props), 0.0, 0.6);
+    marker.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+    let marker: RigidBodyHandle<f32> = world.add_rigid_body(marker);
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let trajectory = world.predict_trajectory(&marker, 1.0 / 60.0, 120);
+
+    // The marker lands on the plane instead of falling through it.
+    let y = trajectory.last().unwrap().translation.vector.y;
+    assert!((y - 0.5).abs() < 0.1, "{}", y);
+}
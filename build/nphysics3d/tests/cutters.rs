@@ -0,0 +1,80 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::detection::Cut;
+
+#[test]
+fn cutter_reports_intersections_without_response() {
+    let mut world = World::new();
+
+    // A floating box, and a thin static blade going down through it.
+    let target = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 1.0, 1.0)), 1.0, 0.0, 0.6);
+    let target = world.add_rigid_body(target);
+
+    let mut blade = RigidBody::new_static(Cuboid::new(Vector3::new(0.02f32, 1.0, 2.0)), 0.0, 0.6);
+    blade.set_translation(Translation3::new(0.3, 3.0, 0.0));
+    let blade = world.add_rigid_body(blade);
+
+    let cuts   = Rc::new(RefCell::new(Vec::new()));
+    let out    = cuts.clone();
+    world.add_cutter(&blade, move |cut: &Cut<f32>| {
+        let on_blade = cut.contacts.iter().map(|c| c.world1).collect::<Vec<_>>();
+        out.borrow_mut().push((cut.target.clone(), cut.points(), on_blade))
+    });
+    assert!(world.is_cutter(&blade));
+
+    for i in 0 .. 60 {
+        blade.borrow_mut().set_translation(Translation3::new(0.3, 3.0 - i as f32 * 0.05, 0.0));
+        world.step(0.016);
+    }
+
+    let cuts = cuts.borrow();
+    assert!(!cuts.is_empty());
+
+    for &(ref rb, ref points, _) in cuts.iter() {
+        assert!(&**rb as *const _ == &*target as *const _);
+
+        // The points lie inside the target.
+        for pt in points.iter() {
+            assert!(pt.iter().all(|x| x.abs() < 1.1), "Unexpected cut point: {}", pt);
+        }
+    }
+
+    // The blade enters the target through its edge.
+    for pt in cuts[0].2.iter() {
+        assert!((pt.x - 0.3).abs() < 0.05 && (pt.y - 1.0).abs() < 0.1, "Unexpected blade point: {}", pt);
+    }
+
+    // The blade did not push the target.
+    assert!(target.borrow().position().translation.vector.norm() < 1.0e-3);
+}
+
+#[test]
+fn removed_cutter_is_solid() {
+    let mut world = World::new();
+
+    let target = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 1.0, 1.0)), 1.0, 0.0, 0.6);
+    let target = world.add_rigid_body(target);
+
+    let mut blade = RigidBody::new_static(Cuboid::new(Vector3::new(0.5f32, 0.5, 2.0)), 0.0, 0.6);
+    blade.set_translation(Translation3::new(0.0, 1.4, 0.0));
+    let blade = world.add_rigid_body(blade);
+
+    world.add_cutter(&blade, |_: &Cut<f32>| { });
+    assert!(world.remove_cutter(&blade));
+    assert!(!world.remove_cutter(&blade));
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    // The overlap is resolved by pushing the target away.
+    assert!(target.borrow().position().translation.vector.y < -0.05);
+}
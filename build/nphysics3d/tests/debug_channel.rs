@@ -0,0 +1,33 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::debug::{DebugChannel, DebugPrimitive};
+
+#[test]
+fn debug_frames_are_sent_each_step() {
+    let mut world = World::new();
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.3, 0.6);
+    ball.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    world.add_rigid_body(ball);
+
+    let (channel, receiver) = DebugChannel::new(1);
+    let _ = world.set_debug_channel(Some(channel));
+
+    world.step(0.016);
+    world.step(0.016);
+
+    // The second frame was dropped as the first one was not consumed yet.
+    assert_eq!(world.debug_channel().unwrap().num_dropped_frames(), 1);
+
+    let frame = receiver.try_recv().unwrap();
+    assert_eq!(frame.step, 0);
+    assert!(frame.primitives.iter().any(|p| match *p { DebugPrimitive::Line(..) => true, _ => false }));
+    assert!(receiver.try_recv().is_err());
+}
@@ -0,0 +1,53 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Translation3, Isometry3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+fn falling_box(i: usize) -> RigidBody<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    let x = (i % 3) as f32 * 0.7 - 0.7;
+    let z = (i % 2) as f32 * 0.4;
+    rb.set_translation(Translation3::new(x, 1.0 + i as f32 * 1.2, z));
+
+    rb
+}
+
+// The positions of a pile of boxes after a few seconds.
+//
+// If `shuffle` is true, the bodies are allocated at scattered addresses.
+fn pile(shuffle: bool) -> Vec<Isometry3<f32>> {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut handles = Vec::new();
+
+    for i in 0 .. 12 {
+        // Allocate, then free, blocks of the size of a body so that the next one lands elsewhere.
+        let _holes: Vec<_> = if shuffle {
+            (0 .. i + 1).map(|_| Rc::new(RefCell::new(falling_box(0)))).collect()
+        }
+        else {
+            Vec::new()
+        };
+
+        handles.push(world.add_rigid_body(falling_box(i)));
+    }
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    handles.iter().map(|rb| *rb.borrow().position()).collect()
+}
+
+#[test]
+fn the_simulation_does_not_depend_on_the_addresses_of_the_bodies() {
+    assert_eq!(pile(false), pile(true));
+}
@@ -0,0 +1,64 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::detection::joint::{Anchor, BallInSocket};
+
+// The largest velocity error left on the joints of a horizontal arm of links of decreasing
+// masses, hanging from its base, during a few steps with a single iteration.
+fn arm_error(direct: Option<usize>) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    {
+        let solver = world.constraints_solver();
+        solver.set_num_second_order_iter(1);
+        solver.set_direct_solver_threshold(direct);
+        assert_eq!(solver.direct_solver_threshold(), direct);
+    }
+
+    let mut prev = None;
+
+    for i in 0 .. 6 {
+        let density = 10.0 / (1 + 3 * i) as f32;
+        let mut rb  = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.1, 0.1)), density, 0.0, 0.6);
+        rb.set_translation(Translation3::new(0.5 + i as f32, 5.0, 0.0));
+        let rb = world.add_rigid_body(rb);
+
+        let anchor1 = match prev {
+            None            => Anchor::new(None, Point3::new(0.0, 5.0, 0.0)),
+            Some(ref other) => Anchor::new(Some(Rc::clone(other)), Point3::new(0.5, 0.0, 0.0))
+        };
+
+        let _ = world.add_ball_in_socket(BallInSocket::new(anchor1, Anchor::new(Some(rb.clone()), Point3::new(-0.5, 0.0, 0.0))));
+        prev = Some(rb);
+    }
+
+    let mut max_error = 0.0f32;
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+        max_error = max_error.max(world.solver_statistics().max_residual);
+    }
+
+    max_error
+}
+
+#[test]
+fn small_mechanisms_are_solved_exactly() {
+    let iterative = arm_error(None);
+    let direct    = arm_error(Some(10));
+
+    assert!(iterative > 1.0e-2, "Unexpected iterative error: {}", iterative);
+    assert!(direct < 1.0e-5, "Unexpected direct error: {}", direct);
+}
+
+#[test]
+fn large_islands_are_solved_iteratively() {
+    assert_eq!(arm_error(Some(5)), arm_error(None));
+}
@@ -0,0 +1,107 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Distance};
+
+// A box hanging at (`x`, 5 - `y`, 0) from a distance joint attached at (0, 5, 0).
+fn hanging_box(world: &mut World<f32>, x: f32, y: f32, min: f32, max: f32)
+               -> (RigidBodyHandle<f32>, Rc<RefCell<Distance<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.1f32, 0.1, 0.1)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(x, 5.0 - y, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Point3::new(0.0, 5.0, 0.0));
+    let anchor2 = Anchor::new(Some(rb.clone()), Point3::origin());
+    let joint   = world.add_distance(Distance::new_with_limits(anchor1, anchor2, min, max));
+
+    (rb, joint)
+}
+
+#[test]
+fn rod_keeps_a_swinging_box_at_its_length() {
+    let mut world = World::new();
+    let (rb, rod) = hanging_box(&mut world, 2.0, 0.0, 2.0, 2.0);
+    let mut lowest = 5.0f32;
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+        lowest = lowest.min(rb.borrow().position().translation.vector.y);
+
+        let length = rod.borrow().length();
+        assert!((length - 2.0).abs() < 2.0e-2, "Unexpected length: {}", length);
+    }
+
+    assert!(lowest < 3.1, "Unexpected height: {}", lowest);
+    assert_eq!(world.summary().num_distances, 1);
+
+    // Unlike a rope, a rod also holds the box when it is pushed towards the anchor.
+    world.set_gravity(Vector3::new(0.0, 9.81, 0.0));
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    let length = rod.borrow().length();
+    assert!((length - 2.0).abs() < 2.0e-2, "Unexpected length: {}", length);
+}
+
+#[test]
+fn rope_goes_slack() {
+    let mut world = World::new();
+    let (rb, rope) = hanging_box(&mut world, 0.0, 1.0, 0.0, 2.0);
+
+    // The box falls freely until the rope is tight.
+    for _ in 0 .. 10 {
+        world.step(0.016);
+    }
+
+    assert!(rb.borrow().lin_vel().y < -1.0);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    let length = rope.borrow().length();
+    assert!((length - 2.0).abs() < 2.0e-2, "Unexpected length: {}", length);
+
+    // Throwing the box upwards does not compress the rope.
+    rb.borrow_mut().set_lin_vel(Vector3::new(0.0, 5.0, 0.0));
+    world.step(0.016);
+
+    assert!(rope.borrow().length() < 1.95);
+}
+
+#[test]
+fn minimum_distance_pushes_apart() {
+    let mut world = World::new();
+    let (_, joint) = hanging_box(&mut world, 0.0, 1.0, 1.5, 3.0);
+
+    world.set_gravity(Vector3::new(0.0, 9.81, 0.0));
+    world.activation_manager().set_deactivation_enabled(false);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    let length = joint.borrow().length();
+    assert!((length - 1.5).abs() < 2.0e-2, "Unexpected length: {}", length);
+
+    joint.borrow_mut().set_length(1.0);
+    assert_eq!(joint.borrow().limits(), (1.0, 1.0));
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    let length = joint.borrow().length();
+    assert!((length - 1.0).abs() < 2.0e-2, "Unexpected length: {}", length);
+}
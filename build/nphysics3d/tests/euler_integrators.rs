@@ -0,0 +1,64 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::Vector3;
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::integration::{Integrator, BodyExpEulerIntegrator, BodySmpEulerIntegrator};
+
+// The height of a body falling during `n` steps of `dt`.
+fn fall<I: Integrator<f32, RigidBody<f32>> + Clone + 'static>(integrator: I, n: usize, dt: f32) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -10.0, 0.0));
+    world.set_integrator(integrator);
+
+    let rb = world.add_rigid_body(RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6));
+
+    for _ in 0 .. n {
+        world.step(dt);
+    }
+
+    let y = rb.borrow().position().translation.vector.y;
+    y
+}
+
+#[test]
+fn explicit_and_semi_implicit_euler_bracket_the_exact_fall() {
+    let exact = -10.0 * 0.5;
+    let exp   = fall(BodyExpEulerIntegrator::new(), 10, 0.1);
+    let smp   = fall(BodySmpEulerIntegrator::new(), 10, 0.1);
+
+    // The explicit integrator uses the velocity at the beginning of each step, and the
+    // semi-implicit one the velocity at its end.
+    assert!((exp - (-10.0 * 0.01 * 45.0)).abs() < 1.0e-3, "Unexpected height: {}", exp);
+    assert!((smp - (-10.0 * 0.01 * 55.0)).abs() < 1.0e-3, "Unexpected height: {}", smp);
+    assert!(exp > exact && smp < exact);
+}
+
+// The energy of a unit harmonic oscillator `x'' = -x` integrated for about one period.
+fn oscillate<I: Integrator<f32, RigidBody<f32>>>(mut integrator: I) -> f32 {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_lin_vel(Vector3::x());
+
+    for _ in 0 .. 63 {
+        let x = rb.position().translation.vector.x;
+        rb.set_lin_acc(Vector3::new(-x, 0.0, 0.0));
+        integrator.update(0.1, &mut rb);
+    }
+
+    let x = rb.position().translation.vector.x;
+    let v = rb.lin_vel().x;
+
+    (x * x + v * v) * 0.5
+}
+
+#[test]
+fn explicit_euler_gains_energy_on_oscillations() {
+    let exp = oscillate(BodyExpEulerIntegrator::new());
+    let smp = oscillate(BodySmpEulerIntegrator::new());
+
+    assert!(exp > 0.9, "Unexpected energy: {}", exp);
+    assert!((smp - 0.5).abs() < 0.05, "Unexpected energy: {}", smp);
+}
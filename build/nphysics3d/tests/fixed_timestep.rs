@@ -0,0 +1,87 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::Ball;
+use nphysics3d::world::{World, FixedTimestep};
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// A ball moving along `x` at 1 m/s.
+fn moving_ball(world: &mut World<f32>) -> RigidBodyHandle<f32> {
+    let rb = world.add_rigid_body(RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6));
+    rb.borrow_mut().set_lin_vel(Vector3::x());
+    rb.borrow_mut().set_deactivation_threshold(None);
+
+    rb
+}
+
+#[test]
+fn elapsed_time_is_accumulated() {
+    let mut world = World::new();
+    let mut stepper = FixedTimestep::new(0.01f32);
+
+    assert_eq!(stepper.advance(&mut world, 0.004), 0);
+    assert_eq!(stepper.advance(&mut world, 0.004), 0);
+    assert_eq!(stepper.advance(&mut world, 0.004), 1);
+    assert_eq!(stepper.advance(&mut world, 0.025), 2);
+
+    assert_eq!(world.num_steps(), 3);
+    assert!((stepper.accumulator() - 0.007).abs() < 1.0e-5);
+    assert!((stepper.alpha() - 0.7).abs() < 1.0e-3);
+}
+
+#[test]
+fn positions_are_interpolated_between_steps() {
+    let mut world = World::new();
+    let rb = moving_ball(&mut world);
+    let mut stepper = FixedTimestep::new(0.01f32);
+
+    let _ = stepper.advance(&mut world, 0.0125);
+
+    // The ball moved by 0.01 during the only step, and is rendered a quarter of a step later.
+    let x = stepper.interpolated_position(&rb).translation.vector.x;
+    assert!((x - 0.0025).abs() < 1.0e-5, "Unexpected position: {}", x);
+
+    let _ = stepper.advance(&mut world, 0.005);
+
+    let x = stepper.interpolated_position(&rb).translation.vector.x;
+    assert!((x - 0.0075).abs() < 1.0e-5, "Unexpected position: {}", x);
+}
+
+#[test]
+fn rotations_are_interpolated_between_steps() {
+    let mut world = World::new();
+    let rb = moving_ball(&mut world);
+    rb.borrow_mut().set_ang_vel(Vector3::new(0.0, 0.0, 10.0));
+    let mut stepper = FixedTimestep::new(0.01f32);
+
+    let _ = stepper.advance(&mut world, 0.015);
+
+    let angle = stepper.interpolated_position(&rb).rotation.angle();
+    assert!((angle - 0.05).abs() < 1.0e-4, "Unexpected angle: {}", angle);
+}
+
+#[test]
+fn slow_frames_drop_time() {
+    let mut world = World::new();
+    let mut stepper = FixedTimestep::new(0.01f32);
+    stepper.set_max_steps(3);
+
+    assert_eq!(stepper.advance(&mut world, 1.0055), 3);
+    assert!(stepper.accumulator() < 0.01);
+    assert!((stepper.accumulator() - 0.0055).abs() < 1.0e-4);
+}
+
+#[test]
+fn new_bodies_are_rendered_at_their_position() {
+    let mut world = World::new();
+    let mut stepper = FixedTimestep::new(0.01f32);
+    let _ = stepper.advance(&mut world, 0.015);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(1.0, 2.0, 3.0));
+    let rb = world.add_rigid_body(rb);
+
+    assert_eq!(stepper.interpolated_position(&rb).translation.vector, Vector3::new(1.0, 2.0, 3.0));
+}
@@ -0,0 +1,75 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::integration::{Falloff, RadialField, VortexField};
+
+fn add_ball(world: &mut World<f32>, density: f32, pos: Vector3<f32>) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), density, 0.3, 0.6);
+    rb.set_translation(Translation3::from_vector(pos));
+    rb.set_deactivation_threshold(None);
+
+    world.add_rigid_body(rb)
+}
+
+#[test]
+fn falloff_factors() {
+    assert_eq!(Falloff::Constant.factor(3.0f32, 4.0), 1.0);
+    assert_eq!(Falloff::Linear.factor(3.0f32, 4.0), 0.25);
+    assert_eq!(Falloff::Quadratic.factor(2.0f32, 4.0), 0.25);
+    assert_eq!(Falloff::InverseSquare(0.5f32).factor(2.0, 4.0), 0.25);
+    assert_eq!(Falloff::InverseSquare(0.5f32).factor(0.1, 4.0), 4.0);
+    assert_eq!(Falloff::Constant.factor(5.0f32, 4.0), 0.0);
+}
+
+#[test]
+fn radial_field_attracts_within_its_radius() {
+    let mut world = World::new();
+    let rb1 = add_ball(&mut world, 1.0, Vector3::new(2.0, 0.0, 0.0));
+    let rb2 = add_ball(&mut world, 1.0, Vector3::new(0.0, 0.0, 10.0));
+
+    let mut field = RadialField::new(Point3::origin(), 1.0, Falloff::Constant);
+    field.set_radius(5.0);
+    world.register_force_generator("attractor", field);
+    world.step(0.5);
+
+    assert!((rb1.borrow().lin_vel() - Vector3::new(-0.5, 0.0, 0.0)).norm() < 1.0e-5);
+    assert_eq!(rb2.borrow().lin_vel(), na::zero());
+}
+
+#[test]
+fn explosion_pushes_light_bodies_further() {
+    let mut world = World::new();
+    let light = add_ball(&mut world, 1.0, Vector3::new(2.0, 0.0, 0.0));
+    let heavy = add_ball(&mut world, 4.0, Vector3::new(-2.0, 0.0, 0.0));
+
+    let mut field = RadialField::new(Point3::origin(), -10.0, Falloff::InverseSquare(0.1));
+    field.set_mass_proportional(false);
+    world.register_force_generator("explosion", field);
+    world.step(0.1);
+
+    let v1 = light.borrow().lin_vel();
+    let v2 = heavy.borrow().lin_vel();
+    assert!(v1.x > 0.0 && v2.x < 0.0);
+    assert!((v1.x + v2.x * 4.0).abs() < 1.0e-5, "Unexpected velocities: {} {}", v1, v2);
+}
+
+#[test]
+fn vortex_field_swirls_bodies_around_its_axis() {
+    let mut world = World::new();
+    let rb = add_ball(&mut world, 1.0, Vector3::new(2.0, 5.0, 0.0));
+
+    let mut field = VortexField::new(Point3::origin(), Vector3::y(), 1.0, Falloff::Linear);
+    field.set_inward_strength(0.5);
+    field.set_radius(4.0);
+    world.register_force_generator("tornado", field);
+    world.step(0.5);
+
+    // Counterclockwise around `y`, towards the axis, with half the strength at half the radius.
+    let v = rb.borrow().lin_vel();
+    assert!((v - Vector3::new(-0.125, 0.0, -0.25)).norm() < 1.0e-5, "Unexpected velocity: {}", v);
+}
@@ -0,0 +1,71 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::Vector3;
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::integration::ForceGenerator;
+
+// A constant force applied on every body.
+#[derive(Clone)]
+struct Wind(Vector3<f32>);
+
+impl ForceGenerator<f32> for Wind {
+    fn forces(&mut self, _: f32, _: &RigidBody<f32>) -> (Vector3<f32>, Vector3<f32>) {
+        (self.0, na::zero())
+    }
+}
+
+// A torque proportional to the mass of each body.
+#[derive(Clone)]
+struct Spin;
+
+impl ForceGenerator<f32> for Spin {
+    fn forces(&mut self, _: f32, rb: &RigidBody<f32>) -> (Vector3<f32>, Vector3<f32>) {
+        (na::zero(), Vector3::y() * rb.mass().unwrap())
+    }
+}
+
+fn add_ball(world: &mut World<f32>) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_deactivation_threshold(None);
+
+    world.add_rigid_body(rb)
+}
+
+#[test]
+fn force_generators_are_summed() {
+    let mut world = World::new();
+    let rb = add_ball(&mut world);
+    let mass = rb.borrow().mass().unwrap();
+
+    world.register_force_generator("east", Wind(Vector3::x() * mass));
+    world.register_force_generator("north", Wind(Vector3::z() * mass * 2.0));
+    world.register_force_generator("spin", Spin);
+    world.step(0.5);
+
+    let lin_vel = rb.borrow().lin_vel();
+    assert!((lin_vel - Vector3::new(0.5, 0.0, 1.0)).norm() < 1.0e-5, "Unexpected velocity: {}", lin_vel);
+    assert!(rb.borrow().ang_vel().y > 0.0);
+}
+
+#[test]
+fn force_generators_are_replaced_and_unregistered() {
+    let mut world = World::new();
+    let rb = add_ball(&mut world);
+    let mass = rb.borrow().mass().unwrap();
+
+    world.register_force_generator("wind", Wind(Vector3::x() * mass));
+    world.register_force_generator("wind", Wind(-Vector3::x() * mass));
+    world.step(0.5);
+
+    assert!((rb.borrow().lin_vel().x + 0.5).abs() < 1.0e-5);
+
+    world.unregister_force_generator("wind");
+    world.step(0.5);
+
+    assert!((rb.borrow().lin_vel().x + 0.5).abs() < 1.0e-5);
+    assert_eq!(rb.borrow().lin_acc(), na::zero());
+}
@@ -0,0 +1,44 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Cuboid, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, FrictionCurve};
+
+fn slide_distance(curve: Option<FrictionCurve<f32>>) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 1.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)), 1.0, 0.0, 1.0);
+    rb.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    rb.set_lin_vel(Vector3::new(5.0, 0.0, 0.0));
+    rb.set_friction_curve(curve);
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 120 {
+        world.step(0.016);
+    }
+
+    let x = rb.borrow().position().translation.vector.x;
+    x
+}
+
+#[test]
+fn friction_curve_sampling() {
+    let curve = FrictionCurve::new(vec![ (1.0, 0.5), (0.0, 1.0), (2.0, 0.5) ]);
+    assert_eq!(curve.sample(-1.0), 1.0);
+    assert_eq!(curve.sample(0.5), 0.75);
+    assert_eq!(curve.sample(1.5), 0.5);
+    assert_eq!(curve.sample(10.0), 0.5);
+}
+
+#[test]
+fn speed_weakening_friction_slides_farther() {
+    let coulomb  = slide_distance(None);
+    let weakened = slide_distance(Some(FrictionCurve::static_to_dynamic(1.0, 0.1, 0.5)));
+
+    assert!(weakened > coulomb + 1.0, "{} vs. {}", weakened, coulomb);
+}
@@ -0,0 +1,70 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::resolution::FrictionModel;
+
+// The displacement of a box launched on the ground at 3 m/s along `dir`, until it stops.
+fn slide(model: FrictionModel, dir: Vector3<f32>) -> Vector3<f32> {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.constraints_solver().set_friction_model(model);
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+    rb.set_translation(Translation3::new(0.0, 0.5, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    let start = rb.borrow().position().translation.vector;
+    rb.borrow_mut().set_lin_vel(na::normalize(&dir) * 3.0);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    assert!(na::norm(&rb.borrow().lin_vel()) < 1.0e-2);
+
+    let end = rb.borrow().position().translation.vector;
+
+    end - start
+}
+
+#[test]
+fn the_pyramid_is_the_default_friction_model() {
+    let mut world = World::<f32>::new();
+
+    assert_eq!(world.constraints_solver().friction_model(), FrictionModel::Pyramid);
+}
+
+#[test]
+fn the_cone_keeps_the_sliding_direction() {
+    let d = slide(FrictionModel::Cone, Vector3::new(1.0, 0.0, 0.3));
+
+    assert!(d.x > 0.5);
+    assert!((d.z / d.x - 0.3).abs() < 0.03, "Unexpected displacement: {}", d);
+}
+
+#[test]
+fn the_pyramid_drifts_towards_its_axes() {
+    let d = slide(FrictionModel::Pyramid, Vector3::new(1.0, 0.0, 0.3));
+
+    assert!(d.x > 0.5);
+    assert!(d.z / d.x < 0.15, "Unexpected displacement: {}", d);
+}
+
+#[test]
+fn the_cone_applies_less_friction_along_diagonals() {
+    let pyramid = slide(FrictionModel::Pyramid, Vector3::new(1.0, 0.0, 1.0));
+    let cone    = slide(FrictionModel::Cone, Vector3::new(1.0, 0.0, 1.0));
+
+    assert!((cone.z - cone.x).abs() < 0.05, "Unexpected displacement: {}", cone);
+    assert!(na::norm(&cone) > na::norm(&pyramid) + 0.05, "cone: {}, pyramid: {}", cone, pyramid);
+}
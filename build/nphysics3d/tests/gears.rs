@@ -0,0 +1,90 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Hinge, Gear, JointMotor};
+
+// A wheel of half-width `radius` hinged around `z` at its center (`x`, 5, 0).
+fn wheel(world: &mut World<f32>, x: f32, radius: f32) -> (RigidBodyHandle<f32>, Rc<RefCell<Hinge<f32>>>) {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(radius, radius, 0.2)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(x, 5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(x, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), na::one());
+    let hinge   = world.add_hinge(Hinge::new(anchor1, anchor2, Vector3::z()));
+
+    (rb, hinge)
+}
+
+#[test]
+fn meshing_wheels_turn_in_ratio() {
+    let mut world = World::new();
+    let (rb1, hinge1) = wheel(&mut world, 0.0, 0.5);
+    let (rb2, hinge2) = wheel(&mut world, 3.0, 1.0);
+
+    // The large wheel turns twice as slowly, in the opposite direction.
+    let gear = world.add_gear(Gear::new(hinge1.clone(), hinge2.clone(), -0.5));
+    hinge1.borrow_mut().set_motor(Some(JointMotor::velocity(4.0, 1000.0)));
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    let w1 = rb1.borrow().ang_vel().z;
+    let w2 = rb2.borrow().ang_vel().z;
+    assert!((w1 - 4.0).abs() < 1.0e-2, "Unexpected velocity: {}", w1);
+    assert!((w2 + 2.0).abs() < 1.0e-2, "Unexpected velocity: {}", w2);
+
+    // The small wheel made more than a full turn, which the gear keeps track of.
+    let (turns1, turns2) = gear.borrow().turns();
+    assert!(turns1 > 12.0, "Unexpected turns: {}", turns1);
+    assert!((turns2 + turns1 * 0.5).abs() < 1.0e-2, "Unexpected turns: {}", turns2);
+    assert!(gear.borrow().error().abs() < 1.0e-2);
+    assert_eq!(world.summary().num_gears, 1);
+}
+
+#[test]
+fn gear_transmits_the_momentum() {
+    let mut world = World::new();
+    let (rb1, hinge1) = wheel(&mut world, 0.0, 1.0);
+    let (rb2, hinge2) = wheel(&mut world, 3.0, 1.0);
+
+    let _ = world.add_gear(Gear::new(hinge1, hinge2, 1.0));
+    rb1.borrow_mut().set_ang_vel(Vector3::new(0.0, 0.0, 2.0));
+
+    for _ in 0 .. 10 {
+        world.step(0.016);
+    }
+
+    // Both identical wheels end up spinning together at half the initial speed.
+    assert!((rb1.borrow().ang_vel().z - 1.0).abs() < 1.0e-2);
+    assert!((rb2.borrow().ang_vel().z - 1.0).abs() < 1.0e-2);
+}
+
+#[test]
+fn removing_the_gear_decouples_the_hinges() {
+    let mut world = World::new();
+    let (rb1, hinge1) = wheel(&mut world, 0.0, 1.0);
+    let (rb2, hinge2) = wheel(&mut world, 3.0, 1.0);
+
+    let gear = world.add_gear(Gear::new(hinge1, hinge2, 1.0));
+    world.step(0.016);
+    world.remove_gear(&gear);
+
+    rb1.borrow_mut().set_ang_vel(Vector3::new(0.0, 0.0, 2.0));
+
+    for _ in 0 .. 10 {
+        world.step(0.016);
+    }
+
+    assert!((rb1.borrow().ang_vel().z - 2.0).abs() < 1.0e-2);
+    assert_eq!(rb2.borrow().ang_vel().z, 0.0);
+    assert_eq!(world.summary().num_gears, 0);
+}
@@ -0,0 +1,72 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// A box falling asleep on the ground.
+fn sleeping_box(world: &mut World<f32>) -> RigidBodyHandle<f32> {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+    rb.set_translation(Translation3::new(0.0, 0.6, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    assert!(!rb.borrow().is_active());
+
+    rb
+}
+
+#[test]
+fn flipped_gravity_wakes_sleeping_bodies_up() {
+    let mut world = World::new();
+    let rb = sleeping_box(&mut world);
+    let y = rb.borrow().position().translation.vector.y;
+
+    world.set_gravity(Vector3::new(0.0, 9.81, 0.0));
+    assert_eq!(world.gravity(), Vector3::new(0.0, 9.81, 0.0));
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    assert!(rb.borrow().position().translation.vector.y > y + 1.0);
+}
+
+#[test]
+fn unchanged_gravity_lets_bodies_sleep() {
+    let mut world = World::new();
+    let rb = sleeping_box(&mut world);
+
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    assert!(!rb.borrow().is_active());
+}
+
+#[test]
+fn angular_acceleration_spins_every_body() {
+    let mut world = World::new();
+
+    let rb1 = world.add_rigid_body(RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6));
+    let mut rb2 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(2.0f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+    rb2.set_translation(Translation3::new(10.0, 0.0, 0.0));
+    let rb2 = world.add_rigid_body(rb2);
+
+    world.set_angular_acceleration(Vector3::new(0.0, 1.0, 0.0));
+    assert_eq!(world.angular_acceleration(), Vector3::new(0.0, 1.0, 0.0));
+
+    for _ in 0 .. 10 {
+        world.step(0.1);
+    }
+
+    // The acceleration does not depend on the inertia.
+    assert!((rb1.borrow().ang_vel().y - 1.0).abs() < 1.0e-4);
+    assert!((rb2.borrow().ang_vel().y - 1.0).abs() < 1.0e-4);
+}
@@ -0,0 +1,92 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Ball;
+use ncollide::bounding_volume::AABB;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::integration::GravityZone;
+
+fn add_ball(world: &mut World<f32>, x: f32, y: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(x, y, 0.0));
+
+    world.add_rigid_body(rb)
+}
+
+fn zero_g_room() -> GravityZone<f32> {
+    GravityZone::uniform(AABB::new(Point3::new(-5.0, -5.0, -5.0), Point3::new(5.0, 5.0, 5.0)), na::zero())
+}
+
+#[test]
+fn bodies_float_in_a_zero_g_room() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.register_gravity_zone("room", zero_g_room());
+
+    let inside  = add_ball(&mut world, 0.0, 0.0);
+    let outside = add_ball(&mut world, 20.0, 0.0);
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    assert_eq!(inside.borrow().lin_vel(), na::zero());
+    assert!(outside.borrow().lin_vel().y < -4.0);
+}
+
+#[test]
+fn bodies_fall_towards_a_planet() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.register_gravity_zone("planet", GravityZone::spherical(Point3::new(100.0, 0.0, 0.0), 20.0, 5.0));
+
+    let left  = add_ball(&mut world, 90.0, 0.0);
+    let above = add_ball(&mut world, 100.0, 10.0);
+
+    world.step(0.1);
+
+    assert!(na::norm(&(left.borrow().lin_vel() - Vector3::new(0.5, 0.0, 0.0))) < 1.0e-5);
+    assert!(na::norm(&(above.borrow().lin_vel() - Vector3::new(0.0, -0.5, 0.0))) < 1.0e-5);
+}
+
+#[test]
+fn the_last_registered_zone_applies() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.register_gravity_zone("room", zero_g_room());
+    world.register_gravity_zone("planet", GravityZone::spherical(Point3::new(0.0, -3.0, 0.0), 10.0, 2.0));
+
+    let rb = add_ball(&mut world, 0.0, 0.0);
+    world.step(0.1);
+    assert!(na::norm(&(rb.borrow().lin_vel() - Vector3::new(0.0, -0.2, 0.0))) < 1.0e-5);
+
+    world.unregister_gravity_zone("planet");
+
+    rb.borrow_mut().set_lin_vel(na::zero());
+    world.step(0.1);
+    assert_eq!(rb.borrow().lin_vel(), na::zero());
+
+    world.unregister_gravity_zone("room");
+    world.step(0.1);
+    assert!(rb.borrow().lin_vel().y < -0.9);
+}
+
+#[test]
+fn sleeping_bodies_wake_up_when_a_zone_is_registered() {
+    let mut world = World::new();
+    let rb = add_ball(&mut world, 0.0, 0.0);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    assert!(!rb.borrow().is_active());
+
+    world.register_gravity_zone("planet", GravityZone::spherical(Point3::new(0.0, -3.0, 0.0), 10.0, 2.0));
+    world.step(0.016);
+
+    assert!(rb.borrow().lin_vel().y < 0.0);
+}
@@ -0,0 +1,72 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::Vector3;
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::integration::BodySmpEulerIntegrator;
+
+// The angular momentum and the rotational energy of `rb`.
+fn momentum_and_energy(rb: &RigidBodyHandle<f32>) -> (Vector3<f32>, f32) {
+    let rb       = rb.borrow();
+    let av       = rb.ang_vel();
+    let momentum = rb.inv_inertia().try_inverse().unwrap() * av;
+
+    (momentum, na::dot(&av, &momentum) * 0.5)
+}
+
+// An elongated box spinning around an axis which is not one of its principal axes.
+fn spin(gyroscopic: bool) -> (RigidBodyHandle<f32>, Vector3<f32>, Vector3<f32>, f32, f32) {
+    let mut world = World::new();
+    let mut integrator = BodySmpEulerIntegrator::new();
+    integrator.set_gyroscopic(gyroscopic);
+    world.set_integrator(integrator);
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 0.2, 0.1)), 1.0, 0.3, 0.6);
+    rb.set_ang_vel(Vector3::new(0.5, 2.0, 0.2));
+    let rb = world.add_rigid_body(rb);
+
+    let (momentum0, energy0) = momentum_and_energy(&rb);
+
+    for _ in 0 .. 400 {
+        world.step(0.016);
+    }
+
+    let (momentum, energy) = momentum_and_energy(&rb);
+
+    (rb, momentum0, momentum, energy0, energy)
+}
+
+#[test]
+fn gyroscopic_torques_preserve_the_angular_momentum() {
+    let (_, momentum0, momentum, energy0, energy) = spin(true);
+    let (_, _, free_momentum, _, _) = spin(false);
+
+    let drift      = (momentum - momentum0).norm();
+    let free_drift = (free_momentum - momentum0).norm();
+
+    assert!(drift < 0.1 * momentum0.norm(), "Unexpected momentum: {}", momentum);
+    assert!(drift < 0.5 * free_drift, "Unexpected momentum: {} {}", momentum, free_momentum);
+
+    // The implicit integration is stable: it may lose some energy but never gains any.
+    assert!(energy <= energy0, "Unexpected energy: {}", energy);
+}
+
+#[test]
+fn gyroscopic_torques_are_disabled_by_default() {
+    assert!(!BodySmpEulerIntegrator::new().gyroscopic());
+
+    let (rb, _, _, energy0, energy) = spin(false);
+
+    assert!((rb.borrow().ang_vel() - Vector3::new(0.5, 2.0, 0.2)).norm() < 1.0e-5);
+    assert!((energy - energy0).abs() < 1.0e-3 * energy0);
+}
+
+#[test]
+fn the_default_integrator_is_configurable() {
+    let mut world = World::<f32>::new();
+    world.integrator().set_gyroscopic(true);
+    assert!(world.integrator().gyroscopic());
+}
@@ -0,0 +1,45 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, heightmap_from_image};
+
+#[test]
+fn heightmap_vertices_and_normals() {
+    // A slope rising along `x`.
+    let pixels = [0u8, 255, 255,
+                  0,   255, 255];
+    let mesh   = heightmap_from_image(&pixels, 3, 2, Vector3::new(4.0f32, 2.0, 1.0));
+
+    assert_eq!(mesh.vertices().len(), 6);
+    assert_eq!(mesh.indices().len(), 4);
+    assert_eq!(mesh.vertices()[0], na::Point3::new(-2.0, 0.0, -0.5));
+    assert_eq!(mesh.vertices()[5], na::Point3::new(2.0, 2.0, 0.5));
+
+    let normals = mesh.normals().as_ref().unwrap();
+    assert!(normals[0].x < 0.0 && normals[0].y > 0.0);
+    assert!(normals[2].x == 0.0 && normals[2].y == 1.0);
+}
+
+#[test]
+fn ball_rests_on_heightmap() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let pixels = [128u8; 16];
+    let _      = world.add_rigid_body(RigidBody::new_static_heightmap(&pixels, 4, 4, Vector3::new(10.0, 2.0, 10.0), 0.0, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    ball.append_translation(&Translation3::new(0.1, 3.0, 0.1));
+    let ball = world.add_rigid_body(ball);
+
+    for _ in 0 .. 200 {
+        world.step(1.0 / 60.0);
+    }
+
+    let y = ball.borrow().position().translation.vector.y;
+    assert!((y - (128.0 / 255.0 * 2.0 + 0.5)).abs() < 0.1, "{}", y);
+}
@@ -0,0 +1,80 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Point3, Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Hinge};
+
+// A horizontal pendulum hanging from a hinge at (0, 5, 0) rotating around `z`.
+fn pendulum(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Hinge<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 0.1, 0.1)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(2.0, 5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), Isometry3::new(Vector3::new(-2.0, 0.0, 0.0), na::zero()));
+    let hinge   = world.add_hinge(Hinge::new(anchor1, anchor2, Vector3::z()));
+
+    (rb, hinge)
+}
+
+#[test]
+fn hinge_rotates_around_its_axis_only() {
+    let mut world = World::new();
+    let (rb, hinge) = pendulum(&mut world);
+
+    // This initial rotation around `x` is cancelled by the hinge.
+    rb.borrow_mut().set_ang_vel(Vector3::new(1.0, 0.0, 0.0));
+
+    let mut min_angle = 0.0f32;
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+
+        let rb  = rb.borrow();
+        let pos = rb.position();
+        let center = Point3::from_coordinates(pos.translation.vector);
+
+        assert!(center.z.abs() < 0.05, "The pendulum left its plane: {}", center);
+        assert!(((center - Point3::new(0.0, 5.0, 0.0)).norm() - 2.0).abs() < 0.05);
+
+        let swing = pos.rotation.scaled_axis();
+        assert!(swing.x.abs() < 0.05 && swing.y.abs() < 0.05, "Unexpected rotation: {}", swing);
+
+        min_angle = min_angle.min(hinge.borrow().angle());
+    }
+
+    // The pendulum swung down.
+    assert!(min_angle < -1.0);
+}
+
+#[test]
+fn hinge_angle_limits() {
+    let mut world = World::new();
+    let (rb, hinge) = pendulum(&mut world);
+
+    hinge.borrow_mut().set_limits(Some((-0.5, 0.5)));
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+
+        let angle = hinge.borrow().angle();
+        assert!(angle > -0.6 && angle < 0.6, "Limits exceeded: {}", angle);
+    }
+
+    // The pendulum rests on its lower limit.
+    assert!((hinge.borrow().angle() + 0.5).abs() < 0.05);
+    assert!(rb.borrow().position().translation.vector.y < 5.0);
+
+    assert_eq!(world.summary().num_hinges, 1);
+
+    world.remove_hinge(&hinge);
+    assert_eq!(world.summary().num_hinges, 0);
+}
@@ -0,0 +1,84 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Translation3};
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, BallInSocket};
+
+#[test]
+fn island_statistics_and_bridges() {
+    let mut world = World::new();
+    let mut chain: Vec<RigidBodyHandle<f32>> = Vec::new();
+
+    // A chain of 7 bodies, plus an isolated one.
+    for i in 0usize .. 8 {
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.1), 1.0, 0.3, 0.6);
+        rb.append_translation(&Translation3::new(i as f32, 0.0, 0.0));
+        let rb = world.add_rigid_body(rb);
+
+        if i != 0 && i < 7 {
+            let anchor1 = Anchor::new(Some(chain[i - 1].clone()), Point3::new(0.5, 0.0, 0.0));
+            let anchor2 = Anchor::new(Some(rb.clone()), Point3::new(-0.5, 0.0, 0.0));
+            let _ = world.add_ball_in_socket(BallInSocket::new(anchor1, anchor2));
+        }
+
+        chain.push(rb);
+    }
+
+    world.step(0.016);
+
+    let stats = world.island_statistics();
+    assert_eq!(stats.num_islands, 2);
+    assert_eq!(stats.largest, 7);
+    assert_eq!(stats.histogram, vec![1, 0, 1]);
+
+    let bridges = world.bridge_bodies(3);
+    assert_eq!(bridges.len(), 1);
+    assert!(&*bridges[0].body as *const _ == &*chain[3] as *const _);
+    assert_eq!(bridges[0].island_size, 7);
+    assert_eq!(bridges[0].parts, vec![3, 3]);
+}
+
+#[test]
+fn islands_list_interacting_bodies() {
+    let mut world = World::new();
+    let mut bodies: Vec<RigidBodyHandle<f32>> = Vec::new();
+
+    // Two pairs of jointed bodies, an isolated body, and a static one.
+    for i in 0usize .. 5 {
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.1), 1.0, 0.3, 0.6);
+        rb.append_translation(&Translation3::new(i as f32, 0.0, 0.0));
+        let rb = world.add_rigid_body(rb);
+
+        if i == 1 || i == 3 {
+            let anchor1 = Anchor::new(Some(bodies[i - 1].clone()), Point3::new(0.5, 0.0, 0.0));
+            let anchor2 = Anchor::new(Some(rb.clone()), Point3::new(-0.5, 0.0, 0.0));
+            let _ = world.add_ball_in_socket(BallInSocket::new(anchor1, anchor2));
+        }
+
+        bodies.push(rb);
+    }
+
+    let mut ground = RigidBody::new_static(Ball::new(0.1), 0.3, 0.6);
+    ground.append_translation(&Translation3::new(0.0, -10.0, 0.0));
+    let _ = world.add_rigid_body(ground);
+
+    world.step(0.016);
+
+    let island_of = |islands: &Vec<Vec<RigidBodyHandle<f32>>>, rb: &RigidBodyHandle<f32>| {
+        islands.iter().position(|island| island.iter().any(|b| &**b as *const _ == &**rb as *const _))
+    };
+
+    let islands = world.islands();
+    let mut sizes: Vec<usize> = islands.iter().map(|island| island.len()).collect();
+    sizes.sort();
+
+    assert_eq!(sizes, vec![1, 2, 2]);
+    assert_eq!(island_of(&islands, &bodies[0]), island_of(&islands, &bodies[1]));
+    assert_eq!(island_of(&islands, &bodies[2]), island_of(&islands, &bodies[3]));
+    assert!(island_of(&islands, &bodies[1]) != island_of(&islands, &bodies[2]));
+    assert!(island_of(&islands, &bodies[4]).is_some());
+}
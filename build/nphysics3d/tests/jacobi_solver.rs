@@ -0,0 +1,101 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::f32;
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::resolution::{self, VelocityConstraint, Velocities, FrictionModel, IterationScheme};
+
+// A contact at `point` relative to the center of mass of a body of unit mass and inertia,
+// with a normal along `y` and the opposite of the normal velocity `objective`.
+fn contact(point: Vector3<f32>, objective: f32) -> VelocityConstraint<f32> {
+    let mut c = VelocityConstraint::new();
+
+    c.normal             = Vector3::y();
+    c.normal2            = Vector3::y();
+    c.weighted_normal2   = Vector3::y();
+    c.rot_axis2          = point.cross(&Vector3::y());
+    c.weighted_rot_axis2 = c.rot_axis2;
+    c.inv_projected_mass = 1.0 / (1.0 + na::norm_squared(&c.rot_axis2));
+    c.hibound            = f32::MAX;
+    c.objective          = objective;
+    c.id2                = 0;
+
+    c
+}
+
+// The impulses of three contacts solved in the given order.
+fn impulses(scheme: IterationScheme, order: [usize; 3]) -> [f32; 3] {
+    let all = [ contact(Vector3::new(1.0, 0.0, 0.0), 1.0),
+                contact(Vector3::new(-0.5, 0.0, 0.5), 1.0),
+                contact(Vector3::new(0.0, 0.0, -1.0), 0.5) ];
+    let mut contacts: Vec<_> = order.iter().map(|i| all[*i].clone()).collect();
+    let mut result = [ Velocities::new() ];
+
+    match scheme {
+        IterationScheme::GaussSeidel =>
+            resolution::projected_gauss_seidel_solve(&mut contacts[..], &mut [][..], &mut result[..], 1, 2, true),
+        IterationScheme::Jacobi =>
+            resolution::projected_jacobi_solve(&mut contacts[..], &mut [][..], FrictionModel::Pyramid,
+                                               &mut result[..], 1, 2, true)
+    }
+
+    let mut impulses = [ 0.0; 3 ];
+
+    for (i, c) in order.iter().zip(contacts.iter()) {
+        impulses[*i] = c.impulse;
+    }
+
+    impulses
+}
+
+#[test]
+fn jacobi_iterations_do_not_depend_on_the_constraint_order() {
+    let jacobi1 = impulses(IterationScheme::Jacobi, [ 0, 1, 2 ]);
+    let jacobi2 = impulses(IterationScheme::Jacobi, [ 2, 1, 0 ]);
+    let gs1     = impulses(IterationScheme::GaussSeidel, [ 0, 1, 2 ]);
+    let gs2     = impulses(IterationScheme::GaussSeidel, [ 2, 1, 0 ]);
+
+    for i in 0 .. 3 {
+        assert!((jacobi1[i] - jacobi2[i]).abs() < 1.0e-6, "{:?} != {:?}", jacobi1, jacobi2);
+    }
+
+    assert!((0 .. 3).any(|i| (gs1[i] - gs2[i]).abs() > 1.0e-3));
+}
+
+fn stack(world: &mut World<f32>) -> Vec<RigidBodyHandle<f32>> {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    (0 .. 3).map(|i| {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+        rb.set_translation(Translation3::new(0.0, 0.55 + 1.1 * i as f32, 0.0));
+        world.add_rigid_body(rb)
+    }).collect()
+}
+
+#[test]
+fn jacobi_iterations_keep_a_stack_standing() {
+    let mut world = World::new();
+    world.constraints_solver().set_iteration_scheme(IterationScheme::Jacobi);
+    world.constraints_solver().set_num_second_order_iter(30);
+    assert_eq!(world.constraints_solver().iteration_scheme(), IterationScheme::Jacobi);
+
+    let boxes = stack(&mut world);
+    let start: Vec<_> = boxes.iter().map(|rb| rb.borrow().position().translation.vector).collect();
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    // The boxes settle by the margins of their shapes, and creep slightly like with Gauss-Seidel.
+    for (rb, start) in boxes.iter().zip(start.iter()) {
+        let pos      = rb.borrow().position().translation.vector;
+        let expected = *start;
+
+        assert!(na::norm(&(pos - expected)) < 0.1, "Unexpected position: {}", pos);
+    }
+}
@@ -0,0 +1,85 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, BallInSocket, Hinge};
+
+fn bar(world: &mut World<f32>, x: f32, width: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, width, width)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(x, 5.0, 0.0));
+    world.add_rigid_body(rb)
+}
+
+// The largest angular speed of a horizontal pendulum hinged at (0, 5, 0) during its last 100 steps.
+fn swinging_speed(damping: f32) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let rb      = bar(&mut world, 2.0, 0.1);
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), Isometry3::new(Vector3::new(-2.0, 0.0, 0.0), na::zero()));
+    let hinge   = world.add_hinge(Hinge::new(anchor1, anchor2, Vector3::z()));
+
+    hinge.borrow_mut().set_angular_damping(damping);
+    assert_eq!(hinge.borrow().angular_damping(), damping);
+
+    let mut max_speed = 0.0f32;
+
+    for i in 0 .. 400 {
+        world.step(0.016);
+
+        if i >= 300 {
+            max_speed = max_speed.max(rb.borrow().ang_vel().norm());
+        }
+    }
+
+    max_speed
+}
+
+#[test]
+fn hinge_damping_stops_the_swing() {
+    let free   = swinging_speed(0.0);
+    let damped = swinging_speed(2.0);
+
+    assert!(free > 1.0, "The free pendulum stopped: {}", free);
+    assert!(damped < 0.1 * free, "The damped pendulum still swings: {} (free: {})", damped, free);
+}
+
+#[test]
+fn ball_in_socket_damping_only_resists_relative_rotations() {
+    let mut world = World::new();
+
+    let rb1 = bar(&mut world, -1.5, 0.5);
+    let rb2 = bar(&mut world, 1.5, 0.5);
+
+    // The bars are separated by a gap so that they do not collide.
+    let anchor1 = Anchor::new(Some(rb1.clone()), Point3::new(1.5, 0.0, 0.0));
+    let anchor2 = Anchor::new(Some(rb2.clone()), Point3::new(-1.5, 0.0, 0.0));
+    let joint   = world.add_ball_in_socket(BallInSocket::new(anchor1, anchor2));
+
+    joint.borrow_mut().set_angular_damping(2.0);
+
+    // The second bar spins around its axis, and both bars drift.
+    rb1.borrow_mut().set_lin_vel(Vector3::new(0.0, 0.0, 1.0));
+    rb2.borrow_mut().set_lin_vel(Vector3::new(0.0, 0.0, 1.0));
+    rb2.borrow_mut().set_ang_vel(Vector3::new(2.0, 0.0, 0.0));
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    let rb1 = rb1.borrow();
+    let rb2 = rb2.borrow();
+
+    // Both bars end up spinning together with the initial angular momentum shared.
+    let relative = rb2.ang_vel() - rb1.ang_vel();
+    assert!(relative.norm() < 0.1, "The relative rotation was not damped: {}", relative);
+    assert!((rb1.ang_vel().x - 1.0).abs() < 0.1, "Unexpected angular velocity: {}", rb1.ang_vel());
+
+    // Their common motion is not slowed down.
+    assert!((rb1.lin_vel().z - 1.0).abs() < 0.01 && (rb2.lin_vel().z - 1.0).abs() < 0.01);
+}
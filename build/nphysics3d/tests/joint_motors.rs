@@ -0,0 +1,160 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Hinge, Prismatic, JointMotor};
+
+// A wheel hinged around `z` at its center (0, 5, 0).
+fn wheel(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Hinge<f32>>>) {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 1.0, 0.2)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), na::one());
+    let hinge   = world.add_hinge(Hinge::new(anchor1, anchor2, Vector3::z()));
+
+    (rb, hinge)
+}
+
+// A box of mass 1 on a vertical slider going through (0, 5, 0).
+fn elevator(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Prismatic<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), na::one());
+    let slider  = world.add_prismatic(Prismatic::new(anchor1, anchor2, Vector3::y()));
+
+    (rb, slider)
+}
+
+#[test]
+fn hinge_velocity_motor_spins_a_wheel() {
+    let mut world = World::new();
+    let (rb, hinge) = wheel(&mut world);
+
+    hinge.borrow_mut().set_motor(Some(JointMotor::velocity(2.0, 100.0)));
+    assert_eq!(hinge.borrow().motor(), Some(&JointMotor::velocity(2.0, 100.0)));
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    let w = rb.borrow().ang_vel();
+    assert!((w - Vector3::z() * 2.0).norm() < 1.0e-2, "Unexpected angular velocity: {}", w);
+
+    // Without its motor, the wheel keeps spinning freely.
+    hinge.borrow_mut().set_motor(None);
+    rb.borrow_mut().set_ang_vel(Vector3::z() * 3.0);
+    world.step(0.016);
+    assert!((rb.borrow().ang_vel().z - 3.0).abs() < 1.0e-2);
+}
+
+#[test]
+fn hinge_servo_reaches_its_target_angle() {
+    let mut world = World::new();
+    let (_, hinge) = wheel(&mut world);
+
+    hinge.borrow_mut().set_motor(Some(JointMotor::servo(1.0, 2.0, 100.0)));
+
+    // The servo turns at its maximal speed first.
+    for _ in 0 .. 10 {
+        world.step(0.016);
+    }
+
+    let angle = hinge.borrow().angle();
+    assert!(angle > 0.25 && angle < 0.33, "Unexpected angle: {}", angle);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    assert!((hinge.borrow().angle() - 1.0).abs() < 0.02, "Unexpected angle: {}", hinge.borrow().angle());
+}
+
+#[test]
+fn hinge_servo_turns_the_shortest_way() {
+    let mut world = World::new();
+    let (rb, hinge) = wheel(&mut world);
+
+    // Start at -3 radians, close to -pi.
+    rb.borrow_mut().set_rotation(na::UnitQuaternion::from_scaled_axis(Vector3::z() * -3.0));
+    assert!((hinge.borrow().angle() + 3.0).abs() < 1.0e-4);
+
+    // The target at 3 radians is 2 pi - 6 radians away through -pi.
+    hinge.borrow_mut().set_motor(Some(JointMotor::servo(3.0, 1.0, 100.0)));
+    world.step(0.016);
+
+    let w = rb.borrow().ang_vel().z;
+    assert!((w + 1.0).abs() < 1.0e-2, "Unexpected angular velocity: {}", w);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    assert!((hinge.borrow().angle() - 3.0).abs() < 0.02, "Unexpected angle: {}", hinge.borrow().angle());
+}
+
+#[test]
+fn prismatic_velocity_motor_lifts_a_box() {
+    let mut world = World::new();
+    let (rb, slider) = elevator(&mut world);
+
+    slider.borrow_mut().set_motor(Some(JointMotor::velocity(1.0, 100.0)));
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    let start = slider.borrow().translation();
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    // The box rises at the target speed.
+    let speed = (slider.borrow().translation() - start) / 0.8;
+    assert!((speed - 1.0).abs() < 1.0e-2, "Unexpected speed: {}", speed);
+    assert!(rb.borrow().lin_vel().x.abs() < 1.0e-3 && rb.borrow().lin_vel().z.abs() < 1.0e-3);
+}
+
+#[test]
+fn prismatic_servo_holds_a_box_in_place() {
+    let mut world = World::new();
+    let (_, slider) = elevator(&mut world);
+
+    slider.borrow_mut().set_motor(Some(JointMotor::servo(0.5, 1.0, 100.0)));
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    assert!((slider.borrow().translation() - 0.5).abs() < 0.02, "Unexpected translation: {}", slider.borrow().translation());
+}
+
+#[test]
+fn weak_motor_cannot_lift_a_box() {
+    let mut world = World::new();
+    let (rb, slider) = elevator(&mut world);
+
+    // The weight of the box is 9.81 but the motor pulls with a force of 5 only.
+    slider.borrow_mut().set_motor(Some(JointMotor::velocity(1.0, 5.0)));
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    // The box falls with an acceleration of 4.81.
+    let v = rb.borrow().lin_vel().y;
+    assert!((v + 4.81 * 0.8).abs() < 0.1, "Unexpected velocity: {}", v);
+}
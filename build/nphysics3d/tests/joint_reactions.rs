@@ -0,0 +1,84 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, BallInSocket};
+
+fn add_box(world: &mut World<f32>, y: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, y, 0.0));
+
+    world.add_rigid_body(rb)
+}
+
+// Two boxes of mass 1 hanging from (0, 5, 0), the second one below the first one.
+fn hanging_chain(world: &mut World<f32>) -> (Rc<RefCell<BallInSocket<f32>>>, Rc<RefCell<BallInSocket<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let rb1 = add_box(world, 4.0);
+    let rb2 = add_box(world, 2.0);
+
+    let top = world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Point3::new(0.0, 5.0, 0.0)),
+                                                         Anchor::new(Some(rb1.clone()), Point3::new(0.0, 1.0, 0.0))));
+    let mid = world.add_ball_in_socket(BallInSocket::new(Anchor::new(Some(rb1), Point3::new(0.0, -1.0, 0.0)),
+                                                         Anchor::new(Some(rb2), Point3::new(0.0, 1.0, 0.0))));
+
+    (top, mid)
+}
+
+#[test]
+fn joints_carry_the_weight_below_them() {
+    let mut world = World::new();
+    let (top, mid) = hanging_chain(&mut world);
+
+    assert!(world.joint_reaction(&top).is_none());
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    let top = world.joint_reaction(&top).unwrap();
+    let mid = world.joint_reaction(&mid).unwrap();
+
+    assert!(na::norm(&(top.force2 - Vector3::new(0.0, 2.0 * 9.81, 0.0))) < 0.2);
+    assert!(na::norm(&(mid.force2 - Vector3::new(0.0, 9.81, 0.0))) < 0.1);
+    assert!(na::norm(&(mid.force1 + mid.force2)) < 1.0e-3);
+    // The boxes hang straight below their anchors.
+    assert!(na::norm(&mid.torque1) < 0.1 && na::norm(&mid.torque2) < 0.1);
+    assert_eq!(world.joint_reactions().len(), 2);
+}
+
+#[test]
+fn sleeping_joints_keep_their_last_reaction() {
+    let mut world = World::new();
+    let (top, _) = hanging_chain(&mut world);
+
+    for _ in 0 .. 500 {
+        world.step(0.016);
+    }
+
+    assert!(!world.rigid_bodies().any(|rb| rb.borrow().is_active()));
+
+    let reaction = world.joint_reaction(&top).unwrap();
+    assert!(na::norm(&(reaction.force2 - Vector3::new(0.0, 2.0 * 9.81, 0.0))) < 0.2);
+}
+
+#[test]
+fn removed_joints_have_no_reaction() {
+    let mut world = World::new();
+    let (top, mid) = hanging_chain(&mut world);
+
+    world.step(0.016);
+    world.remove_ball_in_socket(&mid);
+    world.step(0.016);
+
+    assert!(world.joint_reaction(&top).is_some());
+    assert!(world.joint_reaction(&mid).is_none());
+    assert_eq!(world.joint_reactions().len(), 1);
+}
@@ -0,0 +1,76 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::constraint::Constraint;
+use nphysics3d::detection::joint::{Anchor, BallInSocket, Spring};
+
+fn add_box(world: &mut World<f32>, y: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, y, 0.0));
+
+    world.add_rigid_body(rb)
+}
+
+// Two boxes hanging from (0, 5, 0), the second one below the first one.
+fn hanging_chain(world: &mut World<f32>) -> (RigidBodyHandle<f32>, RigidBodyHandle<f32>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let rb1 = add_box(world, 4.0);
+    let rb2 = add_box(world, 2.0);
+
+    let _ = world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Point3::new(0.0, 5.0, 0.0)),
+                                                       Anchor::new(Some(rb1.clone()), Point3::new(0.0, 1.0, 0.0))));
+    let _ = world.add_spring(Spring::new(Anchor::new(Some(rb1.clone()), Point3::new(0.0, -1.0, 0.0)),
+                                         Anchor::new(Some(rb2.clone()), Point3::new(0.0, 1.0, 0.0)),
+                                         0.0, 100.0, 0.0));
+
+    (rb1, rb2)
+}
+
+#[test]
+fn joints_are_enumerated_and_removed() {
+    let mut world = World::new();
+    let _ = hanging_chain(&mut world);
+
+    assert_eq!(world.joints().count(), 2);
+
+    let spring = world.joints().find(|j| match **j { Constraint::Spring(_) => true, _ => false }).unwrap().clone();
+    world.remove_joint(&spring);
+
+    assert_eq!(world.joints().count(), 1);
+    assert_eq!(world.summary().num_springs, 0);
+    assert_eq!(world.summary().num_ball_in_sockets, 1);
+
+    // Removing a joint twice does nothing.
+    world.remove_joint(&spring);
+    assert_eq!(world.joints().count(), 1);
+}
+
+#[test]
+fn removing_a_body_removes_its_joints() {
+    let mut world = World::new();
+    let (rb1, rb2) = hanging_chain(&mut world);
+
+    for _ in 0 .. 10 {
+        world.step(0.016);
+    }
+
+    world.remove_rigid_body(&rb1);
+
+    assert_eq!(world.joints().count(), 0);
+    assert!(world.joint_manager().joints_with_body(&rb2).map(|js| js.is_empty()).unwrap_or(true));
+
+    // The second box falls freely.
+    let y = rb2.borrow().position().translation.vector.y;
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    assert!(rb2.borrow().position().translation.vector.y < y - 1.0);
+}
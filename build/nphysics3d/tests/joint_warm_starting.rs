@@ -0,0 +1,80 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle, WorldObject};
+use nphysics3d::detection::joint::{Anchor, BallInSocket};
+
+// A chain of `n` boxes of mass 1 hanging from (0, 0, 0), solved with few iterations.
+fn hanging_chain(world: &mut World<f32>, n: usize) -> RigidBodyHandle<f32> {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.constraints_solver().set_num_first_order_iter(2);
+
+    let mut parent: Option<RigidBodyHandle<f32>> = None;
+
+    for i in 0 .. n {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+        rb.set_translation(Translation3::new(0.0, -1.0 - 2.0 * i as f32, 0.0));
+        rb.set_deactivation_threshold(None);
+        let rb = world.add_rigid_body(rb);
+
+        let anchor1 = match parent {
+            Some(ref p) => Anchor::new(Some(p.clone()), Point3::new(0.0, -1.0, 0.0)),
+            None        => Anchor::new(None, Point3::origin())
+        };
+        let _ = world.add_ball_in_socket(BallInSocket::new(anchor1, Anchor::new(Some(rb.clone()), Point3::new(0.0, 1.0, 0.0))));
+
+        parent = Some(rb);
+    }
+
+    parent.unwrap()
+}
+
+// The height of the bottom of the chain and the residual of the solver after 100 steps.
+fn settled_chain(warm_started: bool) -> (f32, f32) {
+    let mut world = World::new();
+    let bottom = hanging_chain(&mut world, 10);
+
+    for _ in 0 .. 100 {
+        if !warm_started {
+            world.constraints_solver().clear_impulse_cache();
+        }
+
+        world.step(0.016);
+    }
+
+    let y = bottom.borrow().position().translation.vector.y;
+
+    (y, world.constraints_solver().max_residual())
+}
+
+#[test]
+fn warm_started_joints_converge_with_few_iterations() {
+    let (warm_y, warm_residual) = settled_chain(true);
+    let (cold_y, cold_residual) = settled_chain(false);
+
+    assert!(warm_residual < 0.2 * cold_residual, "warm: {}, cold: {}", warm_residual, cold_residual);
+    assert!(warm_y > cold_y, "warm: {}, cold: {}", warm_y, cold_y);
+}
+
+#[test]
+fn predictions_are_warm_started_like_the_world() {
+    let mut world = World::new();
+    let bottom = hanging_chain(&mut world, 10);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    let (mut prediction, copies) = world.clone_for_prediction();
+    let copy = copies.find(&WorldObject::rigid_body_uid(&bottom)).unwrap().clone();
+
+    world.step(0.016);
+    prediction.step(0.016);
+
+    assert_eq!(bottom.borrow().position(), copy.borrow().position());
+    assert_eq!(world.constraints_solver().max_residual(), prediction.constraints_solver().max_residual());
+}
@@ -0,0 +1,83 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::CollisionEvent;
+use nphysics3d::detection::joint::{Anchor, Joint, BallInSocket};
+
+fn add_box(world: &mut World<f32>, x: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(x, 0.0, 0.0));
+
+    world.add_rigid_body(rb)
+}
+
+// Two overlapping boxes attached at the middle of their overlap.
+fn overlapping_boxes(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<BallInSocket<f32>>>) {
+    let rb1 = add_box(world, 0.0);
+    let rb2 = add_box(world, 0.8);
+
+    let joint = world.add_ball_in_socket(BallInSocket::new(Anchor::new(Some(rb1.clone()), Point3::new(0.4, 0.0, 0.0)),
+                                                           Anchor::new(Some(rb2), Point3::new(-0.4, 0.0, 0.0))));
+
+    (rb1, joint)
+}
+
+#[test]
+fn jointed_bodies_do_not_collide_by_default() {
+    let mut world = World::new();
+    let (rb1, joint) = overlapping_boxes(&mut world);
+
+    assert!(!joint.borrow().collisions_enabled());
+
+    for _ in 0 .. 10 {
+        world.step(0.016);
+        assert_eq!(world.contact_force(&rb1), na::zero());
+    }
+
+    assert!(na::norm(&rb1.borrow().position().translation.vector) < 1.0e-3);
+}
+
+#[test]
+fn jointed_bodies_are_filtered_out_by_the_broad_phase() {
+    let mut world = World::new();
+    let (_, joint) = overlapping_boxes(&mut world);
+
+    world.enable_collision_event_queue();
+    world.step(0.016);
+
+    // No contact is computed, nor reported, for the jointed bodies.
+    assert_eq!(world.collision_world().contact_pairs().count(), 0);
+    assert!(world.drain_collision_events().iter().all(|e| match *e {
+        CollisionEvent::ContactStarted(_, _) => false,
+        _                                    => true
+    }));
+
+    joint.borrow_mut().set_collisions_enabled(true);
+    world.step(0.016);
+
+    assert_eq!(world.collision_world().contact_pairs().count(), 1);
+}
+
+#[test]
+fn jointed_bodies_collide_once_enabled() {
+    let mut world = World::new();
+    let (rb1, joint) = overlapping_boxes(&mut world);
+
+    joint.borrow_mut().set_collisions_enabled(true);
+    world.step(0.016);
+
+    assert!(world.contact_force(&rb1) != na::zero());
+
+    // Disabling the collisions again takes effect at the next step.
+    joint.borrow_mut().set_collisions_enabled(false);
+    world.step(0.016);
+
+    assert_eq!(world.contact_force(&rb1), na::zero());
+}
@@ -0,0 +1,101 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3, Isometry3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// A box resting on a large platform.
+fn box_on_platform(world: &mut World<f32>, kinematic: bool) -> (RigidBodyHandle<f32>, RigidBodyHandle<f32>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut platform = RigidBody::new_static(Cuboid::new(Vector3::new(10.0, 0.5, 10.0)), 0.0, 1.0);
+    platform.set_kinematic(kinematic);
+    let platform = world.add_rigid_body(platform);
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 1.0);
+    rb.set_translation(Translation3::new(0.0, 1.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    (platform, rb)
+}
+
+// Slides `platform` along `x` at one unit per second for `num_steps` steps.
+fn animate(world: &mut World<f32>, platform: &RigidBodyHandle<f32>, num_steps: usize) {
+    for _ in 0 .. num_steps {
+        platform.borrow_mut().append_translation(&Translation3::new(0.016, 0.0, 0.0));
+        world.step(0.016);
+    }
+}
+
+#[test]
+fn kinematic_platform_carries_a_box() {
+    let mut world = World::new();
+    let (platform, rb) = box_on_platform(&mut world, true);
+
+    animate(&mut world, &platform, 200);
+
+    // The velocity of the platform was derived from its animation.
+    assert!((platform.borrow().lin_vel() - Vector3::x()).norm() < 1.0e-3);
+
+    // The box is dragged by friction.
+    let rb = rb.borrow();
+    assert!((rb.lin_vel().x - 1.0).abs() < 0.05, "Unexpected velocity: {}", rb.lin_vel());
+    assert!(rb.position().translation.vector.x > 2.5);
+}
+
+#[test]
+fn static_platform_slides_under_a_box() {
+    let mut world = World::new();
+    let (platform, rb) = box_on_platform(&mut world, false);
+
+    animate(&mut world, &platform, 200);
+
+    // A moved static body is considered stationary.
+    assert!(rb.borrow().position().translation.vector.x.abs() < 0.05);
+}
+
+#[test]
+fn kinematic_platform_wakes_up_the_box() {
+    let mut world = World::new();
+    let (platform, rb) = box_on_platform(&mut world, true);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    assert!(!rb.borrow().is_active());
+
+    animate(&mut world, &platform, 10);
+
+    assert!(rb.borrow().is_active());
+    assert!(rb.borrow().lin_vel().x > 0.1);
+}
+
+#[test]
+fn kinematic_angular_velocity() {
+    let mut world = World::new();
+
+    let mut turntable = RigidBody::new_static(Cuboid::new(Vector3::new(2.0f32, 0.1, 2.0)), 0.0, 1.0);
+    turntable.set_kinematic(true);
+    let turntable = world.add_rigid_body(turntable);
+
+    for i in 0 .. 10 {
+        let angle = 0.5 * 0.016 * (i as f32);
+        turntable.borrow_mut().set_transformation(Isometry3::new(na::zero(), Vector3::y() * angle));
+        world.step(0.016);
+    }
+
+    let turntable = turntable.borrow();
+    assert!((turntable.ang_vel() - Vector3::y() * 0.5).norm() < 1.0e-2, "Unexpected velocity: {}", turntable.ang_vel());
+    assert!(turntable.lin_vel().norm() < 1.0e-3);
+}
+
+#[test]
+#[should_panic]
+fn dynamic_bodies_cannot_be_kinematic() {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 1.0);
+    rb.set_kinematic(true);
+}
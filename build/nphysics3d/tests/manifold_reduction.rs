@@ -0,0 +1,81 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::{Cuboid, Plane};
+use ncollide::query::Contact;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::detection::reduce_contact_manifold;
+
+fn contact(x: f32, z: f32, depth: f32) -> Contact<Point3<f32>> {
+    let p = Point3::new(x, 0.0, z);
+    Contact::new(p, p, -Vector3::y(), depth)
+}
+
+#[test]
+fn reduce_coplanar_grid() {
+    let mut contacts = Vec::new();
+
+    for i in 0 .. 3 {
+        for j in 0 .. 3 {
+            contacts.push(contact(i as f32, j as f32, 0.01));
+        }
+    }
+
+    contacts[4].depth = 0.1; // The center is the deepest.
+
+    reduce_contact_manifold(&mut contacts, 4);
+
+    assert_eq!(contacts.len(), 4);
+    assert!(contacts.iter().any(|c| c.depth == 0.1));
+
+    // The three other contacts are corners of the grid.
+    let corners = contacts.iter().filter(|c| c.world1.x != 1.0 && c.world1.z != 1.0).count();
+    assert_eq!(corners, 3);
+}
+
+#[test]
+fn reduce_aligned_contacts() {
+    let mut contacts: Vec<_> = (0 .. 5).map(|i| contact(i as f32, 0.0, 0.01)).collect();
+    contacts[2].depth = 0.1;
+
+    reduce_contact_manifold(&mut contacts, 4);
+
+    assert_eq!(contacts.len(), 2);
+    assert!(contacts.iter().any(|c| c.depth == 0.1));
+}
+
+#[test]
+fn stack_rests_with_reduction() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    assert!(!world.contact_manifold_reduction_enabled());
+    world.enable_contact_manifold_reduction();
+    assert!(world.contact_manifold_reduction_enabled());
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let mut top = None;
+
+    for i in 0 .. 3 {
+        let mut block = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.25, 0.5)), 1.0, 0.0, 0.5);
+        block.append_translation(&Translation3::new(0.0, 0.25 + 0.5 * i as f32, 0.0));
+        top = Some(world.add_rigid_body(block));
+    }
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+
+        let mut constraints = Vec::new();
+        world.constraints(&mut constraints);
+        assert!(constraints.len() <= 3 * 4);
+    }
+
+    let top = top.unwrap();
+    let pos = top.borrow().position().translation.vector;
+    // Still on top of the stack.
+    assert!(pos.x.abs() < 0.5 && pos.z.abs() < 0.5);
+    assert!(pos.y > 1.0 && pos.y < 1.5);
+}
@@ -0,0 +1,56 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Matrix3};
+use ncollide::shape::{Ball, ShapeHandle};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::volumetric;
+
+#[test]
+fn invalid_mass_makes_the_body_static() {
+    assert!(!RigidBody::new_dynamic(Ball::new(0.5f32), -1.0, 0.3, 0.6).can_move());
+    assert!(!RigidBody::new_dynamic(Ball::new(0.5f32), 0.0, 0.3, 0.6).can_move());
+    assert!(!RigidBody::new_dynamic(Ball::new(0.5f32), std::f32::NAN, 0.3, 0.6).can_move());
+}
+
+#[test]
+fn singular_inertia_is_inflated() {
+    let inertia = Matrix3::from_diagonal(&Vector3::new(1.0f32, 1.0, 0.0));
+    let mut rb  = RigidBody::new(ShapeHandle::new(Ball::new(0.5f32)), Some((1.0, Point3::origin(), inertia)), 0.3, 0.6);
+    assert!(rb.can_move());
+    assert!(rb.inv_inertia().iter().all(|e| e.is_finite()));
+
+    rb.set_ang_vel(Vector3::new(1.0, 2.0, 3.0));
+
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    let rb = rb.borrow();
+    assert!(rb.position().translation.vector.iter().all(|e| e.is_finite()));
+    assert!(rb.ang_vel().iter().all(|e| e.is_finite()));
+}
+
+#[test]
+fn the_mass_of_a_ball_is_its_volume_times_its_density() {
+    let pi = std::f32::consts::PI;
+
+    assert!((volumetric::ball_volume(2, 2.0f32) - pi * 4.0).abs() < 1.0e-5);
+    assert!((volumetric::ball_volume(3, 2.0f32) - pi * 32.0 / 3.0).abs() < 1.0e-5);
+
+    let rb   = RigidBody::new_dynamic(Ball::new(2.0f32), 3.0, 0.3, 0.6);
+    let mass = pi * 32.0;
+
+    assert!((rb.mass().unwrap() - mass).abs() < 1.0e-4 * mass);
+
+    // The inertia of a solid ball is 2/5 m r².
+    for i in 0 .. 3 {
+        assert!((rb.inv_inertia()[(i, i)] * 0.4 * mass * 4.0 - 1.0).abs() < 1.0e-4);
+    }
+}
@@ -0,0 +1,97 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Cuboid, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+// Whether a coin 10000 times lighter than the truck it rests on falls asleep in `world`, i.e.,
+// stops vibrating.
+fn coin_rests_on_truck(mut world: World<f32>) -> bool {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let mut truck = RigidBody::new_dynamic(Cuboid::new(Vector3::new(2.0f32, 1.0, 1.0)), 1.0, 0.0, 0.5);
+    truck.append_translation(&Translation3::new(0.0, 1.1, 0.0));
+    let truck = world.add_rigid_body(truck);
+
+    // A coin 10000 times lighter than the truck.
+    let truck_mass = 1.0 / truck.borrow().inv_mass();
+    let coin_density = truck_mass / 10000.0 / (0.5 * 0.2 * 0.5);
+    let mut coin = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.25f32, 0.1, 0.25)), coin_density, 0.0, 0.5);
+    coin.append_translation(&Translation3::new(0.0, 2.3, 0.0));
+    let coin = world.add_rigid_body(coin);
+
+    assert!((coin.borrow().inv_mass() / truck.borrow().inv_mass() - 10000.0).abs() < 1.0);
+
+    for _ in 0 .. 600 {
+        world.step(0.016);
+    }
+
+    // The coin did not slide off the truck.
+    let rel = coin.borrow().position().translation.vector - truck.borrow().position().translation.vector;
+    assert!(rel.x.abs() < 0.1 && rel.z.abs() < 0.1);
+    assert!(rel.y > 1.0 && rel.y < 1.3);
+
+    // Both came to rest instead of vibrating forever.
+    !coin.borrow().is_active() && !truck.borrow().is_active()
+}
+
+#[test]
+fn coin_rests_on_truck_by_default() {
+    assert!(coin_rests_on_truck(World::new()));
+}
+
+#[test]
+fn coin_rests_on_truck_with_a_maximum_mass_ratio() {
+    let mut world = World::new();
+    world.constraints_solver().set_max_mass_ratio(Some(1000.0));
+
+    assert!(coin_rests_on_truck(world));
+}
+
+// The height of a coin 10000 times lighter than the truck resting on it.
+fn coin_height_under_truck(max_mass_ratio: Option<f32>) -> f32 {
+    let mut world = World::new();
+    world.constraints_solver().set_max_mass_ratio(max_mass_ratio);
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let mut coin = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.25f32, 0.1, 0.25)), 0.032, 0.0, 0.5);
+    coin.append_translation(&Translation3::new(0.0, 0.2, 0.0));
+    coin.set_deactivation_threshold(None);
+    let coin = world.add_rigid_body(coin);
+
+    let mut truck = RigidBody::new_dynamic(Cuboid::new(Vector3::new(2.0f32, 1.0, 1.0)), 1.0, 0.0, 0.5);
+    truck.append_translation(&Translation3::new(0.0, 1.45, 0.0));
+    truck.set_deactivation_threshold(None);
+    let truck = world.add_rigid_body(truck);
+
+    assert!((coin.borrow().inv_mass() / truck.borrow().inv_mass() - 10000.0).abs() < 1.0);
+
+    for _ in 0 .. 600 {
+        world.step(0.016);
+    }
+
+    let height = coin.borrow().position().translation.vector.y;
+    height
+}
+
+#[test]
+fn coin_holds_truck_with_a_maximum_mass_ratio() {
+    // The coin stays on the ground, its center above its half height.
+    assert!(coin_height_under_truck(Some(10.0)) > 0.1);
+}
+
+#[test]
+fn mass_ratio_scaling_is_configurable() {
+    let mut world = World::<f32>::new();
+
+    assert_eq!(world.constraints_solver().max_mass_ratio(), None);
+    world.constraints_solver().set_max_mass_ratio(Some(10.0));
+    assert_eq!(world.constraints_solver().max_mass_ratio(), Some(10.0));
+}
@@ -0,0 +1,42 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::Vector3;
+use ncollide::shape::{Ball, Cuboid};
+use nphysics3d::object::{RigidBody, Material, MaterialTable, ShapeRegistry};
+
+#[test]
+fn mass_is_volume_times_density() {
+    let table = MaterialTable::<f32>::with_presets();
+
+    // A 1m³ cube.
+    let wood  = table.new_dynamic(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)), "wood");
+    let steel = table.new_dynamic(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)), "steel");
+
+    assert!((wood.mass().unwrap() - 700.0).abs() < 1.0e-2);
+    assert!((steel.mass().unwrap() - 7850.0).abs() < 1.0e-1);
+    assert_eq!(steel.restitution(), Material::<f32>::steel().restitution);
+
+    let mut registry = ShapeRegistry::new();
+    let ball         = registry.add(Ball::new(1.0f32));
+    let rubber       = table.new_dynamic_from_registry(&registry, ball, "rubber");
+    let expected     = 4.0 / 3.0 * std::f32::consts::PI * 1100.0;
+
+    assert!((rubber.mass().unwrap() - expected).abs() / expected < 1.0e-4);
+    assert_eq!(rubber.friction(), 0.9);
+}
+
+#[test]
+fn custom_materials() {
+    let mut table = MaterialTable::new();
+    assert!(table.get("wood").is_none());
+
+    assert!(table.insert("foam", Material::new(30.0f32, 0.1, 0.8)).is_none());
+
+    let rb = RigidBody::new_dynamic_with_material(Cuboid::new(Vector3::new(1.0, 1.0, 1.0)), table.get("foam").unwrap());
+    assert!((rb.mass().unwrap() - 240.0).abs() < 1.0e-3);
+
+    assert!(table.remove("foam").is_some());
+    assert!(table.get("foam").is_none());
+}
@@ -0,0 +1,112 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// A stack of three boxes falling on the ground.
+fn stack() -> (World<f32>, RigidBodyHandle<f32>) {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut top = None;
+
+    for i in 0 .. 3 {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+        rb.append_translation(&Translation3::new(0.0, 0.55 + i as f32 * 1.1, 0.0));
+        top = Some(world.add_rigid_body(rb));
+    }
+
+    (world, top.unwrap())
+}
+
+#[test]
+fn long_steps_are_split() {
+    let (mut world, top) = stack();
+    world.set_max_substep_dt(Some(0.02));
+    world.step(0.1);
+
+    let (mut reference, ref_top) = stack();
+
+    for _ in 0 .. 5 {
+        reference.step(0.02);
+    }
+
+    assert_eq!(world.num_steps(), 5);
+    assert!((world.time() - 0.1).abs() < 1.0e-6);
+    assert_eq!(top.borrow().position().translation.vector, ref_top.borrow().position().translation.vector);
+}
+
+#[test]
+fn short_steps_are_not_split() {
+    let (mut world, _) = stack();
+    world.set_max_substep_dt(Some(0.02));
+    world.step(0.016);
+
+    assert_eq!(world.num_steps(), 1);
+}
+
+#[test]
+fn stacks_survive_frame_hitches() {
+    let (mut world, top) = stack();
+    let (mut reference, ref_top) = stack();
+    world.set_max_substep_dt(Some(0.016));
+
+    for i in 0 .. 60 {
+        // Every fifth frame stalls for a quarter of a second.
+        world.step(if i % 5 == 0 { 0.25 } else { 0.016 });
+        reference.step(0.016);
+    }
+
+    let pos     = top.borrow().position().translation.vector;
+    let ref_pos = ref_top.borrow().position().translation.vector;
+    assert!(na::norm(&(pos - ref_pos)) < 0.1, "Unexpected position: {}", pos);
+}
+
+#[test]
+fn accumulated_forces_apply_during_the_whole_step() {
+    let mut world = World::new();
+    world.set_max_substep_dt(Some(0.02));
+
+    let rb = world.add_rigid_body(RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6));
+    let force = Vector3::new(2.0, 0.0, 0.0) * rb.borrow().mass().unwrap();
+    rb.borrow_mut().accumulate_force(force);
+
+    world.step(0.1);
+
+    assert_eq!(world.num_steps(), 5);
+    assert!((rb.borrow().lin_vel().x - 0.2).abs() < 1.0e-5, "Unexpected velocity: {}", rb.borrow().lin_vel());
+    assert_eq!(rb.borrow().accumulated_force(), na::zero());
+}
+
+#[test]
+fn kinematic_velocities_are_derived_from_the_whole_step() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.set_max_substep_dt(Some(0.02));
+
+    let mut platform = RigidBody::new_static(Cuboid::new(Vector3::new(10.0, 0.5, 10.0)), 0.0, 1.0);
+    platform.set_kinematic(true);
+    let platform = world.add_rigid_body(platform);
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 1.0);
+    rb.set_translation(Translation3::new(0.0, 1.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    // The platform slides along `x` at one unit per second.
+    for i in 0 .. 30 {
+        platform.borrow_mut().append_translation(&Translation3::new(0.1, 0.0, 0.0));
+        world.step(0.1);
+
+        // The first step only records the initial position of the platform.
+        assert!(i == 0 || (platform.borrow().lin_vel() - Vector3::x()).norm() < 1.0e-4,
+                "Unexpected velocity: {}", platform.borrow().lin_vel());
+    }
+
+    // The box is dragged by friction.
+    assert!((rb.borrow().lin_vel().x - 1.0).abs() < 0.05, "Unexpected velocity: {}", rb.borrow().lin_vel());
+}
@@ -0,0 +1,37 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn bodies_jump_through_one_way_platforms() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut platform = RigidBody::new_static(Cuboid::new(Vector3::new(5.0, 0.1, 5.0)), 0.0, 0.6);
+    platform.set_one_way_normal(Some(Vector3::y()));
+    world.add_rigid_body(platform);
+
+    // Thrown upward from below the platform.
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    rb.append_translation(&Translation3::new(0.0, -1.0, 0.0));
+    rb.set_lin_vel(Vector3::new(0.0, 8.0, 0.0));
+    rb.set_deactivation_threshold(None);
+    let ball = world.add_rigid_body(rb);
+
+    let mut max_y = -1.0f32;
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+        max_y = max_y.max(ball.borrow().position().translation.vector.y);
+    }
+
+    // It went through the platform, then landed on it.
+    let y = ball.borrow().position().translation.vector.y;
+    assert!(max_y > 2.0);
+    assert!((y - 0.6).abs() < 0.1, "The ball rests at {}.", y);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "parallel")]
+
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// Separate stacks of boxes, each one being an island.
+fn stacks(world: &mut World<f32>) -> Vec<RigidBodyHandle<f32>> {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut boxes = Vec::new();
+
+    for i in 0 .. 8 {
+        for j in 0 .. 3 {
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+            rb.set_translation(Translation3::new(3.0 * i as f32, 0.55 + 1.1 * j as f32, 0.1 * j as f32));
+            boxes.push(world.add_rigid_body(rb));
+        }
+    }
+
+    boxes
+}
+
+#[test]
+fn islands_solved_on_several_threads_evolve_like_on_one() {
+    let mut reference = World::new();
+    let reference_boxes = stacks(&mut reference);
+
+    let mut world = World::new();
+    assert_eq!(world.constraints_solver().num_threads(), 1);
+    world.constraints_solver().set_num_threads(4);
+    assert_eq!(world.constraints_solver().num_threads(), 4);
+
+    let boxes = stacks(&mut world);
+
+    for _ in 0 .. 100 {
+        reference.step(0.016);
+        world.step(0.016);
+    }
+
+    for (rb, reference) in boxes.iter().zip(reference_boxes.iter()) {
+        assert_eq!(rb.borrow().position(), reference.borrow().position());
+        assert_eq!(rb.borrow().lin_vel(), reference.borrow().lin_vel());
+    }
+}
+
+#[test]
+#[should_panic]
+fn the_number_of_threads_cannot_be_zero() {
+    let mut world = World::<f32>::new();
+
+    world.constraints_solver().set_num_threads(0);
+}
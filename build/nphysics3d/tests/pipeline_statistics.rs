@@ -0,0 +1,36 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn pipeline_statistics_count_pairs_and_contacts() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 3.0, 0.0));
+    world.add_rigid_body(rb);
+
+    // The plane's bounding box is infinite so the pair exists right away.
+    world.step(0.016);
+    assert_eq!(world.stats().created_pairs, 1);
+    assert_eq!(world.stats().active_pairs, 1);
+    assert_eq!(world.stats().contacts, 0);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    let stats = world.stats().clone();
+    assert_eq!(stats.created_pairs, 0);
+    assert_eq!(stats.destroyed_pairs, 0);
+    assert_eq!(stats.active_pairs, 1);
+    assert_eq!(stats.contact_pairs, 1);
+    assert!(stats.contacts >= 1);
+}
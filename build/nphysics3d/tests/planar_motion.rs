@@ -0,0 +1,99 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3, UnitQuaternion};
+use ncollide::shape::{Plane, Ball, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::integration::{BodyPlanarIntegrator, BodySmpEulerIntegrator};
+
+fn planar_world() -> World<f32> {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.set_integrator(BodyPlanarIntegrator::new(BodySmpEulerIntegrator::new()));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    world
+}
+
+fn add_ball(world: &mut World<f32>, x: f32, y: f32, z: f32, depth: Option<f32>) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(x, y, z));
+    rb.set_planar_depth(depth);
+
+    world.add_rigid_body(rb)
+}
+
+#[test]
+fn planar_bodies_do_not_leave_their_plane() {
+    let mut world = planar_world();
+    let rb = add_ball(&mut world, 0.0, 2.0, 1.0, Some(1.0));
+    rb.borrow_mut().set_lin_vel(Vector3::new(1.0, 0.0, 3.0));
+    rb.borrow_mut().set_ang_vel(Vector3::new(2.0, 2.0, 2.0));
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+
+        let rb = rb.borrow();
+        assert_eq!(rb.position().translation.vector.z, 1.0);
+        assert_eq!(rb.lin_vel().z, 0.0);
+        assert_eq!(rb.ang_vel().x, 0.0);
+        assert_eq!(rb.ang_vel().y, 0.0);
+    }
+
+    assert!(rb.borrow().position().translation.vector.x > 0.5);
+}
+
+#[test]
+fn collisions_do_not_push_planar_bodies_out_of_their_plane() {
+    let mut world = planar_world();
+
+    // Two balls in slightly different planes colliding off-center.
+    let rb1 = add_ball(&mut world, -2.0, 0.5, 0.0, Some(0.0));
+    let rb2 = add_ball(&mut world, 2.0, 0.5, 0.3, Some(0.3));
+    rb1.borrow_mut().set_lin_vel(Vector3::new(5.0, 0.0, 0.0));
+    rb2.borrow_mut().set_lin_vel(Vector3::new(-5.0, 0.0, 0.0));
+
+    for _ in 0 .. 60 {
+        world.step(0.016);
+    }
+
+    assert_eq!(rb1.borrow().position().translation.vector.z, 0.0);
+    assert_eq!(rb2.borrow().position().translation.vector.z, 0.3);
+
+    // They did not slide past each other.
+    assert!(rb1.borrow().position().translation.vector.x < rb2.borrow().position().translation.vector.x - 0.9);
+}
+
+#[test]
+fn planar_bodies_only_rotate_around_z() {
+    let mut world = planar_world();
+
+    // A box landing on one of its edges.
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 2.0, 0.0));
+    rb.set_rotation(UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.5)));
+    rb.set_planar_depth(Some(0.0));
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    let z = rb.borrow().position().rotation * Vector3::z();
+    assert!(na::norm(&(z - Vector3::z())) < 1.0e-5, "Unexpected axis: {}", z);
+}
+
+#[test]
+fn other_bodies_move_freely() {
+    let mut world = planar_world();
+    let rb = add_ball(&mut world, 0.0, 2.0, 0.0, None);
+    rb.borrow_mut().set_lin_vel(Vector3::new(0.0, 0.0, 1.0));
+
+    for _ in 0 .. 10 {
+        world.step(0.016);
+    }
+
+    assert!(rb.borrow().position().translation.vector.z > 0.1);
+}
@@ -0,0 +1,75 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Prismatic};
+
+// A box on a vertical slider going through (0, 5, 0), pulled down and sideways by the gravity.
+fn elevator(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Prismatic<f32>>>) {
+    world.set_gravity(Vector3::new(3.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 5.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), na::one());
+    let slider  = world.add_prismatic(Prismatic::new(anchor1, anchor2, Vector3::y() * 2.0));
+
+    (rb, slider)
+}
+
+#[test]
+fn prismatic_translates_along_its_axis_only() {
+    let mut world = World::new();
+    let (rb, slider) = elevator(&mut world);
+
+    // This initial spin is cancelled by the joint.
+    rb.borrow_mut().set_ang_vel(Vector3::new(0.0, 1.0, 1.0));
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+
+        let rb  = rb.borrow();
+        let pos = rb.position();
+
+        assert!(pos.translation.vector.x.abs() < 0.05, "The body left its axis: {}", pos.translation.vector);
+        assert!(pos.translation.vector.z.abs() < 0.05, "The body left its axis: {}", pos.translation.vector);
+        assert!(pos.rotation.angle() < 0.05, "Unexpected rotation: {}", pos.rotation.angle());
+    }
+
+    // The body slid down.
+    let translation = slider.borrow().translation();
+    assert!(translation < -5.0, "Unexpected translation: {}", translation);
+    assert!((rb.borrow().position().translation.vector.y - 5.0 - translation).abs() < 1.0e-3);
+}
+
+#[test]
+fn prismatic_translation_limits() {
+    let mut world = World::new();
+    let (rb, slider) = elevator(&mut world);
+
+    slider.borrow_mut().set_limits(Some((-1.0, 0.0)));
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+
+        let translation = slider.borrow().translation();
+        assert!(translation > -1.1 && translation < 0.1, "Limits exceeded: {}", translation);
+    }
+
+    // The body rests on its lower limit.
+    assert!((slider.borrow().translation() + 1.0).abs() < 0.05);
+    assert!(rb.borrow().position().translation.vector.x.abs() < 0.05);
+
+    assert_eq!(world.summary().num_prismatics, 1);
+
+    world.remove_prismatic(&slider);
+    assert_eq!(world.summary().num_prismatics, 0);
+}
@@ -0,0 +1,89 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Pulley};
+
+// Two boxes of masses `mass1` and `mass2` hanging 3 units below pulleys at (-1, 5, 0) and (1, 5, 0).
+fn pulley(world: &mut World<f32>, mass1: f32, mass2: f32, ratio: f32)
+          -> (RigidBodyHandle<f32>, RigidBodyHandle<f32>, Rc<RefCell<Pulley<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb1 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.1f32, 0.1, 0.1)), mass1 / 0.008, 0.3, 0.6);
+    rb1.set_translation(Translation3::new(-1.0, 2.0, 0.0));
+    let rb1 = world.add_rigid_body(rb1);
+
+    let mut rb2 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.1f32, 0.1, 0.1)), mass2 / 0.008, 0.3, 0.6);
+    rb2.set_translation(Translation3::new(1.0, 2.0, 0.0));
+    let rb2 = world.add_rigid_body(rb2);
+
+    let anchor1 = Anchor::new(Some(rb1.clone()), Point3::origin());
+    let anchor2 = Anchor::new(Some(rb2.clone()), Point3::origin());
+    let pulley  = world.add_pulley(Pulley::new(anchor1, anchor2, Point3::new(-1.0, 5.0, 0.0),
+                                               Point3::new(1.0, 5.0, 0.0), ratio));
+
+    (rb1, rb2, pulley)
+}
+
+fn height(rb: &RigidBodyHandle<f32>) -> f32 {
+    rb.borrow().position().translation.vector.y
+}
+
+#[test]
+fn counterweight_lifts_the_lighter_box() {
+    let mut world = World::new();
+    let (rb1, rb2, pulley) = pulley(&mut world, 1.0, 3.0, 1.0);
+
+    assert!((pulley.borrow().length() - 6.0).abs() < 1.0e-5);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    // The boxes accelerate at g * (3 - 1) / (3 + 1).
+    let t        = 50.0 * 0.016;
+    let expected = 0.5 * 9.81 * 0.5 * t * t;
+    assert!((height(&rb1) - 2.0 - expected).abs() < 0.05, "Unexpected height: {}", height(&rb1));
+    assert!((height(&rb2) - 2.0 + expected).abs() < 0.05, "Unexpected height: {}", height(&rb2));
+
+    let (length1, length2) = pulley.borrow().lengths();
+    assert!((length1 + length2 - 6.0).abs() < 1.0e-2);
+    assert_eq!(world.summary().num_pulleys, 1);
+}
+
+#[test]
+fn block_and_tackle_balances_twice_the_mass() {
+    let mut world = World::new();
+    let (rb1, rb2, _) = pulley(&mut world, 1.0, 2.0, 2.0);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    assert!((height(&rb1) - 2.0).abs() < 1.0e-2, "Unexpected height: {}", height(&rb1));
+    assert!((height(&rb2) - 2.0).abs() < 1.0e-2, "Unexpected height: {}", height(&rb2));
+}
+
+#[test]
+fn slack_rope_does_not_push() {
+    let mut world = World::new();
+    let (rb1, rb2, pulley) = pulley(&mut world, 1.0, 1.0, 1.0);
+
+    // Thrown upwards, the first box does not pull the second one down.
+    rb1.borrow_mut().set_lin_vel(Vector3::new(0.0, 5.0, 0.0));
+
+    for _ in 0 .. 10 {
+        world.step(0.016);
+    }
+
+    let (length1, length2) = pulley.borrow().lengths();
+    assert!(length1 + length2 < 5.5);
+    assert!(height(&rb1) > 2.5);
+    assert!(rb2.borrow().lin_vel().y < -1.0);
+}
@@ -0,0 +1,100 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Hinge, Prismatic, RackAndPinion, JointMotor};
+
+// A pinion hinged around `z` at the origin.
+fn pinion(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Hinge<f32>>>) {
+    let rb = world.add_rigid_body(RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.2)), 1.0, 0.3, 0.6));
+
+    let anchor1 = Anchor::new(None, na::one());
+    let anchor2 = Anchor::new(Some(rb.clone()), na::one());
+    let hinge   = world.add_hinge(Hinge::new(anchor1, anchor2, Vector3::z()));
+
+    (rb, hinge)
+}
+
+// A rack sliding along `axis` below the pinion.
+fn rack(world: &mut World<f32>, axis: Vector3<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Prismatic<f32>>>) {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(2.0f32, 0.2, 0.2)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, -1.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1   = Anchor::new(None, Isometry3::new(Vector3::new(0.0, -1.0, 0.0), na::zero()));
+    let anchor2   = Anchor::new(Some(rb.clone()), na::one());
+    let prismatic = world.add_prismatic(Prismatic::new(anchor1, anchor2, axis));
+
+    (rb, prismatic)
+}
+
+#[test]
+fn rack_slides_as_the_pinion_turns() {
+    let mut world = World::new();
+    let (rb1, hinge) = pinion(&mut world);
+    let (rb2, prismatic) = rack(&mut world, Vector3::x());
+
+    let joint = world.add_rack_and_pinion(RackAndPinion::new(hinge.clone(), prismatic.clone(), -0.5));
+    hinge.borrow_mut().set_motor(Some(JointMotor::velocity(2.0, 1000.0)));
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    let w = rb1.borrow().ang_vel().z;
+    let v = rb2.borrow().lin_vel().x;
+    assert!((w - 2.0).abs() < 1.0e-2, "Unexpected velocity: {}", w);
+    assert!((v + 1.0).abs() < 1.0e-2, "Unexpected velocity: {}", v);
+
+    // The pinion made more than a full turn, which the joint keeps track of.
+    let turns = joint.borrow().turns();
+    assert!(turns > 6.0, "Unexpected turns: {}", turns);
+    assert!((prismatic.borrow().translation() + turns * 0.5).abs() < 1.0e-2);
+    assert!(joint.borrow().error().abs() < 1.0e-2);
+    assert_eq!(world.summary().num_racks, 1);
+}
+
+#[test]
+fn falling_rack_turns_the_pinion() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let (rb1, hinge) = pinion(&mut world);
+    let (_, prismatic) = rack(&mut world, Vector3::y());
+
+    let joint = world.add_rack_and_pinion(RackAndPinion::new(hinge, prismatic.clone(), 0.5));
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+        assert!(joint.borrow().error().abs() < 1.0e-2);
+    }
+
+    // The pinion inertia slows the fall down.
+    assert!(prismatic.borrow().translation() < -0.5);
+    assert!(prismatic.borrow().translation() > -0.5 * 9.81 * 0.8 * 0.8);
+    assert!(rb1.borrow().ang_vel().z < 0.0);
+}
+
+#[test]
+fn removed_rack_and_pinion_lets_the_joints_move_freely() {
+    let mut world = World::new();
+    let (_, hinge) = pinion(&mut world);
+    let (rb2, prismatic) = rack(&mut world, Vector3::x());
+
+    let joint = world.add_rack_and_pinion(RackAndPinion::new(hinge.clone(), prismatic, 0.5));
+    world.remove_rack_and_pinion(&joint);
+    hinge.borrow_mut().set_motor(Some(JointMotor::velocity(2.0, 1000.0)));
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    assert!(rb2.borrow().lin_vel().x.abs() < 1.0e-3);
+    assert_eq!(world.summary().num_racks, 0);
+}
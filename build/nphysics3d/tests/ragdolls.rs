@@ -0,0 +1,102 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+use na::{Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::{World, RagdollBuilder, Ragdoll, RagdollJoint};
+use nphysics3d::object::{RigidBody, RigidBodyHandle, WorldObject};
+
+fn limb(world: &mut World<f32>, y: f32, half_height: f32, fixed: bool) -> RigidBodyHandle<f32> {
+    let shape  = Cuboid::new(Vector3::new(0.2f32, half_height, 0.2));
+    let mut rb = if fixed { RigidBody::new_static(shape, 0.3, 0.6) } else { RigidBody::new_dynamic(shape, 1.0, 0.3, 0.6) };
+    rb.set_translation(Translation3::new(0.0, y, 0.0));
+
+    world.add_rigid_body(rb)
+}
+
+// A leg hanging from a fixed torso, each limb slightly overlapping the next one.
+fn leg(world: &mut World<f32>) -> Ragdoll<f32> {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut builder = RagdollBuilder::new();
+    let torso = builder.add_limb(limb(world, 5.0, 0.5, true));
+    let thigh = builder.add_limb(limb(world, 4.0, 0.6, false));
+    let shin  = builder.add_limb(limb(world, 2.85, 0.6, false));
+
+    // The twist axis of the hip goes down the leg, the knee bends around `x`.
+    builder.add_ball_in_socket(torso, thigh, Isometry3::new(Vector3::new(0.0, 4.5, 0.0),
+                                                            Vector3::z() * -FRAC_PI_2));
+    builder.add_hinge(thigh, shin, Isometry3::new(Vector3::new(0.0, 3.4, 0.0), na::zero()), Vector3::x());
+
+    builder.build(world, "leg")
+}
+
+fn touching(world: &World<f32>, rb1: &RigidBodyHandle<f32>, rb2: &RigidBodyHandle<f32>) -> bool {
+    let uid1 = WorldObject::rigid_body_uid(rb1);
+    let uid2 = WorldObject::rigid_body_uid(rb2);
+
+    world.collision_world().contact_pairs().any(|(o1, o2, cd)| {
+        cd.num_contacts() != 0 &&
+        ((o1.uid == uid1 && o2.uid == uid2) || (o1.uid == uid2 && o2.uid == uid1))
+    })
+}
+
+#[test]
+fn adjacent_limbs_do_not_collide() {
+    let mut world = World::new();
+    let leg = leg(&mut world);
+
+    world.step(0.016);
+
+    assert_eq!(leg.limbs().len(), 3);
+    assert_eq!(leg.joints().len(), 2);
+    assert!(!touching(&world, &leg.limbs()[0], &leg.limbs()[1]));
+    assert!(!touching(&world, &leg.limbs()[1], &leg.limbs()[2]));
+    assert_eq!(world.summary().num_ball_in_sockets, 1);
+    assert_eq!(world.summary().num_hinges, 1);
+
+    leg.remove_joints(&mut world);
+    world.step(0.016);
+
+    assert!(touching(&world, &leg.limbs()[0], &leg.limbs()[1]));
+    assert_eq!(world.summary().num_ball_in_sockets, 0);
+    assert_eq!(world.summary().num_hinges, 0);
+}
+
+#[test]
+fn limbs_are_kept_within_the_joint_limits() {
+    let mut world = World::new();
+    let leg = leg(&mut world);
+
+    // Kick the leg forward, and the shin backward.
+    leg.limbs()[1].borrow_mut().set_ang_vel(Vector3::new(25.0, 0.0, 0.0));
+    leg.limbs()[2].borrow_mut().set_ang_vel(Vector3::new(-20.0, 3.0, 0.0));
+
+    let (hip, knee) = match (&leg.joints()[0], &leg.joints()[1]) {
+        (&RagdollJoint::BallInSocket(ref hip), &RagdollJoint::Hinge(ref knee)) => (hip.clone(), knee.clone()),
+        _ => panic!("Unexpected joints.")
+    };
+
+    let mut max_angle = 0.0f32;
+    let tolerance     = 0.1;
+
+    for i in 0 .. 100 {
+        world.step(0.016);
+
+        let (swing, twist) = hip.borrow().swing_twist().unwrap();
+        let angle          = knee.borrow().angle();
+        max_angle          = max_angle.max(angle);
+
+        // The limits are enforced once they are exceeded, which takes a few steps at first.
+        if i >= 3 {
+            assert!(swing < FRAC_PI_4 + tolerance, "Unexpected swing: {}", swing);
+            assert!(twist.abs() < FRAC_PI_4 + tolerance, "Unexpected twist: {}", twist);
+            assert!(angle > -tolerance && angle < 3.0 * FRAC_PI_4 + tolerance, "Unexpected angle: {}", angle);
+        }
+    }
+
+    // The knee reached its limit.
+    assert!(max_angle > 3.0 * FRAC_PI_4 - tolerance, "Unexpected angle: {}", max_angle);
+}
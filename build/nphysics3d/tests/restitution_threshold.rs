@@ -0,0 +1,50 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// A perfectly elastic ball hitting the ground at 0.8 m/s.
+fn slow_impact(world: &mut World<f32>) -> RigidBodyHandle<f32> {
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 1.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 1.0, 0.6);
+    rb.set_translation(Translation3::new(0.0, 0.7, 0.0));
+    rb.set_lin_vel(Vector3::new(0.0, -0.8, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+    }
+
+    rb
+}
+
+#[test]
+fn slow_impacts_do_not_bounce() {
+    let mut world = World::new();
+    assert_eq!(world.constraints_solver().restitution_threshold(), 1.0);
+
+    let rb = slow_impact(&mut world);
+    assert!(rb.borrow().lin_vel().y.abs() < 0.1, "Unexpected velocity: {}", rb.borrow().lin_vel());
+}
+
+#[test]
+fn faster_impacts_than_the_threshold_bounce() {
+    let mut world = World::new();
+    world.constraints_solver().set_restitution_threshold(0.5);
+    assert_eq!(world.constraints_solver().restitution_threshold(), 0.5);
+
+    let rb = slow_impact(&mut world);
+    assert!(rb.borrow().lin_vel().y > 0.7, "Unexpected velocity: {}", rb.borrow().lin_vel());
+}
+
+#[test]
+#[should_panic]
+fn the_threshold_is_not_negative() {
+    let mut world = World::<f32>::new();
+    world.constraints_solver().set_restitution_threshold(-1.0);
+}
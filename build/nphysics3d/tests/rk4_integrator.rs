@@ -0,0 +1,102 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::f32;
+use na::{Vector3, Point3, Translation3, UnitQuaternion};
+use ncollide::shape::Ball;
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::integration::{Integrator, BodyRk4Integrator, BodySmpEulerIntegrator, RadialField, Falloff, rk4};
+
+#[test]
+fn rk4_ballistic_trajectory_is_exact() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.set_integrator(BodyRk4Integrator::new());
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_lin_vel(Vector3::new(3.0, 10.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 60 {
+        world.step(1.0 / 60.0);
+    }
+
+    let pos = rb.borrow().position().translation.vector;
+    assert!((pos.x - 3.0).abs() < 1.0e-3, "Unexpected position: {}", pos);
+    assert!((pos.y - (10.0 - 9.81 * 0.5)).abs() < 1.0e-3, "Unexpected position: {}", pos);
+}
+
+#[test]
+fn rk4_rotation_follows_a_changing_axis() {
+    let av  = Vector3::new(0.0, 0.0, 4.0);
+    let acc = Vector3::new(6.0, 0.0, 0.0);
+    let dt  = 0.2f64;
+
+    // Reference rotation, with many midpoint substeps.
+    let n   = 1000;
+    let h   = dt / n as f64;
+    let mut exact = UnitQuaternion::identity();
+
+    for i in 0 .. n {
+        let t = (i as f64 + 0.5) * h;
+        exact = UnitQuaternion::from_scaled_axis((av + acc * t) * h) * exact;
+    }
+
+    let rot = rk4::rotation(dt, &av, &acc);
+    assert!(rot.angle_to(&exact) < 1.0e-3, "Unexpected error: {}", rot.angle_to(&exact));
+
+    // The semi-implicit Euler integrator is much less accurate.
+    let mut rb1 = RigidBody::new_dynamic(Ball::new(0.5f64), 1.0, 0.3, 0.6);
+    rb1.set_ang_vel(av);
+    rb1.set_ang_acc(acc);
+    let mut rb2 = rb1.clone();
+
+    BodyRk4Integrator::new().update(dt, &mut rb1);
+    BodySmpEulerIntegrator::new().update(dt, &mut rb2);
+
+    let err1 = rb1.position().rotation.angle_to(&exact);
+    let err2 = rb2.position().rotation.angle_to(&exact);
+    assert!(err1 < 1.0e-3, "Unexpected error: {}", err1);
+    assert!(err2 > 10.0 * err1, "Unexpected error: {}", err2);
+    assert_eq!(rb1.ang_vel(), rb2.ang_vel());
+}
+
+// The distance to its initial position of a body after one period of a circular orbit around an
+// attractor, integrated with `rk4` or with the default integrator.
+fn orbit_error(rk4: bool) -> f32 {
+    let mut world = World::new();
+
+    if rk4 {
+        world.set_integrator(BodyRk4Integrator::new());
+    }
+
+    // A circular orbit of radius 10, of speed `sqrt(100 / 10)`.
+    world.register_force_generator("attractor", RadialField::new(Point3::origin(), 100.0, Falloff::InverseSquare(0.1)));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(10.0, 0.0, 0.0));
+    rb.set_lin_vel(Vector3::new(0.0, 10.0f32.sqrt(), 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let period = 2.0 * f32::consts::PI * 10.0 / 10.0f32.sqrt();
+    let num_steps = 200;
+
+    for _ in 0 .. num_steps {
+        world.step(period / num_steps as f32);
+    }
+
+    let pos = rb.borrow().position().translation.vector;
+    na::norm(&(pos - Vector3::new(10.0, 0.0, 0.0)))
+}
+
+#[test]
+fn rk4_orbit_is_closed() {
+    let err1 = orbit_error(true);
+    let err2 = orbit_error(false);
+
+    // The attraction is evaluated at each stage of the Runge-Kutta integrator.
+    assert!(err1 < 1.0e-3, "Unexpected error: {}", err1);
+    assert!(err2 > 10.0 * err1, "Unexpected error: {}", err2);
+}
@@ -0,0 +1,82 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Isometry3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane, ShapeHandle};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, Sensor};
+use nphysics3d::detection::joint::{Anchor, BallInSocket, Fixed};
+
+#[test]
+fn summary_of_scene() {
+    let mut world = World::new();
+
+    let summary = world.summary();
+    assert_eq!(summary.num_static_bodies + summary.num_active_bodies + summary.num_sleeping_bodies, 0);
+    assert!(summary.bounds.is_none());
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    let mut rb1 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 1.0, 1.0)), 2.0, 0.3, 0.6);
+    rb1.set_translation(Translation3::new(0.0, 5.0, 0.0));
+    let rb1 = world.add_rigid_body(rb1);
+
+    let mut rb2 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 1.0, 1.0)), 1.0, 0.3, 0.6);
+    rb2.set_translation(Translation3::new(3.0, 5.0, 0.0));
+    let rb2 = world.add_rigid_body(rb2);
+
+    let mut rb3 = RigidBody::new_dynamic(Ball::new(1.0f32), 1.0, 0.3, 0.6);
+    rb3.set_translation(Translation3::new(-3.0, 5.0, 0.0));
+    rb3.deactivate();
+    let _ = world.add_rigid_body(rb3);
+
+    let _ = world.add_ball_in_socket(BallInSocket::new(Anchor::new(Some(rb1.clone()), Point3::new(1.5, 0.0, 0.0)),
+                                                       Anchor::new(Some(rb2.clone()), Point3::new(-1.5, 0.0, 0.0))));
+    let _ = world.add_fixed(Fixed::new(Anchor::new(None, Isometry3::new(Vector3::new(0.0, 7.0, 0.0), na::zero())),
+                                       Anchor::new(Some(rb1.clone()), Isometry3::new(Vector3::new(0.0, 2.0, 0.0), na::zero()))));
+
+    let mut sensor = Sensor::new(Ball::new(0.5f32), None);
+    sensor.set_relative_position(Isometry3::new(Vector3::new(0.0, 10.0, 0.0), na::zero()));
+    let _ = world.add_sensor(sensor);
+
+    let summary = world.summary();
+    assert_eq!(summary.num_static_bodies, 1);
+    assert_eq!(summary.num_active_bodies, 2);
+    assert_eq!(summary.num_sleeping_bodies, 1);
+    assert_eq!(summary.num_sensors, 1);
+    assert_eq!(summary.num_ball_in_sockets, 1);
+    assert_eq!(summary.num_fixed_joints, 1);
+
+    // 16 + 8 for the cuboids, and 4/3 pi for the ball.
+    let expected_mass = 24.0 + 4.0 / 3.0 * std::f32::consts::PI;
+    assert!((summary.total_mass - expected_mass).abs() < 1.0e-2, "Unexpected mass: {}", summary.total_mass);
+
+    // The plane is ignored. The margins loosen the bounds a bit.
+    let bounds = summary.bounds.unwrap();
+    assert!((bounds.mins() - Point3::new(-4.0, 4.0, -1.0)).norm() < 0.1);
+    assert!((bounds.maxs() - Point3::new(4.0, 10.5, 1.0)).norm() < 0.1);
+
+    world.step(0.016);
+
+    // The jointed bodies belong to the same island.
+    assert_eq!(world.summary().largest_island, 2);
+}
+
+#[test]
+fn summary_includes_trigger_volumes() {
+    let mut world = World::<f32>::new();
+    let shape     = ShapeHandle::new(Cuboid::new(Vector3::new(1.0f32, 1.0, 1.0)));
+    let volume    = world.add_trigger_volume(shape, Isometry3::new(Vector3::new(5.0, 0.0, 0.0), na::zero()));
+
+    let summary = world.summary();
+    assert_eq!(summary.num_sensors, 1);
+
+    let bounds = summary.bounds.unwrap();
+    assert!((bounds.mins() - Point3::new(4.0, -1.0, -1.0)).norm() < 0.1);
+    assert!((bounds.maxs() - Point3::new(6.0, 1.0, 1.0)).norm() < 0.1);
+
+    assert!(world.remove_trigger_volume(volume));
+    assert_eq!(world.summary().num_sensors, 0);
+    assert!(world.summary().bounds.is_none());
+}
@@ -0,0 +1,62 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Isometry3};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics3d::object::{RigidBody, ShapeRegistry, MaterialTable};
+
+// Whether two bodies have the same mass properties, up to rounding errors.
+fn assert_same_mass_properties(rb: &RigidBody<f32>, expected: &RigidBody<f32>) {
+    let rel = |a: f32, b: f32| (a - b).abs() <= 1.0e-5 * b.abs();
+
+    assert!(rel(rb.inv_mass(), expected.inv_mass()), "{} != {}", rb.inv_mass(), expected.inv_mass());
+
+    for i in 0 .. 3 {
+        for j in 0 .. 3 {
+            let inertia          = rb.inv_inertia()[(i, j)];
+            let expected_inertia = expected.inv_inertia()[(i, j)];
+
+            assert!(rel(inertia, expected_inertia), "{} != {}", inertia, expected_inertia);
+        }
+    }
+}
+
+#[test]
+fn registered_shapes_have_the_mass_properties_of_their_shape() {
+    let mut registry = ShapeRegistry::new();
+    let ball         = registry.add(Ball::new(2.0f32));
+    let cuboid       = registry.add(Cuboid::new(Vector3::new(0.5f32, 1.0, 3.0)));
+
+    for &density in [0.5f32, 1.0, 7.0].iter() {
+        assert_same_mass_properties(&registry.new_dynamic(ball, density, 0.3, 0.6),
+                                    &RigidBody::new_dynamic(Ball::new(2.0f32), density, 0.3, 0.6));
+        assert_same_mass_properties(&registry.new_dynamic(cuboid, density, 0.3, 0.6),
+                                    &RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 1.0, 3.0)), density, 0.3, 0.6));
+    }
+
+    let table = MaterialTable::with_presets();
+
+    assert_same_mass_properties(&table.new_dynamic_from_registry(&registry, ball, "steel"),
+                                &table.new_dynamic(Ball::new(2.0f32), "steel"));
+}
+
+#[test]
+fn scaled_instances_have_the_mass_properties_of_the_scaled_shape() {
+    let mut registry = ShapeRegistry::new();
+    let ball         = registry.add(Ball::new(2.0f32));
+    let cuboid       = registry.add(Cuboid::new(Vector3::new(0.5f32, 1.0, 3.0)));
+
+    assert_same_mass_properties(&registry.new_dynamic_scaled(ball, 1.5, 2.0, 0.3, 0.6),
+                                &RigidBody::new_dynamic(Ball::new(3.0f32), 2.0, 0.3, 0.6));
+    assert_same_mass_properties(&registry.new_dynamic_scaled(cuboid, 0.5, 2.0, 0.3, 0.6),
+                                &RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.25f32, 0.5, 1.5)), 2.0, 0.3, 0.6));
+
+    let aabb = registry.scaled_shape(cuboid, 0.5).aabb(&Isometry3::new(Vector3::new(1.0, 2.0, 3.0), na::zero()));
+    assert!((aabb.mins().coords - Vector3::new(0.75, 1.5, 1.5)).norm() < 1.0e-5);
+    assert!((aabb.maxs().coords - Vector3::new(1.25, 2.5, 4.5)).norm() < 1.0e-5);
+
+    // The registered shape itself is left unscaled.
+    let aabb = registry.shape(cuboid).aabb(&Isometry3::identity());
+    assert!((aabb.maxs().coords - Vector3::new(0.5, 1.0, 3.0)).norm() < 1.0e-5);
+}
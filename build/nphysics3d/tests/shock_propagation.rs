@@ -0,0 +1,67 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+// The top box of a tower of 20 boxes solved with very few iterations, after 8 seconds.
+fn tower_top(shock_propagation: bool) -> RigidBodyHandle<f32> {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    {
+        let solver = world.constraints_solver();
+        solver.set_num_first_order_iter(2);
+        solver.set_num_second_order_iter(2);
+
+        if shock_propagation {
+            solver.enable_shock_propagation();
+        }
+
+        assert_eq!(solver.shock_propagation_enabled(), shock_propagation);
+    }
+
+    let mut top = None;
+
+    for i in 0 .. 20 {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+        rb.set_translation(Translation3::new(0.0, 0.54 + 1.08 * i as f32, 0.0));
+        rb.set_deactivation_threshold(None);
+        top = Some(world.add_rigid_body(rb));
+    }
+
+    for _ in 0 .. 500 {
+        world.step(0.016);
+    }
+
+    top.unwrap()
+}
+
+#[test]
+fn tall_towers_collapse_without_shock_propagation() {
+    let top = tower_top(false);
+    assert!(top.borrow().position().translation.vector.y < 10.0);
+}
+
+#[test]
+fn shock_propagation_keeps_tall_towers_up() {
+    let top = tower_top(true);
+    let pos = top.borrow().position().translation.vector;
+
+    assert!(pos.y > 21.0, "Unexpected position: {}", pos);
+    assert!(pos.x.abs() < 1.0 && pos.z.abs() < 1.0, "Unexpected position: {}", pos);
+}
+
+#[test]
+fn shock_propagation_is_disabled_by_default() {
+    let mut world = World::<f32>::new();
+    assert!(!world.constraints_solver().shock_propagation_enabled());
+
+    world.constraints_solver().enable_shock_propagation();
+    world.constraints_solver().disable_shock_propagation();
+    assert!(!world.constraints_solver().shock_propagation_enabled());
+}
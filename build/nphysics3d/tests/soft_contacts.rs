@@ -0,0 +1,61 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, ContactCompliance};
+
+// The height at which a box of mass 1 comes to rest on the ground, both with the given
+// compliances.
+fn resting_height(ground_compliance: Option<ContactCompliance<f32>>,
+                  box_compliance:    Option<ContactCompliance<f32>>)
+                  -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -10.0, 0.0));
+
+    let mut ground = RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6);
+    ground.set_contact_compliance(ground_compliance);
+    world.add_rigid_body(ground);
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+    rb.set_translation(Translation3::new(0.0, 0.6, 0.0));
+    rb.set_contact_compliance(box_compliance);
+    rb.set_deactivation_threshold(None);
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    let y = rb.borrow().position().translation.vector.y;
+    y
+}
+
+#[test]
+fn soft_contacts_compress_under_the_load() {
+    let rigid = resting_height(None, None);
+    let soft  = resting_height(Some(ContactCompliance::new(200.0, 20.0)), None);
+    let stiff = resting_height(Some(ContactCompliance::new(1000.0, 20.0)), None);
+
+    // The box rests on four contact points, each supporting a quarter of its weight.
+    assert!(((rigid - soft) - 10.0 / 800.0).abs() < 1.0e-3, "Unexpected heights: {} {}", rigid, soft);
+    assert!(((rigid - stiff) - 10.0 / 4000.0).abs() < 1.0e-3, "Unexpected heights: {} {}", rigid, stiff);
+}
+
+#[test]
+fn the_compliances_of_two_soft_bodies_are_in_series() {
+    let c = ContactCompliance::new(400.0f32, 40.0);
+    assert_eq!(c.in_series(&c), ContactCompliance::new(200.0, 20.0));
+
+    let one  = resting_height(Some(ContactCompliance::new(200.0, 20.0)), None);
+    let both = resting_height(Some(c), Some(c));
+    assert!((one - both).abs() < 1.0e-4, "Unexpected heights: {} {}", one, both);
+}
+
+#[test]
+#[should_panic]
+fn the_stiffness_is_positive() {
+    let _ = ContactCompliance::new(0.0f32, 1.0);
+}
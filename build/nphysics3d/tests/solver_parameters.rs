@@ -0,0 +1,55 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::resolution::CorrectionMode;
+
+#[test]
+fn solver_parameters_can_be_changed() {
+    let mut world = World::<f32>::new();
+    let solver    = world.constraints_solver();
+
+    solver.set_num_first_order_iter(3);
+    solver.set_num_second_order_iter(4);
+    solver.set_correction_mode(CorrectionMode::VelocityAndPositionThresold(0.3, 0.4, 0.2));
+    solver.set_joint_correction_factor(0.7);
+
+    assert_eq!(solver.num_first_order_iter(), 3);
+    assert_eq!(solver.num_second_order_iter(), 4);
+    assert_eq!(*solver.correction_mode(), CorrectionMode::VelocityAndPositionThresold(0.3, 0.4, 0.2));
+    assert_eq!(solver.joint_correction_factor(), 0.7);
+}
+
+#[test]
+fn the_quality_can_be_scaled_between_steps() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    // A stack of boxes.
+    for i in 0 .. 4 {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+        rb.set_translation(Translation3::new(0.0, 0.55 + 1.1 * i as f32, 0.0));
+        rb.set_deactivation_threshold(None);
+        let _ = world.add_rigid_body(rb);
+    }
+
+    world.constraints_solver().set_num_second_order_iter(1);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    let low_quality = world.constraints_solver().max_residual();
+
+    world.constraints_solver().set_num_second_order_iter(20);
+    world.step(0.016);
+
+    let high_quality = world.constraints_solver().max_residual();
+
+    assert!(high_quality < 0.5 * low_quality, "low: {}, high: {}", low_quality, high_quality);
+}
@@ -0,0 +1,86 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, BallInSocket};
+use nphysics3d::resolution::SolverStatistics;
+
+fn add_box(world: &mut World<f32>, y: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+    rb.set_translation(Translation3::new(0.0, y, 0.0));
+
+    world.add_rigid_body(rb)
+}
+
+fn ground(world: &mut World<f32>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+}
+
+#[test]
+fn nothing_is_solved_without_constraints() {
+    let mut world = World::new();
+    let _ = add_box(&mut world, 5.0);
+    world.step(0.016);
+
+    assert_eq!(*world.solver_statistics(), SolverStatistics::new());
+}
+
+#[test]
+fn resting_contacts_are_counted() {
+    let mut world = World::new();
+    ground(&mut world);
+    let _ = add_box(&mut world, 0.55);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    let stats = world.solver_statistics().clone();
+
+    assert!(stats.contacts > 0);
+    assert!(stats.active_contacts > 0 && stats.active_contacts <= stats.contacts);
+    assert_eq!(stats.joint_equations, 0);
+    assert_eq!(stats.max_residual, world.constraints_solver().max_residual());
+    assert!(stats.max_residual < 1.0e-2);
+}
+
+#[test]
+fn the_remaining_depth_decreases() {
+    let mut world = World::new();
+    ground(&mut world);
+    let _ = add_box(&mut world, 0.2);
+
+    world.step(0.016);
+    let first = world.solver_statistics().max_depth;
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+    }
+
+    let last = world.solver_statistics().max_depth;
+
+    // The box penetrates the ground by 0.3 plus its margins, and is partially pushed out by the
+    // first step.
+    assert!(first > 0.25 && first < 0.35, "Unexpected depth: {}", first);
+    assert!(last < 0.01, "Unexpected depth: {}", last);
+}
+
+#[test]
+fn joint_equations_are_counted() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    let rb = add_box(&mut world, 4.0);
+    let _  = world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Point3::new(0.0, 5.0, 0.0)),
+                                                        Anchor::new(Some(rb), Point3::new(0.0, 1.0, 0.0))));
+
+    world.step(0.016);
+
+    let stats = world.solver_statistics();
+    assert_eq!(stats.joint_equations, 3);
+    assert_eq!(stats.contacts, 0);
+}
@@ -0,0 +1,78 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::collections::BTreeSet;
+use na::Point3;
+use ncollide::bounding_volume::{AABB, BoundingVolume};
+use ncollide::broad_phase::BroadPhase;
+use nphysics3d::detection::SpatialHashBroadPhase;
+
+fn aabb(i: usize, t: f32) -> AABB<Point3<f32>> {
+    // The first box, e.g. the ground, is much larger than the cells.
+    if i == 0 {
+        return AABB::new(Point3::new(-100.0, -100.0, -100.0), Point3::new(100.0, 0.5, 100.0));
+    }
+
+    // Small boxes scattered on a grid, moving around.
+    let x = (i % 6) as f32 * 1.3 + (t + i as f32).sin();
+    let y = (i / 6) as f32 * 1.3 + (t * 0.7 + i as f32).cos();
+    let z = (i % 4) as f32 * 0.5;
+
+    AABB::new(Point3::new(x, y, z), Point3::new(x + 1.0, y + 1.0, z + 1.0))
+}
+
+#[test]
+fn spatial_hash_reports_overlaps() {
+    let mut bf: SpatialHashBroadPhase<f32, usize> = SpatialHashBroadPhase::new(1.5, 0.0, 64);
+    let mut pairs = BTreeSet::new();
+    let num = 30;
+
+    for i in 0 .. num {
+        bf.deferred_add(i, aabb(i, 0.0), i);
+    }
+
+    for step in 0 .. 50 {
+        let t = step as f32 * 0.1;
+
+        for i in 0 .. num {
+            bf.deferred_set_bounding_volume(i, aabb(i, t));
+        }
+
+        if step == 25 {
+            bf.deferred_remove(3);
+        }
+
+        bf.update(&mut |a, b| a != b, &mut |a, b, started| {
+            let pair = (*a.min(b), *a.max(b));
+
+            if started {
+                assert!(pairs.insert(pair));
+            }
+            else {
+                assert!(pairs.remove(&pair));
+            }
+        });
+
+        let mut expected = BTreeSet::new();
+
+        for i in 0 .. num {
+            for j in i + 1 .. num {
+                if (step < 25 || (i != 3 && j != 3)) && aabb(i, t).intersects(&aabb(j, t)) {
+                    let _ = expected.insert((i, j));
+                }
+            }
+        }
+
+        assert_eq!(pairs, expected);
+        assert_eq!(bf.num_interferences(), expected.len());
+    }
+
+    let mut out = Vec::new();
+    bf.interferences_with_bounding_volume(&aabb(10, 4.9), &mut out);
+    assert!(out.contains(&&10) && !out.contains(&&3));
+
+    out.clear();
+    bf.interferences_with_point(&Point3::new(50.0, 0.0, 50.0), &mut out);
+    assert_eq!(out, vec![&0]);
+}
@@ -0,0 +1,46 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, ShapeRegistry};
+
+fn world() -> World<f32> {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+    world
+}
+
+#[test]
+fn batch_spawning_matches_individual_insertions() {
+    let mut shapes = ShapeRegistry::new();
+    let ball       = shapes.add(Ball::new(0.5f32));
+
+    let props = || (0 .. 200).map(|i| {
+        let mut rb = shapes.new_dynamic(ball, 1.0, 0.3, 0.6);
+        rb.append_translation(&Translation3::new((i % 20) as f32 * 0.9, 0.6 + (i / 20) as f32 * 1.1, 0.0));
+        rb
+    });
+
+    let mut batched = world();
+    let handles     = batched.spawn_batch(props());
+    assert_eq!(handles.len(), 200);
+    assert_eq!(batched.rigid_bodies().count(), 201);
+
+    let mut individual = world();
+
+    for rb in props() {
+        individual.add_rigid_body(rb);
+    }
+
+    // The solver order depends on the body addresses, so only the first step is compared.
+    batched.step(0.016);
+    individual.step(0.016);
+
+    assert_eq!(batched.stats().active_pairs, individual.stats().active_pairs);
+    assert_eq!(batched.stats().contacts, individual.stats().contacts);
+    assert!(batched.stats().contacts > 0);
+}
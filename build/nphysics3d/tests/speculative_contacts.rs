@@ -0,0 +1,99 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::Cell;
+use na::{Point3, Isometry3, Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane, ShapeHandle};
+use ncollide::narrow_phase::{ContactHandler, ContactAlgorithm};
+use nphysics3d::world::{World, WorldCollisionObject};
+use nphysics3d::object::{RigidBody, RigidBodyHandle, WorldObject};
+use nphysics3d::detection::{TriggerHandler, TriggerVolumeId};
+
+// Counts the contact and trigger volume events.
+#[derive(Clone)]
+struct Counter {
+    events: Rc<Cell<usize>>
+}
+
+impl ContactHandler<Point3<f32>, Isometry3<f32>, WorldObject<f32>> for Counter {
+    fn handle_contact_started(&mut self,
+                              _: &WorldCollisionObject<f32>,
+                              _: &WorldCollisionObject<f32>,
+                              _: &ContactAlgorithm<Point3<f32>, Isometry3<f32>>) {
+        self.events.set(self.events.get() + 1)
+    }
+
+    fn handle_contact_stopped(&mut self, _: &WorldCollisionObject<f32>, _: &WorldCollisionObject<f32>) {
+        self.events.set(self.events.get() + 1)
+    }
+}
+
+impl TriggerHandler<f32> for Counter {
+    fn handle_body_entered(&mut self, _: TriggerVolumeId, _: &RigidBodyHandle<f32>) {
+        self.events.set(self.events.get() + 1)
+    }
+
+    fn handle_body_exited(&mut self, _: TriggerVolumeId, _: &RigidBodyHandle<f32>) {
+        self.events.set(self.events.get() + 1)
+    }
+}
+
+#[test]
+fn speculative_contacts_prevent_tunnelling() {
+    let mut world = World::new();
+
+    let wall = RigidBody::new_static(Cuboid::new(Vector3::new(0.05, 2.0, 2.0)), 0.0, 0.5);
+    world.add_rigid_body(wall);
+
+    let mut bullet = RigidBody::new_dynamic(Ball::new(0.1), 1.0, 0.0, 0.5);
+    bullet.append_translation(&Translation3::new(-5.0, 0.0, 0.0));
+    bullet.set_lin_vel(Vector3::new(200.0, 0.0, 0.0));
+    bullet.enable_speculative_contacts();
+    let bullet = world.add_rigid_body(bullet);
+
+    for _ in 0 .. 60 {
+        world.step(1.0 / 60.0);
+    }
+
+    assert!(bullet.borrow().position().translation.vector.x < 0.0);
+}
+
+#[test]
+fn speeding_up_emits_no_events() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let shape = ShapeHandle::new(Cuboid::new(Vector3::new(100.0f32, 2.0, 100.0)));
+    let _     = world.add_trigger_volume(shape, Isometry3::new(na::zero(), na::zero()));
+
+    let counter = Counter { events: Rc::new(Cell::new(0)) };
+    world.register_contact_handler("counter", counter.clone());
+    world.register_trigger_handler("counter", counter.clone());
+
+    // A ball rolling faster and faster on the ground, inside the trigger volume.
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    ball.set_deactivation_threshold(None);
+    ball.set_max_lin_vel(Some(30.0));
+    ball.enable_speculative_contacts();
+    let ball = world.add_rigid_body(ball);
+
+    for _ in 0 .. 10 {
+        world.step(1.0 / 60.0);
+    }
+
+    // The contact with the ground started, and the ball entered the trigger volume.
+    let initial_events = counter.events.get();
+    assert_eq!(initial_events, 2);
+
+    for i in 0 .. 120 {
+        ball.borrow_mut().set_lin_vel(Vector3::new(i as f32 * 0.2, 0.0, 0.0));
+        world.step(1.0 / 60.0);
+    }
+
+    assert!(ball.borrow().position().translation.vector.x > 20.0);
+    assert_eq!(counter.events.get(), initial_events);
+}
@@ -0,0 +1,108 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Spring};
+
+// A box of mass 1 hanging from a spring attached at (0, 5, 0), its center `length` below.
+fn hanging_box(world: &mut World<f32>, length: f32, stiffness: f32, damping: f32)
+               -> (RigidBodyHandle<f32>, Rc<RefCell<Spring<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.1f32, 0.1, 0.1)), 1.0 / 0.008, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 5.0 - length, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Point3::new(0.0, 5.0, 0.0));
+    let anchor2 = Anchor::new(Some(rb.clone()), Point3::origin());
+    let spring  = world.add_spring(Spring::new(anchor1, anchor2, 1.0, stiffness, damping));
+
+    (rb, spring)
+}
+
+#[test]
+fn spring_stretches_under_the_weight_of_a_box() {
+    let mut world = World::new();
+    let (_, spring) = hanging_box(&mut world, 1.0, 100.0, 5.0);
+
+    // The box starts at the rest length, so the spring is soft enough to let it fall first.
+    world.step(0.016);
+    assert!(spring.borrow().length() > 1.0);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    // At equilibrium, the elongation is m * g / k.
+    let length = spring.borrow().length();
+    assert!((length - 1.0981).abs() < 5.0e-3, "Unexpected length: {}", length);
+    assert_eq!(world.summary().num_springs, 1);
+}
+
+#[test]
+fn damped_spring_overshoots_less() {
+    fn max_length(damping: f32) -> f32 {
+        let mut world = World::new();
+        let (_, spring) = hanging_box(&mut world, 1.0, 100.0, damping);
+        let mut res = 0.0f32;
+
+        for _ in 0 .. 100 {
+            world.step(0.016);
+            res = res.max(spring.borrow().length());
+        }
+
+        res
+    }
+
+    // Without damping, the box falls twice as low as the equilibrium.
+    let free   = max_length(0.0);
+    let damped = max_length(20.0);
+
+    assert!(free > 1.15, "Unexpected length: {}", free);
+    assert!(damped < 1.11, "Unexpected length: {}", damped);
+}
+
+#[test]
+fn stiff_spring_remains_stable() {
+    let mut world = World::new();
+    let (rb, spring) = hanging_box(&mut world, 1.5, 1.0e6, 0.0);
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+    }
+
+    let length = spring.borrow().length();
+    assert!((length - 1.0).abs() < 1.0e-2, "Unexpected length: {}", length);
+    assert!(rb.borrow().lin_vel().norm() < 1.0);
+}
+
+#[test]
+fn bungee_does_not_push() {
+    let mut world = World::new();
+    let (_, spring) = hanging_box(&mut world, 0.5, 100.0, 5.0);
+
+    world.set_gravity(na::zero());
+    spring.borrow_mut().set_pulls_only(true);
+    assert!(spring.borrow().pulls_only());
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    // The slack bungee leaves the box in place, a spring would push it away.
+    assert!((spring.borrow().length() - 0.5).abs() < 1.0e-3);
+
+    spring.borrow_mut().set_pulls_only(false);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    assert!((spring.borrow().length() - 1.0).abs() < 1.0e-2, "Unexpected length: {}", spring.borrow().length());
+}
@@ -0,0 +1,95 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, WorldObject};
+use nphysics3d::detection::CollisionEvent;
+
+#[test]
+fn deferred_events_are_stamped_with_their_step() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    ball.set_translation(Translation3::new(0.0, 1.0, 0.0));
+    let _ = world.add_rigid_body(ball);
+
+    world.enable_collision_event_queue();
+    assert_eq!(world.num_steps(), 0);
+
+    let mut events = Vec::new();
+
+    // The events are only processed every 50 steps.
+    for i in 0 .. 500 {
+        world.step(0.016);
+        assert_eq!(world.num_steps(), i + 1);
+
+        if i % 50 == 49 {
+            for event in world.drain_stamped_collision_events() {
+                assert!(event.step <= i && event.step + 50 > i);
+                events.push(event);
+            }
+        }
+    }
+
+    // The ball touched the ground, then fell asleep.
+    let started = events.iter().find(|e| match e.event {
+        CollisionEvent::ContactStarted(..) => true,
+        _                                  => false
+    }).unwrap();
+    let deactivated = events.iter().find(|e| match e.event {
+        CollisionEvent::BodyDeactivated(..) => true,
+        _                                   => false
+    }).unwrap();
+
+    assert!(started.step < deactivated.step);
+
+    // The identifiers follow the order of the events.
+    for pair in events.windows(2) {
+        assert!(pair[0].id < pair[1].id);
+        assert!(pair[0].step <= pair[1].step);
+    }
+}
+
+#[test]
+fn ccd_hits_are_collected() {
+    let mut world = World::new();
+
+    let wall = world.add_rigid_body(RigidBody::new_static(Cuboid::new(Vector3::new(0.05, 2.0, 2.0)), 0.0, 0.5));
+
+    let mut plate = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.02, 0.3, 0.3)), 1.0, 0.0, 0.5);
+    plate.append_translation(&Translation3::new(-5.0, 0.0, 0.0));
+    plate.set_lin_vel(Vector3::new(200.0, 0.0, 0.0));
+    plate.enable_ccd(0.01);
+    let plate = world.add_rigid_body(plate);
+
+    world.enable_collision_event_queue();
+
+    let mut hits = Vec::new();
+
+    for _ in 0 .. 10 {
+        world.step(1.0 / 60.0);
+
+        for event in world.drain_stamped_collision_events() {
+            if let CollisionEvent::CcdHit(ref rb, ref obstacle, toi) = event.event {
+                assert!(&**rb as *const _ == &*plate as *const _);
+                assert!(toi >= 0.0 && toi <= 1.0);
+
+                match *obstacle {
+                    WorldObject::RigidBody(ref obstacle) => assert!(&**obstacle as *const _ == &*wall as *const _),
+                    WorldObject::Sensor(_)               => panic!("The plate did not hit the wall.")
+                }
+
+                hits.push(event.step);
+            }
+        }
+    }
+
+    // The plate moving 3.3 units per step reached the wall during the second step.
+    assert_eq!(hits.first(), Some(&1));
+}
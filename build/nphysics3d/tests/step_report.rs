@@ -0,0 +1,68 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+// Two balls resting on the ground, and one falling far away from them.
+fn three_balls() -> World<f32> {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    for &(x, y) in [(0.0f32, 0.5f32), (3.0, 0.5), (10.0, 10.0)].iter() {
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+        rb.set_translation(Translation3::new(x, y, 0.0));
+        let _ = world.add_rigid_body(rb);
+    }
+
+    world
+}
+
+#[test]
+fn the_report_summarizes_the_step() {
+    let mut world = three_balls();
+    world.step(0.016);
+    let report = world.step(0.016);
+
+    assert_eq!(report.num_steps, 1);
+    assert_eq!(report.bodies_integrated, 3);
+    assert_eq!(report.pairs, world.stats().active_pairs);
+    assert_eq!(report.contacts, world.stats().contacts);
+    assert!(report.contacts >= 2);
+    assert_eq!(report.islands, world.island_statistics().num_islands);
+    assert_eq!(report.solver_iterations, world.solver_statistics().iterations);
+    assert!(report.solver_iterations >= world.constraints_solver().num_second_order_iter());
+
+    let stages = report.integration_time + report.collision_time + report.activation_time + report.solver_time;
+    assert!(stages <= report.total_time);
+}
+
+#[test]
+fn split_steps_are_accumulated() {
+    let mut world = three_balls();
+    world.set_max_substep_dt(Some(0.01));
+
+    let report = world.step(0.035);
+
+    assert_eq!(report.num_steps, 4);
+    assert_eq!(report.bodies_integrated, 12);
+    assert_eq!(report.contacts, world.stats().contacts);
+}
+
+#[test]
+fn sleeping_bodies_are_not_integrated() {
+    let mut world = three_balls();
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    let report = world.step(0.016);
+
+    assert_eq!(report.bodies_integrated, 0);
+    assert_eq!(report.solver_iterations, 0);
+}
@@ -0,0 +1,87 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Plane, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+fn scene(substepping: bool) -> (World<f32>, RigidBodyHandle<f32>, RigidBodyHandle<f32>) {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+
+    if substepping {
+        rb.enable_substepping();
+    }
+
+    let fast = world.add_rigid_body(rb);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    rb.append_translation(&Translation3::new(5.0, 2.0, 0.0));
+    let slow = world.add_rigid_body(rb);
+
+    (world, fast, slow)
+}
+
+#[test]
+fn substepped_bodies_match_a_smaller_time_step() {
+    let (mut world, fast, slow) = scene(true);
+    world.set_num_substeps(4);
+    world.step(0.04);
+
+    let (mut reference, ref_fast, ref_slow) = scene(false);
+
+    for _ in 0 .. 4 {
+        reference.step(0.01);
+    }
+
+    let y = fast.borrow().position().translation.vector.y;
+    let ref_y = ref_fast.borrow().position().translation.vector.y;
+    assert!((y - ref_y).abs() < 1.0e-5);
+
+    // The other bodies are not substepped.
+    let y = slow.borrow().position().translation.vector.y;
+    let ref_y = ref_slow.borrow().position().translation.vector.y;
+    assert!(y < ref_y);
+}
+
+#[test]
+fn substepped_bodies_rest_on_the_ground() {
+    let (mut world, fast, _) = scene(true);
+    world.set_num_substeps(4);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    let y = fast.borrow().position().translation.vector.y;
+    assert!((y - 0.5).abs() < 0.1);
+}
+
+#[test]
+fn substepped_contacts_match_a_smaller_time_step() {
+    let (mut world, fast, _) = scene(true);
+    world.set_num_substeps(4);
+    fast.borrow_mut().set_lin_vel(Vector3::new(3.0, 0.0, 0.0));
+
+    let (mut reference, ref_fast, _) = scene(false);
+    ref_fast.borrow_mut().set_lin_vel(Vector3::new(3.0, 0.0, 0.0));
+
+    // The ball falls, hits the ground, then rolls on it.
+    for _ in 0 .. 25 {
+        world.step(0.04);
+
+        for _ in 0 .. 4 {
+            reference.step(0.01);
+        }
+
+        let pos = fast.borrow().position().translation.vector;
+        let ref_pos = ref_fast.borrow().position().translation.vector;
+        assert!(na::norm(&(pos - ref_pos)) < 1.0e-4);
+    }
+}
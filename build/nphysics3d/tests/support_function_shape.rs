@@ -0,0 +1,88 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Matrix3, Isometry3, Translation3};
+use ncollide::bounding_volume::AABB;
+use ncollide::query::Ray;
+use ncollide::shape::{Compound, Plane, ShapeHandle};
+use nphysics3d::world::{World, RayCastOptions};
+use nphysics3d::object::{RigidBody, RigidBodyCollisionGroups, SupportFunctionShape};
+use nphysics3d::volumetric::Volumetric;
+
+// A ball of radius 0.5 defined by its support function.
+fn custom_ball() -> SupportFunctionShape<f32> {
+    SupportFunctionShape::new(
+        |dir: &Vector3<f32>| Point3::from_coordinates(na::normalize(dir) * 0.5),
+        |m: &Isometry3<f32>| {
+            let center = Point3::from_coordinates(m.translation.vector);
+            let half   = Vector3::new(0.5, 0.5, 0.5);
+
+            AABB::new(center - half, center + half)
+        },
+        |density: f32| {
+            let mass = density * 4.0 / 3.0 * std::f32::consts::PI * 0.125;
+
+            (mass, Point3::origin(), Matrix3::from_diagonal_element(mass * 0.4 * 0.25))
+        })
+}
+
+#[test]
+fn custom_shape_rests_on_ground() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(custom_ball(), 1.0, 0.0, 0.6);
+    rb.set_translation(Translation3::new(0.0, 2.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    // The margins of both bodies keep them slightly apart.
+    let y = rb.borrow().position().translation.vector.y;
+    assert!(y > 0.45 && y < 0.6, "Unexpected height: {}", y);
+}
+
+#[test]
+fn custom_shape_queries() {
+    let mut world = World::new();
+
+    let mut rb = RigidBody::new_dynamic(custom_ball(), 1.0, 0.0, 0.6);
+    rb.set_translation(Translation3::new(0.0, 3.0, 0.0));
+    let _ = world.add_rigid_body(rb);
+    world.step(0.0);
+
+    let groups  = RigidBodyCollisionGroups::new_dynamic();
+    let options = RayCastOptions::new(groups.as_collision_groups());
+    let ray     = Ray::new(Point3::new(0.0, 10.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+    let hit     = world.cast_ray_closest(&ray, &options).unwrap();
+    assert!((hit.toi - 6.5).abs() < 1.0e-3);
+    assert!((hit.normal - Vector3::y()).norm() < 1.0e-3);
+
+    let mut out = Vec::new();
+    world.bodies_containing_point(&Point3::new(0.1, 3.2, 0.0), groups.as_collision_groups(), &mut out);
+    assert_eq!(out.len(), 1);
+
+    out.clear();
+    world.bodies_containing_point(&Point3::new(0.4, 3.4, 0.0), groups.as_collision_groups(), &mut out);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn custom_shape_mass_properties() {
+    let rb = RigidBody::new_dynamic(custom_ball(), 2.0, 0.0, 0.6);
+    let expected = 2.0 * 4.0 / 3.0 * std::f32::consts::PI * 0.125;
+    assert!((rb.mass().unwrap() - expected).abs() < 1.0e-5);
+
+    // Also within compounds.
+    let shapes = vec![
+        (Isometry3::new(Vector3::new(-1.0, 0.0, 0.0), na::zero()), ShapeHandle::new(custom_ball())),
+        (Isometry3::new(Vector3::new(1.0, 0.0, 0.0), na::zero()), ShapeHandle::new(custom_ball()))
+    ];
+    let compound = Compound::new(shapes);
+    assert!((compound.mass(2.0) - 2.0 * expected).abs() < 1.0e-4);
+}
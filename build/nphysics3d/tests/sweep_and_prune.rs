@@ -0,0 +1,68 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::collections::BTreeSet;
+use na::Point3;
+use ncollide::bounding_volume::{AABB, BoundingVolume};
+use ncollide::broad_phase::BroadPhase;
+use nphysics3d::detection::SweepAndPruneBroadPhase;
+
+fn aabb(i: usize, t: f32) -> AABB<Point3<f32>> {
+    // Boxes moving coherently along `x`, with some of them oscillating along `y`.
+    let x = i as f32 * 0.7 + t;
+    let y = if i % 3 == 0 { (t + i as f32).sin() * 2.0 } else { 0.0 };
+
+    AABB::new(Point3::new(x, y, 0.0), Point3::new(x + 1.0, y + 1.0, 1.0))
+}
+
+#[test]
+fn sweep_and_prune_reports_overlaps() {
+    let mut bf: SweepAndPruneBroadPhase<f32, usize> = SweepAndPruneBroadPhase::new(0.0);
+    let mut pairs = BTreeSet::new();
+    let num = 30;
+
+    for i in 0 .. num {
+        bf.deferred_add(i, aabb(i, 0.0), i);
+    }
+
+    for step in 0 .. 50 {
+        let t = step as f32 * 0.1;
+
+        for i in 0 .. num {
+            bf.deferred_set_bounding_volume(i, aabb(i, t));
+        }
+
+        if step == 25 {
+            bf.deferred_remove(3);
+        }
+
+        bf.update(&mut |a, b| a != b, &mut |a, b, started| {
+            let pair = (*a.min(b), *a.max(b));
+
+            if started {
+                assert!(pairs.insert(pair));
+            }
+            else {
+                assert!(pairs.remove(&pair));
+            }
+        });
+
+        let mut expected = BTreeSet::new();
+
+        for i in 0 .. num {
+            for j in i + 1 .. num {
+                if (step < 25 || (i != 3 && j != 3)) && aabb(i, t).intersects(&aabb(j, t)) {
+                    let _ = expected.insert((i, j));
+                }
+            }
+        }
+
+        assert_eq!(pairs, expected);
+        assert_eq!(bf.num_interferences(), expected.len());
+    }
+
+    let mut out = Vec::new();
+    bf.interferences_with_bounding_volume(&aabb(10, 4.9), &mut out);
+    assert!(out.contains(&&10) && !out.contains(&&3));
+}
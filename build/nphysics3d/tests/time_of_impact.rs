@@ -0,0 +1,36 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn time_of_impact_between_moving_bodies() {
+    let mut world = World::new();
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Translation3::new(-5.0, 0.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    let mut cube = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+    cube.append_translation(&Translation3::new(5.0, 0.0, 0.0));
+    let cube = world.add_rigid_body(cube);
+
+    // 9 units apart, closing at 3 units per second.
+    let toi = world.time_of_impact(&ball, &Vector3::new(2.0, 0.0, 0.0), &cube, &Vector3::new(-1.0, 0.0, 0.0), 10.0);
+    let toi = toi.expect("The bodies should collide.");
+    assert!((toi - 3.0).abs() < 0.01, "Unexpected time of impact: {}", toi);
+
+    // Too slow to hit before the maximum time.
+    assert!(world.time_of_impact(&ball, &Vector3::new(1.0, 0.0, 0.0), &cube, &na::zero(), 5.0).is_none());
+
+    // Moving apart or missing each other.
+    assert!(world.time_of_impact(&ball, &Vector3::new(-1.0, 0.0, 0.0), &cube, &na::zero(), 100.0).is_none());
+    assert!(world.time_of_impact(&ball, &Vector3::new(1.0, 1.0, 0.0), &cube, &na::zero(), 100.0).is_none());
+
+    // The world was not stepped.
+    assert_eq!(ball.borrow().position().translation.vector, Vector3::new(-5.0, 0.0, 0.0));
+}
@@ -0,0 +1,71 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics3d::world::{World, TimestepLimit};
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn resting_world_keeps_the_maximum_timestep() {
+    let mut world = World::new();
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 0.5, 0.0));
+    let _ = world.add_rigid_body(rb);
+
+    let suggestion = world.suggest_timestep(0.016, 10);
+
+    assert_eq!(suggestion.limit, TimestepLimit::MaxTimestep);
+    assert_eq!(suggestion.num_substeps, 1);
+    assert_eq!(suggestion.dt, 0.016);
+}
+
+#[test]
+fn fast_body_shortens_the_timestep() {
+    let mut world = World::new();
+
+    // A ball of diameter 0.2 at 100 units per second must move by less than 0.1 per step.
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.1f32), 1.0, 0.3, 0.6);
+    rb.set_lin_vel(Vector3::new(100.0, 0.0, 0.0));
+    let _ = world.add_rigid_body(rb);
+
+    let suggestion = world.suggest_timestep(0.016, 100);
+
+    assert_eq!(suggestion.limit, TimestepLimit::Speed);
+    assert_eq!(suggestion.num_substeps, 16);
+    assert!((suggestion.dt - 0.001).abs() < 1.0e-6);
+
+    let suggestion = world.suggest_timestep(0.016, 4);
+
+    assert_eq!(suggestion.limit, TimestepLimit::MaxSubsteps);
+    assert_eq!(suggestion.num_substeps, 4);
+    assert!((suggestion.dt - 0.004).abs() < 1.0e-6);
+}
+
+#[test]
+fn unconverged_solver_shortens_the_timestep() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.constraints_solver().set_num_second_order_iter(1);
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    // A heavy plate on a light one.
+    for (i, density) in [1.0f32, 1000.0].iter().enumerate() {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.02, 0.5)), *density, 0.0, 0.6);
+        rb.set_translation(Translation3::new(0.0, 0.02 + 0.04 * i as f32, 0.0));
+        let _ = world.add_rigid_body(rb);
+    }
+
+    world.step(0.016);
+
+    let residual   = world.constraints_solver().max_residual();
+    let suggestion = world.suggest_timestep(0.016, 100);
+
+    assert!(residual > 0.0);
+    assert_eq!(suggestion.limit, TimestepLimit::Convergence);
+    assert!(suggestion.num_substeps > 1);
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "tracing")]
+
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::time::Instant;
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::trace::{Span, Stage, TraceSink, ChromeTrace};
+
+struct Recorder(Rc<RefCell<Vec<Span>>>);
+
+impl TraceSink for Recorder {
+    fn record(&mut self, span: &Span) {
+        self.0.borrow_mut().push(span.clone())
+    }
+}
+
+#[test]
+fn every_stage_is_traced() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+    world.add_rigid_body(RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.3, 0.6));
+
+    let spans = Rc::new(RefCell::new(Vec::new()));
+    let _ = world.set_trace_sink(Some(Box::new(Recorder(spans.clone()))));
+
+    world.step(0.016);
+    world.step(0.016);
+
+    // The islands are checked by `every_island_is_traced`.
+    let spans: Vec<_> = spans.borrow().iter().filter(|s| s.stage != Stage::Island).cloned().collect();
+    assert_eq!(spans.len(), 18);
+    assert_eq!(spans[8].stage, Stage::Step);
+    assert_eq!(spans[17].step, 1);
+
+    for span in spans.iter() {
+        assert!(span.end >= span.begin);
+    }
+}
+
+#[test]
+fn every_island_is_traced() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    for x in [-5.0, 5.0].iter() {
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.3, 0.6);
+        rb.set_translation(Translation3::new(*x, 0.45, 0.0));
+        world.add_rigid_body(rb);
+    }
+
+    let spans = Rc::new(RefCell::new(Vec::new()));
+    let _ = world.set_trace_sink(Some(Box::new(Recorder(spans.clone()))));
+
+    world.step(0.016);
+
+    let spans   = spans.borrow();
+    let islands: Vec<_> = spans.iter().filter(|s| s.stage == Stage::Island).collect();
+    let solver  = spans.iter().position(|s| s.stage == Stage::Solver).unwrap();
+
+    // Each ball resting on the ground makes an island of one contact, with two friction equations.
+    assert_eq!(islands.len(), 2);
+
+    for island in islands.iter() {
+        assert_eq!(island.count, Some(3));
+        assert!(island.begin >= spans[solver].begin && island.end <= spans[solver].end);
+    }
+
+    // The islands are emitted before the whole resolution.
+    assert!(spans.iter().rposition(|s| s.stage == Stage::Island).unwrap() < solver);
+}
+
+#[test]
+fn chrome_trace_output() {
+    let mut trace = ChromeTrace::new(1, 2);
+    let now       = Instant::now();
+
+    trace.record(&Span { stage: Stage::Solver, step: 3, begin: now, end: now, count: Some(4) });
+    trace.record(&Span { stage: Stage::Step, step: 3, begin: now, end: now, count: None });
+
+    let json = trace.to_json();
+    assert!(json.starts_with("{\"traceEvents\":[{\"name\":\"solver\""));
+    assert!(json.contains("\"pid\":1,\"tid\":2,\"args\":{\"step\":3,\"count\":4}},{\"name\":\"step\""));
+    assert!(json.ends_with("}}]}"));
+}
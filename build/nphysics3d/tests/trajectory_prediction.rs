@@ -0,0 +1,86 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Translation3};
+use ncollide::shape::{Ball, Plane, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+use nphysics3d::integration::{Falloff, RadialField};
+
+#[test]
+fn predicted_trajectory_matches_simulation() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.2f32), 1.0, 0.3, 0.6);
+    ball.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+    ball.set_lin_vel(Vector3::new(3.0, 4.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    // Another dynamic body on the path of the ball, ignored by the prediction.
+    let mut obstacle = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    obstacle.append_translation(&Translation3::new(20.0, 0.5, 0.0));
+    let _ = world.add_rigid_body(obstacle);
+
+    let trajectory = world.predict_trajectory(&ball, 1.0 / 60.0, 120);
+
+    assert_eq!(trajectory.len(), 120);
+    // The world is untouched.
+    assert_eq!(ball.borrow().position().translation.vector, Vector3::new(0.0, 2.0, 0.0));
+    assert_eq!(world.rigid_bodies().count(), 3);
+
+    let apex = trajectory.iter().map(|p| p.translation.vector.y).fold(0.0, f32::max);
+    assert!(apex > 2.7 && apex < 2.9, "{}", apex);
+
+    // The ground stops the ball.
+    assert!(trajectory.iter().all(|p| p.translation.vector.y > 0.1));
+
+    for expected in trajectory.iter().take(60) {
+        world.step(1.0 / 60.0);
+        assert!(na::norm(&(ball.borrow().position().translation.vector - expected.translation.vector)) < 1.0e-4);
+    }
+}
+
+#[test]
+fn predicted_trajectory_does_not_tunnel() {
+    let mut world = World::new();
+    let _ = world.add_rigid_body(RigidBody::new_static(Cuboid::new(Vector3::new(0.05, 2.0, 2.0)), 0.0, 0.5));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.1f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Translation3::new(-5.0, 0.0, 0.0));
+    ball.set_lin_vel(Vector3::new(200.0, 0.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    let trajectory = world.predict_trajectory(&ball, 1.0 / 60.0, 10);
+
+    // Without the sweep, the ball would cross the wall during the second step.
+    assert!(trajectory.iter().all(|p| p.translation.vector.x < -0.14));
+    assert!(trajectory.last().unwrap().translation.vector.x > -0.16);
+}
+
+#[test]
+fn predicted_trajectory_undergoes_the_force_generators() {
+    let mut world = World::new();
+
+    let mut field = RadialField::new(Point3::origin(), 1.0, Falloff::Constant);
+    field.set_radius(10.0);
+    world.register_force_generator("attractor", field);
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.1f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Translation3::new(5.0, 0.0, 0.0));
+    ball.set_lin_vel(Vector3::new(0.0, 2.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    let trajectory = world.predict_trajectory(&ball, 1.0 / 60.0, 60);
+
+    // The field bends the trajectory towards its center.
+    assert!(trajectory.last().unwrap().translation.vector.x < 4.6);
+
+    for expected in trajectory.iter() {
+        world.step(1.0 / 60.0);
+        assert!(na::norm(&(ball.borrow().position().translation.vector - expected.translation.vector)) < 1.0e-4);
+    }
+}
@@ -0,0 +1,86 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Plane, Cuboid};
+use nphysics3d::world::{World, TransformChangeMonitor, TransformChange};
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn only_moving_bodies_are_notified() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    // A ball resting on the ground, and another one falling.
+    let mut resting = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    resting.set_translation(Translation3::new(-2.0, 0.58, 0.0));
+    let resting = world.add_rigid_body(resting);
+
+    let mut falling = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    falling.set_translation(Translation3::new(2.0, 10.0, 0.0));
+    let falling = world.add_rigid_body(falling);
+
+    let notified = Rc::new(RefCell::new(Vec::new()));
+    let out      = notified.clone();
+    let monitor  = TransformChangeMonitor::new(0.01, 0.01, move |changes: &[TransformChange<f32>]| {
+        for change in changes.iter() {
+            out.borrow_mut().push((change.body.clone(), change.position.translation.vector.y,
+                                   change.delta.translation.vector.y))
+        }
+    });
+    assert!(world.set_transform_change_monitor(Some(monitor)).is_none());
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    let notified = notified.borrow();
+
+    assert!(!notified.is_empty());
+    assert!(notified.iter().all(|n| &*n.0 as *const _ == &*falling as *const _));
+    assert!(notified.iter().all(|n| n.2 < -0.01));
+    assert!(!notified.iter().any(|n| &*n.0 as *const _ == &*resting as *const _));
+
+    // The last notified position is the current one, up to the threshold.
+    let last = notified.last().unwrap().1;
+    assert!((falling.borrow().position().translation.vector.y - last).abs() < 0.01);
+}
+
+#[test]
+fn notified_positions_are_clamped_by_the_ccd() {
+    let mut world = World::new();
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Cuboid::new(Vector3::new(0.05, 2.0, 2.0)), 0.0, 0.5));
+
+    // A thin plate which would tunnel through the wall without CCD.
+    let mut plate = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.02, 0.3, 0.3)), 1.0, 0.0, 0.5);
+    plate.append_translation(&Translation3::new(-5.5, 0.0, 0.0));
+    plate.set_lin_vel(Vector3::new(200.0, 0.0, 0.0));
+    plate.enable_ccd(0.01);
+    let plate = world.add_rigid_body(plate);
+
+    let notified = Rc::new(RefCell::new(Vec::new()));
+    let out      = notified.clone();
+    let monitor  = TransformChangeMonitor::new(0.01, 0.01, move |changes: &[TransformChange<f32>]| {
+        for change in changes.iter() {
+            out.borrow_mut().push(change.position.translation.vector)
+        }
+    });
+    let _ = world.set_transform_change_monitor(Some(monitor));
+
+    // The first step only records the initial position of the plate, the second one hits the wall.
+    world.step(1.0 / 60.0);
+    world.step(1.0 / 60.0);
+
+    let notified = notified.borrow();
+    let position = plate.borrow().position().translation.vector;
+
+    assert!(position.x < 0.0, "Unexpected position: {}", position);
+    assert_eq!(notified.len(), 1);
+    assert_eq!(notified[0], position);
+}
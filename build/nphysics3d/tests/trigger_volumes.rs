@@ -0,0 +1,61 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Isometry3, Vector3};
+use ncollide::shape::{Ball, Cuboid, ShapeHandle};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::{TriggerHandler, TriggerVolumeId};
+
+struct Recorder {
+    events: Rc<RefCell<Vec<(TriggerVolumeId, bool)>>>
+}
+
+impl TriggerHandler<f32> for Recorder {
+    fn handle_body_entered(&mut self, volume: TriggerVolumeId, _: &RigidBodyHandle<f32>) {
+        self.events.borrow_mut().push((volume, true))
+    }
+
+    fn handle_body_exited(&mut self, volume: TriggerVolumeId, _: &RigidBodyHandle<f32>) {
+        self.events.borrow_mut().push((volume, false))
+    }
+}
+
+#[test]
+fn falling_body_enters_and_exits_trigger_volume() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let shape  = ShapeHandle::new(Cuboid::new(Vector3::new(2.0f32, 0.5, 2.0)));
+    let volume = world.add_trigger_volume(shape, Isometry3::new(Vector3::new(0.0, 0.0, 0.0), na::zero()));
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    world.register_trigger_handler("recorder", Recorder { events: events.clone() });
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.25f32), 1.0, 0.3, 0.6);
+    ball.set_translation(na::Translation3::new(0.0, 2.0, 0.0));
+    ball.set_deactivation_threshold(None);
+    let ball = world.add_rigid_body(ball);
+
+    let mut was_inside = false;
+
+    for _ in 0 .. 120 {
+        world.step(1.0 / 60.0);
+
+        let inside = world.bodies_inside_trigger_volume(volume).unwrap();
+        was_inside = was_inside || inside.iter().any(|b| Rc::ptr_eq(b, &ball));
+    }
+
+    // The trigger volume does not stop the ball.
+    assert!(ball.borrow().position().translation.vector.y < -2.0);
+    assert!(was_inside);
+    assert_eq!(*events.borrow(), vec![(volume, true), (volume, false)]);
+    assert!(world.bodies_inside_trigger_volume(volume).unwrap().is_empty());
+
+    assert!(world.remove_trigger_volume(volume));
+    assert!(!world.remove_trigger_volume(volume));
+    assert!(world.bodies_inside_trigger_volume(volume).is_none());
+}
@@ -0,0 +1,92 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Point3, Vector3, Isometry3, Translation3};
+use ncollide::shape::Cuboid;
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, Universal};
+
+// A lamp hanging 2 units below a universal joint at (0, 5, 0) swinging around `x` and `z`.
+fn lamp(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Rc<RefCell<Universal<f32>>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.3f32, 0.3, 0.3)), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(0.0, 3.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(rb.clone()), Isometry3::new(Vector3::new(0.0, 2.0, 0.0), na::zero()));
+    let joint   = world.add_universal(Universal::new(anchor1, anchor2, Vector3::x(), Vector3::z()));
+
+    (rb, joint)
+}
+
+#[test]
+fn universal_joint_swings_without_spinning() {
+    let mut world = World::new();
+    let (rb, joint) = lamp(&mut world);
+
+    // The lamp is pushed sideways. Its spin around `y` is cancelled by the joint, which keeps
+    // its axes perpendicular.
+    rb.borrow_mut().set_lin_vel(Vector3::new(2.0, 0.0, 2.0));
+    rb.borrow_mut().set_ang_vel(Vector3::new(0.0, 3.0, 0.0));
+
+    let mut max_x = 0.0f32;
+    let mut max_z = 0.0f32;
+
+    for _ in 0 .. 200 {
+        world.step(0.016);
+
+        let rb     = rb.borrow();
+        let center = Point3::from_coordinates(rb.position().translation.vector);
+
+        assert!(((center - Point3::new(0.0, 5.0, 0.0)).norm() - 2.0).abs() < 0.05, "The lamp detached: {}", center);
+
+        let (axis1, axis2) = joint.borrow().global_axes();
+        assert!(na::dot(&axis1, &axis2).abs() < 0.05, "The lamp spun.");
+
+        max_x = max_x.max(center.x.abs());
+        max_z = max_z.max(center.z.abs());
+    }
+
+    // The lamp swung in both directions.
+    assert!(max_x > 0.2 && max_z > 0.2, "Unexpected swing: {} {}", max_x, max_z);
+
+    assert_eq!(world.summary().num_universals, 1);
+
+    world.remove_universal(&joint);
+    assert_eq!(world.summary().num_universals, 0);
+}
+
+#[test]
+fn universal_joint_transmits_the_rotation_of_a_shaft() {
+    let mut world = World::new();
+
+    // A shaft along `x`, its end jointed to a second shaft along `x`.
+    let mut shaft1 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 0.1, 0.1)), 1.0, 0.3, 0.6);
+    shaft1.set_translation(Translation3::new(-1.5, 0.0, 0.0));
+    let shaft1 = world.add_rigid_body(shaft1);
+
+    let mut shaft2 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0f32, 0.1, 0.1)), 1.0, 0.3, 0.6);
+    shaft2.set_translation(Translation3::new(1.5, 0.0, 0.0));
+    let shaft2 = world.add_rigid_body(shaft2);
+
+    let anchor1 = Anchor::new(Some(shaft1.clone()), Isometry3::new(Vector3::new(1.5, 0.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(shaft2.clone()), Isometry3::new(Vector3::new(-1.5, 0.0, 0.0), na::zero()));
+    let _ = world.add_universal(Universal::new(anchor1, anchor2, Vector3::y(), Vector3::z()));
+
+    shaft1.borrow_mut().set_ang_vel(Vector3::new(2.0, 0.0, 0.0));
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+    }
+
+    // Both shafts spin together around `x`.
+    let w1 = shaft1.borrow().ang_vel();
+    let w2 = shaft2.borrow().ang_vel();
+    assert!((w1.x - 1.0).abs() < 0.1 && (w2.x - 1.0).abs() < 0.1, "Unexpected angular velocities: {} {}", w1, w2);
+}
@@ -0,0 +1,83 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+
+fn add_ball(world: &mut World<f32>, x: f32) -> RigidBodyHandle<f32> {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(x, 0.0, 0.0));
+
+    world.add_rigid_body(rb)
+}
+
+#[test]
+fn falling_bodies_are_clamped_to_the_world_maximum() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+    world.set_max_lin_vel(Some(2.0));
+
+    let rb1 = add_ball(&mut world, 0.0);
+    let rb2 = add_ball(&mut world, 5.0);
+    rb2.borrow_mut().set_max_lin_vel(Some(4.0));
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+        assert!(na::norm(&rb1.borrow().lin_vel()) <= 2.0 + 1.0e-5);
+    }
+
+    // The per-body maximum overrides the world one.
+    assert!((na::norm(&rb1.borrow().lin_vel()) - 2.0).abs() < 1.0e-5);
+    assert!((na::norm(&rb2.borrow().lin_vel()) - 4.0).abs() < 1.0e-5);
+}
+
+#[test]
+fn angular_velocities_are_clamped() {
+    let mut world = World::new();
+    let rb = add_ball(&mut world, 0.0);
+    rb.borrow_mut().set_max_ang_vel(Some(1.0));
+    rb.borrow_mut().set_ang_vel(Vector3::new(0.0, 3.0, 4.0));
+
+    world.step(0.016);
+
+    let av = rb.borrow().ang_vel();
+    assert!((na::norm(&av) - 1.0).abs() < 1.0e-5);
+
+    // The direction is preserved.
+    assert!(na::norm(&(av - Vector3::new(0.0, 0.6, 0.8))) < 1.0e-5);
+}
+
+#[test]
+fn deeply_penetrating_bodies_separate_slowly() {
+    let mut world = World::new();
+    world.set_max_lin_vel(Some(1.0));
+
+    let rb1 = world.add_rigid_body(RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6));
+    let mut rb2 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb2.set_translation(Translation3::new(0.2, 0.0, 0.0));
+    let rb2 = world.add_rigid_body(rb2);
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+        assert!(na::norm(&rb1.borrow().lin_vel()) <= 1.0 + 1.0e-5);
+        assert!(na::norm(&rb2.borrow().lin_vel()) <= 1.0 + 1.0e-5);
+    }
+
+    assert!(rb2.borrow().position().translation.vector.x > 0.2);
+}
+
+#[test]
+fn velocities_are_unlimited_by_default() {
+    let mut world = World::new();
+    let rb = add_ball(&mut world, 0.0);
+    rb.borrow_mut().set_lin_vel(Vector3::new(100.0, 0.0, 0.0));
+
+    world.step(0.016);
+
+    assert_eq!(world.max_lin_vel(), None);
+    assert_eq!(world.max_ang_vel(), None);
+    assert_eq!(rb.borrow().lin_vel(), Vector3::new(100.0, 0.0, 0.0));
+}
@@ -0,0 +1,179 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Point3, Isometry3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle, WorldObject};
+use nphysics3d::detection::joint::{Anchor, Hinge};
+use nphysics3d::integration::{BodyRk4Integrator, RadialField, Falloff};
+
+// A ball rolling toward a stack of boxes, and a pendulum.
+fn scene(world: &mut World<f32>) -> (RigidBodyHandle<f32>, Vec<RigidBodyHandle<f32>>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    ball.set_translation(Translation3::new(-5.0, 0.5, 0.0));
+    ball.set_lin_vel(Vector3::new(2.0, 0.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    let mut boxes = Vec::new();
+
+    for i in 0 .. 3 {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+        rb.set_translation(Translation3::new(0.0, 0.5 + i as f32, 0.0));
+        boxes.push(world.add_rigid_body(rb));
+    }
+
+    let mut bob = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.2f32, 0.2, 0.2)), 1.0, 0.3, 0.6);
+    bob.set_translation(Translation3::new(12.0, 5.0, 0.0));
+    let bob = world.add_rigid_body(bob);
+
+    let anchor1 = Anchor::new(None, Isometry3::new(Vector3::new(10.0, 5.0, 0.0), na::zero()));
+    let anchor2 = Anchor::new(Some(bob.clone()), Isometry3::new(Vector3::new(-2.0, 0.0, 0.0), na::zero()));
+    let _ = world.add_hinge(Hinge::new(anchor1, anchor2, Vector3::z()));
+
+    boxes.push(bob);
+
+    (ball, boxes)
+}
+
+#[test]
+fn clone_evolves_like_the_original() {
+    let mut world = World::new();
+    let (ball, boxes) = scene(&mut world);
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    let (mut copy, copies) = world.clone_for_prediction();
+
+    assert_eq!(copy.rigid_bodies().count(), world.rigid_bodies().count());
+    assert_eq!(copy.summary().num_hinges, 1);
+    assert_eq!(copy.num_steps(), world.num_steps());
+
+    for _ in 0 .. 100 {
+        world.step(0.016);
+        copy.step(0.016);
+    }
+
+    // The contact manifolds of the stack are rebuilt by the copy, so it drifts slightly.
+    let tolerances = [5.0e-2, 5.0e-2, 5.0e-2, 1.0e-4, 1.0e-4];
+
+    for (rb, tolerance) in boxes.iter().chain(Some(&ball)).zip(tolerances.iter()) {
+        let original = rb.borrow();
+        let copy     = copies.find(&WorldObject::rigid_body_uid(rb)).unwrap().borrow();
+        let error    = (original.position().translation.vector - copy.position().translation.vector).norm();
+
+        assert!(error < *tolerance, "The copy diverged: {}", error);
+    }
+
+    // The ball rolled, and the pendulum swung.
+    assert!(ball.borrow().position().translation.vector.x > -3.0);
+    assert!(boxes[3].borrow().position().translation.vector.y < 4.5);
+}
+
+#[test]
+fn clone_copies_the_integrator_and_the_force_generators() {
+    let mut world = World::new();
+    world.set_integrator(BodyRk4Integrator::new());
+    world.register_force_generator("attractor", RadialField::new(Point3::origin(), 100.0, Falloff::InverseSquare(0.1)));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_translation(Translation3::new(10.0, 0.0, 0.0));
+    rb.set_lin_vel(Vector3::new(0.0, 3.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let (mut copy, copies) = world.clone_for_prediction();
+    let rb_copy = copies.find(&WorldObject::rigid_body_uid(&rb)).unwrap().clone();
+
+    for _ in 0 .. 100 {
+        world.step(0.05);
+        copy.step(0.05);
+    }
+
+    assert_eq!(rb.borrow().position(), rb_copy.borrow().position());
+    assert!(rb.borrow().position().translation.vector.x < 9.0);
+}
+
+#[test]
+fn clone_is_independent() {
+    let mut world = World::new();
+    let (ball, boxes) = scene(&mut world);
+
+    let (mut copy, copies) = world.clone_for_prediction();
+    let ball_copy = copies.find(&WorldObject::rigid_body_uid(&ball)).unwrap().clone();
+
+    // What if the ball was thrown the other way, and the top box removed?
+    ball_copy.borrow_mut().set_lin_vel(Vector3::new(-2.0, 0.0, 0.0));
+    copy.remove_rigid_body(copies.find(&WorldObject::rigid_body_uid(&boxes[2])).unwrap());
+    assert_eq!(copy.rigid_bodies().count(), 5);
+
+    for _ in 0 .. 50 {
+        world.step(0.016);
+        copy.step(0.016);
+    }
+
+    assert!(ball_copy.borrow().position().translation.vector.x < -5.5);
+    assert!(ball.borrow().position().translation.vector.x > -4.5);
+    assert_eq!(world.rigid_bodies().count(), 6);
+    assert_eq!(world.summary().num_hinges, 1);
+}
+
+#[test]
+fn sleeping_bodies_stay_asleep_in_the_clone() {
+    let mut world = World::new();
+    let (_, boxes) = scene(&mut world);
+
+    // Let the stack and the pendulum settle.
+    world.activation_manager().set_energy_threshold(Some(0.5));
+
+    for _ in 0 .. 1000 {
+        world.step(0.016);
+    }
+
+    assert!(!boxes[0].borrow().is_active());
+
+    let (mut copy, copies) = world.clone_for_prediction();
+    copy.step(0.016);
+
+    let box_copy = copies.find(&WorldObject::rigid_body_uid(&boxes[0])).unwrap();
+    assert!(!box_copy.borrow().is_active());
+    assert_eq!(copy.activation_manager().energy_threshold(), Some(0.5));
+}
+
+#[test]
+fn clone_is_warm_started() {
+    let mut world = World::new();
+    let _ = scene(&mut world);
+
+    for _ in 0 .. 60 {
+        world.step(0.016);
+    }
+
+    let (mut copy, _) = world.clone_for_prediction();
+
+    // Without any iteration, the impulses of the next step are those of the caches.
+    for w in [&mut world, &mut copy].iter_mut() {
+        w.constraints_solver().set_num_first_order_iter(0);
+        w.constraints_solver().set_num_second_order_iter(0);
+        w.step(0.016);
+    }
+
+    let sum = |w: &mut World<f32>| -> (f32, f32) {
+        let contacts = w.constraints_solver().contact_impulses().iter().map(|c| c.1.norm()).sum();
+        let joints   = w.constraints_solver().joint_impulses().iter().map(|j| j.1.norm()).sum();
+        (contacts, joints)
+    };
+
+    let (contacts, joints)           = sum(&mut world);
+    let (copy_contacts, copy_joints) = sum(&mut copy);
+
+    assert!(joints > 0.0 && contacts > 0.0);
+    assert!((contacts - copy_contacts).abs() < 1.0e-4);
+    assert!((joints - copy_joints).abs() < 1.0e-4);
+}
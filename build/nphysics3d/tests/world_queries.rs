@@ -0,0 +1,252 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Point3, Vector3, Isometry3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane, Compound, ShapeHandle};
+use ncollide::query::Ray;
+use nphysics3d::world::{World, RayCastOptions};
+use nphysics3d::object::{RigidBody, RigidBodyCollisionGroups, WorldObject};
+
+fn ground_world() -> World<f64> {
+    let mut world = World::new();
+    world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.3, 0.6));
+    world
+}
+
+#[test]
+fn cast_shape_hits_ground() {
+    let world = ground_world();
+    let start  = Isometry3::new(Vector3::new(1.0, 2.0, 0.0), na::zero());
+    let ball   = Ball::new(0.5);
+    let dir    = Vector3::new(0.0, -1.0, 0.0);
+    let groups = RigidBodyCollisionGroups::new_dynamic();
+    let groups = groups.as_collision_groups();
+
+    let hit = world.cast_shape(&ball, &start, &dir, 10.0, groups).unwrap();
+    assert!((hit.toi - 1.5).abs() < 1.0e-3);
+    assert!((hit.normal - Vector3::y()).norm() < 1.0e-3);
+    assert!(hit.point.y.abs() < 1.0e-3);
+
+    assert!(world.cast_shape(&ball, &start, &dir, 1.0, groups).is_none());
+}
+
+#[test]
+fn bodies_containing_point() {
+    let mut world = ground_world();
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(1.0, 1.0, 1.0)), 1.0, 0.3, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 1.0, 0.0));
+    let cube = world.add_rigid_body(rb);
+
+    let groups = RigidBodyCollisionGroups::new_dynamic();
+    let mut out = Vec::new();
+
+    world.bodies_containing_point(&Point3::new(0.5, 1.5, 0.0), groups.as_collision_groups(), &mut out);
+    assert_eq!(out.len(), 1);
+    assert!(&*out[0] as *const _ == &*cube as *const _);
+
+    out.clear();
+    world.bodies_containing_point(&Point3::new(0.5, 2.5, 0.0), groups.as_collision_groups(), &mut out);
+    assert!(out.is_empty());
+
+    // Below the ground plane.
+    world.bodies_containing_point(&Point3::new(5.0, -1.0, 0.0), groups.as_collision_groups(), &mut out);
+    assert_eq!(out.len(), 1);
+}
+
+#[test]
+fn closest_points_between_bodies() {
+    let mut world = ground_world();
+
+    let mut rb1 = RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.3, 0.6);
+    rb1.append_translation(&Translation3::new(0.0, 3.0, 0.0));
+    let rb1 = world.add_rigid_body(rb1);
+
+    let mut rb2 = RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.3, 0.6);
+    rb2.append_translation(&Translation3::new(2.0, 3.0, 0.0));
+    let rb2 = world.add_rigid_body(rb2);
+
+    assert!((world.distance(&rb1, &rb2) - 1.0).abs() < 1.0e-5);
+    assert!(world.closest_points(&rb1, &rb2, 0.5).is_none());
+
+    let cp = world.closest_points(&rb1, &rb2, 2.0).unwrap();
+    assert!((cp.distance - 1.0).abs() < 1.0e-5);
+    assert!((cp.point1 - Point3::new(0.5, 3.0, 0.0)).norm() < 1.0e-5);
+    assert!((cp.point2 - Point3::new(1.5, 3.0, 0.0)).norm() < 1.0e-5);
+    assert!((cp.normal - Vector3::x()).norm() < 1.0e-5);
+}
+
+#[test]
+fn penetration_between_bodies() {
+    let mut world = ground_world();
+
+    let mut rb1 = RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.3, 0.6);
+    rb1.append_translation(&Translation3::new(0.0, 3.0, 0.0));
+    let rb1 = world.add_rigid_body(rb1);
+
+    let mut rb2 = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb2.append_translation(&Translation3::new(0.8, 3.0, 0.0));
+    let rb2 = world.add_rigid_body(rb2);
+
+    let (normal, depth) = world.penetration(&rb1, &rb2).unwrap();
+    assert!((normal - Vector3::x()).norm() < 1.0e-5);
+    assert!((depth - 0.2).abs() < 1.0e-5);
+
+    let (normal, depth) = world.penetration(&rb2, &rb1).unwrap();
+    assert!((normal + Vector3::x()).norm() < 1.0e-5);
+    assert!((depth - 0.2).abs() < 1.0e-5);
+
+    // Touching is not penetrating.
+    rb2.borrow_mut().set_translation(Translation3::new(1.0, 3.0, 0.0));
+    assert!(world.penetration(&rb1, &rb2).is_none());
+
+    rb2.borrow_mut().set_translation(Translation3::new(2.0, 3.0, 0.0));
+    assert!(world.penetration(&rb1, &rb2).is_none());
+}
+
+#[test]
+fn ray_hits_compound_child() {
+    let mut world = ground_world();
+
+    let shapes = vec![
+        (Isometry3::new(Vector3::new(-1.0, 0.0, 0.0), na::zero()), ShapeHandle::new(Ball::new(0.5))),
+        (Isometry3::new(Vector3::new(1.0, 0.0, 0.0), na::zero()), ShapeHandle::new(Ball::new(0.5)))
+    ];
+    let mut rb = RigidBody::new_dynamic(Compound::new(shapes), 1.0, 0.3, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 3.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+    world.step(0.0);
+
+    let ray     = Ray::new(Point3::new(1.0, 10.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+    let groups  = RigidBodyCollisionGroups::new_dynamic();
+    let options = RayCastOptions::new(groups.as_collision_groups());
+    let mut hits = Vec::new();
+    world.interferences_with_ray(&ray, &options, &mut hits);
+    assert_eq!(hits.len(), 2);
+
+    let mut short_hits = Vec::new();
+    world.interferences_with_ray(&ray, &RayCastOptions::new(groups.as_collision_groups()).with_max_toi(8.0), &mut short_hits);
+    assert_eq!(short_hits.len(), 1);
+
+    let ignored = [WorldObject::RigidBody(rb.clone())];
+    let mut unignored_hits = Vec::new();
+    world.interferences_with_ray(&ray, &options.with_ignored(&ignored), &mut unignored_hits);
+    assert_eq!(unignored_hits.len(), 1);
+    assert!(unignored_hits[0].sub_shape.is_none());
+
+    let closest = world.cast_ray_closest(&ray, &RayCastOptions::new(groups.as_collision_groups())).unwrap();
+    assert_eq!(closest.sub_shape, Some(1));
+    let closest = world.cast_ray_closest(&ray, &RayCastOptions::new(groups.as_collision_groups()).with_max_toi(20.0)).unwrap();
+    assert!((closest.toi - 6.5).abs() < 1.0e-3);
+    assert!(world.cast_ray_closest(&ray, &RayCastOptions::new(groups.as_collision_groups()).with_max_toi(5.0)).is_none());
+
+    let hit = hits.iter().find(|h| h.sub_shape.is_some()).unwrap();
+    assert_eq!(hit.sub_shape, Some(1));
+    assert!((hit.toi - 6.5).abs() < 1.0e-3);
+    assert!((hit.point - Point3::new(1.0, 3.5, 0.0)).norm() < 1.0e-3);
+    assert!((hit.normal - Vector3::y()).norm() < 1.0e-3);
+}
+
+#[test]
+fn bodies_invisible_to_queries() {
+    let mut world = ground_world();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.0, 0.6);
+    let mut rb_groups = RigidBodyCollisionGroups::new_dynamic();
+    rb_groups.set_visible_to_queries(false);
+    rb.set_collision_groups(rb_groups);
+    rb.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+    world.step(0.0);
+
+    let ray    = Ray::new(Point3::new(0.0, 10.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+    let groups = RigidBodyCollisionGroups::new_dynamic();
+    let hit    = world.cast_ray_closest(&ray, &RayCastOptions::new(groups.as_collision_groups())).unwrap();
+    assert!(hit.object.is_rigid_body() && !hit.object.unwrap_rigid_body().borrow().can_move());
+
+    let start = Isometry3::new(Vector3::new(0.0, 5.0, 0.0), na::zero());
+    let hit   = world.cast_shape(&Ball::new(0.5), &start, &-Vector3::y(), 10.0, groups.as_collision_groups()).unwrap();
+    assert!((hit.toi - 4.5).abs() < 1.0e-3);
+
+    // It still collides with the ground.
+    for _ in 0 .. 200 {
+        world.step(0.016);
+    }
+
+    assert!((rb.borrow().position().translation.vector.y - 0.5).abs() < 0.1);
+}
+
+#[test]
+fn unbounded_ray_hits_the_closest_of_a_row() {
+    let mut world = ground_world();
+    let mut balls = Vec::new();
+
+    // Added from the farthest to the closest to the ray origin.
+    for i in 0 .. 10 {
+        let mut rb = RigidBody::new_static(Ball::new(0.5), 0.3, 0.6);
+        rb.append_translation(&Translation3::new(20.0 - 2.0 * i as f64, 1.0, 0.0));
+        balls.push(world.add_rigid_body(rb));
+    }
+    world.step(0.0);
+
+    let ray     = Ray::new(Point3::new(-5.0, 1.0, 0.0), Vector3::x());
+    let groups  = RigidBodyCollisionGroups::new_dynamic();
+    let options = RayCastOptions::new(groups.as_collision_groups());
+
+    let hit = world.cast_ray_closest(&ray, &options).unwrap();
+    assert!((hit.toi - 6.5).abs() < 1.0e-3);
+
+    let ignored = [WorldObject::RigidBody(balls[9].clone())];
+    let hit     = world.cast_ray_closest(&ray, &options.with_ignored(&ignored)).unwrap();
+    assert!((hit.toi - 8.5).abs() < 1.0e-3);
+}
+
+#[test]
+fn every_user_group_can_be_queried() {
+    assert_eq!(RigidBodyCollisionGroups::max_group_id(), 27);
+
+    let mut world = ground_world();
+
+    let mut rb = RigidBody::new_static(Ball::new(0.5), 0.3, 0.6);
+    let mut rb_groups = RigidBodyCollisionGroups::new_static();
+    rb_groups.set_membership(&[27]);
+    rb.set_collision_groups(rb_groups);
+    rb.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+    let _ = world.add_rigid_body(rb);
+    world.step(0.0);
+
+    let ray        = Ray::new(Point3::new(0.0, 10.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+    let mut groups = RigidBodyCollisionGroups::new_dynamic();
+    groups.set_whitelist(&[27]);
+
+    let hit = world.cast_ray_closest(&ray, &RayCastOptions::new(groups.as_collision_groups())).unwrap();
+    assert!((hit.toi - 7.5).abs() < 1.0e-3);
+}
+
+#[test]
+fn queries_follow_the_bodies() {
+    let mut world = ground_world();
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5), 1.0, 0.3, 0.6);
+    rb.append_translation(&Translation3::new(0.0, 2.0, 0.0));
+    let rb = world.add_rigid_body(rb);
+
+    let ray     = Ray::new(Point3::new(5.0, 10.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+    let groups  = RigidBodyCollisionGroups::new_dynamic();
+    let options = RayCastOptions::new(groups.as_collision_groups()).with_max_toi(9.0);
+    assert!(world.cast_ray_closest(&ray, &options).is_none());
+
+    rb.borrow_mut().set_translation(Translation3::new(5.0, 2.0, 0.0));
+    world.step(0.0);
+    let hit = world.cast_ray_closest(&ray, &options).unwrap();
+    assert!((hit.toi - 7.5).abs() < 1.0e-3);
+
+    // Added without stepping.
+    let mut rb = RigidBody::new_static(Ball::new(0.5), 0.3, 0.6);
+    rb.append_translation(&Translation3::new(5.0, 4.0, 0.0));
+    let _ = world.add_rigid_body(rb);
+    let hit = world.cast_ray_closest(&ray, &options).unwrap();
+    assert!((hit.toi - 5.5).abs() < 1.0e-3);
+}
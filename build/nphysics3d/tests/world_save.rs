@@ -0,0 +1,78 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics3d::world::World;
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn incremental_save_only_contains_awake_bodies() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.6));
+
+    // Ten boxes resting on the ground.
+    let mut boxes = Vec::new();
+    for i in 0 .. 10 {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+        rb.set_translation(Translation3::new(i as f32 * 3.0, 0.58, 0.0));
+        boxes.push(world.add_rigid_body(rb));
+    }
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    assert!(boxes.iter().all(|b| !b.borrow().is_active()));
+
+    let baseline = world.save();
+    assert!(!baseline.is_incremental());
+    assert_eq!(baseline.bodies().len(), 10);
+
+    // A ball falling far from the boxes.
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    ball.set_translation(Translation3::new(-10.0, 10.0, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+    }
+
+    let save = world.save_incremental(&baseline);
+    assert_eq!(save.baseline(), Some(baseline.id()));
+    assert_eq!(save.bodies().len(), 1);
+    assert!(save.body(&ball).is_some());
+    let saved_ball = save.body(&ball).unwrap().position.translation.vector;
+
+    // Move a sleeping box and keep simulating.
+    boxes[0].borrow_mut().set_translation(Translation3::new(0.0, 5.0, 0.0));
+
+    for _ in 0 .. 20 {
+        world.step(0.016);
+    }
+
+    world.restore(&save, Some(&baseline));
+
+    assert_eq!(ball.borrow().position().translation.vector, saved_ball);
+    assert!((boxes[0].borrow().position().translation.vector.y - 0.58).abs() < 0.05);
+    assert!(!boxes[0].borrow().is_active());
+
+    // A sleeping body that moved since the baseline is part of the incremental saves.
+    boxes[1].borrow_mut().set_translation(Translation3::new(3.0, 0.7, 0.0));
+    assert!(world.save_incremental(&baseline).body(&boxes[1]).is_some());
+}
+
+#[test]
+#[should_panic]
+fn restore_with_wrong_baseline() {
+    let mut world = World::<f32>::new();
+
+    let baseline = world.save();
+    let other    = world.save();
+    let save     = world.save_incremental(&baseline);
+
+    world.restore(&save, Some(&other));
+}
@@ -0,0 +1,115 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use na::{Vector3, Translation3};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics3d::world::{World, WorldStage};
+use nphysics3d::object::RigidBody;
+
+#[test]
+fn toggle_ccd() {
+    let mut world = World::new();
+
+    let wall = RigidBody::new_static(Cuboid::new(Vector3::new(0.05, 2.0, 2.0)), 0.0, 0.5);
+    let _ = world.add_rigid_body(wall);
+
+    let mut plate = RigidBody::new_dynamic(Cuboid::new(Vector3::new(0.02, 0.3, 0.3)), 1.0, 0.0, 0.5);
+    plate.append_translation(&Translation3::new(-5.0, 0.0, 0.0));
+    plate.set_lin_vel(Vector3::new(200.0, 0.0, 0.0));
+    plate.enable_ccd(0.01);
+    let plate = world.add_rigid_body(plate);
+
+    world.set_stage_enabled(WorldStage::ContinuousCollisionDetection, false);
+    assert!(!world.stage_enabled(WorldStage::ContinuousCollisionDetection));
+
+    for _ in 0 .. 10 {
+        world.step(1.0 / 60.0);
+    }
+
+    // Tunneled through the wall.
+    let x = plate.borrow().position().translation.vector.x;
+    assert!(x > 0.0);
+
+    // Its motion while the CCD was disabled is not clamped afterward.
+    world.set_stage_enabled(WorldStage::ContinuousCollisionDetection, true);
+    world.step(1.0 / 60.0);
+    assert!(plate.borrow().position().translation.vector.x > x);
+}
+
+#[test]
+fn toggle_sleeping() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    let ball = world.add_rigid_body(ball);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    assert!(!ball.borrow().is_active());
+
+    // Sleeping bodies are woken up, and stay awake.
+    world.set_stage_enabled(WorldStage::Sleeping, false);
+    assert!(!world.stage_enabled(WorldStage::Sleeping));
+    assert!(ball.borrow().is_active());
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    assert!(ball.borrow().is_active());
+
+    world.set_stage_enabled(WorldStage::Sleeping, true);
+
+    for _ in 0 .. 300 {
+        world.step(0.016);
+    }
+
+    assert!(!ball.borrow().is_active());
+}
+
+#[test]
+fn toggle_solver() {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let _ = world.add_rigid_body(RigidBody::new_static(Plane::new(Vector3::y()), 0.0, 0.5));
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Translation3::new(0.0, 0.5, 0.0));
+    ball.set_deactivation_threshold(None);
+    let ball = world.add_rigid_body(ball);
+
+    for _ in 0 .. 60 {
+        world.step(0.016);
+    }
+
+    assert!(ball.borrow().position().translation.vector.y > 0.4);
+
+    // Without solver, the ball falls through the ground.
+    world.set_stage_enabled(WorldStage::Solver, false);
+
+    for _ in 0 .. 30 {
+        world.step(0.016);
+    }
+
+    assert!(ball.borrow().position().translation.vector.y < 0.0);
+
+    // And is pushed back out once the solver is re-enabled.
+    world.set_stage_enabled(WorldStage::Solver, true);
+    ball.borrow_mut().set_lin_vel(na::zero());
+
+    let y = ball.borrow().position().translation.vector.y;
+
+    for _ in 0 .. 60 {
+        world.step(0.016);
+    }
+
+    assert!(ball.borrow().position().translation.vector.y > y);
+}
@@ -53,6 +53,68 @@ pub fn draw_colls(window:  &mut graphics::RenderWindow,
                     &Point2::from_coordinates(bis.borrow().anchor2_pos().translation.vector),
                     &Color::new_rgb(255, 0, 0)
                 );
+            },
+            Constraint::Hinge(ref h) => {
+                draw_line(
+                    window,
+                    &Point2::from_coordinates(h.borrow().anchor1_pos().translation.vector),
+                    &Point2::from_coordinates(h.borrow().anchor2_pos().translation.vector),
+                    &Color::new_rgb(255, 0, 0)
+                );
+            },
+            Constraint::Prismatic(ref p) => {
+                draw_line(
+                    window,
+                    &Point2::from_coordinates(p.borrow().anchor1_pos().translation.vector),
+                    &Point2::from_coordinates(p.borrow().anchor2_pos().translation.vector),
+                    &Color::new_rgb(255, 0, 0)
+                );
+            },
+            Constraint::Spring(ref s) => {
+                draw_line(
+                    window,
+                    &s.borrow().anchor1_pos(),
+                    &s.borrow().anchor2_pos(),
+                    &Color::new_rgb(255, 0, 0)
+                );
+            },
+            Constraint::Distance(ref d) => {
+                draw_line(
+                    window,
+                    &d.borrow().anchor1_pos(),
+                    &d.borrow().anchor2_pos(),
+                    &Color::new_rgb(255, 0, 0)
+                );
+            },
+            Constraint::AngularMotor(ref m) => {
+                draw_line(
+                    window,
+                    &Point2::from_coordinates(m.borrow().anchor1_pos().translation.vector),
+                    &Point2::from_coordinates(m.borrow().anchor2_pos().translation.vector),
+                    &Color::new_rgb(255, 0, 0)
+                );
+            },
+            Constraint::RackAndPinion(ref r) => {
+                draw_line(
+                    window,
+                    &Point2::from_coordinates(r.borrow().anchor1_pos().translation.vector),
+                    &Point2::from_coordinates(r.borrow().anchor2_pos().translation.vector),
+                    &Color::new_rgb(255, 0, 0)
+                );
+            },
+            Constraint::Pulley(ref p) => {
+                let bp = p.borrow();
+                draw_line(window, &bp.anchor1_pos(), bp.pulley1(), &Color::new_rgb(255, 0, 0));
+                draw_line(window, bp.pulley1(), bp.pulley2(), &Color::new_rgb(255, 0, 0));
+                draw_line(window, bp.pulley2(), &bp.anchor2_pos(), &Color::new_rgb(255, 0, 0));
+            },
+            Constraint::Gear(ref g) => {
+                draw_line(
+                    window,
+                    &Point2::from_coordinates(g.borrow().anchor1_pos().translation.vector),
+                    &Point2::from_coordinates(g.borrow().anchor2_pos().translation.vector),
+                    &Color::new_rgb(255, 0, 0)
+                );
             }
         }
     }
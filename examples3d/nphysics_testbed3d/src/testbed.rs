@@ -476,6 +476,56 @@ fn draw_collisions(window: &mut Window, physics: &mut World<f32>) {
                 let p1 = Point3::from_coordinates(f.borrow().anchor1_pos().translation.vector);
                 let p2 = Point3::from_coordinates(f.borrow().anchor2_pos().translation.vector);
 
+                window.draw_line(&p1, &p2, &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Hinge(ref h) => {
+                let p1 = Point3::from_coordinates(h.borrow().anchor1_pos().translation.vector);
+                let p2 = Point3::from_coordinates(h.borrow().anchor2_pos().translation.vector);
+
+                window.draw_line(&p1, &p2, &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Prismatic(ref p) => {
+                let p1 = Point3::from_coordinates(p.borrow().anchor1_pos().translation.vector);
+                let p2 = Point3::from_coordinates(p.borrow().anchor2_pos().translation.vector);
+
+                window.draw_line(&p1, &p2, &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Spring(ref s) => {
+                let bs = s.borrow();
+                window.draw_line(&bs.anchor1_pos(), &bs.anchor2_pos(), &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Distance(ref d) => {
+                let bd = d.borrow();
+                window.draw_line(&bd.anchor1_pos(), &bd.anchor2_pos(), &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::AngularMotor(ref m) => {
+                let p1 = Point3::from_coordinates(m.borrow().anchor1_pos().translation.vector);
+                let p2 = Point3::from_coordinates(m.borrow().anchor2_pos().translation.vector);
+
+                window.draw_line(&p1, &p2, &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::RackAndPinion(ref r) => {
+                let p1 = Point3::from_coordinates(r.borrow().anchor1_pos().translation.vector);
+                let p2 = Point3::from_coordinates(r.borrow().anchor2_pos().translation.vector);
+
+                window.draw_line(&p1, &p2, &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Pulley(ref p) => {
+                let bp = p.borrow();
+                window.draw_line(&bp.anchor1_pos(), bp.pulley1(), &Point3::new(0.0, 1.0, 0.0));
+                window.draw_line(bp.pulley1(), bp.pulley2(), &Point3::new(0.0, 1.0, 0.0));
+                window.draw_line(bp.pulley2(), &bp.anchor2_pos(), &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Gear(ref g) => {
+                let p1 = Point3::from_coordinates(g.borrow().anchor1_pos().translation.vector);
+                let p2 = Point3::from_coordinates(g.borrow().anchor2_pos().translation.vector);
+
+                window.draw_line(&p1, &p2, &Point3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Universal(ref u) => {
+                let p1 = Point3::from_coordinates(u.borrow().anchor1_pos().translation.vector);
+                let p2 = Point3::from_coordinates(u.borrow().anchor2_pos().translation.vector);
+
                 window.draw_line(&p1, &p2, &Point3::new(0.0, 1.0, 0.0));
             }
         }
@@ -0,0 +1,230 @@
+extern crate nalgebra as na;
+extern crate ncollide;
+extern crate nphysics3d;
+
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Point3, Vector3, Translation3};
+use ncollide::shape::{Plane, Cuboid, Ball};
+use nphysics3d::world::World;
+use nphysics3d::object::{RigidBody, RigidBodyHandle};
+use nphysics3d::detection::joint::{Anchor, BallInSocket, Joint};
+
+/*
+ * Long-running, headless, simulation of a few scenes.
+ *
+ * Usage: soak [stack|chain|all] [num_steps] [report_interval]
+ *
+ * This reports drift statistics (stack height loss, joint separation, energy trend) and memory
+ * usage over time, to catch slow leaks and gradual instabilities that short tests miss.
+ */
+
+const DT:      f32 = 0.016;
+const GRAVITY: f32 = 9.81;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let scene     = args.get(1).map(|s| &s[..]).unwrap_or("all").to_string();
+    let num_steps = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(1_000_000usize);
+    let interval  = args.get(3).and_then(|s| s.parse().ok()).unwrap_or(10_000usize);
+
+    match &scene[..] {
+        "stack" => soak("stack", stack_scene(), num_steps, interval),
+        "chain" => soak("chain", chain_scene(), num_steps, interval),
+        "all"   => {
+            soak("stack", stack_scene(), num_steps, interval);
+            soak("chain", chain_scene(), num_steps, interval);
+        },
+        _ => panic!("Unknown scene `{}`. Expected one of: stack, chain, all.", scene)
+    }
+}
+
+/// A scene to be soaked, with the objects the drift metrics are computed from.
+struct Scene {
+    world:  World<f32>,
+    bodies: Vec<RigidBodyHandle<f32>>,
+    top:    Option<RigidBodyHandle<f32>>,
+    joints: Vec<Rc<RefCell<BallInSocket<f32>>>>
+}
+
+/// Drift statistics measured at a given step.
+struct Metrics {
+    height_loss:      f32,
+    joint_separation: f32,
+    energy:           f32,
+    resident_memory:  Option<usize>
+}
+
+fn soak(name: &str, mut scene: Scene, num_steps: usize, interval: usize) {
+    let initial_height = scene.top.as_ref().map(|rb| rb.borrow().position().translation.vector.y);
+    let initial        = measure(&scene, initial_height);
+
+    println!("=== Soaking scene `{}` for {} steps.", name, num_steps);
+    println!("{:>10} {:>12} {:>12} {:>12} {:>12}", "step", "height loss", "joint sep.", "energy", "memory (kB)");
+    print_metrics(0, &initial);
+
+    let mut max_height_loss      = initial.height_loss;
+    let mut max_joint_separation = initial.joint_separation;
+    let mut last                 = initial;
+    let initial_energy           = last.energy;
+    let initial_memory           = last.resident_memory;
+
+    for step in 1 .. num_steps + 1 {
+        scene.world.step(DT);
+
+        if step % interval == 0 || step == num_steps {
+            last = measure(&scene, initial_height);
+            max_height_loss      = max_height_loss.max(last.height_loss);
+            max_joint_separation = max_joint_separation.max(last.joint_separation);
+            print_metrics(step, &last);
+        }
+    }
+
+    println!("--- Summary for `{}`:", name);
+    println!("    max. height loss:      {}", max_height_loss);
+    println!("    max. joint separation: {}", max_joint_separation);
+    println!("    energy trend:          {} -> {} ({:+} per million steps)",
+             initial_energy, last.energy,
+             (last.energy - initial_energy) * 1.0e6 / num_steps.max(1) as f32);
+
+    match (initial_memory, last.resident_memory) {
+        (Some(m1), Some(m2)) => println!("    memory growth:         {} kB", m2 as isize - m1 as isize),
+        _                    => println!("    memory growth:         unavailable on this platform")
+    }
+}
+
+fn print_metrics(step: usize, m: &Metrics) {
+    let memory = m.resident_memory.map(|m| m.to_string()).unwrap_or("n/a".to_string());
+
+    println!("{:>10} {:>12.6} {:>12.6} {:>12.4} {:>12}", step, m.height_loss, m.joint_separation, m.energy, memory);
+}
+
+fn measure(scene: &Scene, initial_height: Option<f32>) -> Metrics {
+    let height_loss = match (scene.top.as_ref(), initial_height) {
+        (Some(rb), Some(h)) => h - rb.borrow().position().translation.vector.y,
+        _                   => 0.0
+    };
+
+    let mut joint_separation = 0.0f32;
+
+    for joint in scene.joints.iter() {
+        let j = joint.borrow();
+        joint_separation = joint_separation.max(na::distance(&j.anchor1_pos(), &j.anchor2_pos()));
+    }
+
+    Metrics {
+        height_loss:      height_loss,
+        joint_separation: joint_separation,
+        energy:           total_energy(&scene.bodies[..]),
+        resident_memory:  resident_memory()
+    }
+}
+
+/// The sum of the kinetic and potential energies of the given bodies.
+fn total_energy(bodies: &[RigidBodyHandle<f32>]) -> f32 {
+    let mut energy = 0.0;
+
+    for b in bodies.iter() {
+        let rb = b.borrow();
+
+        if let Some(mass) = rb.mass() {
+            let lin_vel = rb.lin_vel();
+            let ang_vel = rb.ang_vel();
+            let inertia = rb.inv_inertia().try_inverse().unwrap_or(na::zero());
+
+            energy += 0.5 * mass * na::norm_squared(&lin_vel);
+            energy += 0.5 * na::dot(&ang_vel, &(inertia * ang_vel));
+            energy += mass * GRAVITY * rb.center_of_mass().y;
+        }
+    }
+
+    energy
+}
+
+/// The resident memory of this process in kilobytes, if it can be determined.
+fn resident_memory() -> Option<usize> {
+    let mut statm = String::new();
+
+    match File::open("/proc/self/statm") {
+        Ok(mut f) => if f.read_to_string(&mut statm).is_err() { return None },
+        Err(_)    => return None
+    }
+
+    // The second field is the number of resident pages, assumed to be 4kB each.
+    statm.split_whitespace().nth(1).and_then(|s| s.parse::<usize>().ok()).map(|pages| pages * 4)
+}
+
+fn ground(world: &mut World<f32>) {
+    let rb = RigidBody::new_static(Plane::new(Vector3::new(0.0, 1.0, 0.0)), 0.3, 0.6);
+    world.add_rigid_body(rb);
+}
+
+/// A single tall stack of boxes.
+fn stack_scene() -> Scene {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -GRAVITY, 0.0));
+    ground(&mut world);
+
+    let num    = 10;
+    let rad    = 0.5;
+    let mut bodies = Vec::new();
+
+    for i in 0usize .. num {
+        let y = rad + 0.04 + i as f32 * rad * 2.0;
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vector3::new(rad - 0.04, rad - 0.04, rad - 0.04)), 1.0, 0.3, 0.6);
+        rb.append_translation(&Translation3::new(0.0, y, 0.0));
+
+        bodies.push(world.add_rigid_body(rb));
+    }
+
+    let top = bodies.last().cloned();
+
+    Scene {
+        world:  world,
+        bodies: bodies,
+        top:    top,
+        joints: Vec::new()
+    }
+}
+
+/// A chain of balls hanging from a fixed point.
+fn chain_scene() -> Scene {
+    let mut world = World::new();
+    world.set_gravity(Vector3::new(0.0, -GRAVITY, 0.0));
+
+    let num   = 20;
+    let rad   = 0.2;
+    let shift = rad * 3.0;
+    let mut bodies = Vec::new();
+    let mut joints = Vec::new();
+    let mut prev: Option<RigidBodyHandle<f32>> = None;
+
+    for i in 0usize .. num {
+        let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6);
+        rb.set_deactivation_threshold(None);
+        rb.append_translation(&Translation3::new(i as f32 * shift, 20.0, 0.0));
+
+        let curr = world.add_rigid_body(rb);
+
+        let anchor1 = match prev {
+            Some(ref p) => Anchor::new(Some(p.clone()), Point3::new(shift / 2.0, 0.0, 0.0)),
+            None        => Anchor::new(None, Point3::new(-shift / 2.0, 20.0, 0.0))
+        };
+        let anchor2 = Anchor::new(Some(curr.clone()), Point3::new(-shift / 2.0, 0.0, 0.0));
+
+        joints.push(world.add_ball_in_socket(BallInSocket::new(anchor1, anchor2)));
+        bodies.push(curr.clone());
+        prev = Some(curr);
+    }
+
+    Scene {
+        world:  world,
+        bodies: bodies,
+        top:    None,
+        joints: joints
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::mpsc::{self, SyncSender, Receiver, TrySendError};
+
+use alga::general::Real;
+use na;
+use math::Point;
+
+/// A RGB color with components in `[0, 1]`.
+pub type DebugColor = [f32; 3];
+
+/// A primitive describing part of the state of the physics world.
+#[derive(Clone, Debug)]
+pub enum DebugPrimitive<N: Real> {
+    /// A line segment.
+    Line(Point<N>, Point<N>, DebugColor),
+    /// A single point.
+    Point(Point<N>, DebugColor),
+    /// A text label located at a point.
+    Label(Point<N>, String, DebugColor)
+}
+
+/// All the debug primitives generated at a given step.
+#[derive(Clone, Debug)]
+pub struct DebugFrame<N: Real> {
+    /// The number of steps performed before this frame was generated.
+    pub step:       usize,
+    /// The primitives to be drawn.
+    pub primitives: Vec<DebugPrimitive<N>>
+}
+
+/// The emitting side of a bounded channel of debug frames.
+///
+/// Once a channel is given to the physics world, it will send a frame at the end of each step.
+/// The frames can be consumed by any external viewer, possibly on another thread or process, so
+/// that the physics does not have to be linked against a graphics library. If the receiver does
+/// not keep up, new frames are dropped instead of blocking the simulation.
+pub struct DebugChannel<N: Real> {
+    sender:        SyncSender<DebugFrame<N>>,
+    step:          usize,
+    dropped:       usize,
+    disconnected:  bool,
+    normal_length: N
+}
+
+impl<N: Real> DebugChannel<N> {
+    /// Creates a new debug channel able to hold at most `capacity` frames not consumed yet.
+    pub fn new(capacity: usize) -> (DebugChannel<N>, Receiver<DebugFrame<N>>) {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+
+        let channel = DebugChannel {
+            sender:        sender,
+            step:          0,
+            dropped:       0,
+            disconnected:  false,
+            normal_length: na::convert(0.1f64)
+        };
+
+        (channel, receiver)
+    }
+
+    /// The length of the lines representing contact normals.
+    #[inline]
+    pub fn normal_length(&self) -> N {
+        self.normal_length
+    }
+
+    /// Sets the length of the lines representing contact normals.
+    #[inline]
+    pub fn set_normal_length(&mut self, length: N) {
+        self.normal_length = length
+    }
+
+    /// The number of frames dropped because the receiver did not consume them fast enough.
+    #[inline]
+    pub fn num_dropped_frames(&self) -> usize {
+        self.dropped
+    }
+
+    /// Whether the receiving side of this channel has hung up.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// Sends a frame made of the given primitives.
+    ///
+    /// Returns `false` if the frame has been dropped.
+    pub fn send(&mut self, primitives: Vec<DebugPrimitive<N>>) -> bool {
+        let frame = DebugFrame {
+            step:       self.step,
+            primitives: primitives
+        };
+
+        self.step += 1;
+
+        match self.sender.try_send(frame) {
+            Ok(_) => true,
+            Err(TrySendError::Full(_)) => {
+                self.dropped += 1;
+                false
+            },
+            Err(TrySendError::Disconnected(_)) => {
+                self.disconnected = true;
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Renderer-agnostic debug output.
+
+pub use self::debug_channel::{DebugChannel, DebugFrame, DebugPrimitive, DebugColor};
+
+mod debug_channel;
@@ -0,0 +1,91 @@
+use alga::general::Real;
+use na;
+use ncollide::bounding_volume::{self, AABB};
+use ncollide::shape::ShapeHandle;
+use object::RigidBodyHandle;
+use math::{Point, Isometry};
+
+/// A rigid body which broad phase bounding box grew abnormally large.
+pub struct AabbGrowthEvent<N: Real> {
+    /// The rigid body.
+    pub body:  RigidBodyHandle<N>,
+    /// The bounding box of the rigid body on the broad phase, enlarged by its contact prediction.
+    /// This is the last valid bounding box if the body position is not finite.
+    pub aabb:  AABB<Point<N>>,
+    /// The ratio between the size of `aabb` and the size of the body's shape. This is NaN if the
+    /// body position is not finite.
+    pub ratio: N
+}
+
+/// Trait implemented by the receivers of abnormal bounding box growth events.
+pub trait AabbGrowthHandler<N: Real> {
+    /// Called at each step, for each rigid body which bounding box is abnormally large.
+    ///
+    /// The body may be modified by the handler. Until its position is made finite again, the
+    /// collision world keeps it at its last valid position.
+    fn handle_aabb_growth(&mut self, event: &AabbGrowthEvent<N>);
+}
+
+impl<N: Real, F: FnMut(&AabbGrowthEvent<N>)> AabbGrowthHandler<N> for F {
+    #[inline]
+    fn handle_aabb_growth(&mut self, event: &AabbGrowthEvent<N>) {
+        self(event)
+    }
+}
+
+/// Watches the size of the broad phase bounding boxes of the active rigid bodies.
+///
+/// A bounding box getting much larger than the shape it contains usually indicates a runaway
+/// velocity enlarging the speculative contact margins, or a position that is no longer finite.
+/// Detecting those early lets the application fix exploding bodies before they degrade the
+/// broad phase performance.
+pub struct AabbGrowthMonitor<N: Real> {
+    max_ratio: N,
+    handler:   Box<AabbGrowthHandler<N>>
+}
+
+impl<N: Real> AabbGrowthMonitor<N> {
+    /// Creates a monitor calling `handler` for each body which bounding box is more than
+    /// `max_ratio` times larger than its shape.
+    pub fn new<H: AabbGrowthHandler<N> + 'static>(max_ratio: N, handler: H) -> AabbGrowthMonitor<N> {
+        AabbGrowthMonitor {
+            max_ratio: max_ratio,
+            handler:   Box::new(handler)
+        }
+    }
+
+    /// The maximum ratio between the size of a bounding box and the size of its shape.
+    #[inline]
+    pub fn max_ratio(&self) -> N {
+        self.max_ratio
+    }
+
+    /// Sets the maximum ratio between the size of a bounding box and the size of its shape.
+    #[inline]
+    pub fn set_max_ratio(&mut self, max_ratio: N) {
+        self.max_ratio = max_ratio
+    }
+
+    /// The ratio between the size of `aabb` and the size of the bounding box of `shape` in its
+    /// local space, if it exceeds the maximum ratio.
+    #[doc(hidden)]
+    pub fn abnormal_ratio(&self, shape: &ShapeHandle<Point<N>, Isometry<N>>, aabb: &AABB<Point<N>>) -> Option<N> {
+        let local = bounding_volume::aabb(shape.as_ref(), &na::one::<Isometry<N>>());
+        let ratio = na::norm(&(*aabb.maxs() - *aabb.mins())) / na::norm(&(*local.maxs() - *local.mins()));
+
+        // Also catches NaNs.
+        if ratio <= self.max_ratio {
+            None
+        }
+        else {
+            Some(ratio)
+        }
+    }
+
+    /// Sends an event to the handler.
+    #[doc(hidden)]
+    #[inline]
+    pub fn emit(&mut self, event: &AabbGrowthEvent<N>) {
+        self.handler.handle_aabb_growth(event)
+    }
+}
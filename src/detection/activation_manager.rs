@@ -5,19 +5,58 @@ use ncollide::utils::data::hash_map::HashMap;
 use ncollide::utils::data::hash::UintTWHash;
 use world::RigidBodyCollisionWorld;
 use detection::constraint::Constraint;
-use detection::joint::{JointManager, Joint};
+use detection::joint::JointManager;
 use object::{WorldObject, RigidBody, RigidBodyHandle, ActivationState};
 use utils::union_find::UnionFindSet;
 use utils::union_find;
 
+const UNVISITED: usize = usize::MAX;
+
+/// Statistics about the islands built during the last update of an `ActivationManager`.
+///
+/// An island is a set of movable bodies interacting through contacts or joints.
+#[derive(Clone, Debug, Default)]
+pub struct IslandStatistics {
+    /// The number of islands, including those made of a single isolated body.
+    pub num_islands: usize,
+    /// The number of bodies of the largest island.
+    pub largest:     usize,
+    /// The island size histogram: `histogram[i]` is the number of islands with a size in
+    /// `[2^i, 2^(i + 1)[`.
+    pub histogram:   Vec<usize>
+}
+
+/// A body which removal would split its island into several large parts.
+///
+/// Scenes with such bodies usually benefit from being restructured, e.g., by breaking a long
+/// chain of bodies resting on each other.
+pub struct IslandBridge<N: Real> {
+    /// The bridge body.
+    pub body:        RigidBodyHandle<N>,
+    /// The number of bodies on the island of `body`.
+    pub island_size: usize,
+    /// The sizes of the parts `body` connects, in decreasing order.
+    pub parts:       Vec<usize>
+}
+
 /// Structure that monitors island-based activation/deactivation of objects.
 ///
 /// It is responsible for making objects sleep or wake up.
 pub struct ActivationManager<N: Real> {
     mix_factor:     N,
+    threshold:      Option<N>,
+    delay:          usize,
+    deactivation:   bool,
+    // The number of consecutive updates each body spent with a low energy.
+    calm_updates:   HashMap<usize, usize, UintTWHash>,
     ufind:          Vec<UnionFindSet>,
     can_deactivate: Vec<bool>,
     to_activate:    Vec<RigidBodyHandle<N>>,
+    edges:          Vec<(usize, usize)>,
+    island_sizes:   Vec<usize>,
+    // The island root of each body, or `UNVISITED` for the bodies that cannot move.
+    island_roots:   Vec<usize>,
+    statistics:     IslandStatistics
 }
 
 impl<N: Real> ActivationManager<N> {
@@ -31,10 +70,134 @@ impl<N: Real> ActivationManager<N> {
 
         ActivationManager {
             mix_factor:     mix_factor,
+            threshold:      None,
+            delay:          0,
+            deactivation:   true,
+            calm_updates:   HashMap::new(UintTWHash::new()),
             ufind:          Vec::new(),
             can_deactivate: Vec::new(),
             to_activate:    Vec::new(),
+            edges:          Vec::new(),
+            island_sizes:   Vec::new(),
+            island_roots:   Vec::new(),
+            statistics:     IslandStatistics::default()
+        }
+    }
+
+    /// The ratio of energy to keep between two frames.
+    #[inline]
+    pub fn mix_factor(&self) -> N {
+        self.mix_factor
+    }
+
+    /// Sets the ratio of energy to keep between two frames.
+    #[inline]
+    pub fn set_mix_factor(&mut self, mix_factor: N) {
+        assert!(mix_factor >= na::zero(), "The energy mixing factor must be between 0.0 and 1.0.");
+        self.mix_factor = mix_factor
+    }
+
+    /// The energy threshold overriding the deactivation threshold of every rigid body, if any.
+    #[inline]
+    pub fn energy_threshold(&self) -> Option<N> {
+        self.threshold
+    }
+
+    /// Sets the energy threshold overriding the deactivation threshold of every rigid body.
+    ///
+    /// The bodies without deactivation threshold are not affected: they still never sleep. Set
+    /// this to `None` to use the deactivation thresholds of the bodies again.
+    #[inline]
+    pub fn set_energy_threshold(&mut self, threshold: Option<N>) {
+        self.threshold = threshold
+    }
+
+    /// The number of consecutive updates the bodies of an island must spend below their energy
+    /// threshold before the island is deactivated.
+    #[inline]
+    pub fn deactivation_delay(&self) -> usize {
+        self.delay
+    }
+
+    /// Sets the number of consecutive updates the bodies of an island must spend below their
+    /// energy threshold before the island is deactivated.
+    ///
+    /// Defaults to zero, i.e., an island is deactivated as soon as all its bodies have a low
+    /// energy.
+    #[inline]
+    pub fn set_deactivation_delay(&mut self, delay: usize) {
+        self.delay = delay
+    }
+
+    /// Whether islands with a low energy are put to sleep.
+    #[inline]
+    pub fn deactivation_enabled(&self) -> bool {
+        self.deactivation
+    }
+
+    /// Enables or disables the deactivation of the islands with a low energy.
+    ///
+    /// Islands are still built while the deactivation is disabled, but the bodies do not count
+    /// the updates they spent with a low energy. Disabling the deactivation does not wake the
+    /// sleeping bodies up, see `wake_all`.
+    #[inline]
+    pub fn set_deactivation_enabled(&mut self, enabled: bool) {
+        self.deactivation = enabled
+    }
+
+    /// A copy of this activation manager for the copies `bodies` of the rigid bodies, indexed by
+    /// the identifiers of the originals.
+    ///
+    /// The parameters, the number of updates each body spent with a low energy, and the pending
+    /// activations are copied.
+    #[doc(hidden)]
+    pub fn clone_for_prediction(&self, bodies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>) -> ActivationManager<N> {
+        let mut res = ActivationManager::new(self.mix_factor);
+
+        res.threshold    = self.threshold;
+        res.delay        = self.delay;
+        res.deactivation = self.deactivation;
+        res.statistics   = self.statistics.clone();
+
+        for e in self.calm_updates.elements().iter() {
+            if let Some(rb) = bodies.find(&e.key) {
+                let _ = res.calm_updates.insert(WorldObject::rigid_body_uid(rb), e.value);
+            }
+        }
+
+        for b in self.to_activate.iter() {
+            if let Some(rb) = bodies.find(&WorldObject::rigid_body_uid(b)) {
+                res.to_activate.push(rb.clone())
+            }
         }
+
+        res
+    }
+
+    /// Activates every sleeping rigid body of `bodies` immediately.
+    pub fn wake_all(&mut self, bodies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>) {
+        for e in bodies.elements().iter() {
+            let mut b = e.value.borrow_mut();
+
+            if b.can_move() && !b.is_active() {
+                if let Some(threshold) = self.threshold_of(&*b) {
+                    b.activate(threshold * na::convert(2.0f64));
+                }
+            }
+        }
+
+        self.calm_updates.clear();
+    }
+
+    // The energy threshold of `b`, taking the override in account.
+    #[inline]
+    fn threshold_of(&self, b: &RigidBody<N>) -> Option<N> {
+        b.deactivation_threshold().map(|threshold| self.threshold.unwrap_or(threshold))
+    }
+
+    /// Statistics about the islands built during the last update.
+    pub fn island_statistics(&self) -> &IslandStatistics {
+        &self.statistics
     }
 
     /// Notify the `ActivationManager` that is has to activate an object at the next update.
@@ -46,7 +209,7 @@ impl<N: Real> ActivationManager<N> {
     }
 
     fn update_energy(&self, b: &mut RigidBody<N>) {
-        match b.deactivation_threshold() {
+        match self.threshold_of(b) {
             Some(threshold) => {
                 // FIXME: take the time in account (to make a true RWA)
                 let _1         = na::one::<N>();
@@ -64,6 +227,28 @@ impl<N: Real> ActivationManager<N> {
                   world:  &mut RigidBodyCollisionWorld<N>,
                   joints: &JointManager<N>,
                   bodies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>) {
+        self.do_update(world, joints, bodies, None)
+    }
+
+    /// Update the activation manager like `update`, but only the energy of the bodies which
+    /// substepping is enabled if `substepped` is `true`, disabled otherwise.
+    ///
+    /// This keeps the energy of the bodies simulated at the base rate from being updated during
+    /// the substeps.
+    #[doc(hidden)]
+    pub fn update_substepped(&mut self,
+                             world:      &mut RigidBodyCollisionWorld<N>,
+                             joints:     &JointManager<N>,
+                             bodies:     &HashMap<usize, RigidBodyHandle<N>, UintTWHash>,
+                             substepped: bool) {
+        self.do_update(world, joints, bodies, Some(substepped))
+    }
+
+    fn do_update(&mut self,
+                 world:      &mut RigidBodyCollisionWorld<N>,
+                 joints:     &JointManager<N>,
+                 bodies:     &HashMap<usize, RigidBodyHandle<N>, UintTWHash>,
+                 substepped: Option<bool>) {
         /*
          *
          * Update bodies energy
@@ -73,7 +258,7 @@ impl<N: Real> ActivationManager<N> {
             let mut b = b.value.borrow_mut();
 
             assert!(*b.activation_state() != ActivationState::Deleted);
-            if b.is_active() {
+            if b.is_active() && substepped.map_or(true, |s| b.substepping_enabled() == s) {
                 self.update_energy(&mut *b);
             }
 
@@ -88,7 +273,7 @@ impl<N: Real> ActivationManager<N> {
         for b in self.to_activate.iter() {
             let mut rb = b.borrow_mut();
 
-            match rb.deactivation_threshold() {
+            match self.threshold_of(&*rb) {
                 Some(threshold) => rb.activate(threshold * na::convert(2.0f64)),
                 None => { }
             }
@@ -121,70 +306,91 @@ impl<N: Real> ActivationManager<N> {
             *d = true
         }
 
+        self.edges.clear();
+
         // Run the union-find.
-        fn make_union<N: Real>(b1: &RigidBodyHandle<N>, b2: &RigidBodyHandle<N>, ufs: &mut [UnionFindSet]) {
+        fn make_union<N: Real>(b1:    &RigidBodyHandle<N>,
+                               b2:    &RigidBodyHandle<N>,
+                               ufs:   &mut [UnionFindSet],
+                               edges: &mut Vec<(usize, usize)>) {
             let rb1 = b1.borrow();
             let rb2 = b2.borrow();
 
             if rb1.can_move() && rb2.can_move() {
-                union_find::union(rb1.index() as usize, rb2.index() as usize, ufs)
+                union_find::union(rb1.index() as usize, rb2.index() as usize, ufs);
+                edges.push((rb1.index() as usize, rb2.index() as usize))
             }
         }
 
         for (b1, b2, cd) in world.contact_pairs() {
             if let (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) = (&b1.data, &b2.data) {
                 if cd.num_contacts() != 0 {
-                    make_union(&rb1, &rb2, &mut self.ufind[..])
+                    make_union(&rb1, &rb2, &mut self.ufind[..], &mut self.edges)
                 }
             }
         }
 
         for e in joints.joints().elements().iter() {
-            match e.value {
-                Constraint::RBRB(ref b1, ref b2, _) => make_union(b1, b2, &mut self.ufind[..]),
-                Constraint::BallInSocket(ref b)   => {
-                    match (b.borrow().anchor1().body.as_ref(), b.borrow().anchor2().body.as_ref()) {
-                        (Some(b1), Some(b2)) => make_union(b1, b2, &mut self.ufind[..]),
-                        _ => { }
+            match e.value.joint_info() {
+                Some(joint) => {
+                    if let (Some(b1), Some(b2)) = (joint.body1.as_ref(), joint.body2.as_ref()) {
+                        make_union(b1, b2, &mut self.ufind[..], &mut self.edges)
                     }
                 },
-                Constraint::Fixed(ref f)   => {
-                    match (f.borrow().anchor1().body.as_ref(), f.borrow().anchor2().body.as_ref()) {
-                        (Some(b1), Some(b2)) => make_union(b1, b2, &mut self.ufind[..]),
-                        _ => { }
+                None => {
+                    if let Constraint::RBRB(ref b1, ref b2, _) = e.value {
+                        make_union(b1, b2, &mut self.ufind[..], &mut self.edges)
                     }
                 }
             }
         }
 
+        self.update_statistics(bodies);
+
         /*
          * Body activation/deactivation.
          */
         // Find deactivable islands.
+        let mut calm_updates = HashMap::new(UintTWHash::new());
+
         for i in 0usize .. self.ufind.len() {
             let root = union_find::find(i, &mut self.ufind[..]);
-            let b    = bodies.elements()[i].value.borrow();
+            let e    = &bodies.elements()[i];
+            let b    = e.value.borrow();
+            let calm = match self.threshold_of(&*b) {
+                Some(threshold) if self.deactivation && b.activation_state().energy() < threshold => {
+                    self.calm_updates.find(&e.key).cloned().unwrap_or(0) + 1
+                },
+                _ => 0
+            };
+
+            if calm != 0 {
+                let _ = calm_updates.insert(e.key, calm);
+            }
 
             self.can_deactivate[root] =
-                match b.deactivation_threshold() {
-                    Some(threshold) => {
-                        self.can_deactivate[root] && b.activation_state().energy() < threshold
-                    },
-                    None => false
+                match self.threshold_of(&*b) {
+                    Some(_) => self.can_deactivate[root] && calm > self.delay,
+                    None    => false
                 };
         }
 
+        self.calm_updates = calm_updates;
+
         // Activate/deactivate islands.
         for i in 0usize .. self.ufind.len() {
             let root = union_find::find(i, &mut self.ufind[..]);
             let mut b = bodies.elements()[i].value.borrow_mut();
 
             if self.can_deactivate[root] { // Everybody in this set can be deactivacted.
-                b.deactivate();
+                // The velocities of the kinematic bodies are derived from their animation.
+                if !b.is_kinematic() {
+                    b.deactivate();
+                }
             }
             else { // Everybody in this set must be reactivated.
                 if !b.is_active() && b.can_move() {
-                    match b.deactivation_threshold() {
+                    match self.threshold_of(&*b) {
                         Some(threshold) => b.activate(threshold * na::convert::<f64, N>(2.0f64)),
                         None => { }
                     }
@@ -192,4 +398,193 @@ impl<N: Real> ActivationManager<N> {
             }
         }
     }
+
+    fn update_statistics(&mut self, bodies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>) {
+        self.island_sizes.clear();
+        self.island_sizes.extend(iter::repeat(0).take(bodies.len()));
+        self.island_roots.clear();
+        self.island_roots.extend(iter::repeat(UNVISITED).take(bodies.len()));
+
+        for i in 0usize .. self.ufind.len() {
+            if bodies.elements()[i].value.borrow().can_move() {
+                let root = union_find::find(i, &mut self.ufind[..]);
+                self.island_sizes[root] += 1;
+                self.island_roots[i]     = root;
+            }
+        }
+
+        self.statistics.num_islands = 0;
+        self.statistics.largest     = 0;
+        self.statistics.histogram.clear();
+
+        for size in self.island_sizes.iter().cloned().filter(|s| *s != 0) {
+            let mut bucket = 0;
+
+            while (2 << bucket) <= size {
+                bucket += 1;
+            }
+
+            if bucket >= self.statistics.histogram.len() {
+                let to_add = bucket + 1 - self.statistics.histogram.len();
+                self.statistics.histogram.extend(iter::repeat(0).take(to_add));
+            }
+
+            self.statistics.histogram[bucket] += 1;
+            self.statistics.num_islands       += 1;
+            self.statistics.largest            = self.statistics.largest.max(size);
+        }
+    }
+
+    /// The islands built during the last update, as lists of movable bodies interacting through
+    /// contacts or joints.
+    ///
+    /// Islands made of a single isolated body are included, as well as sleeping islands. Bodies
+    /// that cannot move belong to no island. As for `bridge_bodies`, `bodies` must be the set of
+    /// bodies given to the last call to `update`. Returns an empty vector otherwise.
+    pub fn islands(&self, bodies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>) -> Vec<Vec<RigidBodyHandle<N>>> {
+        let mut res = Vec::new();
+
+        if bodies.len() != self.island_roots.len() {
+            return res;
+        }
+
+        // Index of the island of each root on `res`.
+        let mut ids: Vec<usize> = iter::repeat(UNVISITED).take(bodies.len()).collect();
+
+        for (i, root) in self.island_roots.iter().cloned().enumerate() {
+            if root == UNVISITED {
+                continue;
+            }
+
+            if ids[root] == UNVISITED {
+                ids[root] = res.len();
+                res.push(Vec::with_capacity(self.island_sizes[root]));
+            }
+
+            res[ids[root]].push(bodies.elements()[i].value.clone());
+        }
+
+        res
+    }
+
+    /// Finds the bodies which removal would split their island into at least two parts with
+    /// `min_part_size` bodies or more.
+    ///
+    /// This analyses the islands built during the last update so `bodies` must be the set of
+    /// bodies given to the last call to `update`. Returns an empty vector otherwise.
+    pub fn bridge_bodies(&self,
+                         bodies:        &HashMap<usize, RigidBodyHandle<N>, UintTWHash>,
+                         min_part_size: usize)
+                         -> Vec<IslandBridge<N>> {
+        let mut res = Vec::new();
+        let n       = self.island_sizes.len();
+
+        if bodies.len() != n {
+            return res;
+        }
+
+        let mut adj: Vec<Vec<usize>> = iter::repeat(Vec::new()).take(n).collect();
+
+        for &(i1, i2) in self.edges.iter() {
+            if i1 != i2 {
+                adj[i1].push(i2);
+                adj[i2].push(i1);
+            }
+        }
+
+        for a in adj.iter_mut() {
+            a.sort();
+            a.dedup();
+        }
+
+        /*
+         * Find the articulation points with an iterative depth-first search, keeping track of
+         * the subtree sizes.
+         */
+        let mut disc:   Vec<usize> = iter::repeat(UNVISITED).take(n).collect();
+        let mut low:    Vec<usize> = iter::repeat(0).take(n).collect();
+        let mut sub:    Vec<usize> = iter::repeat(0).take(n).collect();
+        let mut parent: Vec<usize> = iter::repeat(UNVISITED).take(n).collect();
+        let mut stack  = Vec::new();
+        let mut island = Vec::new();
+        let mut time   = 0;
+
+        for root in 0 .. n {
+            if disc[root] != UNVISITED || adj[root].is_empty() {
+                continue;
+            }
+
+            island.clear();
+            disc[root] = time;
+            low[root]  = time;
+            sub[root]  = 1;
+            time += 1;
+            island.push(root);
+            stack.push((root, 0));
+
+            loop {
+                let (v, next) = match stack.last() {
+                    Some(&e) => e,
+                    None     => break
+                };
+
+                if next < adj[v].len() {
+                    stack.last_mut().unwrap().1 += 1;
+                    let w = adj[v][next];
+
+                    if disc[w] == UNVISITED {
+                        parent[w] = v;
+                        disc[w]   = time;
+                        low[w]    = time;
+                        sub[w]    = 1;
+                        time += 1;
+                        island.push(w);
+                        stack.push((w, 0));
+                    }
+                    else if w != parent[v] {
+                        low[v] = low[v].min(disc[w]);
+                    }
+                }
+                else {
+                    let _ = stack.pop();
+
+                    if let Some(&(p, _)) = stack.last() {
+                        low[p] = low[p].min(low[v]);
+                        sub[p] = sub[p] + sub[v];
+                    }
+                }
+            }
+
+            let island_size = sub[root];
+
+            for &v in island.iter() {
+                let mut parts     = Vec::new();
+                let mut separated = 0;
+
+                for &w in adj[v].iter() {
+                    // The subtree of `w` gets disconnected from the rest of the island.
+                    if parent[w] == v && (v == root || low[w] >= disc[v]) {
+                        parts.push(sub[w]);
+                        separated += sub[w];
+                    }
+                }
+
+                if v != root && separated != 0 {
+                    parts.push(island_size - 1 - separated);
+                }
+
+                if parts.iter().filter(|s| **s >= min_part_size).count() >= 2 {
+                    parts.sort_by(|a, b| b.cmp(a));
+
+                    res.push(IslandBridge {
+                        body:        bodies.elements()[v].value.clone(),
+                        island_size: island_size,
+                        parts:       parts
+                    });
+                }
+            }
+        }
+
+        res
+    }
 }
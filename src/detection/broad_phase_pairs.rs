@@ -0,0 +1,23 @@
+// Utilities shared by the broad phases that recompute all their pairs at each update.
+
+/// Reports the pairs of `new` missing from `old` as started, and the pairs of `old` missing
+/// from `new` as stopped.
+///
+/// Both slices must be sorted. `data` retrieves the data of an object from its index.
+pub fn report_pair_changes<'a, T: 'a, F>(old:     &[(usize, usize)],
+                                         new:     &[(usize, usize)],
+                                         data:    F,
+                                         handler: &mut FnMut(&T, &T, bool))
+    where F: Fn(usize) -> &'a T {
+    let (mut a, mut b) = (0, 0);
+
+    while a < old.len() || b < new.len() {
+        match (old.get(a), new.get(b)) {
+            (Some(o), Some(n)) if o == n => { a = a + 1; b = b + 1; },
+            (Some(o), Some(n)) if o < n  => { handler(data(o.0), data(o.1), false); a = a + 1; },
+            (Some(o), None)              => { handler(data(o.0), data(o.1), false); a = a + 1; },
+            (_, Some(n))                 => { handler(data(n.0), data(n.1), true); b = b + 1; },
+            (None, None)                 => unreachable!()
+        }
+    }
+}
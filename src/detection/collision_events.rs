@@ -0,0 +1,144 @@
+use std::mem;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use alga::general::Real;
+use ncollide::narrow_phase::{ContactHandler, ContactAlgorithm, ProximityHandler};
+use ncollide::query::Proximity;
+use object::{WorldObject, RigidBodyHandle};
+use world::WorldCollisionObject;
+use math::{Point, Isometry};
+
+/// An event collected by the collision event queue of the physics world.
+#[derive(Clone)]
+pub enum CollisionEvent<N: Real> {
+    /// Two objects started being in contact.
+    ContactStarted(WorldObject<N>, WorldObject<N>),
+    /// Two objects stopped being in contact.
+    ContactStopped(WorldObject<N>, WorldObject<N>),
+    /// The proximity status between two objects, one of them being a sensor, changed from the
+    /// first to the second `Proximity`.
+    ProximityChanged(WorldObject<N>, WorldObject<N>, Proximity, Proximity),
+    /// A rigid body has been woken up.
+    BodyActivated(RigidBodyHandle<N>),
+    /// A rigid body has been put to sleep.
+    BodyDeactivated(RigidBodyHandle<N>),
+    /// A CCD-enabled rigid body has been moved back to its time of impact with an object. The
+    /// time of impact is a fraction of the motion of the body during the step, in `[0, 1]`.
+    CcdHit(RigidBodyHandle<N>, WorldObject<N>, N)
+}
+
+/// A collision event stamped with the step it occurred at.
+#[derive(Clone)]
+pub struct StampedCollisionEvent<N: Real> {
+    /// The identifier of the event, unique for its world and increasing in the order the events
+    /// occurred, even across the steps.
+    pub id:    usize,
+    /// The index of the step the event occurred at, i.e., the number of steps performed before.
+    /// The events occurring between two steps, e.g., when an object is removed, carry the index
+    /// of the previous step.
+    pub step:  usize,
+    /// The event.
+    pub event: CollisionEvent<N>
+}
+
+/// The events collected since they were last drained, if the queue is enabled.
+#[doc(hidden)]
+pub struct CollisionEventQueue<N: Real> {
+    enabled: bool,
+    step:    usize,
+    next_id: usize,
+    events:  Vec<StampedCollisionEvent<N>>
+}
+
+impl<N: Real> CollisionEventQueue<N> {
+    /// Creates a disabled, empty, event queue.
+    pub fn new() -> CollisionEventQueue<N> {
+        CollisionEventQueue {
+            enabled: false,
+            step:    0,
+            next_id: 0,
+            events:  Vec::new()
+        }
+    }
+
+    /// Sets the index of the step the next events will be stamped with.
+    #[inline]
+    pub fn set_step(&mut self, step: usize) {
+        self.step = step
+    }
+
+    /// Whether the events are collected.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the collection of events. Disabling discards the pending events.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+
+        if !enabled {
+            self.events.clear();
+        }
+    }
+
+    /// Stamps an event and adds it to the queue, if it is enabled.
+    #[inline]
+    pub fn push(&mut self, event: CollisionEvent<N>) {
+        if self.enabled {
+            self.events.push(StampedCollisionEvent { id: self.next_id, step: self.step, event: event });
+            self.next_id = self.next_id + 1;
+        }
+    }
+
+    /// Removes all the events from the queue, in the order they were pushed.
+    pub fn drain(&mut self) -> Vec<CollisionEvent<N>> {
+        self.drain_stamped().into_iter().map(|e| e.event).collect()
+    }
+
+    /// Removes all the stamped events from the queue, in the order they were pushed.
+    pub fn drain_stamped(&mut self) -> Vec<StampedCollisionEvent<N>> {
+        mem::replace(&mut self.events, Vec::new())
+    }
+}
+
+/// The contact and proximity handler feeding the collision event queue.
+#[doc(hidden)]
+pub struct CollisionEventCollector<N: Real> {
+    queue: Rc<RefCell<CollisionEventQueue<N>>>
+}
+
+impl<N: Real> CollisionEventCollector<N> {
+    /// Creates a handler pushing the events it receives to `queue`.
+    pub fn new(queue: Rc<RefCell<CollisionEventQueue<N>>>) -> CollisionEventCollector<N> {
+        CollisionEventCollector {
+            queue: queue
+        }
+    }
+}
+
+impl<N: Real> ContactHandler<Point<N>, Isometry<N>, WorldObject<N>> for CollisionEventCollector<N> {
+    fn handle_contact_started(&mut self,
+                              co1: &WorldCollisionObject<N>,
+                              co2: &WorldCollisionObject<N>,
+                              _:   &ContactAlgorithm<Point<N>, Isometry<N>>) {
+        self.queue.borrow_mut().push(CollisionEvent::ContactStarted(co1.data.clone(), co2.data.clone()))
+    }
+
+    fn handle_contact_stopped(&mut self, co1: &WorldCollisionObject<N>, co2: &WorldCollisionObject<N>) {
+        self.queue.borrow_mut().push(CollisionEvent::ContactStopped(co1.data.clone(), co2.data.clone()))
+    }
+}
+
+impl<N: Real> ProximityHandler<Point<N>, Isometry<N>, WorldObject<N>> for CollisionEventCollector<N> {
+    fn handle_proximity(&mut self,
+                        co1:         &WorldCollisionObject<N>,
+                        co2:         &WorldCollisionObject<N>,
+                        prev_status: Proximity,
+                        new_status:  Proximity) {
+        let event = CollisionEvent::ProximityChanged(co1.data.clone(), co2.data.clone(), prev_status, new_status);
+
+        self.queue.borrow_mut().push(event)
+    }
+}
@@ -1,14 +1,46 @@
 //! Data structure to describe a constraint between two rigid bodies.
 
 use std::rc::Rc;
+use std::option;
+use std::iter::Chain;
 use std::cell::RefCell;
 
 use alga::general::Real;
 use ncollide::query::Contact;
 use object::RigidBody;
-use detection::joint::{Fixed, BallInSocket};
+use detection::joint::{Joint, Fixed, BallInSocket, Hinge, Prismatic, Spring, Distance, Gear, Pulley, RackAndPinion,
+                       AngularMotor};
+#[cfg(feature = "dim3")]
+use detection::joint::Universal;
 use math::Point;
 
+/// Switches of the physical effects solved at a point of contact.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ContactFlags {
+    /// Whether the friction is solved.
+    pub friction:    bool,
+    /// Whether the restitution is solved.
+    pub restitution: bool
+}
+
+impl ContactFlags {
+    /// Flags enabling both the friction and the restitution.
+    #[inline]
+    pub fn new() -> ContactFlags {
+        ContactFlags {
+            friction:    true,
+            restitution: true
+        }
+    }
+}
+
+impl Default for ContactFlags {
+    #[inline]
+    fn default() -> ContactFlags {
+        ContactFlags::new()
+    }
+}
+
 /// A constraint between two rigid bodies.
 pub enum Constraint<N: Real> {
     /// A contact.
@@ -17,6 +49,84 @@ pub enum Constraint<N: Real> {
     BallInSocket(Rc<RefCell<BallInSocket<N>>>),
     /// A fixed joint.
     Fixed(Rc<RefCell<Fixed<N>>>),
+    /// A hinge joint.
+    Hinge(Rc<RefCell<Hinge<N>>>),
+    /// A prismatic joint.
+    Prismatic(Rc<RefCell<Prismatic<N>>>),
+    /// A spring.
+    Spring(Rc<RefCell<Spring<N>>>),
+    /// A distance joint.
+    Distance(Rc<RefCell<Distance<N>>>),
+    /// A standalone angular motor.
+    AngularMotor(Rc<RefCell<AngularMotor<N>>>),
+    /// A rack-and-pinion.
+    RackAndPinion(Rc<RefCell<RackAndPinion<N>>>),
+    /// A pulley.
+    Pulley(Rc<RefCell<Pulley<N>>>),
+    /// A gear coupling two hinges.
+    Gear(Rc<RefCell<Gear<N>>>),
+    /// A universal joint.
+    #[cfg(feature = "dim3")]
+    Universal(Rc<RefCell<Universal<N>>>),
+}
+
+impl<N: Real> Constraint<N> {
+    /// What the joint of this constraint has in common with every other kind of joint, or `None`
+    /// if this constraint is a contact.
+    #[doc(hidden)]
+    pub fn joint_info(&self) -> Option<JointInfo<N>> {
+        match *self {
+            Constraint::RBRB(_, _, _)        => None,
+            Constraint::BallInSocket(ref b)  => Some(JointInfo::new(&**b)),
+            Constraint::Fixed(ref f)         => Some(JointInfo::new(&**f)),
+            Constraint::Hinge(ref h)         => Some(JointInfo::new(&**h)),
+            Constraint::Prismatic(ref p)     => Some(JointInfo::new(&**p)),
+            Constraint::Spring(ref s)        => Some(JointInfo::new(&**s)),
+            Constraint::Distance(ref d)      => Some(JointInfo::new(&**d)),
+            Constraint::AngularMotor(ref m)  => Some(JointInfo::new(&**m)),
+            Constraint::RackAndPinion(ref r) => Some(JointInfo::new(&**r)),
+            Constraint::Pulley(ref p)        => Some(JointInfo::new(&**p)),
+            Constraint::Gear(ref g)          => Some(JointInfo::new(&**g)),
+            #[cfg(feature = "dim3")]
+            Constraint::Universal(ref u)     => Some(JointInfo::new(&**u))
+        }
+    }
+}
+
+/// What every kind of joint has in common, whatever the type of its anchors.
+#[doc(hidden)]
+pub struct JointInfo<N: Real> {
+    /// The key of the joint, i.e., its address.
+    pub key:                usize,
+    /// The body attached to the first anchor, if any.
+    pub body1:              Option<Rc<RefCell<RigidBody<N>>>>,
+    /// The body attached to the second anchor, if any.
+    pub body2:              Option<Rc<RefCell<RigidBody<N>>>>,
+    /// The reaction force and torque beyond which the joint breaks, if any.
+    pub breaking_forces:    Option<(N, N)>,
+    /// Whether the bodies attached by the joint collide with each other.
+    pub collisions_enabled: bool
+}
+
+impl<N: Real> JointInfo<N> {
+    /// The information on `joint`.
+    pub fn new<A, J: Joint<N, A>>(joint: &RefCell<J>) -> JointInfo<N> {
+        let bj = joint.borrow();
+
+        JointInfo {
+            key:                joint as *const RefCell<J> as usize,
+            body1:              bj.anchor1().body.clone(),
+            body2:              bj.anchor2().body.clone(),
+            breaking_forces:    bj.breaking_forces(),
+            collisions_enabled: bj.collisions_enabled()
+        }
+    }
+
+    /// The bodies attached by the joint.
+    #[inline]
+    pub fn bodies(&self) -> Chain<option::Iter<Rc<RefCell<RigidBody<N>>>>, option::Iter<Rc<RefCell<RigidBody<N>>>>> {
+        self.body1.iter().chain(self.body2.iter())
+    }
 }
 
 impl<N: Real> Clone for Constraint<N> {
@@ -25,6 +135,16 @@ impl<N: Real> Clone for Constraint<N> {
             Constraint::RBRB(ref a, ref b, ref c) => Constraint::RBRB(a.clone(), b.clone(), c.clone()),
             Constraint::BallInSocket(ref bis)     => Constraint::BallInSocket(bis.clone()),
             Constraint::Fixed(ref f)              => Constraint::Fixed(f.clone()),
+            Constraint::Hinge(ref h)              => Constraint::Hinge(h.clone()),
+            Constraint::Prismatic(ref p)          => Constraint::Prismatic(p.clone()),
+            Constraint::Spring(ref s)             => Constraint::Spring(s.clone()),
+            Constraint::Distance(ref d)           => Constraint::Distance(d.clone()),
+            Constraint::AngularMotor(ref m)       => Constraint::AngularMotor(m.clone()),
+            Constraint::RackAndPinion(ref r)      => Constraint::RackAndPinion(r.clone()),
+            Constraint::Pulley(ref p)             => Constraint::Pulley(p.clone()),
+            Constraint::Gear(ref g)               => Constraint::Gear(g.clone()),
+            #[cfg(feature = "dim3")]
+            Constraint::Universal(ref u)          => Constraint::Universal(u.clone()),
         }
     }
 }
@@ -0,0 +1,93 @@
+#![doc(hidden)]
+
+use std::rc::Rc;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use alga::general::Real;
+use ncollide::narrow_phase::{ContactHandler, ContactAlgorithm};
+use world::WorldCollisionObject;
+use object::WorldObject;
+use math::{Point, Isometry};
+use utils::DeterministicState;
+
+struct PairState<N: Real> {
+    // The time the last contact started event was forwarded at.
+    last_started: N,
+    // Whether a contact started event was forwarded without its contact stopped event yet.
+    open:         bool
+}
+
+/// A contact handler forwarding at most one contact started event per pair per cooldown.
+///
+/// The contact stopped events are forwarded only for the pairs which contact started event was
+/// forwarded, so that the wrapped handler always sees a stop after each start.
+pub struct ContactCooldown<N: Real, H> {
+    handler:  H,
+    cooldown: N,
+    clock:    Rc<Cell<N>>,
+    pairs:    HashMap<(usize, usize), PairState<N>, DeterministicState>,
+    max_len:  usize
+}
+
+impl<N: Real, H> ContactCooldown<N, H> {
+    /// Wraps `handler` so that it receives at most one contact started event per pair of objects
+    /// every `cooldown`, measured on `clock`.
+    pub fn new(handler: H, cooldown: N, clock: Rc<Cell<N>>) -> ContactCooldown<N, H> {
+        ContactCooldown {
+            handler:  handler,
+            cooldown: cooldown,
+            clock:    clock,
+            pairs:    HashMap::with_hasher(DeterministicState::new()),
+            max_len:  64
+        }
+    }
+
+    // Forgets the pairs out of their cooldown, once in a while.
+    fn prune(&mut self, now: N) {
+        if self.pairs.len() > self.max_len {
+            let cooldown = self.cooldown;
+
+            self.pairs.retain(|_, state| state.open || now - state.last_started < cooldown);
+            self.max_len = (self.pairs.len() * 2).max(64);
+        }
+    }
+}
+
+fn pair_key<N: Real>(co1: &WorldCollisionObject<N>, co2: &WorldCollisionObject<N>) -> (usize, usize) {
+    let (uid1, uid2) = (co1.data.uid(), co2.data.uid());
+
+    if uid1 < uid2 { (uid1, uid2) } else { (uid2, uid1) }
+}
+
+impl<N: Real, H> ContactHandler<Point<N>, Isometry<N>, WorldObject<N>> for ContactCooldown<N, H>
+    where H: ContactHandler<Point<N>, Isometry<N>, WorldObject<N>> {
+    fn handle_contact_started(&mut self,
+                              co1:      &WorldCollisionObject<N>,
+                              co2:      &WorldCollisionObject<N>,
+                              contacts: &ContactAlgorithm<Point<N>, Isometry<N>>) {
+        let now = self.clock.get();
+        self.prune(now);
+
+        let cooldown = self.cooldown;
+        let state    = self.pairs.entry(pair_key(co1, co2)).or_insert(PairState {
+            last_started: now - cooldown,
+            open:         false
+        });
+
+        if !state.open && now - state.last_started >= cooldown {
+            state.last_started = now;
+            state.open         = true;
+            self.handler.handle_contact_started(co1, co2, contacts);
+        }
+    }
+
+    fn handle_contact_stopped(&mut self, co1: &WorldCollisionObject<N>, co2: &WorldCollisionObject<N>) {
+        if let Some(state) = self.pairs.get_mut(&pair_key(co1, co2)) {
+            if state.open {
+                state.open = false;
+                self.handler.handle_contact_stopped(co1, co2);
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use alga::general::Real;
+use na;
+use ncollide::query::Contact;
+use math::{Point, Vector};
+
+/// Injects tiny, bounded, pseudo-random perturbations into the contacts given to the solver.
+///
+/// This is meant for robustness testing: a scene that behaves very differently with a small
+/// jitter relies on knife-edge configurations, and is likely to behave differently on platforms
+/// with another floating-point behavior. The sequence of perturbations is determined by the seed.
+pub struct ContactJitter<N: Real> {
+    state:              u64,
+    normal_amplitude:   N,
+    position_amplitude: N
+}
+
+impl<N: Real> ContactJitter<N> {
+    /// Creates a new contact jitter generator.
+    ///
+    /// Each component of the contact normals is perturbed by at most `normal_amplitude` (before
+    /// re-normalization), and each component of the contact points by at most
+    /// `position_amplitude`.
+    pub fn new(seed: u64, normal_amplitude: N, position_amplitude: N) -> ContactJitter<N> {
+        assert!(normal_amplitude >= na::zero() && position_amplitude >= na::zero(),
+                "The jitter amplitudes must be positive.");
+
+        ContactJitter {
+            // The xorshift state must not be zero.
+            state:              if seed == 0 { 0x9E3779B97F4A7C15 } else { seed },
+            normal_amplitude:   normal_amplitude,
+            position_amplitude: position_amplitude
+        }
+    }
+
+    /// The maximum perturbation of each component of the contact normals.
+    #[inline]
+    pub fn normal_amplitude(&self) -> N {
+        self.normal_amplitude
+    }
+
+    /// The maximum perturbation of each component of the contact points.
+    #[inline]
+    pub fn position_amplitude(&self) -> N {
+        self.position_amplitude
+    }
+
+    /// Perturbs the normal and the position of the given contact.
+    ///
+    /// Both contact points are moved by the same amount so that the penetration depth is kept.
+    pub fn perturb(&mut self, contact: &mut Contact<Point<N>>) {
+        let dn = self.random_vector() * self.normal_amplitude;
+        let dp = self.random_vector() * self.position_amplitude;
+
+        if let Some(normal) = na::try_normalize(&(contact.normal + dn), N::default_epsilon()) {
+            contact.normal = normal;
+        }
+
+        contact.world1 = contact.world1 + dp;
+        contact.world2 = contact.world2 + dp;
+    }
+
+    // A vector with components uniformly distributed in [-1, 1].
+    fn random_vector(&mut self) -> Vector<N> {
+        Vector::from_fn(|_, _| self.next_signed())
+    }
+
+    // A number uniformly distributed in [-1, 1] generated by a xorshift64* generator.
+    fn next_signed(&mut self) -> N {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+
+        let bits = self.state.wrapping_mul(0x2545F4914F6CDD1D) >> 11;
+        let unit = bits as f64 / (1u64 << 53) as f64;
+
+        na::convert(unit * 2.0 - 1.0)
+    }
+}
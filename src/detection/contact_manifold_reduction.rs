@@ -0,0 +1,99 @@
+use alga::general::Real;
+use na;
+use ncollide::query::Contact;
+use utils::GeneralizedCross;
+use math::Point;
+
+/// Reduces the contacts between two bodies to at most `max_contacts` well-spread points.
+///
+/// The deepest contact is kept first, then the contact farthest from it. The next contacts are
+/// chosen to maximize the area of the polygon formed by the kept contacts, so that at most four
+/// contacts are needed to support a flat face. `max_contacts` is thus clamped to four, and the
+/// manifold is reduced to a segment if all its contacts are aligned.
+pub fn reduce_contact_manifold<N: Real>(contacts: &mut Vec<Contact<Point<N>>>, max_contacts: usize) {
+    let max_contacts = max_contacts.min(4);
+
+    if contacts.len() <= max_contacts {
+        return;
+    }
+
+    if max_contacts == 0 {
+        contacts.clear();
+        return;
+    }
+
+    let points: Vec<Point<N>> = contacts.iter().map(|c| na::center(&c.world1, &c.world2)).collect();
+    let mut kept = Vec::with_capacity(max_contacts);
+
+    // The deepest contact.
+    kept.push(argmax(contacts.len(), |i| contacts[i].depth).0);
+
+    // The farthest from the deepest.
+    if max_contacts >= 2 {
+        let p0 = points[kept[0]];
+
+        kept.push(argmax(points.len(), |i| na::distance_squared(&points[i], &p0)).0);
+    }
+
+    // The one forming the largest triangle.
+    let mut normal = None;
+
+    if max_contacts >= 3 {
+        let (p0, p1)     = (points[kept[0]], points[kept[1]]);
+        let (best, area) = argmax(points.len(), |i| na::norm_squared(&(p1 - p0).gcross(&(points[i] - p0))));
+
+        if !area.is_zero() {
+            let n = (p1 - p0).gcross(&(points[best] - p0));
+
+            kept.push(best);
+            normal = Some(n);
+        }
+    }
+
+    // The one farthest outside of the triangle, i.e., adding the largest area to it.
+    if let (true, Some(n)) = (max_contacts >= 4, normal) {
+        let tri             = [points[kept[0]], points[kept[1]], points[kept[2]]];
+        let (best, outside) = argmax(points.len(), |i| {
+            let mut outside: N = na::zero();
+
+            for k in 0 .. 3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                let signed = na::dot(&(b - a).gcross(&(points[i] - a)), &n);
+
+                outside = outside.max(-signed);
+            }
+
+            outside
+        });
+
+        if outside > na::zero() {
+            kept.push(best);
+        }
+    }
+
+    kept.sort();
+    kept.dedup();
+
+    let mut i = 0;
+    contacts.retain(|_| {
+        let keep = kept.binary_search(&i).is_ok();
+        i += 1;
+        keep
+    });
+}
+
+// The index in `0 .. len` with the largest score, the first one in case of equality, and its
+// score. `len` must not be zero.
+fn argmax<N: Real, F: FnMut(usize) -> N>(len: usize, mut score: F) -> (usize, N) {
+    let mut best = (0, score(0));
+
+    for i in 1 .. len {
+        let s = score(i);
+
+        if s > best.1 {
+            best = (i, s);
+        }
+    }
+
+    best
+}
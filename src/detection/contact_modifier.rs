@@ -0,0 +1,26 @@
+use alga::general::Real;
+use ncollide::query::Contact;
+use detection::constraint::ContactFlags;
+use object::RigidBody;
+use math::Point;
+
+/// Trait implemented by the callbacks modifying the contacts before they are solved.
+pub trait ContactModifier<N: Real> {
+    /// Called at each step for each contact between `rb1` and `rb2` to be solved.
+    ///
+    /// The contact normal points from `rb1` to `rb2`. Clearing the fields of `flags` disables the
+    /// friction or the restitution on this contact only, e.g., to get ice panels on an otherwise
+    /// rough floor.
+    fn modify_contact(&mut self, rb1: &RigidBody<N>, rb2: &RigidBody<N>,
+                      contact: &mut Contact<Point<N>>, flags: &mut ContactFlags);
+}
+
+impl<N, F> ContactModifier<N> for F
+    where N: Real,
+          F: FnMut(&RigidBody<N>, &RigidBody<N>, &mut Contact<Point<N>>, &mut ContactFlags) {
+    #[inline]
+    fn modify_contact(&mut self, rb1: &RigidBody<N>, rb2: &RigidBody<N>,
+                      contact: &mut Contact<Point<N>>, flags: &mut ContactFlags) {
+        self(rb1, rb2, contact, flags)
+    }
+}
@@ -0,0 +1,104 @@
+use std::mem;
+use std::collections::HashMap;
+
+use alga::general::Real;
+use na;
+use ncollide::query::Contact;
+use math::{Point, Vector};
+use utils::DeterministicState;
+
+/// Blends the normals of persistent contacts with their normal of the last frame.
+///
+/// A contact persists from one frame to the next if it involves the same pair of bodies and its
+/// center moved by less than a given distance. When the normal of a persistent contact changed by
+/// less than a given angle, e.g., because a box slides over the seam between two triangles, it is
+/// replaced by a blend of its current and previous normals. This removes the high-frequency
+/// normal flicker that makes sliding bodies buzz, at the cost of a small lag when the normal
+/// really changes.
+pub struct ContactNormalSmoothing<N: Real> {
+    blend:         N,
+    max_angle_cos: N,
+    max_distance:  N,
+    // Contact centers and smoothed normals, grouped by pair of bodies.
+    prev:          HashMap<(usize, usize), Vec<(Point<N>, Vector<N>)>, DeterministicState>,
+    next:          HashMap<(usize, usize), Vec<(Point<N>, Vector<N>)>, DeterministicState>
+}
+
+impl<N: Real> ContactNormalSmoothing<N> {
+    /// Creates a new contact normal smoother.
+    ///
+    /// The smoothed normal is `blend * previous + (1 - blend) * current`, re-normalized, so
+    /// `blend` must be in `[0, 1[`. Contacts which normal changed by more than `max_angle`
+    /// radians, or which center moved by more than `max_distance`, are not smoothed.
+    pub fn new(blend: N, max_angle: N, max_distance: N) -> ContactNormalSmoothing<N> {
+        assert!(blend >= na::zero() && blend < na::one(), "The blend factor must be in [0, 1[.");
+
+        ContactNormalSmoothing {
+            blend:         blend,
+            max_angle_cos: max_angle.cos(),
+            max_distance:  max_distance,
+            prev:          HashMap::with_hasher(DeterministicState::new()),
+            next:          HashMap::with_hasher(DeterministicState::new())
+        }
+    }
+
+    /// The weight of the previous normal.
+    #[inline]
+    pub fn blend(&self) -> N {
+        self.blend
+    }
+
+    /// The maximum angle between the previous and the current normal for the smoothing to
+    /// apply.
+    #[inline]
+    pub fn max_angle(&self) -> N {
+        self.max_angle_cos.acos()
+    }
+
+    /// The maximum displacement of a contact center for it to be considered persistent.
+    #[inline]
+    pub fn max_distance(&self) -> N {
+        self.max_distance
+    }
+
+    /// Smoothes the normal of a contact between the bodies identified by `uid1` and `uid2`.
+    pub fn smooth(&mut self, uid1: usize, uid2: usize, contact: &mut Contact<Point<N>>) {
+        let center = na::center(&contact.world1, &contact.world2);
+
+        if let Some(prev) = self.prev.get_mut(&(uid1, uid2)) {
+            let mut best        = None;
+            let mut best_sqdist = self.max_distance * self.max_distance;
+
+            for (i, &(ref prev_center, _)) in prev.iter().enumerate() {
+                let sqdist = na::distance_squared(prev_center, &center);
+
+                if sqdist <= best_sqdist {
+                    best_sqdist = sqdist;
+                    best        = Some(i);
+                }
+            }
+
+            // Each contact of the last frame smoothes at most one contact.
+            if let Some(i) = best {
+                let prev_normal = prev.swap_remove(i).1;
+
+                if na::dot(&prev_normal, &contact.normal) >= self.max_angle_cos {
+                    let blended = prev_normal * self.blend + contact.normal * (N::one() - self.blend);
+
+                    if let Some(normal) = na::try_normalize(&blended, N::default_epsilon()) {
+                        contact.normal = normal;
+                    }
+                }
+            }
+        }
+
+        self.next.entry((uid1, uid2)).or_insert_with(Vec::new).push((center, contact.normal));
+    }
+
+    /// Forgets the contacts of the last frame, and remembers the ones smoothed since the last
+    /// call to this method.
+    pub fn end_frame(&mut self) {
+        mem::swap(&mut self.prev, &mut self.next);
+        self.next.clear();
+    }
+}
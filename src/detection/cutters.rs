@@ -0,0 +1,148 @@
+use alga::general::Real;
+use na;
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use ncollide::query::Contact;
+use object::RigidBodyHandle;
+use math::Point;
+
+/// The intersection of a cutter with a rigid body during one step.
+pub struct Cut<N: Real> {
+    /// The cutter.
+    pub cutter:   RigidBodyHandle<N>,
+    /// The rigid body intersected by the cutter.
+    pub target:   RigidBodyHandle<N>,
+    /// The penetrating contacts between the cutter and the target, i.e., the points of the contact
+    /// manifold approximating their intersection. The first point of each contact is on the
+    /// cutter, and the normals point toward the target.
+    pub contacts: Vec<Contact<Point<N>>>
+}
+
+impl<N: Real> Cut<N> {
+    /// The points where the cutter intersects the target: the centers of its contacts.
+    pub fn points(&self) -> Vec<Point<N>> {
+        self.contacts.iter().map(|c| na::center(&c.world1, &c.world2)).collect()
+    }
+}
+
+/// Trait implemented by the receivers of cuts.
+pub trait CutHandler<N: Real> {
+    /// Called at the end of each step, for each rigid body intersected by the cutter.
+    ///
+    /// The world cannot be modified from this handler: the cuts are typically recorded, and the
+    /// targets replaced by their pieces once the step is done.
+    fn handle_cut(&mut self, cut: &Cut<N>);
+}
+
+impl<N: Real, F: FnMut(&Cut<N>)> CutHandler<N> for F {
+    #[inline]
+    fn handle_cut(&mut self, cut: &Cut<N>) {
+        self(cut)
+    }
+}
+
+struct Cutter<N: Real> {
+    body:    RigidBodyHandle<N>,
+    handler: Box<CutHandler<N>>
+}
+
+/// The set of the cutters of the physics world.
+///
+/// A cutter is a rigid body, usually moved by the application like a blade, which contacts are
+/// never solved. Instead, its intersections with the other rigid bodies are reported to its
+/// handler at the end of each step, so that a destruction system can decide where to split them.
+#[doc(hidden)]
+pub struct Cutters<N: Real> {
+    cutters: HashMap<usize, Cutter<N>, UintTWHash>,
+    // (cutter uid, cut).
+    cuts:    Vec<(usize, Cut<N>)>
+}
+
+impl<N: Real> Cutters<N> {
+    /// Creates an empty set of cutters.
+    pub fn new() -> Cutters<N> {
+        Cutters {
+            cutters: HashMap::new(UintTWHash::new()),
+            cuts:    Vec::new()
+        }
+    }
+
+    /// Whether there is no cutter.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cutters.is_empty()
+    }
+
+    /// Makes the body identified by `uid` a cutter.
+    pub fn add<H: CutHandler<N> + 'static>(&mut self, uid: usize, body: RigidBodyHandle<N>, handler: H) {
+        let _ = self.cutters.insert_or_replace(uid, Cutter { body: body, handler: Box::new(handler) }, true);
+    }
+
+    /// Makes the body identified by `uid` a regular rigid body again. Returns `false` if it was
+    /// not a cutter.
+    pub fn remove(&mut self, uid: usize) -> bool {
+        self.cuts.retain(|&(cutter, ref cut)| cutter != uid && &*cut.target as *const _ as usize != uid);
+        self.cutters.remove(&uid)
+    }
+
+    /// Whether the body identified by `uid` is a cutter.
+    #[inline]
+    pub fn is_cutter(&self, uid: usize) -> bool {
+        self.cutters.contains_key(&uid)
+    }
+
+    /// The bodies of the cutters.
+    pub fn bodies(&self) -> Vec<RigidBodyHandle<N>> {
+        self.cutters.elements().iter().map(|e| e.value.body.clone()).collect()
+    }
+
+    /// Records the cut between two bodies, if one of them is a cutter.
+    ///
+    /// Returns `true` if one of the bodies is a cutter, i.e., if their contacts must not be
+    /// solved. Only the penetrating contacts are recorded.
+    pub fn record(&mut self,
+                  uid1:     usize,
+                  rb1:      &RigidBodyHandle<N>,
+                  uid2:     usize,
+                  rb2:      &RigidBodyHandle<N>,
+                  contacts: &[Contact<Point<N>>])
+                  -> bool {
+        let (cutter, target, flip) = match (self.is_cutter(uid1), self.is_cutter(uid2)) {
+            (true, true)   => return true, // Cutters do not cut each other.
+            (true, false)  => (uid1, rb2, false),
+            (false, true)  => (uid2, rb1, true),
+            (false, false) => return false
+        };
+
+        let contacts: Vec<_> = contacts.iter().filter(|c| c.depth > na::zero()).map(|c| {
+            let mut c = c.clone();
+
+            if flip {
+                c.flip()
+            }
+
+            c
+        }).collect();
+
+        if !contacts.is_empty() {
+            let body = self.cutters.find(&cutter).unwrap().body.clone();
+
+            self.cuts.push((cutter, Cut {
+                cutter:   body,
+                target:   target.clone(),
+                contacts: contacts
+            }))
+        }
+
+        true
+    }
+
+    /// Sends the cuts recorded since the last dispatch to the handlers of their cutters.
+    pub fn dispatch(&mut self) {
+        for (cutter, cut) in self.cuts.drain(..) {
+            if let Some(cutter) = self.cutters.find_mut(&cutter) {
+                cutter.handler.handle_cut(&cut)
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+#![doc(hidden)]
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use alga::general::Real;
+use ncollide::shape::Shape;
+use ncollide::narrow_phase::{ContactDispatcher, ContactAlgorithm, DefaultContactDispatcher,
+                             ProximityDispatcher, ProximityAlgorithm, DefaultProximityDispatcher};
+use math::{Point, Isometry};
+
+/// A shared list of named dispatchers, in decreasing order of priority.
+pub struct Dispatchers<D: ?Sized> {
+    list: Rc<RefCell<Vec<(String, Box<D>)>>>
+}
+
+impl<D: ?Sized> Clone for Dispatchers<D> {
+    fn clone(&self) -> Dispatchers<D> {
+        Dispatchers {
+            list: self.list.clone()
+        }
+    }
+}
+
+impl<D: ?Sized> Dispatchers<D> {
+    /// Creates an empty list of dispatchers.
+    pub fn new() -> Dispatchers<D> {
+        Dispatchers {
+            list: Rc::new(RefCell::new(Vec::new()))
+        }
+    }
+
+    /// Adds a dispatcher with the lowest priority, or replaces the one with the same name.
+    pub fn register(&self, name: &str, dispatcher: Box<D>) {
+        let mut list = self.list.borrow_mut();
+
+        match list.iter().position(|d| d.0 == name) {
+            Some(i) => list[i].1 = dispatcher,
+            None    => list.push((name.to_string(), dispatcher))
+        }
+    }
+
+    /// Removes the dispatcher named `name`.
+    pub fn unregister(&self, name: &str) {
+        self.list.borrow_mut().retain(|d| d.0 != name)
+    }
+}
+
+/// The user-defined contact dispatchers.
+pub type ContactDispatchers<N> = Dispatchers<ContactDispatcher<Point<N>, Isometry<N>>>;
+
+/// The user-defined proximity dispatchers.
+pub type ProximityDispatchers<N> = Dispatchers<ProximityDispatcher<Point<N>, Isometry<N>>>;
+
+/// A contact dispatcher trying user-defined dispatchers before the default one.
+pub struct ExtensibleContactDispatcher<N: Real> {
+    custom:   ContactDispatchers<N>,
+    fallback: DefaultContactDispatcher<Point<N>, Isometry<N>>
+}
+
+impl<N: Real> ExtensibleContactDispatcher<N> {
+    /// Creates a dispatcher trying the dispatchers of `custom` first.
+    pub fn new(custom: ContactDispatchers<N>) -> ExtensibleContactDispatcher<N> {
+        ExtensibleContactDispatcher {
+            custom:   custom,
+            fallback: DefaultContactDispatcher::new()
+        }
+    }
+}
+
+impl<N: Real> ContactDispatcher<Point<N>, Isometry<N>> for ExtensibleContactDispatcher<N> {
+    fn get_contact_algorithm(&self, a: &Shape<Point<N>, Isometry<N>>, b: &Shape<Point<N>, Isometry<N>>)
+                             -> Option<ContactAlgorithm<Point<N>, Isometry<N>>> {
+        for &(_, ref dispatcher) in self.custom.list.borrow().iter() {
+            let algorithm = dispatcher.get_contact_algorithm(a, b);
+
+            if algorithm.is_some() {
+                return algorithm;
+            }
+        }
+
+        self.fallback.get_contact_algorithm(a, b)
+    }
+}
+
+/// A proximity dispatcher trying user-defined dispatchers before the default one.
+pub struct ExtensibleProximityDispatcher<N: Real> {
+    custom:   ProximityDispatchers<N>,
+    fallback: DefaultProximityDispatcher<Point<N>, Isometry<N>>
+}
+
+impl<N: Real> ExtensibleProximityDispatcher<N> {
+    /// Creates a dispatcher trying the dispatchers of `custom` first.
+    pub fn new(custom: ProximityDispatchers<N>) -> ExtensibleProximityDispatcher<N> {
+        ExtensibleProximityDispatcher {
+            custom:   custom,
+            fallback: DefaultProximityDispatcher::new()
+        }
+    }
+}
+
+impl<N: Real> ProximityDispatcher<Point<N>, Isometry<N>> for ExtensibleProximityDispatcher<N> {
+    fn get_proximity_algorithm(&self, a: &Shape<Point<N>, Isometry<N>>, b: &Shape<Point<N>, Isometry<N>>)
+                               -> Option<ProximityAlgorithm<Point<N>, Isometry<N>>> {
+        for &(_, ref dispatcher) in self.custom.list.borrow().iter() {
+            let algorithm = dispatcher.get_proximity_algorithm(a, b);
+
+            if algorithm.is_some() {
+                return algorithm;
+            }
+        }
+
+        self.fallback.get_proximity_algorithm(a, b)
+    }
+}
@@ -1,17 +1,23 @@
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::ops::Mul;
 
 use alga::general::Real;
 use na;
+use ncollide::shape::Compound;
 use object::RigidBody;
-use math::Point;
+use math::{Point, Isometry};
 
 /// One of the two end points of a joint.
+#[derive(Clone)]
 pub struct Anchor<N: Real, P> {
     /// The body attached to this anchor.
     pub body:     Option<Rc<RefCell<RigidBody<N>>>>,
-    /// The attach position, in local coordinates of the attached body.
-    pub position: P
+    /// The attach position, in local coordinates of the attached body, or of its compound child
+    /// `child` if it is set.
+    pub position: P,
+    /// The index of the child shape of the attached compound body `position` is relative to.
+    pub child:    Option<usize>
 }
 
 impl<N: Real, P> Anchor<N, P> {
@@ -22,7 +28,22 @@ impl<N: Real, P> Anchor<N, P> {
     pub fn new(body: Option<Rc<RefCell<RigidBody<N>>>>, position: P) -> Anchor<N, P> {
         Anchor {
             body:     body,
-            position: position
+            position: position,
+            child:    None
+        }
+    }
+
+    /// Creates a new `Anchor` at a given `position` on the local space of the `child`-th shape
+    /// of a compound `body`.
+    ///
+    /// The position relative to the body is re-derived from the body's compound shape whenever
+    /// the anchor is evaluated. If the body does not have a compound shape or if it has less than
+    /// `child + 1` children, `position` is considered to be expressed in the body local space.
+    pub fn new_on_child(body: Rc<RefCell<RigidBody<N>>>, child: usize, position: P) -> Anchor<N, P> {
+        Anchor {
+            body:     Some(body),
+            position: position,
+            child:    Some(child)
         }
     }
 }
@@ -42,3 +63,32 @@ impl<N: Real, P> Anchor<N, P> {
         }
     }
 }
+
+impl<N: Real, P: Clone> Anchor<N, P>
+    where Isometry<N>: Mul<P, Output = P> {
+    /// The attach position, in local coordinates of the attached body.
+    ///
+    /// This differs from `self.position` only if the anchor is attached to a compound child.
+    pub fn local_position(&self) -> P {
+        match (&self.body, self.child) {
+            (&Some(ref b), Some(child)) => {
+                let rb    = b.borrow();
+                let shape = rb.shape().as_shape::<Compound<Point<N>, Isometry<N>>>();
+
+                match shape.and_then(|c| c.shapes().get(child)) {
+                    Some(&(ref m, _)) => *m * self.position.clone(),
+                    None              => self.position.clone()
+                }
+            },
+            _ => self.position.clone()
+        }
+    }
+
+    /// The attach position in global coordinates.
+    pub fn global_position(&self) -> P {
+        match self.body {
+            Some(ref b) => *b.borrow().position() * self.local_position(),
+            None        => self.position.clone()
+        }
+    }
+}
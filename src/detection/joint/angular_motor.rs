@@ -0,0 +1,183 @@
+use alga::general::Real;
+use na;
+use math::{Isometry, Orientation};
+use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
+use detection::joint::joint::Joint;
+
+/// A joint driving the relative angular velocity of two objects around one axis.
+///
+/// This joint does not constrain the other degrees of freedom of the bodies: combine it with
+/// other joints, e.g., a ball-in-socket, to spin a platform around its attach point. The motor
+/// applies a torque up to its maximum one to reach the target velocity.
+#[derive(Clone)]
+pub struct AngularMotor<N: Real> {
+    up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
+    anchor1:    Anchor<N, Isometry<N>>,
+    anchor2:    Anchor<N, Isometry<N>>,
+    axis:       Orientation<N>,
+    velocity:   N,
+    max_torque: N
+}
+
+impl<N: Real> AngularMotor<N> {
+    /// Creates a new `AngularMotor` spinning the second body around `axis` at `velocity` relative
+    /// to the first one, with a torque up to `max_torque`.
+    ///
+    /// The axis is expressed in the local coordinates of the first anchor frame, and normalized.
+    /// In 2D, the only possible axis is the unit one-dimensional vector.
+    pub fn new(anchor1:    Anchor<N, Isometry<N>>,
+               anchor2:    Anchor<N, Isometry<N>>,
+               axis:       Orientation<N>,
+               velocity:   N,
+               max_torque: N)
+               -> AngularMotor<N> {
+        AngularMotor {
+            up_to_date: false,
+            breaking:   None,
+            collisions: true,
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            axis:       na::normalize(&axis),
+            velocity:   velocity,
+            max_torque: max_torque
+        }
+    }
+
+    /// Tells if the joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the reaction force and torque beyond which this joint breaks.
+    ///
+    /// The motor only applies torques, so the first element of `breaking` is ignored. See
+    /// `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    ///
+    /// The collisions are enabled by default, as this joint does not keep the bodies apart.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> AngularMotor<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The rotation axis, in the local coordinates of the first anchor frame.
+    pub fn axis(&self) -> &Orientation<N> {
+        &self.axis
+    }
+
+    /// Sets the rotation axis, in the local coordinates of the first anchor frame.
+    pub fn set_axis(&mut self, axis: Orientation<N>) {
+        let axis = na::normalize(&axis);
+
+        if axis != self.axis {
+            self.up_to_date = false;
+            self.axis       = axis
+        }
+    }
+
+    /// The target relative angular velocity around the axis, in radians per second.
+    pub fn target_velocity(&self) -> N {
+        self.velocity
+    }
+
+    /// Sets the target relative angular velocity around the axis, in radians per second.
+    pub fn set_target_velocity(&mut self, velocity: N) {
+        if velocity != self.velocity {
+            self.up_to_date = false;
+            self.velocity   = velocity
+        }
+    }
+
+    /// The maximum torque the motor can apply.
+    pub fn max_torque(&self) -> N {
+        self.max_torque
+    }
+
+    /// Sets the maximum torque the motor can apply.
+    pub fn set_max_torque(&mut self, max_torque: N) {
+        if max_torque != self.max_torque {
+            self.up_to_date = false;
+            self.max_torque = max_torque
+        }
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates.
+    pub fn set_local1(&mut self, local1: Isometry<N>) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates.
+    pub fn set_local2(&mut self, local2: Isometry<N>) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+}
+
+impl<N: Real> Joint<N, Isometry<N>> for AngularMotor<N> {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Isometry<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Isometry<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
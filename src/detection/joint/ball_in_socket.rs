@@ -1,15 +1,62 @@
 use alga::general::Real;
+use na;
 use math::Point;
+#[cfg(feature = "dim3")]
+use math::{Vector, Rotation};
 use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
 use detection::joint::joint::Joint;
 
+/// The angular limits of a ball-in-socket joint.
+///
+/// Each body has a joint frame, which `x` axis is the twist axis. The swing is the angle between
+/// the twist axes of both frames. The twist is the rotation of the second frame relative to the
+/// first one around the twist axis, once the swing is removed.
+#[cfg(feature = "dim3")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BallInSocketLimits<N: Real> {
+    /// The orientation of the joint frame of the first body, in its local coordinates.
+    pub frame1:    Rotation<N>,
+    /// The orientation of the joint frame of the second body, in its local coordinates.
+    pub frame2:    Rotation<N>,
+    /// The maximum swing angle, i.e., the half-angle of the cone the twist axis of the second
+    /// frame is kept into.
+    pub max_swing: N,
+    /// The minimum twist angle, in `[-pi, 0]`.
+    pub min_twist: N,
+    /// The maximum twist angle, in `[0, pi]`.
+    pub max_twist: N
+}
+
+#[cfg(feature = "dim3")]
+impl<N: Real> BallInSocketLimits<N> {
+    /// Creates new angular limits.
+    pub fn new(frame1: Rotation<N>, frame2: Rotation<N>, max_swing: N, min_twist: N, max_twist: N)
+               -> BallInSocketLimits<N> {
+        BallInSocketLimits {
+            frame1:    frame1,
+            frame2:    frame2,
+            max_swing: max_swing,
+            min_twist: min_twist,
+            max_twist: max_twist
+        }
+    }
+}
+
 /// A ball-in-socket joint.
 ///
-/// This is usually used to create ragdolls.
+/// This is usually used to create ragdolls. In 3D, the relative rotation of the bodies can be
+/// limited with a swing cone and a twist range.
+#[derive(Clone)]
 pub struct BallInSocket<N: Real> {
     up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
     anchor1:    Anchor<N, Point<N>>,
     anchor2:    Anchor<N, Point<N>>,
+    damping:    N,
+    #[cfg(feature = "dim3")]
+    limits:     Option<BallInSocketLimits<N>>
 }
 
 impl<N: Real> BallInSocket<N> {
@@ -17,8 +64,13 @@ impl<N: Real> BallInSocket<N> {
     pub fn new(anchor1: Anchor<N, Point<N>>, anchor2: Anchor<N, Point<N>>) -> BallInSocket<N> {
         BallInSocket {
             up_to_date: false,
+            breaking:   None,
+            collisions: false,
             anchor1:    anchor1,
-            anchor2:    anchor2
+            anchor2:    anchor2,
+            damping:    na::zero(),
+            #[cfg(feature = "dim3")]
+            limits:     None
         }
     }
 
@@ -32,6 +84,46 @@ impl<N: Real> BallInSocket<N> {
         self.up_to_date = true
     }
 
+    /// Sets the reaction force and torque beyond which this joint breaks.
+    ///
+    /// See `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> BallInSocket<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The angular damping of this joint.
+    pub fn angular_damping(&self) -> N {
+        self.damping
+    }
+
+    /// Sets the angular damping of this joint.
+    ///
+    /// The damping resists the relative rotation of the attached bodies with a torque
+    /// proportional to their relative angular velocity, without slowing down their common
+    /// motion. Defaults to zero.
+    pub fn set_angular_damping(&mut self, damping: N) {
+        if damping != self.damping {
+            self.up_to_date = false;
+            self.damping    = damping
+        }
+    }
+
     /// Sets the the second anchor position.
     ///
     /// The position is expressed in the second attached body’s local coordinates.
@@ -53,6 +145,63 @@ impl<N: Real> BallInSocket<N> {
     }
 }
 
+#[cfg(feature = "dim3")]
+impl<N: Real> BallInSocket<N> {
+    /// The angular limits of this joint, if any.
+    pub fn angular_limits(&self) -> Option<&BallInSocketLimits<N>> {
+        self.limits.as_ref()
+    }
+
+    /// Sets the angular limits of this joint.
+    ///
+    /// Set to `None` to let the bodies rotate freely.
+    pub fn set_angular_limits(&mut self, limits: Option<BallInSocketLimits<N>>) {
+        if limits != self.limits {
+            self.up_to_date = false;
+            self.limits     = limits
+        }
+    }
+
+    /// The current swing and twist angles of this joint, if it has angular limits.
+    pub fn swing_twist(&self) -> Option<(N, N)> {
+        self.limit_axes().map(|(swing, _, twist, _)| (swing, twist))
+    }
+
+    /// The swing angle and axis, and the twist angle and axis, in world coordinates.
+    #[doc(hidden)]
+    pub fn limit_axes(&self) -> Option<(N, Vector<N>, N, Vector<N>)> {
+        fn rotation<N: Real>(anchor: &Anchor<N, Point<N>>) -> Rotation<N> {
+            match anchor.body {
+                Some(ref b) => b.borrow().position().rotation,
+                None        => na::one()
+            }
+        }
+
+        self.limits.as_ref().map(|limits| {
+            let frame1 = rotation(&self.anchor1) * limits.frame1;
+            let frame2 = rotation(&self.anchor2) * limits.frame2;
+            let rel    = frame1.inverse() * frame2;
+            let axis   = Vector::x_axis();
+
+            // Swing-twist decomposition of the relative rotation.
+            let mut scalar = rel.quaternion().scalar();
+            let mut proj   = rel.quaternion().vector().dot(&*axis);
+
+            if scalar < na::zero() {
+                scalar = -scalar;
+                proj   = -proj;
+            }
+
+            let twist_angle = proj.atan2(scalar) * na::convert(2.0f64);
+            let twist       = Rotation::from_axis_angle(&axis, twist_angle);
+            let swing       = rel * twist.inverse();
+            let swing_axis  = swing.axis().unwrap_or(Vector::y_axis());
+
+            (swing.angle(), frame1 * swing_axis.unwrap(), twist_angle, frame2 * axis.unwrap())
+        })
+    }
+}
+
 
 impl<N: Real> Joint<N, Point<N>> for BallInSocket<N> {
     /// The first anchor affected by this joint.
@@ -70,22 +219,24 @@ impl<N: Real> Joint<N, Point<N>> for BallInSocket<N> {
     /// The first attach point in global coordinates.
     #[inline]
     fn anchor1_pos(&self) -> Point<N> {
-        match self.anchor1.body {
-            Some(ref b) => {
-                b.borrow().position() * self.anchor1.position
-            },
-            None => self.anchor1.position.clone()
-        }
+        self.anchor1.global_position()
     }
 
     /// The second attach point in global coordinates.
     #[inline]
     fn anchor2_pos(&self) -> Point<N> {
-        match self.anchor2.body {
-            Some(ref b) => {
-                b.borrow().position() * self.anchor2.position
-            },
-            None => self.anchor2.position.clone()
-        }
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
     }
 }
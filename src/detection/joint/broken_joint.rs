@@ -0,0 +1,31 @@
+use alga::general::Real;
+use detection::constraint::Constraint;
+use math::{Vector, Orientation};
+
+/// A joint removed from the world because its reaction exceeded its breaking forces.
+#[derive(Clone)]
+pub struct BrokenJoint<N: Real> {
+    /// The joint.
+    pub joint:           Constraint<N>,
+    /// The linear impulse the joint applied on its second body during the resolution it broke at.
+    pub linear_impulse:  Vector<N>,
+    /// The angular impulse the joint applied on its second body during the resolution it broke
+    /// at, excluding the torque caused by the linear impulse.
+    pub angular_impulse: Orientation<N>
+}
+
+/// Trait implemented by the receivers of the joint breaks.
+pub trait JointBreakHandler<N: Real> {
+    /// Called for each joint right after it broke, during the step.
+    ///
+    /// The world cannot be modified from this handler: the jointed bodies are typically recorded,
+    /// and replaced by their debris once the step is done.
+    fn handle_joint_broken(&mut self, broken: &BrokenJoint<N>);
+}
+
+impl<N: Real, F: FnMut(&BrokenJoint<N>)> JointBreakHandler<N> for F {
+    #[inline]
+    fn handle_joint_broken(&mut self, broken: &BrokenJoint<N>) {
+        self(broken)
+    }
+}
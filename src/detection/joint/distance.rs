@@ -0,0 +1,168 @@
+use alga::general::Real;
+use na;
+use math::Point;
+use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
+use detection::joint::joint::Joint;
+
+/// A joint keeping the distance between two points fixed, or within bounds.
+///
+/// With a fixed distance, this is a rigid rod which however lets both bodies rotate freely around
+/// their anchors. With a minimum distance of zero, this is a rope which goes slack when its
+/// anchors get closer than its length.
+#[derive(Clone)]
+pub struct Distance<N: Real> {
+    up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
+    anchor1:    Anchor<N, Point<N>>,
+    anchor2:    Anchor<N, Point<N>>,
+    min_length: N,
+    max_length: N
+}
+
+impl<N: Real> Distance<N> {
+    /// Creates a joint keeping its anchors at the distance `length`.
+    pub fn new(anchor1: Anchor<N, Point<N>>, anchor2: Anchor<N, Point<N>>, length: N) -> Distance<N> {
+        Distance::new_with_limits(anchor1, anchor2, length, length)
+    }
+
+    /// Creates a joint keeping the distance between its anchors in `[min_length, max_length]`.
+    pub fn new_with_limits(anchor1:    Anchor<N, Point<N>>,
+                           anchor2:    Anchor<N, Point<N>>,
+                           min_length: N,
+                           max_length: N)
+                           -> Distance<N> {
+        assert!(min_length >= na::zero() && min_length <= max_length,
+                "The distance limits must be positive and ordered.");
+
+        Distance {
+            up_to_date: false,
+            breaking:   None,
+            collisions: false,
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            min_length: min_length,
+            max_length: max_length
+        }
+    }
+
+    /// Tells if this joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the reaction force beyond which this joint breaks.
+    ///
+    /// This joint applies no torque, so that the second element of `breaking` is ignored. See
+    /// `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> Distance<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The minimum and maximum distances between both anchors.
+    pub fn limits(&self) -> (N, N) {
+        (self.min_length, self.max_length)
+    }
+
+    /// Sets the minimum and maximum distances between both anchors.
+    pub fn set_limits(&mut self, min_length: N, max_length: N) {
+        assert!(min_length >= na::zero() && min_length <= max_length,
+                "The distance limits must be positive and ordered.");
+
+        if min_length != self.min_length || max_length != self.max_length {
+            self.up_to_date = false;
+            self.min_length = min_length;
+            self.max_length = max_length
+        }
+    }
+
+    /// Sets the fixed distance between both anchors.
+    pub fn set_length(&mut self, length: N) {
+        self.set_limits(length, length)
+    }
+
+    /// The current distance between both anchors.
+    pub fn length(&self) -> N {
+        na::distance(&self.anchor1_pos(), &self.anchor2_pos())
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates.
+    pub fn set_local1(&mut self, local1: Point<N>) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates.
+    pub fn set_local2(&mut self, local2: Point<N>) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+}
+
+impl<N: Real> Joint<N, Point<N>> for Distance<N> {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Point<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Point<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Point<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Point<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
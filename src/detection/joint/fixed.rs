@@ -1,11 +1,15 @@
 use alga::general::Real;
 use math::Isometry;
 use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
 use detection::joint::joint::Joint;
 
 /// A joint that prevents any relative movement (linear and angular) between two objects.
+#[derive(Clone)]
 pub struct Fixed<N: Real> {
     up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
     anchor1:    Anchor<N, Isometry<N>>,
     anchor2:    Anchor<N, Isometry<N>>,
 }
@@ -15,6 +19,8 @@ impl<N: Real> Fixed<N> {
     pub fn new(anchor1: Anchor<N, Isometry<N>>, anchor2: Anchor<N, Isometry<N>>) -> Fixed<N> {
         Fixed {
             up_to_date: false,
+            breaking:   None,
+            collisions: false,
             anchor1:    anchor1,
             anchor2:    anchor2
         }
@@ -30,6 +36,29 @@ impl<N: Real> Fixed<N> {
         self.up_to_date = true
     }
 
+    /// Sets the reaction force and torque beyond which this joint breaks.
+    ///
+    /// See `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> Fixed<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
     /// Sets the the second anchor position.
     ///
     /// The position is expressed in the second attached body’s local coordinates.
@@ -67,22 +96,24 @@ impl<N: Real> Joint<N, Isometry<N>> for Fixed<N> {
     /// The first attach point in global coordinates.
     #[inline]
     fn anchor1_pos(&self) -> Isometry<N> {
-        match self.anchor1.body {
-            Some(ref b) => {
-                *b.borrow().position() * self.anchor1.position
-            },
-            None => self.anchor1.position.clone()
-        }
+        self.anchor1.global_position()
     }
 
     /// The second attach point in global coordinates.
     #[inline]
     fn anchor2_pos(&self) -> Isometry<N> {
-        match self.anchor2.body {
-            Some(ref b) => {
-                *b.borrow().position() * self.anchor2.position
-            },
-            None => self.anchor2.position.clone()
-        }
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
     }
 }
@@ -0,0 +1,196 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use alga::general::Real;
+use na;
+use math::Isometry;
+use detection::joint::anchor::Anchor;
+use detection::joint::hinge::Hinge;
+use detection::joint::joint::Joint;
+
+/// A joint coupling the rotations of two hinges.
+///
+/// The second hinge turns `ratio` times as fast as the first one, e.g., `-r1 / r2` for two meshing
+/// gears of radii `r1` and `r2`. The gear applies its torques on the second bodies of both hinges
+/// only: their first bodies are considered as carriers, like a fixed ground or a chassis, which
+/// is not pushed back by the gear.
+///
+/// Removing one of the hinges from the world does not remove the gear.
+#[derive(Clone)]
+pub struct Gear<N: Real> {
+    up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
+    hinge1:     Rc<RefCell<Hinge<N>>>,
+    hinge2:     Rc<RefCell<Hinge<N>>>,
+    anchor1:    Anchor<N, Isometry<N>>,
+    anchor2:    Anchor<N, Isometry<N>>,
+    ratio:      N,
+    // The last hinge angles, and how much each hinge turned since the gear creation, which may
+    // exceed a full turn.
+    angles:     (N, N),
+    turns:      (N, N)
+}
+
+impl<N: Real> Gear<N> {
+    /// Creates a gear making `hinge2` turn `ratio` times as fast as `hinge1`.
+    ///
+    /// The current angles of both hinges are the reference ones, which the gear keeps in ratio.
+    pub fn new(hinge1: Rc<RefCell<Hinge<N>>>, hinge2: Rc<RefCell<Hinge<N>>>, ratio: N) -> Gear<N> {
+        let anchor1 = hinge1.borrow().anchor2().clone();
+        let anchor2 = hinge2.borrow().anchor2().clone();
+        let angles  = (hinge1.borrow().angle(), hinge2.borrow().angle());
+
+        Gear {
+            up_to_date: false,
+            breaking:   None,
+            collisions: false,
+            hinge1:     hinge1,
+            hinge2:     hinge2,
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            ratio:      ratio,
+            angles:     angles,
+            turns:      (na::zero(), na::zero())
+        }
+    }
+
+    /// Tells if this joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the reaction torque beyond which this joint breaks.
+    ///
+    /// This joint applies no force, so that the first element of `breaking` is ignored. See
+    /// `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the second bodies of both hinges.
+    ///
+    /// The collisions are disabled by default.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint coupling `hinge1` and `hinge2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_hinges(&self, hinge1: Rc<RefCell<Hinge<N>>>, hinge2: Rc<RefCell<Hinge<N>>>) -> Gear<N> {
+        let mut res = self.clone();
+
+        res.anchor1 = hinge1.borrow().anchor2().clone();
+        res.anchor2 = hinge2.borrow().anchor2().clone();
+        res.hinge1  = hinge1;
+        res.hinge2  = hinge2;
+
+        res
+    }
+
+    /// The first hinge coupled by this gear.
+    pub fn hinge1(&self) -> &Rc<RefCell<Hinge<N>>> {
+        &self.hinge1
+    }
+
+    /// The second hinge coupled by this gear.
+    pub fn hinge2(&self) -> &Rc<RefCell<Hinge<N>>> {
+        &self.hinge2
+    }
+
+    /// How many times as fast as the first hinge the second one turns.
+    pub fn ratio(&self) -> N {
+        self.ratio
+    }
+
+    /// Sets how many times as fast as the first hinge the second one turns.
+    ///
+    /// The current angles of both hinges become the reference ones.
+    pub fn set_ratio(&mut self, ratio: N) {
+        if ratio != self.ratio {
+            self.up_to_date = false;
+            self.ratio      = ratio;
+            self.turns      = (na::zero(), na::zero())
+        }
+    }
+
+    /// How much each hinge turned since the reference angles were set.
+    pub fn turns(&self) -> (N, N) {
+        self.turns
+    }
+
+    /// The rotation of the second hinge not matching the one of the first hinge times the ratio.
+    pub fn error(&self) -> N {
+        self.turns.1 - self.turns.0 * self.ratio
+    }
+
+    /// Accumulates the rotations of both hinges since the last call.
+    ///
+    /// The hinges are assumed to turn by less than half a turn between two calls.
+    #[doc(hidden)]
+    pub fn update_turns(&mut self) {
+        fn wrap<N: Real>(angle: N) -> N {
+            if angle > N::pi() {
+                angle - N::two_pi()
+            }
+            else if angle < -N::pi() {
+                angle + N::two_pi()
+            }
+            else {
+                angle
+            }
+        }
+
+        let angles = (self.hinge1.borrow().angle(), self.hinge2.borrow().angle());
+
+        self.turns.0 = self.turns.0 + wrap(angles.0 - self.angles.0);
+        self.turns.1 = self.turns.1 + wrap(angles.1 - self.angles.1);
+        self.angles  = angles;
+
+        // The hinge anchors may have been modified by the user.
+        self.anchor1 = self.hinge1.borrow().anchor2().clone();
+        self.anchor2 = self.hinge2.borrow().anchor2().clone();
+    }
+}
+
+impl<N: Real> Joint<N, Isometry<N>> for Gear<N> {
+    /// The second anchor of the first hinge.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor of the second hinge.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Isometry<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Isometry<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the second bodies of both hinges collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
@@ -0,0 +1,205 @@
+use alga::general::Real;
+use na;
+use math::{Isometry, Orientation, Rotation};
+use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
+use detection::joint::joint::Joint;
+use detection::joint::joint_motor::JointMotor;
+
+/// A joint that only allows relative rotations around one axis between two objects.
+///
+/// Each anchor defines a frame attached to its body. The joint keeps the origins of both frames
+/// at the same position, and the hinge axis, expressed in both frames, aligned. The angle of the
+/// hinge is the rotation of the second frame relative to the first one around this axis.
+#[derive(Clone)]
+pub struct Hinge<N: Real> {
+    up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
+    anchor1:    Anchor<N, Isometry<N>>,
+    anchor2:    Anchor<N, Isometry<N>>,
+    axis:       Orientation<N>,
+    limits:     Option<(N, N)>,
+    damping:    N,
+    motor:      Option<JointMotor<N>>
+}
+
+impl<N: Real> Hinge<N> {
+    /// Creates a new `Hinge` joint rotating around `axis`.
+    ///
+    /// The axis is expressed in the local coordinates of both anchor frames, and normalized. In
+    /// 2D, the only possible axis is the unit one-dimensional vector.
+    pub fn new(anchor1: Anchor<N, Isometry<N>>, anchor2: Anchor<N, Isometry<N>>, axis: Orientation<N>) -> Hinge<N> {
+        Hinge {
+            up_to_date: false,
+            breaking:   None,
+            collisions: false,
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            axis:       na::normalize(&axis),
+            limits:     None,
+            damping:    na::zero(),
+            motor:      None
+        }
+    }
+
+    /// Tells if the joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the reaction force and torque beyond which this joint breaks.
+    ///
+    /// See `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> Hinge<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The hinge axis, in the local coordinates of both anchor frames.
+    pub fn axis(&self) -> &Orientation<N> {
+        &self.axis
+    }
+
+    /// The minimum and maximum angles of the hinge, if limited.
+    pub fn limits(&self) -> Option<(N, N)> {
+        self.limits
+    }
+
+    /// Sets the minimum and maximum angles of the hinge, in radians.
+    ///
+    /// Both angles should be in `[-pi, pi]`. Set to `None` to let the hinge rotate freely.
+    pub fn set_limits(&mut self, limits: Option<(N, N)>) {
+        if limits != self.limits {
+            self.up_to_date = false;
+            self.limits     = limits
+        }
+    }
+
+    /// The angular damping of the hinge.
+    pub fn angular_damping(&self) -> N {
+        self.damping
+    }
+
+    /// Sets the angular damping of the hinge.
+    ///
+    /// The damping resists the rotation of the hinge with a torque proportional to the relative
+    /// angular velocity of the attached bodies around the hinge axis. Defaults to zero.
+    pub fn set_angular_damping(&mut self, damping: N) {
+        if damping != self.damping {
+            self.up_to_date = false;
+            self.damping    = damping
+        }
+    }
+
+    /// The motor driving the hinge angle, if any.
+    pub fn motor(&self) -> Option<&JointMotor<N>> {
+        self.motor.as_ref()
+    }
+
+    /// Sets the motor driving the hinge angle.
+    ///
+    /// The velocities are in radians per second, and the servo positions are angles in
+    /// `[-pi, pi]`. The angle limits, if any, have precedence over the motor. Set to `None` to
+    /// remove the motor.
+    pub fn set_motor(&mut self, motor: Option<JointMotor<N>>) {
+        if motor != self.motor {
+            self.up_to_date = false;
+            self.motor      = motor
+        }
+    }
+
+    /// The current angle of the hinge, in `[-pi, pi]`.
+    pub fn angle(&self) -> N {
+        let rel = self.anchor1_pos().rotation.inverse() * self.anchor2_pos().rotation;
+
+        na::dot(&rel.scaled_axis(), &self.axis)
+    }
+
+    /// The relative rotation of the anchor frames which is not around the hinge axis.
+    #[doc(hidden)]
+    pub fn swing(&self) -> Rotation<N> {
+        let rel   = self.anchor1_pos().rotation.inverse() * self.anchor2_pos().rotation;
+        let twist = Rotation::from_scaled_axis(self.axis * na::dot(&rel.scaled_axis(), &self.axis));
+
+        rel * twist.inverse()
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates.
+    pub fn set_local1(&mut self, local1: Isometry<N>) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates.
+    pub fn set_local2(&mut self, local2: Isometry<N>) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+}
+
+impl<N: Real> Joint<N, Isometry<N>> for Hinge<N> {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Isometry<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Isometry<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
@@ -12,4 +12,19 @@ pub trait Joint<N: Real, A> {
     fn anchor1_pos(&self) -> A;
     /// The second attach point in global coordinates.
     fn anchor2_pos(&self) -> A;
+    /// The reaction force and torque beyond which this joint breaks, if any.
+    ///
+    /// A broken joint is removed from the world at the end of the resolution. Returns `None` if
+    /// this joint is unbreakable, which is the default.
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        None
+    }
+    /// Whether the bodies attached by this joint collide with each other.
+    ///
+    /// Returns `true` by default. Most joints of this crate disable these collisions unless told
+    /// otherwise with their `set_collisions_enabled` method, as the shapes of both bodies
+    /// typically overlap around the anchors.
+    fn collisions_enabled(&self) -> bool {
+        true
+    }
 }
@@ -1,17 +1,31 @@
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::ptr;
+use std::slice::Iter;
+use std::iter::Map;
 
 use alga::general::Real;
-use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash_map::{HashMap, Entry};
 use ncollide::utils::data::hash::UintTWHash;
 use detection::activation_manager::ActivationManager;
 use detection::joint::ball_in_socket::BallInSocket;
 use detection::joint::fixed::Fixed;
+use detection::joint::hinge::Hinge;
+use detection::joint::prismatic::Prismatic;
+use detection::joint::spring::Spring;
+use detection::joint::distance::Distance;
+use detection::joint::angular_motor::AngularMotor;
+use detection::joint::rack_and_pinion::RackAndPinion;
+use detection::joint::pulley::Pulley;
+use detection::joint::gear::Gear;
+#[cfg(feature = "dim3")]
+use detection::joint::universal::Universal;
 use detection::joint::joint::Joint;
-use detection::constraint::Constraint;
+use detection::constraint::{Constraint, JointInfo};
 use object::RigidBody;
 
+/// An iterator visiting joints.
+pub type Joints<'a, N> = Map<Iter<'a, Entry<usize, Constraint<N>>>, fn(&'a Entry<usize, Constraint<N>>) -> &'a Constraint<N>>;
+
 /// Structure that handles creation and removal of joints.
 pub struct JointManager<N: Real> {
     joints:      HashMap<usize, Constraint<N>, UintTWHash>,
@@ -33,40 +47,51 @@ impl<N: Real> JointManager<N> {
         &self.joints
     }
 
+    /// An iterator visiting all the joints handled by this manager.
+    pub fn iter(&self) -> Joints<N> {
+        fn extract_value<N: Real>(e: &Entry<usize, Constraint<N>>) -> &Constraint<N> {
+            &e.value
+        }
+
+        let extract_value_fn: fn(_) -> _ = extract_value;
+        self.joints.elements().iter().map(extract_value_fn)
+    }
+
     /// List of joints attached to a specific body.
     #[inline]
     pub fn joints_with_body(&self, body: &Rc<RefCell<RigidBody<N>>>) -> Option<&[Constraint<N>]> {
         self.body2joints.find(&(&**body as *const RefCell<RigidBody<N>> as usize)).map(|v| &v[..])
     }
 
+    /// Collects the pairs of bodies attached by a joint disabling their collisions.
+    ///
+    /// Each pair is made of the keys of both bodies, as given by `WorldObject::rigid_body_uid`,
+    /// the smallest first. The pairs are sorted and appear only once.
+    pub fn non_colliding_pairs(&self, out: &mut Vec<(usize, usize)>) {
+        for joint in self.iter().filter_map(|j| j.joint_info()) {
+            if joint.collisions_enabled {
+                continue;
+            }
+
+            if let (Some(b1), Some(b2)) = (joint.body1.as_ref(), joint.body2.as_ref()) {
+                let key1 = &**b1 as *const RefCell<RigidBody<N>> as usize;
+                let key2 = &**b2 as *const RefCell<RigidBody<N>> as usize;
+
+                out.push((key1.min(key2), key1.max(key2)))
+            }
+        }
+
+        out.sort();
+        out.dedup();
+    }
+
     /// Add a `BallInSocket` joint to this manager.
     ///
     /// This will force the activation of the two objects attached to the joint.
     pub fn add_ball_in_socket(&mut self,
                               joint:      Rc<RefCell<BallInSocket<N>>>,
                               activation: &mut ActivationManager<N>) {
-        if self.joints.insert(&*joint as *const RefCell<BallInSocket<N>> as usize,
-                              Constraint::BallInSocket(joint.clone())) {
-            match joint.borrow().anchor1().body.as_ref() {
-                Some(b) => {
-                    activation.deferred_activate(b);
-                    let js = self.body2joints.find_or_insert_lazy(&**b as *const RefCell<RigidBody<N>> as usize,
-                                                                  || Some(Vec::new()));
-                    js.unwrap().push(Constraint::BallInSocket(joint.clone()));
-                },
-                _ => { }
-            }
-
-            match joint.borrow().anchor2().body.as_ref() {
-                Some(b) => {
-                    activation.deferred_activate(b);
-                    let js = self.body2joints.find_or_insert_lazy(&**b as *const RefCell<RigidBody<N>> as usize,
-                                                                  || Some(Vec::new()));
-                    js.unwrap().push(Constraint::BallInSocket(joint.clone()));
-                },
-                _ => { }
-            }
-        }
+        self.add(Constraint::BallInSocket(joint), activation)
     }
 
     /// Removes a `BallInSocket` joint from this manager.
@@ -83,25 +108,83 @@ impl<N: Real> JointManager<N> {
     ///
     /// This will force the activation of the two objects attached to the joint.
     pub fn add_fixed(&mut self, joint: Rc<RefCell<Fixed<N>>>, activation: &mut ActivationManager<N>) {
-        if self.joints.insert(&*joint as *const RefCell<Fixed<N>> as usize, Constraint::Fixed(joint.clone())) {
-            match joint.borrow().anchor1().body.as_ref() {
-                Some(b) => {
-                    activation.deferred_activate(b);
-                    let js = self.body2joints.find_or_insert_lazy(&**b as *const RefCell<RigidBody<N>> as usize,
-                                                                  || Some(Vec::new()));
-                    js.unwrap().push(Constraint::Fixed(joint.clone()));
-                },
-                _ => { }
-            }
+        self.add(Constraint::Fixed(joint), activation)
+    }
 
-            match joint.borrow().anchor2().body.as_ref() {
-                Some(b) => {
-                    activation.deferred_activate(b);
-                    let js = self.body2joints.find_or_insert_lazy(&**b as *const RefCell<RigidBody<N>> as usize,
-                                                                  || Some(Vec::new()));
-                    js.unwrap().push(Constraint::Fixed(joint.clone()));
-                },
-                _ => { }
+    /// Add a `Hinge` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_hinge(&mut self, joint: Rc<RefCell<Hinge<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::Hinge(joint), activation)
+    }
+
+    /// Add a `Prismatic` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_prismatic(&mut self, joint: Rc<RefCell<Prismatic<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::Prismatic(joint), activation)
+    }
+
+    /// Add a `Spring` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_spring(&mut self, joint: Rc<RefCell<Spring<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::Spring(joint), activation)
+    }
+
+    /// Add a `Distance` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_distance(&mut self, joint: Rc<RefCell<Distance<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::Distance(joint), activation)
+    }
+
+    /// Add an `AngularMotor` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_angular_motor(&mut self, joint: Rc<RefCell<AngularMotor<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::AngularMotor(joint), activation)
+    }
+
+    /// Add a `RackAndPinion` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_rack_and_pinion(&mut self, joint: Rc<RefCell<RackAndPinion<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::RackAndPinion(joint), activation)
+    }
+
+    /// Add a `Pulley` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_pulley(&mut self, joint: Rc<RefCell<Pulley<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::Pulley(joint), activation)
+    }
+
+    /// Add a `Gear` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_gear(&mut self, joint: Rc<RefCell<Gear<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::Gear(joint), activation)
+    }
+
+    /// Add a `Universal` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    #[cfg(feature = "dim3")]
+    pub fn add_universal(&mut self, joint: Rc<RefCell<Universal<N>>>, activation: &mut ActivationManager<N>) {
+        self.add(Constraint::Universal(joint), activation)
+    }
+
+    // Adds a joint of any type, and its bodies, to this manager.
+    fn add(&mut self, joint: Constraint<N>, activation: &mut ActivationManager<N>) {
+        let info = joint.joint_info().expect("A contact is not a joint.");
+
+        if self.joints.insert(info.key, joint.clone()) {
+            for b in info.bodies() {
+                activation.deferred_activate(b);
+                let js = self.body2joints.find_or_insert_lazy(&**b as *const RefCell<RigidBody<N>> as usize,
+                                                              || Some(Vec::new()));
+                js.unwrap().push(joint.clone());
             }
         }
     }
@@ -112,39 +195,32 @@ impl<N: Real> JointManager<N> {
     pub fn remove_joint<T: Joint<N, M>, M>(&mut self,
                                            joint:      &Rc<RefCell<T>>,
                                            activation: &mut ActivationManager<N>) {
-        if self.joints.remove(&(&**joint as *const RefCell<T> as usize)) {
-            self.remove_joint_for_body(joint, joint.borrow().anchor1().body.as_ref(), activation);
-            self.remove_joint_for_body(joint, joint.borrow().anchor2().body.as_ref(), activation);
-        }
+        self.remove_joint_info(&JointInfo::new(&**joint), activation)
     }
 
-    fn remove_joint_for_body<T: Joint<N, M>, M>(&mut self,
-                                                joint:      &Rc<RefCell<T>>,
-                                                body:       Option<&Rc<RefCell<RigidBody<N>>>>,
-                                                activation: &mut ActivationManager<N>) {
-        match body {
-            Some(b) => {
-                activation.deferred_activate(b);
-                let key = &**b as *const RefCell<RigidBody<N>> as usize;
-                match self.body2joints.find_mut(&key) {
-                    Some(ref mut js) => {
-                        let jkey = &**joint as *const RefCell<T>;
-                        js.retain(|j| {
-                            // we do not know the type of the joint, so cast it to usize for
-                            // comparison.
-                            let id = match *j {
-                                Constraint::RBRB(_, _, _) => ptr::null::<usize>() as usize,
-                                Constraint::BallInSocket(ref b) => &**b as *const RefCell<BallInSocket<N>> as usize,
-                                Constraint::Fixed(ref f) => &**f as *const RefCell<Fixed<N>> as usize
-                            };
-
-                            id != jkey as usize
-                        });
-                    }
-                    None => { }
-                }
+    /// The key of `joint` on `self.joints()`, or zero for a contact.
+    #[doc(hidden)]
+    pub fn joint_id(joint: &Constraint<N>) -> usize {
+        joint.joint_info().map_or(0, |j| j.key)
+    }
+
+    fn remove_joint_info(&mut self, joint: &JointInfo<N>, activation: &mut ActivationManager<N>) {
+        if self.joints.remove(&joint.key) {
+            for b in joint.bodies() {
+                self.remove_joint_for_body(joint.key, b, activation);
             }
-            None => { }
+        }
+    }
+
+    fn remove_joint_for_body(&mut self,
+                             joint:      usize,
+                             body:       &Rc<RefCell<RigidBody<N>>>,
+                             activation: &mut ActivationManager<N>) {
+        activation.deferred_activate(body);
+        let key = &**body as *const RefCell<RigidBody<N>> as usize;
+
+        if let Some(js) = self.body2joints.find_mut(&key) {
+            js.retain(|j| JointManager::joint_id(j) != joint);
         }
     }
 
@@ -154,70 +230,67 @@ impl<N: Real> JointManager<N> {
     pub fn remove(&mut self, b: &Rc<RefCell<RigidBody<N>>>, activation: &mut ActivationManager<N>) {
         for joints in self.body2joints.get_and_remove(&(&**b as *const RefCell<RigidBody<N>> as usize)).iter() {
             for joint in joints.value.iter() {
-                fn do_remove<N: Real, T: Joint<N, M>, M>(_self:      &mut JointManager<N>,
-                                                         joint:      &Rc<RefCell<T>>,
-                                                         b:          &Rc<RefCell<RigidBody<N>>>,
-                                                         activation: &mut ActivationManager<N>) {
-                    let bj    = joint.borrow();
-                    let body1 = bj.anchor1().body.as_ref();
-                    let body2 = bj.anchor2().body.as_ref();
-
-                    for body in bj.anchor1().body.as_ref().iter() {
-                        if &**(*body) as *const RefCell<RigidBody<N>> == &**b as *const RefCell<RigidBody<N>> {
-                            _self.remove_joint_for_body(joint, body2, activation);
-                        }
-                        else {
-                            _self.remove_joint_for_body(joint, body1, activation);
+                let joint = joint.joint_info().expect("Internal error: a contact RBRB should not be here.");
+
+                if self.joints.remove(&joint.key) {
+                    // The joints of `b` are already removed from its own list.
+                    for body in joint.bodies() {
+                        if &**body as *const RefCell<RigidBody<N>> != &**b as *const RefCell<RigidBody<N>> {
+                            self.remove_joint_for_body(joint.key, body, activation);
                         }
                     }
                 }
-
-                match *joint {
-                    Constraint::BallInSocket(ref bis) => do_remove(self, bis, b, activation),
-                    Constraint::Fixed(ref f)          => do_remove(self, f, b, activation),
-                    Constraint::RBRB(_, _, _) => panic!("Internal error: a contact RBRB should not be here.")
-                }
             }
         }
     }
 
+    /// Removes a joint of any type from this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn remove_constraint(&mut self, joint: &Constraint<N>, activation: &mut ActivationManager<N>) {
+        self.remove_joint_info(&joint.joint_info().expect("A contact is not a joint."), activation)
+    }
+
     // FIXME: do we really want to handle this here instead of in the activation manager directly?
     /// Activates the objects that interact with an activated object through a joint.
     pub fn update(&mut self, activation: &mut ActivationManager<N>) {
+        // Marks a joint as up to date, returning whether it was not.
+        fn refresh<J>(joint: &RefCell<J>, up_to_date: fn(&J) -> bool, update: fn(&mut J)) -> bool {
+            let mut bj = joint.borrow_mut();
+            let outdated = !up_to_date(&*bj);
+            update(&mut *bj);
+
+            outdated
+        }
+
         for joint in self.joints.elements().iter() {
-            match joint.value {
-                Constraint::BallInSocket(ref bis) => {
-                    let mut bbis = bis.borrow_mut();
-                    if !bbis.up_to_date() {
-                        // the joint has been invalidated by the user: wake up the attached bodies
-                        bbis.update();
-                        match bbis.anchor1().body {
-                            Some(ref b) => activation.deferred_activate(b),
-                            None        => { }
-                        }
-                        match bbis.anchor2().body {
-                            Some(ref b) => activation.deferred_activate(b),
-                            None        => { }
-                        }
-                    }
+            let outdated = match joint.value {
+                Constraint::BallInSocket(ref b)  => refresh(b, BallInSocket::up_to_date, BallInSocket::update),
+                Constraint::Fixed(ref f)         => refresh(f, Fixed::up_to_date, Fixed::update),
+                Constraint::Hinge(ref h)         => refresh(h, Hinge::up_to_date, Hinge::update),
+                Constraint::Prismatic(ref p)     => refresh(p, Prismatic::up_to_date, Prismatic::update),
+                Constraint::Spring(ref s)        => refresh(s, Spring::up_to_date, Spring::update),
+                Constraint::Distance(ref d)      => refresh(d, Distance::up_to_date, Distance::update),
+                Constraint::AngularMotor(ref m)  => refresh(m, AngularMotor::up_to_date, AngularMotor::update),
+                Constraint::RackAndPinion(ref r) => {
+                    r.borrow_mut().update_turns();
+                    refresh(r, RackAndPinion::up_to_date, RackAndPinion::update)
                 },
-                Constraint::Fixed(ref f) => { // FIXME: code duplication from BallInSocket
-                    let mut bf = f.borrow_mut();
-                    if !bf.up_to_date() {
-                        // the joint has been invalidated by the user: wake up the attached bodies
-                        bf.update();
-                        match bf.anchor1().body {
-                            Some(ref b) => activation.deferred_activate(b),
-                            None        => { }
-                        }
-                        match bf.anchor2().body {
-                            Some(ref b) => activation.deferred_activate(b),
-                            None        => { }
-                        }
-                    }
+                Constraint::Pulley(ref p)        => refresh(p, Pulley::up_to_date, Pulley::update),
+                Constraint::Gear(ref g)          => {
+                    g.borrow_mut().update_turns();
+                    refresh(g, Gear::up_to_date, Gear::update)
                 },
-                Constraint::RBRB(_, _, _) => panic!("Internal error: a contact RBRB should not be here.")
- 
+                #[cfg(feature = "dim3")]
+                Constraint::Universal(ref u)     => refresh(u, Universal::up_to_date, Universal::update),
+                Constraint::RBRB(_, _, _)        => panic!("Internal error: a contact RBRB should not be here.")
+            };
+
+            // The joint has been invalidated by the user: wake up the attached bodies.
+            if outdated {
+                for b in joint.value.joint_info().unwrap().bodies() {
+                    activation.deferred_activate(b)
+                }
             }
         }
     }
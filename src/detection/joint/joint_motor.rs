@@ -0,0 +1,98 @@
+use alga::general::Real;
+
+/// A motor driving the free degree of freedom of a joint, i.e., the angle of a hinge or the
+/// translation of a prismatic joint.
+///
+/// The motor is solved as an additional constraint of the joint, which impulse is limited by the
+/// maximum force (or torque) of the motor.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JointMotor<N: Real> {
+    /// Drives the joint at a target velocity.
+    Velocity {
+        /// The target velocity, in radians or units per second.
+        velocity:  N,
+        /// The maximum force, or torque, the motor can apply.
+        max_force: N
+    },
+    /// Drives the joint to a target position, like a servo.
+    Servo {
+        /// The target angle or translation.
+        position:     N,
+        /// The maximum speed at which the target position is reached.
+        max_velocity: N,
+        /// The maximum force, or torque, the motor can apply.
+        max_force:    N
+    }
+}
+
+impl<N: Real> JointMotor<N> {
+    /// A motor driving the joint at the target `velocity`, with a force up to `max_force`.
+    pub fn velocity(velocity: N, max_force: N) -> JointMotor<N> {
+        JointMotor::Velocity {
+            velocity:  velocity,
+            max_force: max_force
+        }
+    }
+
+    /// A motor driving the joint to the target `position` at a speed up to `max_velocity`, with
+    /// a force up to `max_force`.
+    pub fn servo(position: N, max_velocity: N, max_force: N) -> JointMotor<N> {
+        JointMotor::Servo {
+            position:     position,
+            max_velocity: max_velocity,
+            max_force:    max_force
+        }
+    }
+
+    /// The maximum force, or torque, the motor can apply.
+    pub fn max_force(&self) -> N {
+        match *self {
+            JointMotor::Velocity { max_force, .. } => max_force,
+            JointMotor::Servo { max_force, .. }    => max_force
+        }
+    }
+
+    /// The velocity the motor drives the joint at during the next time step of length `dt`, if
+    /// the joint is at `position`.
+    ///
+    /// A servo reaches its target at the end of the time step if it is not too far.
+    #[doc(hidden)]
+    pub fn target_velocity(&self, position: N, dt: N) -> N {
+        self.velocity_to_target(|target| target - position, dt)
+    }
+
+    /// The angular velocity the motor drives a hinge at during the next time step of length `dt`,
+    /// if the hinge is at `angle`.
+    ///
+    /// A servo turns the shortest way to its target angle, i.e., by an angle in `(-pi, pi]`.
+    #[doc(hidden)]
+    pub fn target_angular_velocity(&self, angle: N, dt: N) -> N {
+        self.velocity_to_target(|target| {
+            let error   = target - angle;
+            let wrapped = error - N::two_pi() * (error / N::two_pi()).round();
+
+            if wrapped <= -N::pi() { wrapped + N::two_pi() } else { wrapped }
+        }, dt)
+    }
+
+    // The velocity of the motor, `error` giving the displacement from the current position to
+    // the target one of a servo.
+    fn velocity_to_target<F: FnOnce(N) -> N>(&self, error: F, dt: N) -> N {
+        match *self {
+            JointMotor::Velocity { velocity, .. } => velocity,
+            JointMotor::Servo { position: target, max_velocity, .. } => {
+                let velocity = error(target) / dt;
+
+                if velocity > max_velocity {
+                    max_velocity
+                }
+                else if velocity < -max_velocity {
+                    -max_velocity
+                }
+                else {
+                    velocity
+                }
+            }
+        }
+    }
+}
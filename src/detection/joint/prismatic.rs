@@ -0,0 +1,178 @@
+use alga::general::Real;
+use na;
+use math::{Isometry, Vector};
+use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
+use detection::joint::joint::Joint;
+use detection::joint::joint_motor::JointMotor;
+
+/// A joint that only allows relative translations along one axis between two objects.
+///
+/// Each anchor defines a frame attached to its body. The joint keeps both frames with the same
+/// orientation, and the origin of the second frame on the line going through the origin of the
+/// first frame along the slider axis, e.g., for pistons, elevators, or sliding doors.
+#[derive(Clone)]
+pub struct Prismatic<N: Real> {
+    up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
+    anchor1:    Anchor<N, Isometry<N>>,
+    anchor2:    Anchor<N, Isometry<N>>,
+    axis:       Vector<N>,
+    limits:     Option<(N, N)>,
+    motor:      Option<JointMotor<N>>
+}
+
+impl<N: Real> Prismatic<N> {
+    /// Creates a new `Prismatic` joint sliding along `axis`.
+    ///
+    /// The axis is expressed in the local coordinates of the first anchor frame, and normalized.
+    pub fn new(anchor1: Anchor<N, Isometry<N>>, anchor2: Anchor<N, Isometry<N>>, axis: Vector<N>) -> Prismatic<N> {
+        Prismatic {
+            up_to_date: false,
+            breaking:   None,
+            collisions: false,
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            axis:       na::normalize(&axis),
+            limits:     None,
+            motor:      None
+        }
+    }
+
+    /// Tells if the joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the reaction force and torque beyond which this joint breaks.
+    ///
+    /// See `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> Prismatic<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The slider axis, in the local coordinates of the first anchor frame.
+    pub fn axis(&self) -> &Vector<N> {
+        &self.axis
+    }
+
+    /// The minimum and maximum translations along the slider axis, if limited.
+    pub fn limits(&self) -> Option<(N, N)> {
+        self.limits
+    }
+
+    /// Sets the minimum and maximum translations along the slider axis.
+    ///
+    /// Set to `None` to let the joint slide freely.
+    pub fn set_limits(&mut self, limits: Option<(N, N)>) {
+        if limits != self.limits {
+            self.up_to_date = false;
+            self.limits     = limits
+        }
+    }
+
+    /// The motor driving the translation along the slider axis, if any.
+    pub fn motor(&self) -> Option<&JointMotor<N>> {
+        self.motor.as_ref()
+    }
+
+    /// Sets the motor driving the translation along the slider axis.
+    ///
+    /// The translation limits, if any, have precedence over the motor. Set to `None` to remove
+    /// the motor.
+    pub fn set_motor(&mut self, motor: Option<JointMotor<N>>) {
+        if motor != self.motor {
+            self.up_to_date = false;
+            self.motor      = motor
+        }
+    }
+
+    /// The current translation of the second anchor frame relative to the first one, along the
+    /// slider axis.
+    pub fn translation(&self) -> N {
+        let pos1 = self.anchor1_pos();
+        let pos2 = self.anchor2_pos();
+
+        na::dot(&(pos2.translation.vector - pos1.translation.vector), &(pos1.rotation * self.axis))
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates.
+    pub fn set_local1(&mut self, local1: Isometry<N>) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates.
+    pub fn set_local2(&mut self, local2: Isometry<N>) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+}
+
+impl<N: Real> Joint<N, Isometry<N>> for Prismatic<N> {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Isometry<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Isometry<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
@@ -0,0 +1,192 @@
+use alga::general::Real;
+use na;
+use math::Point;
+use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
+use detection::joint::joint::Joint;
+
+/// A rope going from a point of a first body, over two fixed pulleys, to a point of a second body.
+///
+/// The rope goes from the first anchor to the first pulley, then from the second pulley to the
+/// second anchor. It only pulls, and keeps `length1 + ratio * length2` below its total length,
+/// where `length1` and `length2` are the lengths of both sides of the rope. A `ratio` greater than
+/// one makes the second body move more slowly than the first one, like a block and tackle.
+#[derive(Clone)]
+pub struct Pulley<N: Real> {
+    up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
+    anchor1:    Anchor<N, Point<N>>,
+    anchor2:    Anchor<N, Point<N>>,
+    pulley1:    Point<N>,
+    pulley2:    Point<N>,
+    ratio:      N,
+    length:     N
+}
+
+impl<N: Real> Pulley<N> {
+    /// Creates a rope going around the pulleys at the world-space points `pulley1` and `pulley2`.
+    ///
+    /// The total length of the rope is set so that it is taut at the current anchor positions.
+    pub fn new(anchor1: Anchor<N, Point<N>>,
+               anchor2: Anchor<N, Point<N>>,
+               pulley1: Point<N>,
+               pulley2: Point<N>,
+               ratio:   N)
+               -> Pulley<N> {
+        assert!(ratio > na::zero(), "The ratio of a pulley must be positive.");
+
+        let mut res = Pulley {
+            up_to_date: false,
+            breaking:   None,
+            collisions: false,
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            pulley1:    pulley1,
+            pulley2:    pulley2,
+            ratio:      ratio,
+            length:     na::zero()
+        };
+
+        let (length1, length2) = res.lengths();
+        res.length = length1 + length2 * ratio;
+
+        res
+    }
+
+    /// Tells if this joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the tension beyond which this rope breaks.
+    ///
+    /// A rope applies no torque, so that the second element of `breaking` is ignored. See
+    /// `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> Pulley<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The world-space position of the first pulley.
+    pub fn pulley1(&self) -> &Point<N> {
+        &self.pulley1
+    }
+
+    /// The world-space position of the second pulley.
+    pub fn pulley2(&self) -> &Point<N> {
+        &self.pulley2
+    }
+
+    /// Sets the world-space positions of both pulleys.
+    pub fn set_pulleys(&mut self, pulley1: Point<N>, pulley2: Point<N>) {
+        if pulley1 != self.pulley1 || pulley2 != self.pulley2 {
+            self.up_to_date = false;
+            self.pulley1    = pulley1;
+            self.pulley2    = pulley2
+        }
+    }
+
+    /// The factor of the length of the second side of the rope.
+    pub fn ratio(&self) -> N {
+        self.ratio
+    }
+
+    /// The total length of the rope, including the ratio.
+    pub fn length(&self) -> N {
+        self.length
+    }
+
+    /// Sets the total length of the rope, including the ratio.
+    pub fn set_length(&mut self, length: N) {
+        assert!(length >= na::zero(), "The length of a pulley cannot be negative.");
+
+        if length != self.length {
+            self.up_to_date = false;
+            self.length     = length
+        }
+    }
+
+    /// The current lengths of both sides of the rope, without the ratio.
+    pub fn lengths(&self) -> (N, N) {
+        (na::distance(&self.anchor1_pos(), &self.pulley1), na::distance(&self.anchor2_pos(), &self.pulley2))
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates.
+    pub fn set_local1(&mut self, local1: Point<N>) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates.
+    pub fn set_local2(&mut self, local2: Point<N>) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+}
+
+impl<N: Real> Joint<N, Point<N>> for Pulley<N> {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Point<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Point<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Point<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Point<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
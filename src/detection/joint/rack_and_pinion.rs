@@ -0,0 +1,199 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use alga::general::Real;
+use na;
+use math::Isometry;
+use detection::joint::anchor::Anchor;
+use detection::joint::hinge::Hinge;
+use detection::joint::prismatic::Prismatic;
+use detection::joint::joint::Joint;
+
+/// A joint coupling the rotation of a hinge to the translation of a prismatic joint.
+///
+/// The prismatic joint slides by `ratio` for each radian the hinge turns, e.g., the radius of the
+/// pinion of a steering rack, or its opposite to reverse the coupling. Like a `Gear`, this joint
+/// applies its forces on the second bodies of the hinge and the prismatic joint only: the pinion
+/// and the rack.
+///
+/// Removing the hinge or the prismatic joint from the world does not remove this joint.
+#[derive(Clone)]
+pub struct RackAndPinion<N: Real> {
+    up_to_date:  bool,
+    breaking:    Option<(N, N)>,
+    collisions:  bool,
+    hinge:       Rc<RefCell<Hinge<N>>>,
+    prismatic:   Rc<RefCell<Prismatic<N>>>,
+    anchor1:     Anchor<N, Isometry<N>>,
+    anchor2:     Anchor<N, Isometry<N>>,
+    ratio:       N,
+    // The last hinge angle, how much the hinge turned since the reference was set, which may
+    // exceed a full turn, and the reference translation of the prismatic joint.
+    angle:       N,
+    turns:       N,
+    translation: N
+}
+
+impl<N: Real> RackAndPinion<N> {
+    /// Creates a joint making `prismatic` slide by `ratio` for each radian `hinge` turns.
+    ///
+    /// The current hinge angle and prismatic translation are the reference ones, which this joint
+    /// keeps in ratio.
+    pub fn new(hinge: Rc<RefCell<Hinge<N>>>, prismatic: Rc<RefCell<Prismatic<N>>>, ratio: N) -> RackAndPinion<N> {
+        let anchor1     = hinge.borrow().anchor2().clone();
+        let anchor2     = prismatic.borrow().anchor2().clone();
+        let angle       = hinge.borrow().angle();
+        let translation = prismatic.borrow().translation();
+
+        RackAndPinion {
+            up_to_date:  false,
+            breaking:    None,
+            collisions:  false,
+            hinge:       hinge,
+            prismatic:   prismatic,
+            anchor1:     anchor1,
+            anchor2:     anchor2,
+            ratio:       ratio,
+            angle:       angle,
+            turns:       na::zero(),
+            translation: translation
+        }
+    }
+
+    /// Tells if this joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the reaction force beyond which this joint breaks.
+    ///
+    /// The force is the one applied on the rack, and the second element of `breaking` is ignored.
+    /// See `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the pinion and the rack.
+    ///
+    /// The collisions are disabled by default, as the teeth are usually not modeled.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint coupling `hinge` and `prismatic` instead.
+    #[doc(hidden)]
+    pub fn clone_with_joints(&self, hinge: Rc<RefCell<Hinge<N>>>, prismatic: Rc<RefCell<Prismatic<N>>>)
+                             -> RackAndPinion<N> {
+        let mut res = self.clone();
+
+        res.anchor1   = hinge.borrow().anchor2().clone();
+        res.anchor2   = prismatic.borrow().anchor2().clone();
+        res.hinge     = hinge;
+        res.prismatic = prismatic;
+
+        res
+    }
+
+    /// The hinge of the pinion.
+    pub fn hinge(&self) -> &Rc<RefCell<Hinge<N>>> {
+        &self.hinge
+    }
+
+    /// The prismatic joint of the rack.
+    pub fn prismatic(&self) -> &Rc<RefCell<Prismatic<N>>> {
+        &self.prismatic
+    }
+
+    /// The translation of the rack for each radian the pinion turns.
+    pub fn ratio(&self) -> N {
+        self.ratio
+    }
+
+    /// Sets the translation of the rack for each radian the pinion turns.
+    ///
+    /// The current hinge angle and prismatic translation become the reference ones.
+    pub fn set_ratio(&mut self, ratio: N) {
+        if ratio != self.ratio {
+            self.up_to_date  = false;
+            self.ratio       = ratio;
+            self.turns       = na::zero();
+            self.translation = self.prismatic.borrow().translation()
+        }
+    }
+
+    /// How much the hinge turned since the reference angle was set.
+    pub fn turns(&self) -> N {
+        self.turns
+    }
+
+    /// The translation of the prismatic joint not matching the rotation of the hinge times the
+    /// ratio.
+    pub fn error(&self) -> N {
+        self.prismatic.borrow().translation() - self.translation - self.turns * self.ratio
+    }
+
+    /// Accumulates the rotation of the hinge since the last call.
+    ///
+    /// The hinge is assumed to turn by less than half a turn between two calls.
+    #[doc(hidden)]
+    pub fn update_turns(&mut self) {
+        let angle = self.hinge.borrow().angle();
+        let mut delta = angle - self.angle;
+
+        if delta > N::pi() {
+            delta = delta - N::two_pi()
+        }
+        else if delta < -N::pi() {
+            delta = delta + N::two_pi()
+        }
+
+        self.turns = self.turns + delta;
+        self.angle = angle;
+
+        // The anchors may have been modified by the user.
+        self.anchor1 = self.hinge.borrow().anchor2().clone();
+        self.anchor2 = self.prismatic.borrow().anchor2().clone();
+    }
+}
+
+impl<N: Real> Joint<N, Isometry<N>> for RackAndPinion<N> {
+    /// The second anchor of the hinge.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor of the prismatic joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Isometry<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Isometry<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the pinion and the rack collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
@@ -0,0 +1,214 @@
+use alga::general::Real;
+use na;
+use math::Point;
+use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
+use detection::joint::joint::Joint;
+
+/// A damped spring between two points.
+///
+/// The spring pulls its anchors together when it is longer than its rest length, and pushes them
+/// apart when it is shorter, with a force proportional to the difference, plus a damping force
+/// proportional to the velocity at which its length changes. It is solved as a soft distance
+/// constraint, so that even stiff springs remain stable, e.g., for suspensions.
+#[derive(Clone)]
+pub struct Spring<N: Real> {
+    up_to_date:  bool,
+    breaking:    Option<(N, N)>,
+    collisions:  bool,
+    anchor1:     Anchor<N, Point<N>>,
+    anchor2:     Anchor<N, Point<N>>,
+    rest_length: N,
+    stiffness:   N,
+    damping:     N,
+    pulls_only:  bool
+}
+
+impl<N: Real> Spring<N> {
+    /// Creates a spring of length `rest_length` at rest.
+    ///
+    /// The `stiffness` is the force applied per unit of elongation, and the `damping` the force
+    /// applied per unit of elongation velocity. They cannot both be zero.
+    pub fn new(anchor1:     Anchor<N, Point<N>>,
+               anchor2:     Anchor<N, Point<N>>,
+               rest_length: N,
+               stiffness:   N,
+               damping:     N)
+               -> Spring<N> {
+        assert!(rest_length >= na::zero(), "The rest length of a spring cannot be negative.");
+        assert!(stiffness >= na::zero() && damping >= na::zero() && stiffness + damping > na::zero(),
+                "The stiffness and damping of a spring must be positive, and not both zero.");
+
+        Spring {
+            up_to_date:  false,
+            breaking:    None,
+            collisions:  false,
+            anchor1:     anchor1,
+            anchor2:     anchor2,
+            rest_length: rest_length,
+            stiffness:   stiffness,
+            damping:     damping,
+            pulls_only:  false
+        }
+    }
+
+    /// Tells if this joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the force beyond which this spring breaks.
+    ///
+    /// A spring applies no torque, so that the second element of `breaking` is ignored. See
+    /// `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> Spring<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The length of this spring at rest.
+    pub fn rest_length(&self) -> N {
+        self.rest_length
+    }
+
+    /// Sets the length of this spring at rest.
+    pub fn set_rest_length(&mut self, rest_length: N) {
+        assert!(rest_length >= na::zero(), "The rest length of a spring cannot be negative.");
+
+        if rest_length != self.rest_length {
+            self.up_to_date  = false;
+            self.rest_length = rest_length
+        }
+    }
+
+    /// The force applied by this spring per unit of elongation.
+    pub fn stiffness(&self) -> N {
+        self.stiffness
+    }
+
+    /// Sets the force applied by this spring per unit of elongation.
+    pub fn set_stiffness(&mut self, stiffness: N) {
+        assert!(stiffness >= na::zero() && stiffness + self.damping > na::zero(),
+                "The stiffness and damping of a spring must be positive, and not both zero.");
+
+        if stiffness != self.stiffness {
+            self.up_to_date = false;
+            self.stiffness  = stiffness
+        }
+    }
+
+    /// The force applied by this spring per unit of elongation velocity.
+    pub fn damping(&self) -> N {
+        self.damping
+    }
+
+    /// Sets the force applied by this spring per unit of elongation velocity.
+    pub fn set_damping(&mut self, damping: N) {
+        assert!(damping >= na::zero() && self.stiffness + damping > na::zero(),
+                "The stiffness and damping of a spring must be positive, and not both zero.");
+
+        if damping != self.damping {
+            self.up_to_date = false;
+            self.damping    = damping
+        }
+    }
+
+    /// Whether this spring only pulls its anchors together.
+    pub fn pulls_only(&self) -> bool {
+        self.pulls_only
+    }
+
+    /// Sets whether this spring only pulls its anchors together.
+    ///
+    /// Such a spring goes slack when it is shorter than its rest length, like a bungee cord.
+    /// Defaults to `false`.
+    pub fn set_pulls_only(&mut self, pulls_only: bool) {
+        if pulls_only != self.pulls_only {
+            self.up_to_date = false;
+            self.pulls_only = pulls_only
+        }
+    }
+
+    /// The current distance between both anchors.
+    pub fn length(&self) -> N {
+        na::distance(&self.anchor1_pos(), &self.anchor2_pos())
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates.
+    pub fn set_local1(&mut self, local1: Point<N>) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates.
+    pub fn set_local2(&mut self, local2: Point<N>) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+}
+
+impl<N: Real> Joint<N, Point<N>> for Spring<N> {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Point<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Point<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Point<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Point<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
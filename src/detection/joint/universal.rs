@@ -0,0 +1,153 @@
+use alga::general::Real;
+use na;
+use math::{Isometry, Vector};
+use detection::joint::anchor::Anchor;
+use object::RigidBodyHandle;
+use detection::joint::joint::Joint;
+
+/// A joint that allows relative rotations around two perpendicular axes between two objects, also
+/// known as a Cardan joint.
+///
+/// Each anchor defines a frame attached to its body, and carries one of the axes. The joint keeps
+/// the origins of both frames at the same position, and the first axis, attached to the first
+/// frame, perpendicular to the second axis, attached to the second frame. The rotation around the
+/// axis orthogonal to both of them is locked, e.g., for drive shafts, or for lamps swinging in
+/// every direction without spinning.
+#[derive(Clone)]
+pub struct Universal<N: Real> {
+    up_to_date: bool,
+    breaking:   Option<(N, N)>,
+    collisions: bool,
+    anchor1:    Anchor<N, Isometry<N>>,
+    anchor2:    Anchor<N, Isometry<N>>,
+    axis1:      Vector<N>,
+    axis2:      Vector<N>
+}
+
+impl<N: Real> Universal<N> {
+    /// Creates a new `Universal` joint rotating around `axis1` and `axis2`.
+    ///
+    /// The axes are expressed in the local coordinates of the first and second anchor frames
+    /// respectively, and normalized. They should be perpendicular when the joint is created.
+    pub fn new(anchor1: Anchor<N, Isometry<N>>,
+               anchor2: Anchor<N, Isometry<N>>,
+               axis1:   Vector<N>,
+               axis2:   Vector<N>)
+               -> Universal<N> {
+        Universal {
+            up_to_date: false,
+            breaking:   None,
+            collisions: false,
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            axis1:      na::normalize(&axis1),
+            axis2:      na::normalize(&axis2)
+        }
+    }
+
+    /// Tells if the joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Sets the reaction force and torque beyond which this joint breaks.
+    ///
+    /// See `Joint::breaking_forces`.
+    pub fn set_breaking_forces(&mut self, breaking: Option<(N, N)>) {
+        self.breaking = breaking
+    }
+
+    /// Enables or disables the collisions between the bodies attached by this joint.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) {
+        self.collisions = enabled
+    }
+
+    /// A copy of this joint attached to `body1` and `body2` instead.
+    #[doc(hidden)]
+    pub fn clone_with_bodies(&self, body1: Option<RigidBodyHandle<N>>, body2: Option<RigidBodyHandle<N>>) -> Universal<N> {
+        let mut res = self.clone();
+
+        res.anchor1.body = body1;
+        res.anchor2.body = body2;
+
+        res
+    }
+
+    /// The first axis, in the local coordinates of the first anchor frame.
+    pub fn axis1(&self) -> &Vector<N> {
+        &self.axis1
+    }
+
+    /// The second axis, in the local coordinates of the second anchor frame.
+    pub fn axis2(&self) -> &Vector<N> {
+        &self.axis2
+    }
+
+    /// The first and second axes in global coordinates.
+    pub fn global_axes(&self) -> (Vector<N>, Vector<N>) {
+        (self.anchor1_pos().rotation * self.axis1, self.anchor2_pos().rotation * self.axis2)
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates.
+    pub fn set_local1(&mut self, local1: Isometry<N>) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates.
+    pub fn set_local2(&mut self, local2: Isometry<N>) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+}
+
+impl<N: Real> Joint<N, Isometry<N>> for Universal<N> {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<N, Isometry<N>> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Isometry<N> {
+        self.anchor1.global_position()
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Isometry<N> {
+        self.anchor2.global_position()
+    }
+
+    /// The reaction force and torque beyond which this joint breaks.
+    #[inline]
+    fn breaking_forces(&self) -> Option<(N, N)> {
+        self.breaking
+    }
+
+    /// Whether the bodies attached by this joint collide with each other.
+    #[inline]
+    fn collisions_enabled(&self) -> bool {
+        self.collisions
+    }
+}
@@ -1,7 +1,33 @@
 //! Collision detection and joints.
 
 pub use detection::detector::Detector;
-pub use detection::activation_manager::ActivationManager;
+pub use detection::activation_manager::{ActivationManager, IslandStatistics, IslandBridge};
+pub use detection::contact_jitter::ContactJitter;
+pub use detection::contact_normal_smoothing::ContactNormalSmoothing;
+pub use detection::sweep_and_prune_broad_phase::SweepAndPruneBroadPhase;
+pub use detection::spatial_hash_broad_phase::SpatialHashBroadPhase;
+pub use detection::pipeline_statistics::PipelineStatistics;
+pub use detection::aabb_growth_monitor::{AabbGrowthMonitor, AabbGrowthHandler, AabbGrowthEvent};
+pub use detection::trigger_volumes::{TriggerVolumeId, TriggerHandler};
+pub use detection::contact_modifier::ContactModifier;
+pub use detection::contact_manifold_reduction::reduce_contact_manifold;
+pub use detection::collision_events::{CollisionEvent, StampedCollisionEvent};
+pub use detection::cutters::{Cut, CutHandler};
+#[doc(hidden)]
+pub use detection::trigger_volumes::{TriggerVolumes, TriggerVolumeCollector};
+#[doc(hidden)]
+pub use detection::collision_events::{CollisionEventQueue, CollisionEventCollector};
+#[doc(hidden)]
+pub use detection::one_way_contact_filter::OneWayContactFilter;
+#[doc(hidden)]
+pub use detection::cutters::Cutters;
+#[doc(hidden)]
+pub use detection::contact_cooldown::ContactCooldown;
+#[doc(hidden)]
+pub use detection::pipeline_statistics::CountingNarrowPhase;
+#[doc(hidden)]
+pub use detection::extensible_dispatchers::{ExtensibleContactDispatcher, ExtensibleProximityDispatcher,
+                                            Dispatchers, ContactDispatchers, ProximityDispatchers};
 
 pub mod constraint;
 
@@ -13,16 +39,57 @@ pub mod joint {
     pub use detection::joint::anchor::Anchor;
     pub use detection::joint::joint::Joint;
     pub use detection::joint::ball_in_socket::BallInSocket;
+    #[cfg(feature = "dim3")]
+    pub use detection::joint::ball_in_socket::BallInSocketLimits;
     pub use detection::joint::fixed::Fixed;
-    pub use detection::joint::joint_manager::JointManager;
+    pub use detection::joint::hinge::Hinge;
+    pub use detection::joint::prismatic::Prismatic;
+    pub use detection::joint::spring::Spring;
+    pub use detection::joint::distance::Distance;
+    pub use detection::joint::angular_motor::AngularMotor;
+    pub use detection::joint::pulley::Pulley;
+    pub use detection::joint::gear::Gear;
+    pub use detection::joint::rack_and_pinion::RackAndPinion;
+    pub use detection::joint::joint_motor::JointMotor;
+    #[cfg(feature = "dim3")]
+    pub use detection::joint::universal::Universal;
+    pub use detection::joint::joint_manager::{JointManager, Joints};
+    pub use detection::joint::broken_joint::{BrokenJoint, JointBreakHandler};
 
     mod joint_manager;
+    mod broken_joint;
     mod anchor;
     mod ball_in_socket;
     mod fixed;
+    mod hinge;
+    mod prismatic;
+    mod spring;
+    mod distance;
+    mod angular_motor;
+    mod pulley;
+    mod gear;
+    mod rack_and_pinion;
+    mod joint_motor;
+    #[cfg(feature = "dim3")]
+    mod universal;
     // XXX: `pub` due to rust#18241
     #[allow(missing_docs)]
     pub mod joint;
 }
 
 mod activation_manager;
+mod contact_jitter;
+mod contact_normal_smoothing;
+mod sweep_and_prune_broad_phase;
+mod spatial_hash_broad_phase;
+mod pipeline_statistics;
+mod aabb_growth_monitor;
+mod one_way_contact_filter;
+mod contact_cooldown;
+mod trigger_volumes;
+mod extensible_dispatchers;
+mod contact_modifier;
+mod contact_manifold_reduction;
+mod broad_phase_pairs;
+mod collision_events;
+mod cutters;
@@ -0,0 +1,110 @@
+#![doc(hidden)]
+
+use std::mem;
+use std::collections::HashMap;
+
+use alga::general::Real;
+use na;
+use ncollide::query::Contact;
+use ncollide::utils::data::hash_map::HashMap as UidMap;
+use ncollide::utils::data::hash::UintTWHash;
+use object::{RigidBody, RigidBodyHandle, WorldObject};
+use math::{Point, Vector};
+use utils::{DeterministicState, GeneralizedCross};
+
+/// Discards the contacts with one-way rigid bodies that are not approached from the front.
+///
+/// The decision is taken for each pair of bodies when they start touching, and kept until they
+/// stop touching. Thus, a body passing through a one-way platform from below is not pushed back
+/// up when it starts falling again before it is completely through.
+pub struct OneWayContactFilter {
+    // Whether the contacts between two bodies were accepted, for the last and current updates.
+    prev: HashMap<(usize, usize), bool, DeterministicState>,
+    next: HashMap<(usize, usize), bool, DeterministicState>
+}
+
+impl OneWayContactFilter {
+    /// Creates a new filter.
+    pub fn new() -> OneWayContactFilter {
+        OneWayContactFilter {
+            prev: HashMap::with_hasher(DeterministicState::new()),
+            next: HashMap::with_hasher(DeterministicState::new())
+        }
+    }
+
+    /// Whether the contact `c` between the bodies `rb1` and `rb2`, identified by `uid1` and
+    /// `uid2`, should be solved.
+    pub fn accept<N: Real>(&mut self,
+                           uid1: usize, rb1: &RigidBody<N>,
+                           uid2: usize, rb2: &RigidBody<N>,
+                           c:    &Contact<Point<N>>)
+                           -> bool {
+        if rb1.one_way_normal().is_none() && rb2.one_way_normal().is_none() {
+            return true;
+        }
+
+        let key = (uid1, uid2);
+
+        if let Some(accepted) = self.next.get(&key) {
+            return *accepted;
+        }
+
+        let accepted = match self.prev.get(&key) {
+            Some(accepted) => *accepted,
+            None => {
+                let point = na::center(&c.world1, &c.world2);
+
+                approaches_front(rb1, rb2, &c.normal, &point) && approaches_front(rb2, rb1, &-c.normal, &point)
+            }
+        };
+
+        let _ = self.next.insert(key, accepted);
+
+        accepted
+    }
+
+    /// A copy of this filter for the copies `bodies` of the rigid bodies, indexed by the
+    /// identifiers of the originals.
+    pub fn clone_for_prediction<N: Real>(&self, bodies: &UidMap<usize, RigidBodyHandle<N>, UintTWHash>)
+                                         -> OneWayContactFilter {
+        let mut res = OneWayContactFilter::new();
+
+        for (&(uid1, uid2), accepted) in self.prev.iter() {
+            if let (Some(rb1), Some(rb2)) = (bodies.find(&uid1), bodies.find(&uid2)) {
+                let key = (WorldObject::rigid_body_uid(rb1), WorldObject::rigid_body_uid(rb2));
+                let _   = res.prev.insert(key, *accepted);
+            }
+        }
+
+        res
+    }
+
+    /// Forgets the pairs of bodies that did not touch since the last call to this method.
+    pub fn end_frame(&mut self) {
+        mem::swap(&mut self.prev, &mut self.next);
+        self.next.clear();
+    }
+}
+
+// Whether `other` approaches the front of `platform` at `point`, `normal` pointing toward `other`.
+fn approaches_front<N: Real>(platform: &RigidBody<N>, other: &RigidBody<N>, normal: &Vector<N>, point: &Point<N>) -> bool {
+    match platform.one_way_normal() {
+        Some(local_normal) => {
+            let front = platform.position().rotation * local_normal;
+
+            if na::dot(normal, &front) <= na::zero() {
+                return false;
+            }
+
+            point_normal_velocity(other, point, &front) <= point_normal_velocity(platform, point, &front)
+        },
+        None => true
+    }
+}
+
+// The velocity of the point of `rb` located at `point`, projected on `dir`.
+fn point_normal_velocity<N: Real>(rb: &RigidBody<N>, point: &Point<N>, dir: &Vector<N>) -> N {
+    let rot_axis = (*point - *rb.center_of_mass()).gcross(dir);
+
+    na::dot(&rb.lin_vel(), dir) + na::dot(&rb.ang_vel(), &rot_axis)
+}
@@ -0,0 +1,119 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use alga::general::Real;
+use ncollide::utils::data::uid_remap::{UidRemap, FastKey};
+use ncollide::narrow_phase::{NarrowPhase, ContactSignal, ProximitySignal, ContactPairs, ProximityPairs};
+use ncollide::world::CollisionObject;
+use object::WorldObject;
+use math::{Point, Isometry};
+
+/// Counters gathered by the collision detection pipeline.
+///
+/// Those are meant to help tuning the broad phase margins and the activation thresholds: a large
+/// number of pairs created and destroyed at each step usually means the margins are too small,
+/// while a large number of narrow phase updates without contacts means they are too large or that
+/// too many bodies are kept awake.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+    /// The number of pairs of objects with intersecting bounding boxes.
+    pub active_pairs:         usize,
+    /// The number of pairs that appeared since the previous step.
+    pub created_pairs:        usize,
+    /// The number of pairs that disappeared since the previous step.
+    pub destroyed_pairs:      usize,
+    /// The number of pairs updated by the narrow phase, i.e., involving at least one active body.
+    pub narrow_phase_updates: usize,
+    /// The number of pairs with at least one contact.
+    pub contact_pairs:        usize,
+    /// The number of contacts generated by the narrow phase.
+    pub contacts:             usize
+}
+
+impl PipelineStatistics {
+    /// Creates statistics with all counters set to zero.
+    pub fn new() -> PipelineStatistics {
+        PipelineStatistics::default()
+    }
+}
+
+/// A narrow phase counting the pairs it handles before forwarding them to another narrow phase.
+#[doc(hidden)]
+pub struct CountingNarrowPhase<N: Real> {
+    narrow_phase: Box<NarrowPhase<Point<N>, Isometry<N>, WorldObject<N>>>,
+    counters:     Rc<RefCell<PipelineStatistics>>
+}
+
+impl<N: Real> CountingNarrowPhase<N> {
+    /// Wraps `narrow_phase` so that its activity is accumulated into `counters`.
+    pub fn new(narrow_phase: Box<NarrowPhase<Point<N>, Isometry<N>, WorldObject<N>>>,
+               counters:     Rc<RefCell<PipelineStatistics>>)
+               -> CountingNarrowPhase<N> {
+        CountingNarrowPhase {
+            narrow_phase: narrow_phase,
+            counters:     counters
+        }
+    }
+}
+
+fn is_moving<N: Real>(object: &WorldObject<N>) -> bool {
+    match *object {
+        WorldObject::RigidBody(ref rb) => rb.borrow().is_active(),
+        WorldObject::Sensor(ref s)     => s.borrow().parent().map_or(false, |rb| rb.borrow().is_active())
+    }
+}
+
+impl<N: Real> NarrowPhase<Point<N>, Isometry<N>, WorldObject<N>> for CountingNarrowPhase<N> {
+    fn update(&mut self,
+              objects:          &UidRemap<CollisionObject<Point<N>, Isometry<N>, WorldObject<N>>>,
+              contact_signal:   &mut ContactSignal<Point<N>, Isometry<N>, WorldObject<N>>,
+              proximity_signal: &mut ProximitySignal<Point<N>, Isometry<N>, WorldObject<N>>,
+              timestamp:        usize) {
+        self.narrow_phase.update(objects, contact_signal, proximity_signal, timestamp);
+
+        let mut counters = self.counters.borrow_mut();
+
+        for (co1, co2, _) in self.narrow_phase.contact_pairs(objects) {
+            if is_moving(&co1.data) || is_moving(&co2.data) {
+                counters.narrow_phase_updates = counters.narrow_phase_updates + 1;
+            }
+        }
+
+        for (co1, co2, _) in self.narrow_phase.proximity_pairs(objects) {
+            if is_moving(&co1.data) || is_moving(&co2.data) {
+                counters.narrow_phase_updates = counters.narrow_phase_updates + 1;
+            }
+        }
+    }
+
+    fn handle_interaction(&mut self,
+                          contact_signal:   &mut ContactSignal<Point<N>, Isometry<N>, WorldObject<N>>,
+                          proximity_signal: &mut ProximitySignal<Point<N>, Isometry<N>, WorldObject<N>>,
+                          objects:          &UidRemap<CollisionObject<Point<N>, Isometry<N>, WorldObject<N>>>,
+                          fk1:              &FastKey,
+                          fk2:              &FastKey,
+                          started:          bool) {
+        {
+            let mut counters = self.counters.borrow_mut();
+
+            if started {
+                counters.created_pairs = counters.created_pairs + 1;
+            }
+            else {
+                counters.destroyed_pairs = counters.destroyed_pairs + 1;
+            }
+        }
+
+        self.narrow_phase.handle_interaction(contact_signal, proximity_signal, objects, fk1, fk2, started)
+    }
+
+    fn contact_pairs<'a>(&'a self, objects: &'a UidRemap<CollisionObject<Point<N>, Isometry<N>, WorldObject<N>>>)
+                         -> ContactPairs<'a, Point<N>, Isometry<N>, WorldObject<N>> {
+        self.narrow_phase.contact_pairs(objects)
+    }
+
+    fn proximity_pairs<'a>(&'a self, objects: &'a UidRemap<CollisionObject<Point<N>, Isometry<N>, WorldObject<N>>>)
+                           -> ProximityPairs<'a, Point<N>, Isometry<N>, WorldObject<N>> {
+        self.narrow_phase.proximity_pairs(objects)
+    }
+}
@@ -0,0 +1,392 @@
+use std::collections::HashMap as StdHashMap;
+
+use alga::general::{Real, Id};
+use na;
+use ncollide::bounding_volume::{BoundingVolume, AABB};
+use ncollide::broad_phase::BroadPhase;
+use ncollide::query::{Ray, RayCast, PointQuery};
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use math::{Point, Vector};
+use utils::DeterministicState;
+use detection::broad_phase_pairs;
+
+type Cell = [i64; 3];
+
+struct GridProxy<N: Real, T> {
+    aabb:    AABB<Point<N>>, // Loosened by the margin.
+    data:    T,
+    removed: bool,
+    // The range of cells covered by `aabb`, or `None` if it covers too many cells.
+    cells:   Option<(Cell, Cell)>
+}
+
+/// A broad phase based on a uniform grid, hashed on its cells.
+///
+/// Each object is registered into every cell its bounding box intersects. This is efficient for
+/// scenes with many objects of similar size, e.g., particles or rubble, when the cell size is a
+/// bit larger than the typical object. Objects covering more than a given number of cells, e.g.,
+/// the ground, are tested against every other object instead.
+///
+/// Like `SweepAndPruneBroadPhase`, this cannot be plugged into `World`, which is bound to the
+/// `DBVTBroadPhase` its ncollide 0.13 collision world creates.
+pub struct SpatialHashBroadPhase<N: Real, T> {
+    proxies:   Vec<Option<GridProxy<N, T>>>,
+    free:      Vec<usize>,
+    slots:     HashMap<usize, usize, UintTWHash>, // uid -> index of the proxy.
+    grid:      StdHashMap<Cell, Vec<usize>, DeterministicState>,
+    oversized: Vec<usize>,
+    cell_size: N,
+    max_cells: usize,
+    margin:    N,
+    // Sorted pairs of proxy indices with intersecting bounding boxes.
+    pairs:     Vec<(usize, usize)>,
+    new_pairs: Vec<(usize, usize)>,
+    dirty:     Vec<usize>,
+    to_add:    Vec<(usize, AABB<Point<N>>, T)>,
+    to_remove: Vec<usize>
+}
+
+impl<N: Real, T> SpatialHashBroadPhase<N, T> {
+    /// Creates a new spatial hash broad phase with cubic cells of width `cell_size`.
+    ///
+    /// The bounding boxes are enlarged by `margin` so that they do not need to be updated each
+    /// time an object moves slightly. Objects covering more than `max_cells` cells are not
+    /// registered into the grid.
+    pub fn new(cell_size: N, margin: N, max_cells: usize) -> SpatialHashBroadPhase<N, T> {
+        assert!(cell_size > na::zero(), "The cell size must be strictly positive.");
+
+        SpatialHashBroadPhase {
+            proxies:   Vec::new(),
+            free:      Vec::new(),
+            slots:     HashMap::new(UintTWHash::new()),
+            grid:      StdHashMap::with_hasher(DeterministicState::new()),
+            oversized: Vec::new(),
+            cell_size: cell_size,
+            max_cells: max_cells,
+            margin:    margin,
+            pairs:     Vec::new(),
+            new_pairs: Vec::new(),
+            dirty:     Vec::new(),
+            to_add:    Vec::new(),
+            to_remove: Vec::new()
+        }
+    }
+
+    /// The width of the cells of the grid.
+    #[inline]
+    pub fn cell_size(&self) -> N {
+        self.cell_size
+    }
+
+    /// Number of interferences detected by this broad phase.
+    #[inline]
+    pub fn num_interferences(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Number of non-empty cells of the grid.
+    #[inline]
+    pub fn num_cells(&self) -> usize {
+        self.grid.len()
+    }
+
+    // The range of cells covered by `aabb`, if it covers at most `self.max_cells` cells.
+    fn cell_range(&self, aabb: &AABB<Point<N>>) -> Option<(Cell, Cell)> {
+        let mut mins = [0i64; 3];
+        let mut maxs = [0i64; 3];
+        let mut num  = 1.0f64;
+
+        for i in 0 .. na::dimension::<Vector<N>>() {
+            let min: f64 = na::try_convert((aabb.mins()[i] / self.cell_size).floor()).unwrap_or(-1.0e30);
+            let max: f64 = na::try_convert((aabb.maxs()[i] / self.cell_size).floor()).unwrap_or(1.0e30);
+
+            num = num * (max - min + 1.0);
+
+            // Also rejects NaNs and infinite bounding boxes.
+            if !(num <= self.max_cells as f64) {
+                return None;
+            }
+
+            mins[i] = min as i64;
+            maxs[i] = max as i64;
+        }
+
+        Some((mins, maxs))
+    }
+
+    fn register(&mut self, i: usize, range: Option<(Cell, Cell)>) {
+        match range {
+            Some((mins, maxs)) => {
+                for x in mins[0] .. maxs[0] + 1 {
+                    for y in mins[1] .. maxs[1] + 1 {
+                        for z in mins[2] .. maxs[2] + 1 {
+                            self.grid.entry([x, y, z]).or_insert_with(Vec::new).push(i);
+                        }
+                    }
+                }
+            },
+            None => self.oversized.push(i)
+        }
+    }
+
+    fn unregister(&mut self, i: usize, range: Option<(Cell, Cell)>) {
+        match range {
+            Some((mins, maxs)) => {
+                for x in mins[0] .. maxs[0] + 1 {
+                    for y in mins[1] .. maxs[1] + 1 {
+                        for z in mins[2] .. maxs[2] + 1 {
+                            let empty = match self.grid.get_mut(&[x, y, z]) {
+                                Some(cell) => {
+                                    cell.retain(|j| *j != i);
+                                    cell.is_empty()
+                                },
+                                None => false
+                            };
+
+                            if empty {
+                                let _ = self.grid.remove(&[x, y, z]);
+                            }
+                        }
+                    }
+                }
+            },
+            None => self.oversized.retain(|j| *j != i)
+        }
+    }
+
+    // Collects the indices of the proxies which bounding box may intersect `aabb`.
+    fn candidates(&self, aabb: &AABB<Point<N>>, out: &mut Vec<usize>) {
+        match self.cell_range(aabb) {
+            Some((mins, maxs)) => {
+                for x in mins[0] .. maxs[0] + 1 {
+                    for y in mins[1] .. maxs[1] + 1 {
+                        for z in mins[2] .. maxs[2] + 1 {
+                            if let Some(cell) = self.grid.get(&[x, y, z]) {
+                                out.extend(cell.iter().cloned())
+                            }
+                        }
+                    }
+                }
+
+                out.extend(self.oversized.iter().cloned());
+                out.sort();
+                out.dedup();
+            },
+            None => {
+                out.extend((0 .. self.proxies.len()).filter(|i| self.proxies[*i].is_some()))
+            }
+        }
+    }
+}
+
+impl<N: Real, T> BroadPhase<Point<N>, AABB<Point<N>>, T> for SpatialHashBroadPhase<N, T> {
+    fn deferred_add(&mut self, uid: usize, bv: AABB<Point<N>>, data: T) {
+        self.to_add.push((uid, bv, data))
+    }
+
+    fn deferred_remove(&mut self, uid: usize) {
+        if let Some(i) = self.slots.find(&uid) {
+            if let Some(ref mut proxy) = self.proxies[*i] {
+                proxy.removed = true;
+            }
+
+            self.to_remove.push(uid);
+        }
+    }
+
+    fn deferred_set_bounding_volume(&mut self, uid: usize, bv: AABB<Point<N>>) {
+        if let Some(i) = self.slots.find(&uid) {
+            if let Some(ref mut proxy) = self.proxies[*i] {
+                if !proxy.aabb.contains(&bv) {
+                    proxy.aabb = bv.loosened(self.margin);
+                    self.dirty.push(*i);
+                }
+            }
+        }
+    }
+
+    fn deferred_recompute_all_proximities(&mut self) {
+        // Every pair is recomputed at each update anyway.
+    }
+
+    fn update(&mut self, allow_proximity: &mut FnMut(&T, &T) -> bool, handler: &mut FnMut(&T, &T, bool)) {
+        /*
+         * Perform additions.
+         */
+        let to_add: Vec<_> = self.to_add.drain(..).collect();
+
+        for (uid, bv, data) in to_add.into_iter() {
+            let aabb  = bv.loosened(self.margin);
+            let range = self.cell_range(&aabb);
+            let proxy = GridProxy {
+                aabb:    aabb,
+                data:    data,
+                removed: false,
+                cells:   range
+            };
+
+            let i = match self.free.pop() {
+                Some(i) => { self.proxies[i] = Some(proxy); i },
+                None    => { self.proxies.push(Some(proxy)); self.proxies.len() - 1 }
+            };
+
+            let _ = self.slots.insert(uid, i);
+            self.register(i, range);
+        }
+
+        /*
+         * Move the proxies which bounding box changed to their new cells.
+         */
+        let mut dirty = Vec::new();
+        dirty.append(&mut self.dirty);
+        dirty.sort();
+        dirty.dedup();
+
+        for i in dirty.iter() {
+            let (old, new) = match self.proxies[*i] {
+                Some(ref proxy) => (proxy.cells, self.cell_range(&proxy.aabb)),
+                None            => continue
+            };
+
+            if old != new {
+                self.unregister(*i, old);
+                self.register(*i, new);
+                self.proxies[*i].as_mut().unwrap().cells = new;
+            }
+        }
+
+        self.dirty = dirty;
+        self.dirty.clear();
+
+        /*
+         * Find the pairs.
+         */
+        {
+            let proxies   = &self.proxies;
+            let new_pairs = &mut self.new_pairs;
+            let mut test  = |i: usize, j: usize| {
+                let proxy1 = proxies[i].as_ref().unwrap();
+                let proxy2 = proxies[j].as_ref().unwrap();
+
+                if !proxy1.removed && !proxy2.removed && proxy1.aabb.intersects(&proxy2.aabb) &&
+                   allow_proximity(&proxy1.data, &proxy2.data) {
+                    new_pairs.push(if i < j { (i, j) } else { (j, i) })
+                }
+            };
+
+            for (cell, objects) in self.grid.iter() {
+                for (k, i) in objects.iter().enumerate() {
+                    let range1 = proxies[*i].as_ref().unwrap().cells.unwrap();
+
+                    for j in objects[k + 1 ..].iter() {
+                        let range2 = proxies[*j].as_ref().unwrap().cells.unwrap();
+
+                        // Only test each pair once: in the first cell covered by both objects.
+                        let first = [ range1.0[0].max(range2.0[0]),
+                                      range1.0[1].max(range2.0[1]),
+                                      range1.0[2].max(range2.0[2]) ];
+
+                        if first == *cell {
+                            test(*i, *j)
+                        }
+                    }
+                }
+            }
+
+            for (k, i) in self.oversized.iter().enumerate() {
+                for j in 0 .. proxies.len() {
+                    let is_other = match proxies[j] {
+                        // Oversized objects are tested against each other only once.
+                        Some(ref proxy) => j != *i && (proxy.cells.is_some() || !self.oversized[.. k].contains(&j)),
+                        None            => false
+                    };
+
+                    if is_other {
+                        test(*i, j)
+                    }
+                }
+            }
+        }
+
+        self.new_pairs.sort();
+        self.new_pairs.dedup();
+
+        /*
+         * Report the pairs that appeared or disappeared.
+         */
+        {
+            let proxies = &self.proxies;
+            let data    = |i: usize| &proxies[i].as_ref().unwrap().data;
+
+            broad_phase_pairs::report_pair_changes(&self.pairs, &self.new_pairs, data, handler);
+        }
+
+        self.pairs.clear();
+        self.pairs.append(&mut self.new_pairs);
+
+        /*
+         * Perform removals.
+         */
+        let to_remove: Vec<_> = self.to_remove.drain(..).collect();
+
+        for uid in to_remove.into_iter() {
+            // The uid may have been re-added since its removal.
+            let removed = match self.slots.find(&uid) {
+                Some(i) => self.proxies[*i].as_ref().map_or(false, |p| p.removed),
+                None    => false
+            };
+
+            if removed {
+                let _ = self.slots.remove(&uid);
+            }
+        }
+
+        for i in 0 .. self.proxies.len() {
+            let cells = match self.proxies[i] {
+                Some(ref proxy) if proxy.removed => proxy.cells,
+                _                                => continue
+            };
+
+            self.unregister(i, cells);
+            self.proxies[i] = None;
+            self.free.push(i);
+        }
+    }
+
+    fn interferences_with_bounding_volume<'a>(&'a self, bv: &AABB<Point<N>>, out: &mut Vec<&'a T>) {
+        let mut candidates = Vec::new();
+        self.candidates(bv, &mut candidates);
+
+        for i in candidates.into_iter() {
+            let proxy = self.proxies[i].as_ref().unwrap();
+
+            if !proxy.removed && proxy.aabb.intersects(bv) {
+                out.push(&proxy.data)
+            }
+        }
+    }
+
+    fn interferences_with_ray<'a>(&'a self, ray: &Ray<Point<N>>, out: &mut Vec<&'a T>) {
+        for proxy in self.proxies.iter() {
+            if let Some(ref proxy) = *proxy {
+                if !proxy.removed && proxy.aabb.intersects_ray(&Id::new(), ray) {
+                    out.push(&proxy.data)
+                }
+            }
+        }
+    }
+
+    fn interferences_with_point<'a>(&'a self, point: &Point<N>, out: &mut Vec<&'a T>) {
+        let mut candidates = Vec::new();
+        self.candidates(&AABB::new(*point, *point), &mut candidates);
+
+        for i in candidates.into_iter() {
+            let proxy = self.proxies[i].as_ref().unwrap();
+
+            if !proxy.removed && proxy.aabb.contains_point(&Id::new(), point) {
+                out.push(&proxy.data)
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use alga::general::{Real, Id};
+use na;
+use ncollide::bounding_volume::{BoundingVolume, AABB};
+use ncollide::broad_phase::BroadPhase;
+use ncollide::query::{Ray, RayCast, PointQuery};
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use math::{Point, Vector};
+use detection::broad_phase_pairs;
+
+struct SAPProxy<N: Real, T> {
+    aabb:    AABB<Point<N>>, // Loosened by the margin.
+    data:    T,
+    removed: bool
+}
+
+/// A broad phase based on a single-axis sweep and prune.
+///
+/// The bounding boxes are kept sorted along the axis on which their centers are the most spread
+/// out. Because the simulated bodies move little between two updates, re-sorting them is close to
+/// linear. This is efficient on scenes where many objects move coherently, e.g., along a track,
+/// where a bounding volume tree would have to be updated constantly.
+///
+/// This implements the `BroadPhase` trait of ncollide, but `World` cannot use it: the collision
+/// world of ncollide 0.13 always creates its own `DBVTBroadPhase` and cannot be given another
+/// broad phase.
+pub struct SweepAndPruneBroadPhase<N: Real, T> {
+    proxies:   Vec<Option<SAPProxy<N, T>>>,
+    free:      Vec<usize>,
+    slots:     HashMap<usize, usize, UintTWHash>, // uid -> index of the proxy.
+    sorted:    Vec<usize>,
+    axis:      usize,
+    margin:    N,
+    // Sorted pairs of proxy indices with intersecting bounding boxes.
+    pairs:     Vec<(usize, usize)>,
+    new_pairs: Vec<(usize, usize)>,
+    to_add:    Vec<(usize, AABB<Point<N>>, T)>,
+    to_remove: Vec<usize>
+}
+
+impl<N: Real, T> SweepAndPruneBroadPhase<N, T> {
+    /// Creates a new sweep and prune broad phase.
+    ///
+    /// The bounding boxes are enlarged by `margin` so that they do not need to be updated each
+    /// time an object moves slightly.
+    pub fn new(margin: N) -> SweepAndPruneBroadPhase<N, T> {
+        SweepAndPruneBroadPhase {
+            proxies:   Vec::new(),
+            free:      Vec::new(),
+            slots:     HashMap::new(UintTWHash::new()),
+            sorted:    Vec::new(),
+            axis:      0,
+            margin:    margin,
+            pairs:     Vec::new(),
+            new_pairs: Vec::new(),
+            to_add:    Vec::new(),
+            to_remove: Vec::new()
+        }
+    }
+
+    /// Number of interferences detected by this broad phase.
+    #[inline]
+    pub fn num_interferences(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// The axis the bounding boxes are currently sorted along.
+    #[inline]
+    pub fn sweep_axis(&self) -> usize {
+        self.axis
+    }
+
+    // The axis along which the centers of the bounding boxes have the largest variance.
+    fn best_axis(&self) -> usize {
+        let mut sum:    Vector<N> = na::zero();
+        let mut sq_sum: Vector<N> = na::zero();
+
+        for i in self.sorted.iter() {
+            let aabb   = &self.proxy(*i).aabb;
+            let center = aabb.center().coords;
+
+            sum    = sum + center;
+            sq_sum = sq_sum + center.component_mul(&center);
+        }
+
+        let n: N       = na::convert(self.sorted.len().max(1) as f64);
+        let mut best   = 0;
+        let mut best_v = -N::one();
+
+        for i in 0 .. na::dimension::<Vector<N>>() {
+            let mean     = sum[i] / n;
+            let variance = sq_sum[i] / n - mean * mean;
+
+            if variance > best_v {
+                best   = i;
+                best_v = variance;
+            }
+        }
+
+        best
+    }
+
+    #[inline]
+    fn proxy(&self, i: usize) -> &SAPProxy<N, T> {
+        self.proxies[i].as_ref().expect("Invalid sweep and prune proxy.")
+    }
+
+    fn sort(&mut self) {
+        let axis    = self.axis;
+        let proxies = &self.proxies;
+        let min     = |i: usize| proxies[i].as_ref().unwrap().aabb.mins()[axis];
+
+        // Insertion sort, close to linear since the order changes little between two updates.
+        for i in 1 .. self.sorted.len() {
+            let curr = self.sorted[i];
+            let key  = min(curr);
+            let mut j = i;
+
+            while j > 0 && min(self.sorted[j - 1]) > key {
+                self.sorted[j] = self.sorted[j - 1];
+                j = j - 1;
+            }
+
+            self.sorted[j] = curr;
+        }
+    }
+}
+
+impl<N: Real, T> BroadPhase<Point<N>, AABB<Point<N>>, T> for SweepAndPruneBroadPhase<N, T> {
+    fn deferred_add(&mut self, uid: usize, bv: AABB<Point<N>>, data: T) {
+        self.to_add.push((uid, bv, data))
+    }
+
+    fn deferred_remove(&mut self, uid: usize) {
+        if let Some(i) = self.slots.find(&uid) {
+            if let Some(ref mut proxy) = self.proxies[*i] {
+                proxy.removed = true;
+            }
+
+            self.to_remove.push(uid);
+        }
+    }
+
+    fn deferred_set_bounding_volume(&mut self, uid: usize, bv: AABB<Point<N>>) {
+        if let Some(i) = self.slots.find(&uid) {
+            if let Some(ref mut proxy) = self.proxies[*i] {
+                if !proxy.aabb.contains(&bv) {
+                    proxy.aabb = bv.loosened(self.margin);
+                }
+            }
+        }
+    }
+
+    fn deferred_recompute_all_proximities(&mut self) {
+        // Every pair is recomputed at each update anyway.
+    }
+
+    fn update(&mut self, allow_proximity: &mut FnMut(&T, &T) -> bool, handler: &mut FnMut(&T, &T, bool)) {
+        /*
+         * Perform additions.
+         */
+        for (uid, bv, data) in self.to_add.drain(..) {
+            let proxy = SAPProxy {
+                aabb:    bv.loosened(self.margin),
+                data:    data,
+                removed: false
+            };
+
+            let i = match self.free.pop() {
+                Some(i) => { self.proxies[i] = Some(proxy); i },
+                None    => { self.proxies.push(Some(proxy)); self.proxies.len() - 1 }
+            };
+
+            let _ = self.slots.insert(uid, i);
+            self.sorted.push(i);
+        }
+
+        /*
+         * Sort the bounding boxes.
+         */
+        let axis = self.best_axis();
+
+        if axis != self.axis {
+            self.axis = axis;
+
+            let proxies = &self.proxies;
+            self.sorted.sort_by(|a, b| {
+                let a = proxies[*a].as_ref().unwrap().aabb.mins()[axis];
+                let b = proxies[*b].as_ref().unwrap().aabb.mins()[axis];
+
+                a.partial_cmp(&b).unwrap()
+            });
+        }
+        else {
+            self.sort();
+        }
+
+        /*
+         * Sweep.
+         */
+        {
+            let axis      = self.axis;
+            let proxies   = &self.proxies;
+            let new_pairs = &mut self.new_pairs;
+
+            for (k, i) in self.sorted.iter().enumerate() {
+                let proxy1 = proxies[*i].as_ref().unwrap();
+
+                if proxy1.removed {
+                    continue;
+                }
+
+                let max = proxy1.aabb.maxs()[axis];
+
+                for j in self.sorted[k + 1 ..].iter() {
+                    let proxy2 = proxies[*j].as_ref().unwrap();
+
+                    if proxy2.aabb.mins()[axis] > max {
+                        break;
+                    }
+
+                    if !proxy2.removed && proxy1.aabb.intersects(&proxy2.aabb) &&
+                       allow_proximity(&proxy1.data, &proxy2.data) {
+                        new_pairs.push(if *i < *j { (*i, *j) } else { (*j, *i) })
+                    }
+                }
+            }
+        }
+
+        self.new_pairs.sort();
+
+        /*
+         * Report the pairs that appeared or disappeared.
+         */
+        {
+            let proxies = &self.proxies;
+            let data    = |i: usize| &proxies[i].as_ref().unwrap().data;
+
+            broad_phase_pairs::report_pair_changes(&self.pairs, &self.new_pairs, data, handler);
+        }
+
+        self.pairs.clear();
+        self.pairs.append(&mut self.new_pairs);
+
+        /*
+         * Perform removals.
+         */
+        for uid in self.to_remove.drain(..) {
+            // The uid may have been re-added since its removal.
+            let removed = match self.slots.find(&uid) {
+                Some(i) => self.proxies[*i].as_ref().map_or(false, |p| p.removed),
+                None    => false
+            };
+
+            if removed {
+                let _ = self.slots.remove(&uid);
+            }
+        }
+
+        for i in self.sorted.iter() {
+            if self.proxies[*i].as_ref().map_or(false, |p| p.removed) {
+                self.proxies[*i] = None;
+                self.free.push(*i);
+            }
+        }
+
+        let proxies = &self.proxies;
+        self.sorted.retain(|i| proxies[*i].is_some());
+    }
+
+    fn interferences_with_bounding_volume<'a>(&'a self, bv: &AABB<Point<N>>, out: &mut Vec<&'a T>) {
+        let max = bv.maxs()[self.axis];
+
+        for i in self.sorted.iter() {
+            let proxy = self.proxy(*i);
+
+            if proxy.aabb.mins()[self.axis] > max {
+                break;
+            }
+
+            if !proxy.removed && proxy.aabb.intersects(bv) {
+                out.push(&proxy.data)
+            }
+        }
+    }
+
+    fn interferences_with_ray<'a>(&'a self, ray: &Ray<Point<N>>, out: &mut Vec<&'a T>) {
+        for i in self.sorted.iter() {
+            let proxy = self.proxy(*i);
+
+            if !proxy.removed && proxy.aabb.intersects_ray(&Id::new(), ray) {
+                out.push(&proxy.data)
+            }
+        }
+    }
+
+    fn interferences_with_point<'a>(&'a self, point: &Point<N>, out: &mut Vec<&'a T>) {
+        for i in self.sorted.iter() {
+            let proxy = self.proxy(*i);
+
+            if proxy.aabb.mins()[self.axis] > point[self.axis] {
+                break;
+            }
+
+            if !proxy.removed && proxy.aabb.contains_point(&Id::new(), point) {
+                out.push(&proxy.data)
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::mem;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use alga::general::Real;
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use ncollide::narrow_phase::ProximityHandler;
+use ncollide::query::Proximity;
+use object::{WorldObject, RigidBodyHandle, SensorHandle};
+use world::WorldCollisionObject;
+use math::{Point, Isometry};
+
+/// The identifier of a trigger volume added to the physics world.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TriggerVolumeId(usize);
+
+impl TriggerVolumeId {
+    /// The index of this trigger volume on the world that generated this identifier.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Trait implemented by the receivers of trigger volume events.
+pub trait TriggerHandler<N: Real> {
+    /// Called when `body` starts intersecting the trigger volume `volume`.
+    fn handle_body_entered(&mut self, volume: TriggerVolumeId, body: &RigidBodyHandle<N>);
+    /// Called when `body` stops intersecting the trigger volume `volume`, or is removed from the
+    /// world while intersecting it.
+    fn handle_body_exited(&mut self, volume: TriggerVolumeId, body: &RigidBodyHandle<N>);
+}
+
+struct TriggerVolume<N: Real> {
+    sensor: SensorHandle<N>,
+    inside: HashMap<usize, RigidBodyHandle<N>, UintTWHash>
+}
+
+/// The set of static trigger volumes of the physics world.
+///
+/// A trigger volume is a static sensor dedicated to the detection of the rigid bodies entering
+/// and leaving it. It tracks the bodies it contains and reports their transitions to the
+/// registered handlers at the end of each step. Like any sensor, it never generates contacts.
+#[doc(hidden)]
+pub struct TriggerVolumes<N: Real> {
+    volumes:  Vec<Option<TriggerVolume<N>>>,
+    // Maps the sensor uids to the index of their volume.
+    ids:      HashMap<usize, usize, UintTWHash>,
+    // (volume, body, entered).
+    events:   Vec<(TriggerVolumeId, RigidBodyHandle<N>, bool)>,
+    handlers: Vec<(String, Box<TriggerHandler<N>>)>
+}
+
+impl<N: Real> TriggerVolumes<N> {
+    /// Creates an empty set of trigger volumes.
+    pub fn new() -> TriggerVolumes<N> {
+        TriggerVolumes {
+            volumes:  Vec::new(),
+            ids:      HashMap::new(UintTWHash::new()),
+            events:   Vec::new(),
+            handlers: Vec::new()
+        }
+    }
+
+    /// Adds a trigger volume backed by the collision object of `sensor`.
+    pub fn add(&mut self, sensor: SensorHandle<N>) -> TriggerVolumeId {
+        let index = self.volumes.len();
+
+        let _ = self.ids.insert(WorldObject::sensor_uid(&sensor), index);
+        self.volumes.push(Some(TriggerVolume {
+            sensor: sensor,
+            inside: HashMap::new(UintTWHash::new())
+        }));
+
+        TriggerVolumeId(index)
+    }
+
+    /// Removes a trigger volume, returning its sensor.
+    ///
+    /// The events of this volume not yet dispatched are discarded.
+    pub fn remove(&mut self, id: TriggerVolumeId) -> Option<SensorHandle<N>> {
+        let volume = self.volumes.get_mut(id.0).and_then(|v| v.take());
+
+        volume.map(|volume| {
+            let _ = self.ids.remove(&WorldObject::sensor_uid(&volume.sensor));
+            self.events.retain(|e| e.0 != id);
+
+            volume.sensor
+        })
+    }
+
+    /// The sensor of a trigger volume.
+    pub fn sensor(&self, id: TriggerVolumeId) -> Option<&SensorHandle<N>> {
+        self.volumes.get(id.0).and_then(|v| v.as_ref()).map(|v| &v.sensor)
+    }
+
+    /// The sensors of all the trigger volumes.
+    pub fn sensors(&self) -> Vec<SensorHandle<N>> {
+        self.volumes.iter().filter_map(|v| v.as_ref()).map(|v| v.sensor.clone()).collect()
+    }
+
+    /// The rigid bodies currently inside a trigger volume.
+    pub fn bodies_inside(&self, id: TriggerVolumeId) -> Option<Vec<RigidBodyHandle<N>>> {
+        self.volumes.get(id.0).and_then(|v| v.as_ref()).map(|v| {
+            v.inside.elements().iter().map(|e| e.value.clone()).collect()
+        })
+    }
+
+    /// Registers a handler for the trigger volume events.
+    pub fn register_handler<H: TriggerHandler<N> + 'static>(&mut self, name: &str, handler: H) {
+        self.unregister_handler(name);
+        self.handlers.push((name.to_string(), Box::new(handler)));
+    }
+
+    /// Unregisters a handler for the trigger volume events.
+    pub fn unregister_handler(&mut self, name: &str) {
+        self.handlers.retain(|h| h.0 != name);
+    }
+
+    /// Sends the events accumulated since the last call to this method to the handlers.
+    pub fn dispatch(&mut self) {
+        let events = mem::replace(&mut self.events, Vec::new());
+
+        for &(id, ref body, entered) in events.iter() {
+            for &mut (_, ref mut handler) in self.handlers.iter_mut() {
+                if entered {
+                    handler.handle_body_entered(id, body);
+                }
+                else {
+                    handler.handle_body_exited(id, body);
+                }
+            }
+        }
+    }
+
+    fn update(&mut self, sensor_uid: usize, body: &RigidBodyHandle<N>, intersecting: bool) {
+        let index = match self.ids.find(&sensor_uid) {
+            Some(index) => *index,
+            None        => return
+        };
+
+        if let Some(ref mut volume) = self.volumes[index] {
+            let body_uid = WorldObject::rigid_body_uid(body);
+
+            if intersecting {
+                if volume.inside.find(&body_uid).is_none() {
+                    let _ = volume.inside.insert(body_uid, body.clone());
+                    self.events.push((TriggerVolumeId(index), body.clone(), true));
+                }
+            }
+            else if volume.inside.remove(&body_uid) {
+                self.events.push((TriggerVolumeId(index), body.clone(), false));
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct TriggerVolumeCollector<N: Real> {
+    triggers: Rc<RefCell<TriggerVolumes<N>>>
+}
+
+impl<N: Real> TriggerVolumeCollector<N> {
+    /// Creates a proximity handler feeding `triggers`.
+    pub fn new(triggers: Rc<RefCell<TriggerVolumes<N>>>) -> TriggerVolumeCollector<N> {
+        TriggerVolumeCollector {
+            triggers: triggers
+        }
+    }
+}
+
+impl<N: Real> ProximityHandler<Point<N>, Isometry<N>, WorldObject<N>> for TriggerVolumeCollector<N> {
+    fn handle_proximity(&mut self,
+                        o1: &WorldCollisionObject<N>, o2: &WorldCollisionObject<N>,
+                        _: Proximity, new_proximity: Proximity) {
+        let intersecting = new_proximity == Proximity::Intersecting;
+
+        match (&o1.data, &o2.data) {
+            (&WorldObject::RigidBody(ref rb), &WorldObject::Sensor(_)) => {
+                self.triggers.borrow_mut().update(o2.uid, rb, intersecting)
+            },
+            (&WorldObject::Sensor(_), &WorldObject::RigidBody(ref rb)) => {
+                self.triggers.borrow_mut().update(o1.uid, rb, intersecting)
+            },
+            _ => { }
+        }
+    }
+}
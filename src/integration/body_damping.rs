@@ -9,6 +9,7 @@ use object::RigidBody;
 ///
 /// This will remove a part of the linear and angular velocity of every rigid body at each frame.
 /// Do not use unless you are experiencing unrealistic vibrations or very unstable joints.
+#[derive(Clone)]
 pub struct BodyDamping<N: Real> {
     linear_damping:  N,
     angular_damping: N
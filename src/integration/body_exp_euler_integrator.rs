@@ -8,7 +8,11 @@ use integration::euler;
 
 /// An explicit Euler integrator.
 ///
-/// Do not use this, prefer the `BodySmpEulerIntegrator` instead.
+/// The positions are updated with the velocities at the beginning of the time step. This
+/// integrator is not symplectic: the energy of oscillating systems, like bodies attached by
+/// springs or orbiting an attractor, grows at each step until the simulation explodes. Prefer
+/// the `BodySmpEulerIntegrator` unless this behavior is needed, e.g., to match another simulator.
+#[derive(Clone)]
 pub struct BodyExpEulerIntegrator;
 
 impl BodyExpEulerIntegrator {
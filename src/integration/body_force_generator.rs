@@ -2,14 +2,18 @@
 
 use alga::general::Real;
 
-use math::{Vector, Orientation};
+use math::{Point, Vector, Orientation};
 use object::RigidBody;
-use integration::Integrator;
+use integration::{Integrator, GravityZone};
 
 /// A constant linear and angular force generator.
+///
+/// The linear acceleration may be overridden locally by gravity zones.
+#[derive(Clone)]
 pub struct BodyForceGenerator<N: Real> {
     lin_acc: Vector<N>,
-    ang_acc: Orientation<N>
+    ang_acc: Orientation<N>,
+    zones:   Vec<(String, GravityZone<N>)>
 }
 
 impl<N: Real> BodyForceGenerator<N> {
@@ -22,7 +26,8 @@ impl<N: Real> BodyForceGenerator<N> {
     pub fn new(lin_acc: Vector<N>, ang_acc: Orientation<N>) -> BodyForceGenerator<N> {
         BodyForceGenerator {
             lin_acc: lin_acc,
-            ang_acc: ang_acc
+            ang_acc: ang_acc,
+            zones:   Vec::new()
         }
     }
 }
@@ -35,6 +40,9 @@ impl<N: Real> BodyForceGenerator<N> {
     }
 
     /// Sets the linear acceleration applied by this force generator.
+    ///
+    /// The change is taken into account at the next step, but the sleeping bodies are not woken
+    /// up: use `World::set_gravity` instead to wake them up.
     #[inline]
     pub fn set_lin_acc(&mut self, lin_acc: Vector<N>) {
         self.lin_acc = lin_acc;
@@ -47,16 +55,51 @@ impl<N: Real> BodyForceGenerator<N> {
     }
 
     /// Sets the angular acceleration applied by this force generator.
+    ///
+    /// The sleeping bodies are not woken up: use `World::set_angular_acceleration` instead to
+    /// wake them up.
     #[inline]
     pub fn set_ang_acc(&mut self, ang_acc: Orientation<N>) {
         self.ang_acc = ang_acc;
     }
+
+    /// Registers a zone overriding the linear acceleration of the bodies within it.
+    ///
+    /// Where several zones overlap, the last registered one applies. A zone replaces the one
+    /// previously registered with the same name.
+    pub fn register_gravity_zone(&mut self, name: &str, zone: GravityZone<N>) {
+        match self.zones.iter().position(|z| z.0 == name) {
+            Some(i) => self.zones[i].1 = zone,
+            None    => self.zones.push((name.to_string(), zone))
+        }
+    }
+
+    /// Unregisters a gravity zone.
+    pub fn unregister_gravity_zone(&mut self, name: &str) {
+        self.zones.retain(|z| z.0 != name)
+    }
+
+    /// A mutable reference to the gravity zone registered with the given name, e.g., to move it.
+    pub fn gravity_zone_mut(&mut self, name: &str) -> Option<&mut GravityZone<N>> {
+        self.zones.iter_mut().find(|z| z.0 == name).map(|z| &mut z.1)
+    }
+
+    /// The linear acceleration at `point`: the gravity of the last registered zone containing
+    /// it, or the linear acceleration of this force generator.
+    pub fn gravity_at(&self, point: &Point<N>) -> Vector<N> {
+        match self.zones.iter().rev().find(|z| z.1.contains(point)) {
+            Some(z) => z.1.gravity_at(point),
+            None    => self.lin_acc.clone()
+        }
+    }
 }
 
 impl<N: Real> Integrator<N, RigidBody<N>> for BodyForceGenerator<N> {
     #[inline]
     fn update(&mut self, _: N, rb: &mut RigidBody<N>) {
-        rb.set_gravity(self.lin_acc.clone());
-        //rb.set_ang_acc(self.ang_acc.clone());
+        let gravity = self.gravity_at(rb.center_of_mass());
+
+        rb.set_gravity(gravity);
+        rb.set_ang_gravity(self.ang_acc.clone());
     }
 }
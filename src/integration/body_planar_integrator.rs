@@ -0,0 +1,92 @@
+//! Integrator constraining bodies to a plane.
+
+use alga::general::Real;
+use na::{self, Vector3, UnitQuaternion};
+
+use object::RigidBody;
+use integration::{Integrator, CloneableIntegrator};
+
+/// An integrator constraining the bodies with a planar depth to their plane.
+///
+/// This wraps another integrator. The velocities of the constrained bodies are projected on their
+/// plane before and after each update, and their position is reset to the plane, so that the
+/// contacts and joints do not make them drift away from it. Their rotation is reduced to its
+/// twist around the `z` axis. This lets 2.5D games use the 3D pipeline and shapes. See
+/// `RigidBody::set_planar_depth`.
+pub struct BodyPlanarIntegrator<N: Real> {
+    integrator: Box<CloneableIntegrator<N>>
+}
+
+impl<N: Real> Clone for BodyPlanarIntegrator<N> {
+    fn clone(&self) -> BodyPlanarIntegrator<N> {
+        BodyPlanarIntegrator {
+            integrator: self.integrator.clone_box()
+        }
+    }
+}
+
+impl<N: Real> BodyPlanarIntegrator<N> {
+    /// Creates an integrator constraining the bodies updated by `integrator` to their plane.
+    pub fn new<I>(integrator: I) -> BodyPlanarIntegrator<N>
+        where I: Integrator<N, RigidBody<N>> + Clone + 'static {
+        BodyPlanarIntegrator {
+            integrator: Box::new(integrator)
+        }
+    }
+
+    /// The wrapped integrator.
+    pub fn integrator(&mut self) -> &mut Integrator<N, RigidBody<N>> {
+        self.integrator.as_integrator()
+    }
+}
+
+impl<N: Real> Integrator<N, RigidBody<N>> for BodyPlanarIntegrator<N> {
+    fn update(&mut self, dt: N, rb: &mut RigidBody<N>) {
+        match rb.planar_depth() {
+            Some(depth) if rb.can_move() => {
+                project_velocities(rb);
+                self.integrator.update(dt, rb);
+                project_to_plane(rb, depth);
+            },
+            _ => self.integrator.update(dt, rb)
+        }
+    }
+
+    fn update_with_forces(&mut self, dt: N, rb: &mut RigidBody<N>, forces: &mut FnMut(N, &mut RigidBody<N>)) {
+        match rb.planar_depth() {
+            Some(depth) if rb.can_move() => {
+                project_velocities(rb);
+                self.integrator.update_with_forces(dt, rb, forces);
+                project_to_plane(rb, depth);
+            },
+            _ => self.integrator.update_with_forces(dt, rb, forces)
+        }
+    }
+}
+
+// Projects the velocities of `rb` on its plane, and moves it back to its plane at `depth`.
+fn project_to_plane<N: Real>(rb: &mut RigidBody<N>, depth: N) {
+    project_velocities(rb);
+
+    let mut position = rb.position().clone();
+    let q            = *position.rotation.quaternion();
+    let angle        = q.k.atan2(q.w) * na::convert(2.0f64);
+
+    position.translation.vector.z = depth;
+    position.rotation = UnitQuaternion::from_scaled_axis(Vector3::z() * angle);
+
+    rb.set_transformation(position);
+}
+
+// Removes the components of the velocities of `rb` moving it out of its plane.
+fn project_velocities<N: Real>(rb: &mut RigidBody<N>) {
+    let mut lin_vel = rb.lin_vel();
+    let mut ang_vel = rb.ang_vel();
+
+    lin_vel.z = na::zero();
+    ang_vel.x = na::zero();
+    ang_vel.y = na::zero();
+
+    rb.set_lin_vel_internal(lin_vel);
+    rb.set_ang_vel_internal(ang_vel);
+}
@@ -0,0 +1,70 @@
+//! Runge-Kutta 4 integrator.
+
+use alga::general::Real;
+
+use object::RigidBody;
+use integration::Integrator;
+use integration::rk4;
+
+/// A fourth-order Runge-Kutta integrator.
+///
+/// This is more accurate than the `BodySmpEulerIntegrator` for bodies spinning quickly around a
+/// changing axis, or following ballistic or orbital trajectories, at a higher cost: the world
+/// sets the forces applied on each body at the four stages of each step, so that forces
+/// depending on the position or the velocity, e.g., the attraction of a `RadialField`, are
+/// integrated with a fourth-order error. A direct call to `update` assumes the accelerations of
+/// the body to be constant during the time step instead.
+#[derive(Clone)]
+pub struct BodyRk4Integrator;
+
+impl BodyRk4Integrator {
+    /// Creates a new `BodyRk4Integrator`.
+    #[inline]
+    pub fn new() -> BodyRk4Integrator {
+        BodyRk4Integrator
+    }
+}
+
+impl<N: Real> Integrator<N, RigidBody<N>> for BodyRk4Integrator {
+    #[inline]
+    fn update(&mut self, dt: N, rb: &mut RigidBody<N>) {
+        if rb.can_move() {
+            let (t, lv, av) = rk4::integrate(
+                dt.clone(),
+                rb.center_of_mass(),
+                &rb.lin_vel(),
+                &rb.ang_vel(),
+                &rb.lin_acc(),
+                &rb.ang_acc());
+
+            rb.append_transformation(&t);
+            rb.set_lin_vel_internal(lv);
+            rb.set_ang_vel_internal(av);
+        }
+    }
+
+    fn update_with_forces(&mut self, dt: N, rb: &mut RigidBody<N>, forces: &mut FnMut(N, &mut RigidBody<N>)) {
+        if !rb.can_move() {
+            return forces(dt, rb);
+        }
+
+        let position = rb.position().clone();
+        let center   = rb.center_of_mass().clone();
+        let lin_vel  = rb.lin_vel();
+        let ang_vel  = rb.ang_vel();
+
+        // Each stage moves the body to an intermediate state to compute its accelerations.
+        let (t, lv, av) = rk4::integrate_with(dt.clone(), &center, &lin_vel, &ang_vel, |t, lv, av| {
+            rb.set_transformation(*t * position);
+            rb.set_lin_vel_internal(*lv);
+            rb.set_ang_vel_internal(*av);
+            forces(dt.clone(), rb);
+
+            (rb.lin_acc(), rb.ang_acc())
+        });
+
+        rb.set_transformation(t * position);
+        rb.set_lin_vel_internal(lv);
+        rb.set_ang_vel_internal(av);
+    }
+}
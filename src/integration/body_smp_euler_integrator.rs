@@ -6,14 +6,41 @@ use object::RigidBody;
 use integration::Integrator;
 use integration::euler;
 
-/// A semi-implicit Euler integrator.
-pub struct BodySmpEulerIntegrator;
+/// A semi-implicit, or symplectic, Euler integrator.
+///
+/// The velocities are updated first, and the positions are updated with the new velocities. The
+/// energy of oscillating systems, like bodies attached by springs or orbiting an attractor,
+/// oscillates around its exact value instead of growing. This is the default integrator of the
+/// world.
+#[derive(Clone)]
+pub struct BodySmpEulerIntegrator {
+    gyroscopic: bool
+}
 
 impl BodySmpEulerIntegrator {
-    /// Creates a new `BodySmpEulerIntegrator`.
+    /// Creates a new `BodySmpEulerIntegrator`, without gyroscopic torques.
     #[inline]
     pub fn new() -> BodySmpEulerIntegrator {
-        BodySmpEulerIntegrator
+        BodySmpEulerIntegrator {
+            gyroscopic: false
+        }
+    }
+
+    /// Whether this integrator applies the gyroscopic torques.
+    #[inline]
+    pub fn gyroscopic(&self) -> bool {
+        self.gyroscopic
+    }
+
+    /// Enables or disables the gyroscopic torques.
+    ///
+    /// Without them, the angular velocity of a free body is constant instead of its angular
+    /// momentum, so that spinning elongated bodies, e.g., a thrown hammer, do not tumble. The
+    /// gyroscopic torques are integrated implicitly, which is stable at any angular velocity.
+    /// This has no effect in 2D.
+    #[inline]
+    pub fn set_gyroscopic(&mut self, gyroscopic: bool) {
+        self.gyroscopic = gyroscopic
     }
 }
 
@@ -21,12 +48,19 @@ impl<N: Real> Integrator<N, RigidBody<N>> for BodySmpEulerIntegrator {
     #[inline]
     fn update(&mut self, dt: N, rb: &mut RigidBody<N>) {
         if rb.can_move() {
+            let ang_vel = if self.gyroscopic {
+                euler::implicit_gyroscopic_velocity(dt, rb.inv_inertia(), &rb.ang_vel())
+            }
+            else {
+                rb.ang_vel()
+            };
+
             let (t, lv, av) = euler::semi_implicit_integrate(
                 dt.clone(),
                 rb.position(),
                 rb.center_of_mass(),
                 &rb.lin_vel(),
-                &rb.ang_vel(),
+                &ang_vel,
                 &rb.lin_acc(),
                 &rb.ang_acc());
 
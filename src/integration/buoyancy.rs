@@ -0,0 +1,185 @@
+//! Buoyancy and drag of the bodies immersed in a fluid.
+
+use na;
+use alga::general::Real;
+use ncollide::bounding_volume::{self, AABB};
+
+use math::{Point, Vector, Orientation};
+use object::RigidBody;
+use integration::ForceGenerator;
+use volumetric::Volumetric;
+use utils::GeneralizedCross;
+
+/// A force generator applying the buoyancy and the drag of a fluid on the bodies immersed in it.
+///
+/// The fluid fills the half-space below its surface, which is orthogonal to the gravity, and may
+/// be limited to a box, e.g., a pool. The submerged fraction of each body is estimated from its
+/// axis-aligned bounding box, and its volume is the one of its shape, which must implement
+/// `Volumetric`.
+#[derive(Clone)]
+pub struct Buoyancy<N: Real> {
+    gravity:      Vector<N>,
+    surface:      Point<N>,
+    density:      N,
+    bounds:       Option<AABB<Point<N>>>,
+    flow:         Vector<N>,
+    linear_drag:  N,
+    angular_drag: N
+}
+
+impl<N: Real> Buoyancy<N> {
+    /// Creates a fluid of the given `density`, which surface contains `surface`.
+    ///
+    /// The buoyancy opposes `gravity`, which should be the gravity of the world.
+    pub fn new(gravity: Vector<N>, surface: Point<N>, density: N) -> Buoyancy<N> {
+        Buoyancy {
+            gravity:      gravity,
+            surface:      surface,
+            density:      density,
+            bounds:       None,
+            flow:         na::zero(),
+            linear_drag:  na::one(),
+            angular_drag: na::one()
+        }
+    }
+
+    /// The gravity opposed by the buoyancy.
+    pub fn gravity(&self) -> &Vector<N> {
+        &self.gravity
+    }
+
+    /// Sets the gravity opposed by the buoyancy.
+    pub fn set_gravity(&mut self, gravity: Vector<N>) {
+        self.gravity = gravity
+    }
+
+    /// A point of the fluid surface.
+    pub fn surface(&self) -> &Point<N> {
+        &self.surface
+    }
+
+    /// Sets a point of the fluid surface.
+    pub fn set_surface(&mut self, surface: Point<N>) {
+        self.surface = surface
+    }
+
+    /// The density of the fluid.
+    pub fn density(&self) -> N {
+        self.density
+    }
+
+    /// Sets the density of the fluid.
+    pub fn set_density(&mut self, density: N) {
+        self.density = density
+    }
+
+    /// The box the fluid is limited to, if any.
+    pub fn bounds(&self) -> Option<&AABB<Point<N>>> {
+        self.bounds.as_ref()
+    }
+
+    /// Limits the fluid to a box.
+    ///
+    /// Set to `None` to fill the whole half-space below the surface, which is the default. Set the
+    /// surface above the box to fill it completely.
+    pub fn set_bounds(&mut self, bounds: Option<AABB<Point<N>>>) {
+        self.bounds = bounds
+    }
+
+    /// The velocity of the fluid.
+    pub fn flow_velocity(&self) -> &Vector<N> {
+        &self.flow
+    }
+
+    /// Sets the velocity of the fluid, which drags the immersed bodies along. Defaults to zero.
+    pub fn set_flow_velocity(&mut self, flow: Vector<N>) {
+        self.flow = flow
+    }
+
+    /// The linear and angular drag coefficients of the fluid.
+    pub fn drag(&self) -> (N, N) {
+        (self.linear_drag, self.angular_drag)
+    }
+
+    /// Sets the linear and angular drag coefficients of the fluid.
+    ///
+    /// The drag force is `linear_drag` times the mass of the displaced fluid times the velocity
+    /// of the body relative to the flow, and the drag torque is `angular_drag` times the mass of
+    /// the displaced fluid times the angular velocity of the body. Both default to one.
+    pub fn set_drag(&mut self, linear_drag: N, angular_drag: N) {
+        self.linear_drag  = linear_drag;
+        self.angular_drag = angular_drag
+    }
+
+    /// The fraction of the bounding box of `rb` immersed in the fluid, and the center of the
+    /// immersed part.
+    pub fn submerged_fraction(&self, rb: &RigidBody<N>) -> (N, Point<N>) {
+        let zero: N = na::zero();
+        let _1_2: N = na::convert(0.5f64);
+        let aabb    = bounding_volume::aabb(rb.shape().as_ref(), rb.position());
+        let center  = aabb.center();
+        let up      = match na::try_normalize(&-self.gravity, N::default_epsilon()) {
+            Some(up) => up,
+            None     => return (zero, center)
+        };
+
+        // Clip the bounding box by the bounds of the fluid.
+        let (clipped, box_fraction) = match self.bounds {
+            Some(ref bounds) => {
+                let mins    = na::sup(aabb.mins(), bounds.mins());
+                let maxs    = na::inf(aabb.maxs(), bounds.maxs());
+                let extents = maxs - mins;
+
+                if extents.iter().any(|e| *e < zero) {
+                    return (zero, center)
+                }
+
+                let clipped = AABB::new(mins, maxs);
+
+                let fraction = extents.iter().zip((aabb.maxs() - aabb.mins()).iter())
+                                      .fold(N::one(), |f, (c, e)| if *e > zero { f * *c / *e } else { f });
+
+                (clipped, fraction)
+            },
+            None => (aabb.clone(), N::one())
+        };
+
+        // The extent of the clipped box along the vertical axis.
+        let half_extents   = clipped.half_extents();
+        let clipped_center = clipped.center();
+        let radius         = up.iter().zip(half_extents.iter()).fold(zero, |r, (u, h)| r + u.abs() * *h);
+        let bottom         = na::dot(&up, &clipped_center.coords) - radius;
+        let depth          = na::dot(&up, &self.surface.coords) - bottom;
+
+        if depth <= zero {
+            (zero, center)
+        }
+        else if radius == zero || depth >= radius + radius {
+            (box_fraction, clipped_center)
+        }
+        else {
+            let depth_fraction = depth / (radius + radius);
+
+            (box_fraction * depth_fraction, clipped_center + up * (depth * _1_2 - radius))
+        }
+    }
+}
+
+impl<N: Real> ForceGenerator<N> for Buoyancy<N> {
+    fn forces(&mut self, _: N, rb: &RigidBody<N>) -> (Vector<N>, Orientation<N>) {
+        let (fraction, center) = self.submerged_fraction(rb);
+
+        if fraction == na::zero() {
+            return (na::zero(), na::zero())
+        }
+
+        // The mass of the displaced fluid.
+        let mass     = self.density * rb.shape().as_ref().volume() * fraction;
+        let arm      = center - *rb.center_of_mass();
+        let buoyancy = -self.gravity * mass;
+        let lin_drag = (self.flow - rb.lin_vel()) * (mass * self.linear_drag);
+        let ang_drag = -rb.ang_vel() * (mass * self.angular_drag);
+
+        (buoyancy + lin_drag, arm.gcross(&buoyancy) + ang_drag)
+    }
+}
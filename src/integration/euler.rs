@@ -2,9 +2,13 @@
 
 use na;
 use alga::general::Real;
-use math::{Point, Vector, Orientation, Rotation, Translation, Isometry};
+use math::{Point, Vector, Orientation, Rotation, Translation, Isometry, AngularInertia};
+#[cfg(feature = "dim3")]
+use utils::GeneralizedCross;
 
 /// Explicit Euler integrator.
+///
+/// The displacement uses the velocities `lv` and `av` at the beginning of the time step.
 pub fn explicit_integrate<N: Real>(dt: N,
                                    p:  &Isometry<N>, c:  &Point<N>,
                                    lv: &Vector<N>, av: &Orientation<N>,
@@ -26,7 +30,9 @@ pub fn explicit_integrate_wo_rotation<N: Real>(dt: N, p: &Point<N>, lv: &Vector<
     )
 }
 
-/// Semi-implicit Euler integrator.
+/// Semi-implicit, or symplectic, Euler integrator.
+///
+/// The displacement uses the velocities at the end of the time step.
 pub fn semi_implicit_integrate<N: Real>(dt: N,
                                         p:     &Isometry<N>, c:     &Point<N>,
                                         lv:    &Vector<N>, av:    &Orientation<N>,
@@ -64,3 +70,34 @@ pub fn displacement<N: Real>(dt: N, _: &Isometry<N>, center_of_mass: &Point<N>,
 
     res
 }
+
+/// The angular velocity `av` of a body updated by the gyroscopic torque `-av × I av` during the
+/// time step `dt`, where `I` is the inverse of the world-space `inv_inertia`.
+///
+/// The torque is integrated implicitly, which is stable even for fast spinning elongated bodies:
+/// their kinetic energy never grows. The velocity is unchanged if the inertia is singular.
+#[cfg(feature = "dim3")]
+pub fn implicit_gyroscopic_velocity<N: Real>(dt: N, inv_inertia: &AngularInertia<N>, av: &Orientation<N>)
+                                             -> Orientation<N> {
+    let inertia = match inv_inertia.try_inverse() {
+        Some(inertia) => inertia,
+        None          => return *av
+    };
+
+    // One Newton iteration on `I (ω' - ω) + dt ω' × I ω' = 0`, starting from `ω' = ω`.
+    let momentum = inertia * *av;
+    let residual = av.cross(&momentum) * dt;
+    let jacobian = inertia + (av.gcross_matrix() * inertia - momentum.gcross_matrix()) * dt;
+
+    match jacobian.try_inverse() {
+        Some(inv_jacobian) => *av - inv_jacobian * residual,
+        None               => *av
+    }
+}
+
+/// The angular velocity `av` of a body updated by the gyroscopic torque during the time step
+/// `dt`, which is always zero in 2D.
+#[cfg(feature = "dim2")]
+pub fn implicit_gyroscopic_velocity<N: Real>(_: N, _: &AngularInertia<N>, av: &Orientation<N>) -> Orientation<N> {
+    *av
+}
@@ -0,0 +1,43 @@
+//! Falloff of the strength of the force fields.
+
+use alga::general::Real;
+use na;
+
+/// How the strength of a force field decreases with the distance to its center.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Falloff<N: Real> {
+    /// The strength does not depend on the distance.
+    Constant,
+    /// The strength decreases linearly, down to zero at the radius of the field.
+    Linear,
+    /// The strength decreases quadratically, down to zero at the radius of the field.
+    Quadratic,
+    /// The strength is inversely proportional to the squared distance, like gravitation.
+    ///
+    /// The strength is the one at a unit distance, and is constant below the given minimum
+    /// distance to avoid singularities at the center.
+    InverseSquare(N)
+}
+
+impl<N: Real> Falloff<N> {
+    /// The factor of the field strength at `distance` from the center of a field of the given
+    /// `radius`.
+    ///
+    /// The factor is zero beyond the radius.
+    pub fn factor(&self, distance: N, radius: N) -> N {
+        if distance > radius {
+            return na::zero()
+        }
+
+        match *self {
+            Falloff::Constant                    => N::one(),
+            Falloff::Linear                      => N::one() - distance / radius,
+            Falloff::Quadratic                   => (N::one() - distance / radius) * (N::one() - distance / radius),
+            Falloff::InverseSquare(min_distance) => {
+                let distance = distance.max(min_distance);
+
+                N::one() / (distance * distance)
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Trait implemented by user-defined force generators.
+
+use alga::general::Real;
+
+use math::{Vector, Orientation};
+use object::RigidBody;
+
+/// A user-defined generator of forces applied on the rigid bodies, e.g., wind, buoyancy, or
+/// attraction fields.
+///
+/// Each generator registered to the world is called on every active dynamic rigid body before
+/// its integration, and the forces of all generators are summed. Unlike the forces appended to
+/// the rigid bodies, the generated forces are computed again at each step, and at each stage of
+/// integrators of higher order like the `BodyRk4Integrator`.
+pub trait ForceGenerator<N: Real> {
+    /// The force and the torque, relative to the center of mass, applied on `rb` during the next
+    /// time step of length `dt`.
+    fn forces(&mut self, dt: N, rb: &RigidBody<N>) -> (Vector<N>, Orientation<N>);
+}
+
+/// A force generator which can be copied along with the world.
+#[doc(hidden)]
+pub trait CloneableForceGenerator<N: Real>: ForceGenerator<N> {
+    /// A copy of this force generator.
+    fn clone_box(&self) -> Box<CloneableForceGenerator<N>>;
+}
+
+impl<N: Real, G> CloneableForceGenerator<N> for G
+    where G: ForceGenerator<N> + Clone + 'static {
+    fn clone_box(&self) -> Box<CloneableForceGenerator<N>> {
+        Box::new(self.clone())
+    }
+}
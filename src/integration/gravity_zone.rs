@@ -0,0 +1,121 @@
+//! Region of space with its own gravity.
+
+use num::Bounded;
+use na;
+use alga::general::Real;
+use ncollide::bounding_volume::{AABB, BoundingVolume};
+
+use math::{Point, Vector};
+use integration::Falloff;
+
+#[derive(Clone, Debug)]
+enum GravityField<N: Real> {
+    Uniform(Vector<N>),
+    Central(N, Falloff<N>)
+}
+
+/// A region of space where the gravity of the world is replaced by another one, e.g., a zero-G
+/// room or a spherical planet.
+///
+/// A body undergoes the gravity of a zone when its center of mass lies within the radius and the
+/// bounds of the zone. Zones are registered with `World::register_gravity_zone`.
+#[derive(Clone, Debug)]
+pub struct GravityZone<N: Real> {
+    center: Point<N>,
+    radius: N,
+    bounds: Option<AABB<Point<N>>>,
+    field:  GravityField<N>
+}
+
+impl<N: Real> GravityZone<N> {
+    /// Creates a zone with the constant `gravity` in the box `bounds`.
+    ///
+    /// Use a zero gravity for a zero-G room.
+    pub fn uniform(bounds: AABB<Point<N>>, gravity: Vector<N>) -> GravityZone<N> {
+        GravityZone {
+            center: bounds.center(),
+            radius: Bounded::max_value(),
+            bounds: Some(bounds),
+            field:  GravityField::Uniform(gravity)
+        }
+    }
+
+    /// Creates a zone of the given `radius` around `center` with a gravity of norm
+    /// `acceleration` pointing towards `center`, e.g., a spherical planet.
+    ///
+    /// The acceleration is constant within the zone by default.
+    pub fn spherical(center: Point<N>, radius: N, acceleration: N) -> GravityZone<N> {
+        GravityZone {
+            center: center,
+            radius: radius,
+            bounds: None,
+            field:  GravityField::Central(acceleration, Falloff::Constant)
+        }
+    }
+
+    /// The center of this zone.
+    pub fn center(&self) -> &Point<N> {
+        &self.center
+    }
+
+    /// Sets the center of this zone, towards which the gravity of a spherical zone points.
+    pub fn set_center(&mut self, center: Point<N>) {
+        self.center = center
+    }
+
+    /// The distance to the center beyond which this zone does not apply.
+    pub fn radius(&self) -> N {
+        self.radius
+    }
+
+    /// Sets the distance to the center beyond which this zone does not apply.
+    pub fn set_radius(&mut self, radius: N) {
+        self.radius = radius
+    }
+
+    /// The box this zone is limited to, if any.
+    pub fn bounds(&self) -> Option<&AABB<Point<N>>> {
+        self.bounds.as_ref()
+    }
+
+    /// Limits this zone to a box. Set to `None` to remove the limit.
+    pub fn set_bounds(&mut self, bounds: Option<AABB<Point<N>>>) {
+        self.bounds = bounds
+    }
+
+    /// Sets how the gravity of a spherical zone decreases with the distance to its center.
+    ///
+    /// This has no effect on uniform zones.
+    pub fn set_falloff(&mut self, falloff: Falloff<N>) {
+        if let GravityField::Central(_, ref mut f) = self.field {
+            *f = falloff
+        }
+    }
+
+    /// Whether `point` lies within this zone.
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        if let Some(ref bounds) = self.bounds {
+            if !bounds.contains(&AABB::new(*point, *point)) {
+                return false
+            }
+        }
+
+        na::distance(&self.center, point) <= self.radius
+    }
+
+    /// The gravity of this zone at `point`, assumed to lie within it.
+    pub fn gravity_at(&self, point: &Point<N>) -> Vector<N> {
+        match self.field {
+            GravityField::Uniform(gravity) => gravity,
+            GravityField::Central(acceleration, ref falloff) => {
+                let mut dir  = self.center - *point;
+                let distance = match dir.try_normalize_mut(N::default_epsilon()) {
+                    Some(distance) => distance,
+                    None           => return na::zero()
+                };
+
+                dir * (acceleration * falloff.factor(distance, self.radius))
+            }
+        }
+    }
+}
@@ -2,10 +2,43 @@
 
 use alga::general::Real;
 
+use object::RigidBody;
+
 /// Trait implemented by every integrator.
 ///
 /// An integrator is a structure capable of updating a dynamic body position and orientation after a given time-step.
 pub trait Integrator<N: Real, O> {
     /// Updates the position and orientation of the object `o` after a time step of `dt`.
     fn update(&mut self, dt: N, o: &mut O);
+
+    /// Updates the object `o` like `update`, `forces` setting the forces applied on `o` in its
+    /// current state during a time step of `dt`.
+    ///
+    /// By default, the forces are set once, at the beginning of the time step. Integrators of
+    /// higher order may set them again in intermediate states of `o`.
+    fn update_with_forces(&mut self, dt: N, o: &mut O, forces: &mut FnMut(N, &mut O)) {
+        forces(dt.clone(), o);
+        self.update(dt, o)
+    }
+}
+
+/// A rigid body integrator which can be copied along with the world.
+#[doc(hidden)]
+pub trait CloneableIntegrator<N: Real>: Integrator<N, RigidBody<N>> {
+    /// A copy of this integrator.
+    fn clone_box(&self) -> Box<CloneableIntegrator<N>>;
+
+    /// This integrator, as an `Integrator`.
+    fn as_integrator(&mut self) -> &mut Integrator<N, RigidBody<N>>;
+}
+
+impl<N: Real, I> CloneableIntegrator<N> for I
+    where I: Integrator<N, RigidBody<N>> + Clone + 'static {
+    fn clone_box(&self) -> Box<CloneableIntegrator<N>> {
+        Box::new(self.clone())
+    }
+
+    fn as_integrator(&mut self) -> &mut Integrator<N, RigidBody<N>> {
+        self
+    }
 }
@@ -1,9 +1,18 @@
 //! Position and orientation update of rigid bodies.
 
-pub use integration::integrator::Integrator;
+pub use integration::integrator::{Integrator, CloneableIntegrator};
 pub use integration::body_exp_euler_integrator::BodyExpEulerIntegrator;
 pub use integration::body_smp_euler_integrator::BodySmpEulerIntegrator;
+pub use integration::body_rk4_integrator::BodyRk4Integrator;
+#[cfg(feature = "dim3")]
+pub use integration::body_planar_integrator::BodyPlanarIntegrator;
 pub use integration::body_force_generator::BodyForceGenerator;
+pub use integration::gravity_zone::GravityZone;
+pub use integration::force_generator::{ForceGenerator, CloneableForceGenerator};
+pub use integration::buoyancy::Buoyancy;
+pub use integration::falloff::Falloff;
+pub use integration::radial_field::RadialField;
+pub use integration::vortex_field::VortexField;
 pub use integration::body_damping::BodyDamping;
 pub use integration::translational_ccd_motion_clamping::TranslationalCCDMotionClamping;
 
@@ -11,7 +20,17 @@ pub use integration::translational_ccd_motion_clamping::TranslationalCCDMotionCl
 pub mod integrator;
 mod body_exp_euler_integrator;
 mod body_smp_euler_integrator;
+mod body_rk4_integrator;
+#[cfg(feature = "dim3")]
+mod body_planar_integrator;
 mod body_force_generator;
+mod gravity_zone;
+mod force_generator;
+mod buoyancy;
+mod falloff;
+mod radial_field;
+mod vortex_field;
 mod body_damping;
 mod translational_ccd_motion_clamping;
 pub mod euler;
+pub mod rk4;
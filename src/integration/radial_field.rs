@@ -0,0 +1,133 @@
+//! Force field attracting or repelling the bodies from a point.
+
+use num::Bounded;
+use na;
+use alga::general::Real;
+use ncollide::bounding_volume::{AABB, BoundingVolume};
+
+use math::{Point, Vector, Orientation};
+use object::RigidBody;
+use integration::{ForceGenerator, Falloff};
+
+/// A force field attracting the bodies towards its center, or repelling them if its strength is
+/// negative, e.g., a magnet, an explosion, or a planet.
+///
+/// The field applies on the center of mass of the bodies within its radius and its bounds.
+#[derive(Clone)]
+pub struct RadialField<N: Real> {
+    center:   Point<N>,
+    strength: N,
+    falloff:  Falloff<N>,
+    radius:   N,
+    bounds:   Option<AABB<Point<N>>>,
+    per_mass: bool
+}
+
+impl<N: Real> RadialField<N> {
+    /// Creates an unbounded field of the given `strength` towards `center`.
+    ///
+    /// The strength is an acceleration, i.e., the force is proportional to the mass of each body.
+    pub fn new(center: Point<N>, strength: N, falloff: Falloff<N>) -> RadialField<N> {
+        RadialField {
+            center:   center,
+            strength: strength,
+            falloff:  falloff,
+            radius:   Bounded::max_value(),
+            bounds:   None,
+            per_mass: true
+        }
+    }
+
+    /// The center of this field.
+    pub fn center(&self) -> &Point<N> {
+        &self.center
+    }
+
+    /// Sets the center of this field.
+    pub fn set_center(&mut self, center: Point<N>) {
+        self.center = center
+    }
+
+    /// The strength of this field, negative if it repels the bodies.
+    pub fn strength(&self) -> N {
+        self.strength
+    }
+
+    /// Sets the strength of this field, negative to repel the bodies.
+    pub fn set_strength(&mut self, strength: N) {
+        self.strength = strength
+    }
+
+    /// How the strength of this field decreases with the distance to its center.
+    pub fn falloff(&self) -> &Falloff<N> {
+        &self.falloff
+    }
+
+    /// Sets how the strength of this field decreases with the distance to its center.
+    pub fn set_falloff(&mut self, falloff: Falloff<N>) {
+        self.falloff = falloff
+    }
+
+    /// The distance to the center beyond which this field does not apply.
+    pub fn radius(&self) -> N {
+        self.radius
+    }
+
+    /// Sets the distance to the center beyond which this field does not apply.
+    ///
+    /// The field is unbounded by default, in which case the linear and quadratic falloffs are
+    /// constant.
+    pub fn set_radius(&mut self, radius: N) {
+        self.radius = radius
+    }
+
+    /// The box this field is limited to, if any.
+    pub fn bounds(&self) -> Option<&AABB<Point<N>>> {
+        self.bounds.as_ref()
+    }
+
+    /// Limits this field to the bodies which center of mass lies in a box. Set to `None` to
+    /// remove the limit, which is the default.
+    pub fn set_bounds(&mut self, bounds: Option<AABB<Point<N>>>) {
+        self.bounds = bounds
+    }
+
+    /// Whether the strength is an acceleration, or a force independent from the mass of the
+    /// bodies.
+    pub fn is_mass_proportional(&self) -> bool {
+        self.per_mass
+    }
+
+    /// Sets whether the strength is an acceleration, which is the default, or a force
+    /// independent from the mass of the bodies, so that the light bodies are pushed further,
+    /// e.g., by an explosion.
+    pub fn set_mass_proportional(&mut self, per_mass: bool) {
+        self.per_mass = per_mass
+    }
+}
+
+impl<N: Real> ForceGenerator<N> for RadialField<N> {
+    fn forces(&mut self, _: N, rb: &RigidBody<N>) -> (Vector<N>, Orientation<N>) {
+        let com = rb.center_of_mass();
+
+        if let Some(ref bounds) = self.bounds {
+            if !bounds.contains(&AABB::new(*com, *com)) {
+                return (na::zero(), na::zero())
+            }
+        }
+
+        let mut dir  = self.center - *com;
+        let distance = match dir.try_normalize_mut(N::default_epsilon()) {
+            Some(distance) => distance,
+            None           => return (na::zero(), na::zero())
+        };
+
+        let mut magnitude = self.strength * self.falloff.factor(distance, self.radius);
+
+        if self.per_mass {
+            magnitude = magnitude * rb.mass().unwrap_or(na::zero());
+        }
+
+        (dir * magnitude, na::zero())
+    }
+}
@@ -0,0 +1,150 @@
+//! Runge-Kutta 4 integration functions.
+
+use na;
+use alga::general::Real;
+use math::{Point, Vector, Orientation, Rotation, Translation, Isometry};
+#[cfg(feature = "dim3")]
+use na::{Quaternion, Unit};
+
+/// Fourth-order Runge-Kutta integrator.
+///
+/// The accelerations are assumed constant during the time step, so that the integrated
+/// velocities are the same as the Euler ones, and the translation is exact. The orientation is
+/// integrated with a fourth-order error, even if the angular velocity changes direction during
+/// the time step.
+pub fn integrate<N: Real>(dt:    N,
+                          c:     &Point<N>,
+                          lv:    &Vector<N>, av:    &Orientation<N>,
+                          l_acc: &Vector<N>, a_acc: &Orientation<N>)
+                          -> (Isometry<N>, Vector<N>, Orientation<N>) {
+    let _1_2: N = na::convert(0.5f64);
+    let mut res: Isometry<N> = na::one();
+
+    res.append_rotation_wrt_point_mut(&rotation(dt, av, a_acc), c);
+    res.append_translation_mut(&Translation::from_vector(*lv * dt + *l_acc * (dt * dt * _1_2)));
+
+    (
+        res,
+        *lv + *l_acc * dt,
+        *av + *a_acc * dt
+    )
+}
+
+/// Fourth-order Runge-Kutta integrator, the accelerations depending on the state of the object.
+///
+/// `accelerations` gives the linear and angular accelerations of the object displaced by the
+/// given transformation, with the given linear and angular velocities. It is called four times,
+/// first with the identity and the velocities `lv` and `av`. The displacement and the velocities
+/// at the end of the time step are integrated with a fourth-order error, e.g., for an orbit.
+pub fn integrate_with<N: Real, F>(dt: N,
+                                  c:  &Point<N>,
+                                  lv: &Vector<N>, av: &Orientation<N>,
+                                  mut accelerations: F)
+                                  -> (Isometry<N>, Vector<N>, Orientation<N>)
+    where F: FnMut(&Isometry<N>, &Vector<N>, &Orientation<N>) -> (Vector<N>, Orientation<N>) {
+    let _1_2: N = na::convert(0.5f64);
+    let _2:   N = na::convert(2.0f64);
+    let _6:   N = na::convert(6.0f64);
+
+    // The derivatives of the translation of the center of mass, of the attitude, and of the
+    // velocities, at an intermediate state.
+    let mut derivatives = |t: &Vector<N>, q: &Attitude<N>, lv: &Vector<N>, av: &Orientation<N>| {
+        let (l_acc, a_acc) = accelerations(&displacement(c, t, q), lv, av);
+
+        (*lv, attitude_derivative(av, q), l_acc, a_acc)
+    };
+
+    let h  = dt * _1_2;
+    let q0 = attitude_identity();
+
+    let k1 = derivatives(&na::zero(), &q0, lv, av);
+    let k2 = derivatives(&(k1.0 * h), &(q0 + k1.1 * h), &(*lv + k1.2 * h), &(*av + k1.3 * h));
+    let k3 = derivatives(&(k2.0 * h), &(q0 + k2.1 * h), &(*lv + k2.2 * h), &(*av + k2.3 * h));
+    let k4 = derivatives(&(k3.0 * dt), &(q0 + k3.1 * dt), &(*lv + k3.2 * dt), &(*av + k3.3 * dt));
+
+    let w = dt / _6;
+    let t = (k1.0 + k2.0 * _2 + k3.0 * _2 + k4.0) * w;
+    let q = q0 + (k1.1 + k2.1 * _2 + k3.1 * _2 + k4.1) * w;
+
+    (
+        displacement(c, &t, &q),
+        *lv + (k1.2 + k2.2 * _2 + k3.2 * _2 + k4.2) * w,
+        *av + (k1.3 + k2.3 * _2 + k3.3 * _2 + k4.3) * w
+    )
+}
+
+// The rotation of an object accumulated during a time step, not normalized.
+#[cfg(feature = "dim2")]
+type Attitude<N> = Orientation<N>;
+#[cfg(feature = "dim3")]
+type Attitude<N> = Quaternion<N>;
+
+#[cfg(feature = "dim2")]
+fn attitude_identity<N: Real>() -> Attitude<N> {
+    na::zero()
+}
+
+#[cfg(feature = "dim3")]
+fn attitude_identity<N: Real>() -> Attitude<N> {
+    Quaternion::identity()
+}
+
+#[cfg(feature = "dim2")]
+fn attitude_derivative<N: Real>(av: &Orientation<N>, _: &Attitude<N>) -> Attitude<N> {
+    *av
+}
+
+#[cfg(feature = "dim3")]
+fn attitude_derivative<N: Real>(av: &Orientation<N>, q: &Attitude<N>) -> Attitude<N> {
+    Quaternion::from_parts(na::zero(), *av * na::convert::<f64, N>(0.5)) * *q
+}
+
+#[cfg(feature = "dim2")]
+fn attitude_rotation<N: Real>(q: &Attitude<N>) -> Rotation<N> {
+    Rotation::from_scaled_axis(*q)
+}
+
+#[cfg(feature = "dim3")]
+fn attitude_rotation<N: Real>(q: &Attitude<N>) -> Rotation<N> {
+    Unit::new_normalize(*q)
+}
+
+// The rotation `q` around the center of mass `c`, followed by the translation `t`.
+fn displacement<N: Real>(c: &Point<N>, t: &Vector<N>, q: &Attitude<N>) -> Isometry<N> {
+    let mut res: Isometry<N> = na::one();
+
+    res.append_rotation_wrt_point_mut(&attitude_rotation(q), c);
+    res.append_translation_mut(&Translation::from_vector(*t));
+
+    res
+}
+
+/// The rotation during the time step `dt` of an object with an initial angular velocity `av` and
+/// a constant angular acceleration `a_acc`.
+#[cfg(feature = "dim2")]
+pub fn rotation<N: Real>(dt: N, av: &Orientation<N>, a_acc: &Orientation<N>) -> Rotation<N> {
+    let _1_2: N = na::convert(0.5f64);
+
+    // The rotations around the only axis commute: the angle is exact.
+    Rotation::from_scaled_axis(*av * dt + *a_acc * (dt * dt * _1_2))
+}
+
+/// The rotation during the time step `dt` of an object with an initial angular velocity `av` and
+/// a constant angular acceleration `a_acc`.
+#[cfg(feature = "dim3")]
+pub fn rotation<N: Real>(dt: N, av: &Orientation<N>, a_acc: &Orientation<N>) -> Rotation<N> {
+    let _1_2: N = na::convert(0.5f64);
+    let _2:   N = na::convert(2.0f64);
+    let _6:   N = na::convert(6.0f64);
+
+    // The derivative of the rotation `q` at the time `t` after the beginning of the time step.
+    let derivative = |t: N, q: &Quaternion<N>| Quaternion::from_parts(na::zero(), (*av + *a_acc * t) * _1_2) * *q;
+
+    let q0 = Quaternion::identity();
+    let k1 = derivative(na::zero(), &q0);
+    let k2 = derivative(dt * _1_2, &(q0 + k1 * (dt * _1_2)));
+    let k3 = derivative(dt * _1_2, &(q0 + k2 * (dt * _1_2)));
+    let k4 = derivative(dt, &(q0 + k3 * dt));
+
+    Unit::new_normalize(q0 + (k1 + k2 * _2 + k3 * _2 + k4) * (dt / _6))
+}
@@ -6,27 +6,25 @@ use ncollide::utils::data::hash::UintTWHash;
 use ncollide::bounding_volume::BoundingVolume;
 use ncollide::query;
 use ncollide::bounding_volume;
-use ncollide::world::CollisionGroups;
+use ncollide::shape::Shape;
 use world::RigidBodyCollisionWorld;
-use object::{RigidBodyHandle, SensorHandle, RigidBody};
-use math::{Point, Translation};
+use object::{RigidBodyHandle, SensorHandle, RigidBody, WorldObject};
+use math::{Point, Vector, Orientation, Rotation, Translation, Isometry};
 
 
 struct CCDRigidBody<N: Real> {
     rigid_body:      RigidBodyHandle<N>,
-    sqthreshold:     N,
-    last_center:     Point<N>,
+    last_position:   Isometry<N>,
     trigger_sensors: bool,
     accept_zero:     bool
 }
 
 impl<N: Real> CCDRigidBody<N> {
-    fn new(rigid_body: RigidBodyHandle<N>, threshold: N, trigger_sensors: bool) -> CCDRigidBody<N> {
-        let last_center = rigid_body.borrow().position_center();
+    fn new(rigid_body: RigidBodyHandle<N>, trigger_sensors: bool) -> CCDRigidBody<N> {
+        let last_position = rigid_body.borrow().position().clone();
 
         CCDRigidBody {
-            sqthreshold:     threshold * threshold,
-            last_center:     last_center,
+            last_position:   last_position,
             rigid_body:      rigid_body,
             trigger_sensors: trigger_sensors,
             accept_zero:     true
@@ -35,9 +33,22 @@ impl<N: Real> CCDRigidBody<N> {
 }
 
 /// Handles Continuous Collision Detection.
+///
+/// Only the rigid bodies with CCD enabled (see `RigidBody::enable_ccd`) are handled, and only at
+/// the steps they move more than their motion threshold.
+///
+/// The time of impact of each fast-moving body is computed by conservative advancement of its
+/// whole motion (translation and rotation) since the last update. A body that would have
+/// tunneled through an obstacle is moved back to its pose at the time of impact, close enough to
+/// the obstacle for the narrow phase to generate the corresponding contact so it is handled by
+/// the constraints solver at the same step.
 pub struct TranslationalCCDMotionClamping<N: Real> {
-    objects: HashMap<usize, CCDRigidBody<N>, UintTWHash>,
-    intersected_sensors_cache: Vec<(N, SensorHandle<N>)>
+    objects:                   HashMap<usize, CCDRigidBody<N>, UintTWHash>,
+    intersected_sensors_cache: Vec<(N, SensorHandle<N>)>,
+    disabled_cache:            Vec<usize>,
+    hits:                      Vec<(RigidBodyHandle<N>, WorldObject<N>, N)>,
+    tolerance:                 N,
+    max_iterations:            usize
 }
 
 impl<N: Real> TranslationalCCDMotionClamping<N> {
@@ -46,24 +57,144 @@ impl<N: Real> TranslationalCCDMotionClamping<N> {
     pub fn new() -> TranslationalCCDMotionClamping<N> {
         TranslationalCCDMotionClamping {
             objects:                   HashMap::new(UintTWHash::new()),
-            intersected_sensors_cache: Vec::new()
+            intersected_sensors_cache: Vec::new(),
+            disabled_cache:            Vec::new(),
+            hits:                      Vec::new(),
+            tolerance:                 na::convert(0.005f64),
+            max_iterations:            32
         }
     }
 
+    /// The distance below which two bodies are considered to be in contact at the time of impact.
+    ///
+    /// This must be smaller than the contact prediction of the narrow phase so that a contact is
+    /// generated for bodies that were clamped.
+    #[inline]
+    pub fn tolerance(&self) -> N {
+        self.tolerance
+    }
+
+    /// Sets the distance below which two bodies are considered to be in contact at the time of
+    /// impact.
+    #[inline]
+    pub fn set_tolerance(&mut self, tolerance: N) {
+        assert!(tolerance > na::zero(), "The CCD tolerance must be strictly positive.");
+        self.tolerance = tolerance
+    }
+
+    /// The maximum number of conservative advancement iterations performed per pair of bodies.
+    #[inline]
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+
+    /// Sets the maximum number of conservative advancement iterations performed per pair of
+    /// bodies.
+    ///
+    /// If this number is reached, the time of impact found so far is used. It is always smaller
+    /// than the actual time of impact.
+    #[inline]
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations
+    }
+
     /// Enables continuous collision for the given rigid body.
     pub fn add_ccd_to(&mut self,
                       rigid_body:       RigidBodyHandle<N>,
                       motion_threshold: N,
                       trigger_sensors:  bool) {
+        rigid_body.borrow_mut().enable_ccd(motion_threshold);
         let _ = self.objects.insert(&*rigid_body as *const RefCell<RigidBody<N>> as usize,
-                                    CCDRigidBody::new(rigid_body, motion_threshold, trigger_sensors));
+                                    CCDRigidBody::new(rigid_body, trigger_sensors));
     }
 
-    /// Enables continuous collision for the given rigid body.
+    /// A copy of this CCD manager for the copies `bodies` of the rigid bodies, indexed by the
+    /// identifiers of the originals.
+    #[doc(hidden)]
+    pub fn clone_for_prediction(&self, bodies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>)
+                                -> TranslationalCCDMotionClamping<N> {
+        let mut res = TranslationalCCDMotionClamping::new();
+
+        res.tolerance      = self.tolerance;
+        res.max_iterations = self.max_iterations;
+
+        for e in self.objects.elements().iter() {
+            if let Some(rb) = bodies.find(&e.key) {
+                let object = CCDRigidBody {
+                    rigid_body:      rb.clone(),
+                    last_position:   e.value.last_position.clone(),
+                    trigger_sensors: e.value.trigger_sensors,
+                    accept_zero:     e.value.accept_zero
+                };
+
+                let _ = res.objects.insert(WorldObject::rigid_body_uid(rb), object);
+            }
+        }
+
+        res
+    }
+
+    /// Disables continuous collision for the given rigid body.
     pub fn remove_ccd_from(&mut self, rigid_body: &RigidBodyHandle<N>) {
+        rigid_body.borrow_mut().disable_ccd();
         let _ = self.objects.remove(&(&**rigid_body as *const RefCell<RigidBody<N>> as usize));
     }
 
+    /// Computes the time at which two rigid bodies will touch if they keep moving at the given
+    /// constant linear velocities from their current positions.
+    ///
+    /// The bodies are not rotated. Returns `None` if they do not touch before `max_toi`, or if
+    /// they are already penetrating but moving apart. This reuses the conservative advancement
+    /// of the CCD, so the bodies are at most `self.tolerance()` apart at the returned time.
+    pub fn time_of_impact(&self,
+                          rb1:     &RigidBody<N>,
+                          vel1:    &Vector<N>,
+                          rb2:     &RigidBody<N>,
+                          vel2:    &Vector<N>,
+                          max_toi: N)
+                          -> Option<N> {
+        // In the frame moving with `rb2`, only `rb1` moves.
+        let lin_disp = (*vel1 - *vel2) * max_toi;
+        let motion   = Motion::new(rb1.position().clone(), rb1.center_of_mass().clone(), lin_disp, na::zero(), na::zero());
+
+        motion.time_of_impact(rb1.shape().as_ref(),
+                              rb2.position(),
+                              rb2.shape().as_ref(),
+                              na::one(),
+                              self.tolerance,
+                              self.max_iterations)
+              .map(|t| t * max_toi)
+    }
+
+    /// The impacts found by the last update: each clamped rigid body, the object it hit, and the
+    /// time of impact as a fraction of the motion of the body since the previous update.
+    #[inline]
+    pub fn hits(&self) -> &[(RigidBodyHandle<N>, WorldObject<N>, N)] {
+        &self.hits[..]
+    }
+
+    /// Makes the current position of each handled rigid body the start of its next motion.
+    ///
+    /// This must be called when the updates were skipped during several steps, otherwise the
+    /// bodies would be clamped against the obstacles they met during those steps.
+    pub fn forget_motions(&mut self) {
+        for co in self.objects.elements_mut().iter_mut() {
+            co.value.last_position = co.value.rigid_body.borrow().position().clone();
+        }
+    }
+
+    /// Starts handling the given rigid body if it has CCD enabled and is not handled yet.
+    ///
+    /// Its sensors will not be triggered by the CCD.
+    #[doc(hidden)]
+    pub fn track(&mut self, rigid_body: &RigidBodyHandle<N>) {
+        let uid = &**rigid_body as *const RefCell<RigidBody<N>> as usize;
+
+        if rigid_body.borrow().ccd_enabled() && self.objects.find(&uid).is_none() {
+            let _ = self.objects.insert(uid, CCDRigidBody::new(rigid_body.clone(), false));
+        }
+    }
+
     /// Update the time of impacts and apply motion clamping when necessary.
     ///
     /// Returns `false` if no clamping was done. If at least one clamping was performed, the
@@ -71,46 +202,64 @@ impl<N: Real> TranslationalCCDMotionClamping<N> {
     pub fn update(&mut self, cw: &mut RigidBodyCollisionWorld<N>) -> bool {
         let mut update_collision_world = false;
 
+        self.hits.clear();
+
         // XXX: we should no do this in a sequential order because CCD between two fast
         // CCD-enabled objects will not work properly (it will be biased toward the first object).
         for co1 in self.objects.elements_mut().iter_mut() {
             let mut obj1 = co1.value.rigid_body.borrow_mut();
 
-            let movement = obj1.position_center() - co1.value.last_center;
+            let sqthreshold = match obj1.ccd_motion_threshold() {
+                Some(threshold) => threshold * threshold,
+                None            => {
+                    self.disabled_cache.push(co1.key);
+                    continue;
+                }
+            };
+
+            let last_position = co1.value.last_position.clone();
+
+            // The motion since the last update, expressed as a rotation wrt. the last center of
+            // mass followed by a translation.
+            let last_com = last_position * (obj1.position().inverse() * obj1.center_of_mass());
+            let lin_disp = obj1.center_of_mass() - last_com;
+            let ang_disp = (obj1.position().rotation * last_position.rotation.inverse()).scaled_axis();
 
-            if na::norm_squared(&movement) > co1.value.sqthreshold {
+            let bs     = bounding_volume::bounding_sphere(obj1.shape().as_ref(), &last_position);
+            let radius = bs.radius() + na::distance(bs.center(), &last_com);
+            let motion = Motion::new(last_position, last_com, lin_disp, ang_disp, radius);
+
+            if motion.max_disp * motion.max_disp > sqthreshold {
                 // Use CCD for this object.
                 let obj1_uid = &*co1.value.rigid_body as *const RefCell<RigidBody<N>> as usize;
 
-                let last_transform = Translation::from_vector(-movement) * obj1.position();
-                let begin_aabb     = bounding_volume::aabb(obj1.shape().as_ref(), &last_transform);
-                let end_aabb       = bounding_volume::aabb(obj1.shape().as_ref(), obj1.position());
-                let swept_aabb     = begin_aabb.merged(&end_aabb);
+                let begin_aabb = bounding_volume::aabb(obj1.shape().as_ref(), &motion.start);
+                let end_aabb   = bounding_volume::aabb(obj1.shape().as_ref(), obj1.position());
+                let swept_aabb = begin_aabb.merged(&end_aabb).loosened(motion.ang_bound);
 
                 /*
                  * Find the minimum TOI.
                  */
                 let mut min_toi   = na::one::<N>();
                 let mut toi_found = false;
-                let dir = movement.clone();
+                let mut obstacle  = None;
 
                 let _eps = N::default_epsilon();
 
-                // XXX: handle groups.
-                let all_groups = CollisionGroups::new();
+                // Only consider the objects the body is allowed to collide with.
+                let groups = obj1.collision_groups().as_collision_groups().clone();
 
-                // FIXME: performing a convex-cast here would be much more efficient.
-                for co2 in cw.interferences_with_aabb(&swept_aabb, &all_groups) {
+                for co2 in cw.interferences_with_aabb(&swept_aabb, &groups) {
                     if co2.data.uid() != obj1_uid {
                         let obj2 = co2.data.borrow();
 
-                        let toi = query::time_of_impact(
-                            &last_transform,
-                            &dir,
-                            obj1.shape().as_ref(),
-                            &obj2.position(),
-                            &na::zero(), // Assume the other object does not move.
-                            obj2.shape().as_ref());
+                        // Assume the other object does not move.
+                        let toi = motion.time_of_impact(obj1.shape().as_ref(),
+                                                        &obj2.position(),
+                                                        obj2.shape().as_ref(),
+                                                        min_toi,
+                                                        self.tolerance,
+                                                        self.max_iterations);
 
                         match toi {
                             Some(t) => {
@@ -124,7 +273,8 @@ impl<N: Real> TranslationalCCDMotionClamping<N> {
                                     toi_found = true;
 
                                     if t > _eps || co1.value.accept_zero {
-                                        min_toi = t;
+                                        min_toi  = t;
+                                        obstacle = Some(co2.data.clone());
                                     }
                                 }
                             },
@@ -134,15 +284,19 @@ impl<N: Real> TranslationalCCDMotionClamping<N> {
                 }
 
                 /*
-                 * Revert the object translation at the toi.
+                 * Revert the object motion at the toi.
                  */
                 if toi_found {
-                    obj1.append_translation(&Translation::from_vector(-dir * (na::one::<N>() - min_toi)));
+                    obj1.set_transformation(motion.position_at(min_toi));
                     co1.value.accept_zero = false;
 
                     // We moved the object: ensure the broad phase takes that in account.
                     cw.deferred_set_position(obj1_uid, obj1.position().clone());
                     update_collision_world = true;
+
+                    if let Some(obstacle) = obstacle {
+                        self.hits.push((co1.value.rigid_body.clone(), obstacle, min_toi));
+                    }
                 }
                 else {
                     co1.value.accept_zero = true;
@@ -175,10 +329,17 @@ impl<N: Real> TranslationalCCDMotionClamping<N> {
                 // }
             }
 
-            co1.value.last_center = obj1.position_center();
+            co1.value.last_position = obj1.position().clone();
             self.intersected_sensors_cache.clear();
         }
 
+        // Forget about the bodies that had their CCD disabled.
+        for uid in self.disabled_cache.iter() {
+            let _ = self.objects.remove(uid);
+        }
+
+        self.disabled_cache.clear();
+
         if update_collision_world {
             cw.update();
             true
@@ -188,3 +349,87 @@ impl<N: Real> TranslationalCCDMotionClamping<N> {
         }
     }
 }
+
+/// The motion of a body between two updates, parametrized by a time in `[0, 1]`.
+struct Motion<N: Real> {
+    start:     Isometry<N>,
+    com:       Point<N>,
+    lin_disp:  Vector<N>,
+    ang_disp:  Orientation<N>,
+    // Upper bound of the displacement of any point of the body due to its rotation.
+    ang_bound: N,
+    max_disp:  N
+}
+
+impl<N: Real> Motion<N> {
+    fn new(start: Isometry<N>, com: Point<N>, lin_disp: Vector<N>, ang_disp: Orientation<N>, radius: N)
+           -> Motion<N> {
+        let ang_bound = na::norm(&ang_disp) * radius;
+        let max_disp  = na::norm(&lin_disp) + ang_bound;
+
+        Motion {
+            start:     start,
+            com:       com,
+            lin_disp:  lin_disp,
+            ang_disp:  ang_disp,
+            ang_bound: ang_bound,
+            max_disp:  max_disp
+        }
+    }
+
+    /// The position of the body at the time `t`.
+    fn position_at(&self, t: N) -> Isometry<N> {
+        let mut disp: Isometry<N> = na::one();
+        disp.append_rotation_wrt_point_mut(&Rotation::from_scaled_axis(self.ang_disp * t), &self.com);
+        disp.append_translation_mut(&Translation::from_vector(self.lin_disp * t));
+
+        disp * self.start
+    }
+
+    /// Computes the time of impact of the moving shape `g1` with the static shape `g2` using
+    /// conservative advancement.
+    ///
+    /// Returns `None` if there is no impact before `max_toi`.
+    fn time_of_impact(&self,
+                      g1:             &Shape<Point<N>, Isometry<N>>,
+                      m2:             &Isometry<N>,
+                      g2:             &Shape<Point<N>, Isometry<N>>,
+                      max_toi:        N,
+                      tolerance:      N,
+                      max_iterations: usize)
+                      -> Option<N> {
+        let _0_5: N = na::convert(0.5f64);
+        let mut toi = na::zero::<N>();
+
+        for _ in 0 .. max_iterations {
+            // Objects farther than the remaining motion cannot be reached.
+            let prediction = self.max_disp * (max_toi - toi) + tolerance;
+            let contact    = match query::contact(&self.position_at(toi), g1, m2, g2, prediction) {
+                Some(c) => c,
+                None    => return None
+            };
+
+            // Upper bound of the approach speed of the closest points along the contact normal.
+            let approach = na::dot(&self.lin_disp, &contact.normal) + self.ang_bound;
+
+            if approach <= na::zero() {
+                return None;
+            }
+
+            let dist = -contact.depth;
+
+            if dist <= tolerance {
+                return Some(toi);
+            }
+
+            // The shapes stay at least `tolerance / 2` apart until `toi + (dist - tolerance / 2) / approach`.
+            toi = toi + (dist - tolerance * _0_5) / approach;
+
+            if toi > max_toi {
+                return None;
+            }
+        }
+
+        Some(toi)
+    }
+}
@@ -0,0 +1,177 @@
+//! Force field swirling the bodies around an axis.
+
+use num::Bounded;
+use na;
+use alga::general::Real;
+use ncollide::bounding_volume::{AABB, BoundingVolume};
+
+use math::{Point, Vector, Orientation};
+use object::RigidBody;
+use integration::{ForceGenerator, Falloff};
+use utils::GeneralizedCross;
+
+/// A force field swirling the bodies around an axis, e.g., a tornado or a whirlpool.
+///
+/// The field pushes the center of mass of the bodies within its radius and its bounds along the
+/// circles around its axis, and optionally towards the axis. The distance from a body to the
+/// field is the one to the axis.
+#[derive(Clone)]
+pub struct VortexField<N: Real> {
+    center:   Point<N>,
+    axis:     Orientation<N>,
+    strength: N,
+    inward:   N,
+    falloff:  Falloff<N>,
+    radius:   N,
+    bounds:   Option<AABB<Point<N>>>,
+    per_mass: bool
+}
+
+impl<N: Real> VortexField<N> {
+    /// Creates an unbounded field swirling the bodies around the axis through `center`.
+    ///
+    /// The bodies turn counterclockwise around `axis`, which is normalized, if the strength is
+    /// positive. In 2D, the only possible axis is the unit one-dimensional vector. The strength
+    /// is an acceleration, i.e., the force is proportional to the mass of each body.
+    pub fn new(center: Point<N>, axis: Orientation<N>, strength: N, falloff: Falloff<N>) -> VortexField<N> {
+        VortexField {
+            center:   center,
+            axis:     na::normalize(&axis),
+            strength: strength,
+            inward:   na::zero(),
+            falloff:  falloff,
+            radius:   Bounded::max_value(),
+            bounds:   None,
+            per_mass: true
+        }
+    }
+
+    /// A point of the axis of this field.
+    pub fn center(&self) -> &Point<N> {
+        &self.center
+    }
+
+    /// Sets a point of the axis of this field.
+    pub fn set_center(&mut self, center: Point<N>) {
+        self.center = center
+    }
+
+    /// The axis of this field.
+    pub fn axis(&self) -> &Orientation<N> {
+        &self.axis
+    }
+
+    /// Sets the axis of this field, which is normalized.
+    pub fn set_axis(&mut self, axis: Orientation<N>) {
+        self.axis = na::normalize(&axis)
+    }
+
+    /// The strength of this field along the circles around its axis.
+    pub fn strength(&self) -> N {
+        self.strength
+    }
+
+    /// Sets the strength of this field along the circles around its axis, negative to swirl the
+    /// bodies clockwise.
+    pub fn set_strength(&mut self, strength: N) {
+        self.strength = strength
+    }
+
+    /// The strength of this field towards its axis.
+    pub fn inward_strength(&self) -> N {
+        self.inward
+    }
+
+    /// Sets the strength of this field towards its axis, negative to eject the bodies. Defaults
+    /// to zero.
+    pub fn set_inward_strength(&mut self, inward: N) {
+        self.inward = inward
+    }
+
+    /// How the strength of this field decreases with the distance to its axis.
+    pub fn falloff(&self) -> &Falloff<N> {
+        &self.falloff
+    }
+
+    /// Sets how the strength of this field decreases with the distance to its axis.
+    pub fn set_falloff(&mut self, falloff: Falloff<N>) {
+        self.falloff = falloff
+    }
+
+    /// The distance to the axis beyond which this field does not apply.
+    pub fn radius(&self) -> N {
+        self.radius
+    }
+
+    /// Sets the distance to the axis beyond which this field does not apply.
+    ///
+    /// The field is unbounded by default, in which case the linear and quadratic falloffs are
+    /// constant.
+    pub fn set_radius(&mut self, radius: N) {
+        self.radius = radius
+    }
+
+    /// The box this field is limited to, if any.
+    pub fn bounds(&self) -> Option<&AABB<Point<N>>> {
+        self.bounds.as_ref()
+    }
+
+    /// Limits this field to the bodies which center of mass lies in a box. Set to `None` to
+    /// remove the limit, which is the default.
+    pub fn set_bounds(&mut self, bounds: Option<AABB<Point<N>>>) {
+        self.bounds = bounds
+    }
+
+    /// Whether the strengths are accelerations, or forces independent from the mass of the
+    /// bodies.
+    pub fn is_mass_proportional(&self) -> bool {
+        self.per_mass
+    }
+
+    /// Sets whether the strengths are accelerations, which is the default, or forces
+    /// independent from the mass of the bodies.
+    pub fn set_mass_proportional(&mut self, per_mass: bool) {
+        self.per_mass = per_mass
+    }
+}
+
+impl<N: Real> ForceGenerator<N> for VortexField<N> {
+    fn forces(&mut self, _: N, rb: &RigidBody<N>) -> (Vector<N>, Orientation<N>) {
+        let com = rb.center_of_mass();
+
+        if let Some(ref bounds) = self.bounds {
+            if !bounds.contains(&AABB::new(*com, *com)) {
+                return (na::zero(), na::zero())
+            }
+        }
+
+        let mut dir  = off_axis(&self.axis, &(*com - self.center));
+        let distance = match dir.try_normalize_mut(N::default_epsilon()) {
+            Some(distance) => distance,
+            None           => return (na::zero(), na::zero())
+        };
+
+        // The direction of the rotation around the axis, i.e., `axis × dir`.
+        let tangent = dir.gcross_matrix().transpose() * self.axis;
+        let factor  = self.falloff.factor(distance, self.radius);
+        let mut res = (tangent * self.strength - dir * self.inward) * factor;
+
+        if self.per_mass {
+            res = res * rb.mass().unwrap_or(na::zero());
+        }
+
+        (res, na::zero())
+    }
+}
+
+// The component of `v` orthogonal to `axis`.
+#[cfg(feature = "dim3")]
+fn off_axis<N: Real>(axis: &Orientation<N>, v: &Vector<N>) -> Vector<N> {
+    *v - *axis * na::dot(axis, v)
+}
+
+// The component of `v` orthogonal to `axis`, which is orthogonal to the plane.
+#[cfg(feature = "dim2")]
+fn off_axis<N: Real>(_: &Orientation<N>, v: &Vector<N>) -> Vector<N> {
+    *v
+}
@@ -50,15 +50,42 @@ Use `make examples` to build the demos and execute `./your_favorite_example_here
 to see all the cool stuffs you can do.
 
 ## Features
-- Static and dynamic rigid bodies.
+- Static, kinematic, and dynamic rigid bodies.
 - Common convex primitives: cone, box, ball, cylinder.
 - Concave geometries build from convex primitives (aka. compound geometries).
 - Stable stacking.
+- Contacts and joints warm-started with the impulses of the previous step.
+- Contacts between two bodies solved simultaneously by blocks.
+- Pyramidal or exact Coulomb cone friction.
+- Gauss-Seidel or Jacobi constraint solver iterations.
+- Solver convergence statistics.
+- Per-step reports of the work done and of the time spent on each stage.
+- Optional shock propagation for tall stacks.
+- Soft contacts with a stiffness and a damping.
+- Optional direct solver for small articulated systems.
+- Islands of constraints solved on several threads, with the `parallel` feature.
 - Island based sleeping (objects deactivation).
 - Ray casting.
 - Swept sphere based continuous collision detection.
-- Ball-in-socket joint.
+- Semi-implicit and explicit Euler, and fourth-order Runge-Kutta integrators.
+- Per-body and world-wide maximum velocities.
+- Bodies constrained to a plane in 3D, for 2.5D games.
+- Fixed time step accumulator with interpolated positions for rendering.
+- Ball-in-socket joint, with swing and twist limits in 3D, and angular damping.
 - Fixed joint.
+- Hinge joint, with angle limits, angular damping, and velocity and servo motors.
+- Prismatic joint, with translation limits, and velocity and servo motors.
+- Distance joint, with a fixed or bounded distance.
+- Pulleys.
+- Gears coupling hinges.
+- Racks and pinions coupling hinges to prismatic joints.
+- Standalone angular motors driving the relative rotation of two bodies.
+- Damped springs.
+- Universal joint, in 3D.
+- Breakable joints, and joint reaction forces.
+- Ragdoll builder.
+- User-defined force generators, buoyancy, and radial and vortex force fields.
+- Gravity zones overriding the gravity locally, e.g., zero-G rooms or spherical planets.
 - Sensors.
 
 ## What is missing?
@@ -66,11 +93,8 @@ to see all the cool stuffs you can do.
 become a grown up. Many missing features are because of missing features on
 **ncollide**. Features missing from **nphysics** itself include:
 
-- kinematic bodies
-- efficient signaling system
-- more joints, joint limits, joint motors and breakable joints.
 - soft-bodies (see https://github.com/natal/roft for a draft)
-- parallel pipeline
+- parallel collision detection and integration
 - GPU-based pipeline
 
 ## Dependencies
@@ -114,6 +138,9 @@ pub mod world;
 pub mod object;
 pub mod utils;
 pub mod volumetric;
+pub mod debug;
+#[cfg(feature = "tracing")]
+pub mod trace;
 // mod tests;
 
 
@@ -125,6 +125,22 @@ macro_rules! collision_groups_wrapper_impl(
                 self.collision_groups.modify_whitelist(SENSOR_GROUP_ID, false);
             }
 
+            /// Sets whether this entity can be hit by ray casts and shape casts.
+            ///
+            /// This does not affect its interactions with other entities. Objects are visible to
+            /// queries by default. The world notices the change once it is stepped, or once an
+            /// object is added to or removed from it.
+            #[inline]
+            pub fn set_visible_to_queries(&mut self, visible: bool) {
+                self.visible_to_queries = visible
+            }
+
+            /// Whether this entity can be hit by ray casts and shape casts.
+            #[inline]
+            pub fn is_visible_to_queries(&self) -> bool {
+                self.visible_to_queries
+            }
+
             /// Enables self interaction detection.
             #[inline]
             pub fn enable_self_interaction(&mut self) {
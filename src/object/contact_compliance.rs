@@ -0,0 +1,46 @@
+use alga::general::Real;
+use na;
+
+/// The stiffness and damping of the contacts of a soft body, e.g., made of mud, foam, or rubber.
+///
+/// The contacts of a soft body are not resolved rigidly: they behave as a spring of the given
+/// stiffness and damping along the contact normal, so that the bodies interpenetrate visibly
+/// under a load. The stiffness applies at each contact point: a box resting on its four corners
+/// penetrates by a quarter of its weight divided by the stiffness.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactCompliance<N: Real> {
+    /// The force per unit of penetration depth.
+    pub stiffness: N,
+    /// The force per unit of penetration velocity.
+    pub damping:   N
+}
+
+impl<N: Real> ContactCompliance<N> {
+    /// Creates a new contact compliance.
+    ///
+    /// Panics if `stiffness` is not positive, or if `damping` is negative.
+    pub fn new(stiffness: N, damping: N) -> ContactCompliance<N> {
+        assert!(stiffness > na::zero(), "The contact stiffness must be positive.");
+        assert!(damping >= na::zero(), "The contact damping must not be negative.");
+
+        ContactCompliance {
+            stiffness: stiffness,
+            damping:   damping
+        }
+    }
+
+    /// The compliance of a contact between two soft bodies: their springs are in series.
+    pub fn in_series(&self, other: &ContactCompliance<N>) -> ContactCompliance<N> {
+        let damping = if self.damping.is_zero() || other.damping.is_zero() {
+            na::zero()
+        }
+        else {
+            self.damping * other.damping / (self.damping + other.damping)
+        };
+
+        ContactCompliance {
+            stiffness: self.stiffness * other.stiffness / (self.stiffness + other.stiffness),
+            damping:   damping
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use alga::general::Real;
+use na;
+use ncollide::procedural::TriMesh;
+use ncollide::shape::{Compound, ConvexHull, ShapeHandle};
+use ncollide::transformation;
+use object::RigidBody;
+use math::{Point, Isometry};
+
+/// Approximates a concave triangle mesh by a compound of convex pieces.
+///
+/// The narrow phase only handles convex shapes for dynamic rigid bodies, so this is the way to
+/// simulate a concave mesh as a dynamic body. `max_concavity` bounds the concavity of each
+/// piece, relative to the diagonal of the bounding box of `mesh`: smaller values give more
+/// accurate, but more numerous, pieces. The vertex normals of `mesh` are computed if it has none.
+pub fn convex_decomposition<N: Real>(mesh: &TriMesh<Point<N>>, max_concavity: N) -> Compound<Point<N>, Isometry<N>> {
+    let mut mesh = mesh.clone();
+
+    if !mesh.has_normals() {
+        mesh.recompute_normals();
+    }
+
+    let (hulls, _) = transformation::hacd(mesh, max_concavity, 1);
+    let pieces     = hulls.into_iter().map(|hull| {
+        (na::one(), ShapeHandle::new(ConvexHull::new(hull.coords)))
+    }).collect();
+
+    Compound::new(pieces)
+}
+
+impl<N: Real> RigidBody<N> {
+    /// Creates a new dynamic rigid body from a concave triangle mesh.
+    ///
+    /// See `convex_decomposition` for the meaning of `max_concavity`.
+    pub fn new_dynamic_concave(mesh: &TriMesh<Point<N>>, max_concavity: N, density: N, restitution: N, friction: N)
+                               -> RigidBody<N> {
+        RigidBody::new_dynamic(convex_decomposition(mesh, max_concavity), density, restitution, friction)
+    }
+}
@@ -0,0 +1,59 @@
+use alga::general::Real;
+
+/// A friction coefficient that depends on the slip speed of a contact.
+///
+/// The curve is piecewise-linear between its samples, and constant before the first one and
+/// after the last one. This can model the transition from static to dynamic friction, or the
+/// friction weakening of tires, skis, and sleds at high slip speeds.
+#[derive(Clone, Debug)]
+pub struct FrictionCurve<N: Real> {
+    // (slip speed, friction coefficient), sorted by increasing slip speed.
+    samples: Vec<(N, N)>
+}
+
+impl<N: Real> FrictionCurve<N> {
+    /// Creates a friction curve from `(slip speed, friction coefficient)` samples.
+    ///
+    /// The samples do not have to be sorted. Panics if `samples` is empty.
+    pub fn new(mut samples: Vec<(N, N)>) -> FrictionCurve<N> {
+        assert!(!samples.is_empty(), "A friction curve must have at least one sample.");
+
+        samples.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        FrictionCurve {
+            samples: samples
+        }
+    }
+
+    /// A curve going linearly from the `static_coeff` coefficient at rest, to the
+    /// `dynamic_coeff` coefficient at a slip speed of `transition_speed` and above.
+    pub fn static_to_dynamic(static_coeff: N, dynamic_coeff: N, transition_speed: N) -> FrictionCurve<N> {
+        FrictionCurve::new(vec![ (N::zero(), static_coeff), (transition_speed, dynamic_coeff) ])
+    }
+
+    /// The `(slip speed, friction coefficient)` samples of this curve, sorted by slip speed.
+    #[inline]
+    pub fn samples(&self) -> &[(N, N)] {
+        &self.samples[..]
+    }
+
+    /// The friction coefficient at the given slip speed.
+    pub fn sample(&self, slip_speed: N) -> N {
+        let first = self.samples[0];
+
+        if slip_speed <= first.0 {
+            return first.1;
+        }
+
+        for w in self.samples.windows(2) {
+            let (s1, c1) = w[0];
+            let (s2, c2) = w[1];
+
+            if slip_speed <= s2 {
+                return if s2 > s1 { c1 + (c2 - c1) * (slip_speed - s1) / (s2 - s1) } else { c2 };
+            }
+        }
+
+        self.samples[self.samples.len() - 1].1
+    }
+}
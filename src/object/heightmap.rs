@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use alga::general::Real;
+use na::{self, Point3};
+use ncollide::shape::TriMesh;
+use object::RigidBody;
+use math::{Point, Vector};
+
+/// Builds a triangle mesh terrain from a grayscale image.
+///
+/// The image is given as `width * height` pixels stored row by row. The terrain is centered at
+/// the origin, its rows spanning `scale.x` along the `x` axis and its columns spanning `scale.z`
+/// along the `z` axis. A pixel value of 255 is elevated by `scale.y` along the `y` axis. The
+/// vertex normals are computed from the height differences with the neighbouring pixels.
+///
+/// Panics if the image has less than two pixels along one of its dimensions, or if `pixels` does
+/// not contain exactly `width * height` elements.
+pub fn heightmap_from_image<N: Real>(pixels: &[u8], width: usize, height: usize, scale: Vector<N>)
+                                     -> TriMesh<Point<N>> {
+    assert!(width >= 2 && height >= 2, "A heightmap must have at least 2x2 pixels.");
+    assert!(pixels.len() == width * height, "The heightmap pixels do not match its dimensions.");
+
+    let _1: N     = na::one();
+    let _2: N     = na::convert(2.0f64);
+    let _255: N   = na::convert(255.0f64);
+    let dx        = scale.x / na::convert((width - 1) as f64);
+    let dz        = scale.z / na::convert((height - 1) as f64);
+    let elevation = |i: usize, j: usize| na::convert::<f64, N>(pixels[j * width + i] as f64) / _255 * scale.y;
+
+    let mut vertices = Vec::with_capacity(width * height);
+    let mut normals  = Vec::with_capacity(width * height);
+    let mut indices  = Vec::with_capacity((width - 1) * (height - 1) * 2);
+
+    for j in 0 .. height {
+        for i in 0 .. width {
+            let x = dx * na::convert(i as f64) - scale.x / _2;
+            let z = dz * na::convert(j as f64) - scale.z / _2;
+
+            vertices.push(Point::new(x, elevation(i, j), z));
+
+            // Central differences, one-sided on the borders.
+            let (i1, i2) = (i.saturating_sub(1), (i + 1).min(width - 1));
+            let (j1, j2) = (j.saturating_sub(1), (j + 1).min(height - 1));
+            let slope_x  = (elevation(i2, j) - elevation(i1, j)) / (dx * na::convert((i2 - i1) as f64));
+            let slope_z  = (elevation(i, j2) - elevation(i, j1)) / (dz * na::convert((j2 - j1) as f64));
+
+            normals.push(na::normalize(&Vector::new(-slope_x, _1, -slope_z)));
+        }
+    }
+
+    // Two counterclockwise triangles per cell, seen from above.
+    for j in 0 .. height - 1 {
+        for i in 0 .. width - 1 {
+            let a = j * width + i;
+            let b = a + 1;
+            let c = a + width;
+            let d = c + 1;
+
+            indices.push(Point3::new(a, c, b));
+            indices.push(Point3::new(b, c, d));
+        }
+    }
+
+    TriMesh::new(Arc::new(vertices), Arc::new(indices), None, Some(Arc::new(normals)))
+}
+
+impl<N: Real> RigidBody<N> {
+    /// Creates a new static terrain from a grayscale image.
+    ///
+    /// See `heightmap_from_image` for the meaning of `pixels`, `width`, `height`, and `scale`.
+    pub fn new_static_heightmap(pixels: &[u8], width: usize, height: usize, scale: Vector<N>,
+                                restitution: N, friction: N)
+                                -> RigidBody<N> {
+        let terrain = heightmap_from_image(pixels, width, height, scale);
+
+        RigidBody::new_static(terrain, restitution, friction)
+    }
+}
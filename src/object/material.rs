@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use alga::general::Real;
+use na;
+use ncollide::shape::{Shape, ShapeHandle};
+use object::{RigidBody, ShapeRegistry, ShapeId};
+use math::{Point, Isometry, AngularInertia};
+use volumetric::Volumetric;
+
+/// The physical properties of the matter rigid bodies are made of.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material<N: Real> {
+    /// The mass per unit of volume.
+    pub density:     N,
+    /// The restitution coefficient.
+    pub restitution: N,
+    /// The friction coefficient.
+    pub friction:    N
+}
+
+impl<N: Real> Material<N> {
+    /// Creates a new material.
+    pub fn new(density: N, restitution: N, friction: N) -> Material<N> {
+        Material {
+            density:     density,
+            restitution: restitution,
+            friction:    friction
+        }
+    }
+
+    /// Wood, with a density of 700 kg/m³.
+    pub fn wood() -> Material<N> {
+        Material::new(na::convert(700.0f64), na::convert(0.4f64), na::convert(0.5f64))
+    }
+
+    /// Steel, with a density of 7850 kg/m³.
+    pub fn steel() -> Material<N> {
+        Material::new(na::convert(7850.0f64), na::convert(0.3f64), na::convert(0.4f64))
+    }
+
+    /// Rubber, with a density of 1100 kg/m³.
+    pub fn rubber() -> Material<N> {
+        Material::new(na::convert(1100.0f64), na::convert(0.8f64), na::convert(0.9f64))
+    }
+}
+
+/// A set of named materials.
+///
+/// Building the rigid bodies of a scene from a few shared materials ensures their relative masses
+/// are physically plausible, their mass being derived from the volume of their shape.
+#[derive(Clone, Debug)]
+pub struct MaterialTable<N: Real> {
+    materials: HashMap<String, Material<N>>
+}
+
+impl<N: Real> MaterialTable<N> {
+    /// Creates an empty material table.
+    pub fn new() -> MaterialTable<N> {
+        MaterialTable {
+            materials: HashMap::new()
+        }
+    }
+
+    /// Creates a material table containing the `"wood"`, `"steel"`, and `"rubber"` presets.
+    ///
+    /// Their densities are given in kg/m³, i.e., lengths are assumed to be in meters.
+    pub fn with_presets() -> MaterialTable<N> {
+        let mut res = MaterialTable::new();
+
+        let _ = res.insert("wood", Material::wood());
+        let _ = res.insert("steel", Material::steel());
+        let _ = res.insert("rubber", Material::rubber());
+
+        res
+    }
+
+    /// Adds a material, returning the one previously registered with the same name.
+    pub fn insert(&mut self, name: &str, material: Material<N>) -> Option<Material<N>> {
+        self.materials.insert(name.to_string(), material)
+    }
+
+    /// Removes a material.
+    pub fn remove(&mut self, name: &str) -> Option<Material<N>> {
+        self.materials.remove(name)
+    }
+
+    /// The material named `name`.
+    pub fn get(&self, name: &str) -> Option<&Material<N>> {
+        self.materials.get(name)
+    }
+
+    /// Creates a new dynamic rigid body made of the material `name`.
+    ///
+    /// Panics if there is no such material.
+    pub fn new_dynamic<G>(&self, shape: G, name: &str) -> RigidBody<N>
+        where G: Send + Sync + Shape<Point<N>, Isometry<N>> + Volumetric<N, Point<N>, AngularInertia<N>> {
+        RigidBody::new_dynamic_with_material(shape, self.expect(name))
+    }
+
+    /// Creates a new dynamic rigid body that shares the shape `id` of `registry`, made of the
+    /// material `name`.
+    ///
+    /// Panics if there is no such material, or if the shape was registered without mass
+    /// properties.
+    pub fn new_dynamic_from_registry(&self, registry: &ShapeRegistry<N>, id: ShapeId, name: &str) -> RigidBody<N> {
+        let material = self.expect(name);
+
+        registry.new_dynamic(id, material.density, material.restitution, material.friction)
+    }
+
+    fn expect(&self, name: &str) -> &Material<N> {
+        match self.get(name) {
+            Some(material) => material,
+            None           => panic!("Unknown material: {}.", name)
+        }
+    }
+}
+
+impl<N: Real> RigidBody<N> {
+    /// Creates a new rigid body that can move, made of `material`.
+    ///
+    /// Its mass is the volume of `shape` times the density of the material.
+    pub fn new_dynamic_with_material<G>(shape: G, material: &Material<N>) -> RigidBody<N>
+        where G: Send + Sync + Shape<Point<N>, Isometry<N>> + Volumetric<N, Point<N>, AngularInertia<N>> {
+        let props = shape.mass_properties(material.density);
+
+        RigidBody::new(ShapeHandle::new(shape), Some(props), material.restitution, material.friction)
+    }
+}
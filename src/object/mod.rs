@@ -1,15 +1,35 @@
 //! Objects that may be added to the physical world.
 
 pub use self::rigid_body::{RigidBody, RigidBodyHandle, ActivationState, RigidBodyState};
+pub use self::rigid_body_dynamics::RigidBodyDynamics;
+pub use self::friction_curve::FrictionCurve;
+pub use self::contact_compliance::ContactCompliance;
 pub use self::sensor::{Sensor, SensorHandle, SensorProximityCollector};
+pub use self::shape_registry::{ShapeRegistry, ShapeId};
+pub use self::material::{Material, MaterialTable};
+pub use self::support_function_shape::SupportFunctionShape;
 pub use self::world_object::{WorldObject, WorldObjectBorrowed, WorldObjectBorrowedMut};
 pub use self::rigid_body_collision_groups::RigidBodyCollisionGroups;
 pub use self::sensor_collision_groups::SensorCollisionGroups;
 pub use self::collision_groups_wrapper_impl::{STATIC_GROUP_ID, SENSOR_GROUP_ID};
+#[cfg(feature = "dim3")]
+pub use self::heightmap::heightmap_from_image;
+#[cfg(feature = "dim3")]
+pub use self::convex_decomposition::convex_decomposition;
 
 mod rigid_body;
+mod rigid_body_dynamics;
 mod sensor;
+mod friction_curve;
+mod contact_compliance;
+mod shape_registry;
+mod material;
+mod support_function_shape;
 mod world_object;
 mod collision_groups_wrapper_impl;
 mod rigid_body_collision_groups;
 mod sensor_collision_groups;
+#[cfg(feature = "dim3")]
+mod heightmap;
+#[cfg(feature = "dim3")]
+mod convex_decomposition;
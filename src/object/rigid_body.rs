@@ -12,7 +12,7 @@ use ncollide::shape::{Shape, ShapeHandle};
 use utils::GeneralizedCross;
 use math::{Point, Vector, Orientation, Rotation, Translation, Isometry, AngularInertia};
 use volumetric::{InertiaTensor, Volumetric};
-use object::RigidBodyCollisionGroups;
+use object::{RigidBodyCollisionGroups, FrictionCurve, ContactCompliance};
 
 /// A shared, mutable, rigid body.
 pub type RigidBodyHandle<N> = Rc<RefCell<RigidBody<N>>>;
@@ -66,17 +66,40 @@ pub struct RigidBody<N: Real> {
     lin_acc:              Vector<N>,
     ang_acc:              Orientation<N>,
     gravity:              Vector<N>,
+    ang_gravity:          Orientation<N>,
     lin_force:            Vector<N>,
     ang_force:            Orientation<N>,
+    // The force and torque applied by the force generators of the world during the next step.
+    generated_lin_force:  Vector<N>,
+    generated_ang_force:  Orientation<N>,
+    // The force and torque accumulated by the user for the current step only.
+    accumulated_force:    Vector<N>,
+    accumulated_torque:   Orientation<N>,
     restitution:          N,
     friction:             N,
+    friction_curve:       Option<FrictionCurve<N>>,
+    contact_compliance:   Option<ContactCompliance<N>>,
     index:                isize,
+    // The rank of this body in the order of addition to the world.
+    serial:               usize,
     activation_state:     ActivationState<N>,
     sleep_threshold:      Option<N>,
     lin_acc_scale:        Vector<N>,      // FIXME: find a better way of doing that.
     ang_acc_scale:        Orientation<N>, // FIXME: find a better way of doing that.
     margin:               N,
+    prediction:           Option<N>,
+    max_lin_vel:          Option<N>,
+    max_ang_vel:          Option<N>,
     collision_groups:     RigidBodyCollisionGroups,
+    speculative_contacts: bool,
+    substepping:          bool,
+    #[cfg(feature = "dim3")]
+    planar_depth:         Option<N>,
+    one_way_normal:       Option<Vector<N>>,
+    ccd_threshold:        Option<N>,
+    kinematic:            bool,
+    // The position of a kinematic body at the last step, to derive its velocities.
+    kinematic_position:   Option<Isometry<N>>,
     user_data:            Option<Box<Any>>
 }
 
@@ -84,31 +107,50 @@ impl<N: Real> Clone for RigidBody<N> {
     /// Clones this rigid body but not its associated user-data.
     fn clone(&self) -> RigidBody<N> {
         RigidBody {
-            state:             self.state.clone(),
-            shape:             self.shape.clone(),
-            local_to_world:    self.local_to_world.clone(),
-            lin_vel:           self.lin_vel.clone(),
-            ang_vel:           self.ang_vel.clone(),
-            inv_mass:          self.inv_mass.clone(),
-            ls_inv_inertia:    self.ls_inv_inertia.clone(),
-            inv_inertia:       self.inv_inertia.clone(),
-            ls_center_of_mass: self.ls_center_of_mass.clone(),
-            center_of_mass:    self.center_of_mass.clone(),
-            lin_acc:           self.lin_acc.clone(),
-            ang_acc:           self.ang_acc.clone(),
-            gravity:           self.gravity.clone(),
-            lin_force:         self.lin_force.clone(),
-            ang_force:         self.ang_force.clone(),
-            restitution:       self.restitution.clone(),
-            friction:          self.friction.clone(),
-            index:             self.index.clone(),
-            activation_state:  self.activation_state.clone(),
-            sleep_threshold:   self.sleep_threshold.clone(),
-            lin_acc_scale:     self.lin_acc_scale.clone(),
-            ang_acc_scale:     self.ang_acc_scale.clone(),
-            margin:            self.margin.clone(),
-            collision_groups:  self.collision_groups.clone(),
-            user_data:         None
+            state:                self.state.clone(),
+            shape:                self.shape.clone(),
+            local_to_world:       self.local_to_world.clone(),
+            lin_vel:              self.lin_vel.clone(),
+            ang_vel:              self.ang_vel.clone(),
+            inv_mass:             self.inv_mass.clone(),
+            ls_inv_inertia:       self.ls_inv_inertia.clone(),
+            inv_inertia:          self.inv_inertia.clone(),
+            ls_center_of_mass:    self.ls_center_of_mass.clone(),
+            center_of_mass:       self.center_of_mass.clone(),
+            lin_acc:              self.lin_acc.clone(),
+            ang_acc:              self.ang_acc.clone(),
+            gravity:              self.gravity.clone(),
+            ang_gravity:          self.ang_gravity.clone(),
+            lin_force:            self.lin_force.clone(),
+            ang_force:            self.ang_force.clone(),
+            generated_lin_force:  self.generated_lin_force.clone(),
+            generated_ang_force:  self.generated_ang_force.clone(),
+            accumulated_force:    self.accumulated_force.clone(),
+            accumulated_torque:   self.accumulated_torque.clone(),
+            restitution:          self.restitution.clone(),
+            friction:             self.friction.clone(),
+            friction_curve:       self.friction_curve.clone(),
+            contact_compliance:   self.contact_compliance,
+            index:                self.index.clone(),
+            serial:               self.serial,
+            activation_state:     self.activation_state.clone(),
+            sleep_threshold:      self.sleep_threshold.clone(),
+            lin_acc_scale:        self.lin_acc_scale.clone(),
+            ang_acc_scale:        self.ang_acc_scale.clone(),
+            margin:               self.margin.clone(),
+            prediction:           self.prediction.clone(),
+            max_lin_vel:          self.max_lin_vel.clone(),
+            max_ang_vel:          self.max_ang_vel.clone(),
+            collision_groups:     self.collision_groups.clone(),
+            speculative_contacts: self.speculative_contacts,
+            substepping:          self.substepping,
+            #[cfg(feature = "dim3")]
+            planar_depth:         self.planar_depth,
+            one_way_normal:       self.one_way_normal.clone(),
+            ccd_threshold:        self.ccd_threshold.clone(),
+            kinematic:            self.kinematic,
+            kinematic_position:   self.kinematic_position.clone(),
+            user_data:            None
         }
     }
 }
@@ -174,6 +216,82 @@ impl<N: Real> RigidBody<N> {
         self.margin = margin;
     }
 
+    /// The distance within which contacts with this object are generated before they actually
+    /// touch, in addition to its margin.
+    ///
+    /// If `None`, half of the world's prediction is used.
+    #[inline]
+    pub fn prediction(&self) -> Option<N> {
+        self.prediction
+    }
+
+    /// Sets the distance within which contacts with this object are generated before they
+    /// actually touch, in addition to its margin.
+    ///
+    /// This also enlarges its bounding box on the broad phase. Large slow bodies usually need a
+    /// smaller prediction than small fast ones. Set it to `None` to use half of the world's
+    /// prediction. Changes are taken into account at the next step.
+    #[inline]
+    pub fn set_prediction(&mut self, prediction: Option<N>) {
+        self.prediction = prediction;
+    }
+
+    /// The maximum norm of the linear velocity of this rigid body.
+    ///
+    /// If `None`, the world's maximum linear velocity is used.
+    #[inline]
+    pub fn max_lin_vel(&self) -> Option<N> {
+        self.max_lin_vel
+    }
+
+    /// Sets the maximum norm of the linear velocity of this rigid body.
+    ///
+    /// The velocity is clamped at the end of each step, after the integration and the constraints
+    /// resolution, so that a body pushed out of a deep penetration does not fly away and hit the
+    /// rest of the scene. Set it to `None` to use the world's maximum linear velocity.
+    #[inline]
+    pub fn set_max_lin_vel(&mut self, max_lin_vel: Option<N>) {
+        self.max_lin_vel = max_lin_vel;
+    }
+
+    /// The maximum norm of the angular velocity of this rigid body.
+    ///
+    /// If `None`, the world's maximum angular velocity is used.
+    #[inline]
+    pub fn max_ang_vel(&self) -> Option<N> {
+        self.max_ang_vel
+    }
+
+    /// Sets the maximum norm of the angular velocity of this rigid body.
+    ///
+    /// Set it to `None` to use the world's maximum angular velocity.
+    #[inline]
+    pub fn set_max_ang_vel(&mut self, max_ang_vel: Option<N>) {
+        self.max_ang_vel = max_ang_vel;
+    }
+
+    /// Scales the velocities of this rigid body down to its maximum velocities, or to the given
+    /// world ones if it has none. It's internally called by the world at the end of each step.
+    #[doc(hidden)]
+    #[inline]
+    pub fn clamp_velocities(&mut self, max_lin_vel: Option<N>, max_ang_vel: Option<N>) {
+        if let Some(max) = self.max_lin_vel.or(max_lin_vel) {
+            let norm = na::norm(&self.lin_vel);
+
+            if norm > max {
+                self.lin_vel = self.lin_vel * (max / norm);
+            }
+        }
+
+        if let Some(max) = self.max_ang_vel.or(max_ang_vel) {
+            let norm = na::norm(&self.ang_vel);
+
+            if norm > max {
+                self.ang_vel = self.ang_vel * (max / norm);
+            }
+        }
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn index(&self) -> isize {
@@ -186,6 +304,18 @@ impl<N: Real> RigidBody<N> {
         self.index = id
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn serial(&self) -> usize {
+        self.serial
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_serial(&mut self, serial: usize) {
+        self.serial = serial
+    }
+
     /// Gets a reference to this body's center of mass.
     #[inline]
     pub fn center_of_mass(&self) -> &Point<N> {
@@ -205,8 +335,10 @@ impl<N: Real> RigidBody<N> {
 
     /// Gets this body's restitution coefficent.
     ///
-    /// The actual restitution coefficient of a contact is computed averaging the two bodies
-    /// restitution coefficient.
+    /// The actual restitution coefficient of a contact is the product of the two bodies
+    /// restitution coefficients. It is applied independently at each contact point, depending on
+    /// its own approach velocity. The penetration correction of a bouncing contact point does not
+    /// add to its rebound.
     #[inline]
     pub fn restitution(&self) -> N {
         self.restitution.clone()
@@ -214,13 +346,51 @@ impl<N: Real> RigidBody<N> {
 
     /// Gets this body's friction coefficient.
     ///
-    /// The actual friction coefficient of a contact is computed averaging the two bodies friction
-    /// coefficient.
+    /// The actual friction coefficient of a contact is computed multiplying the two bodies friction
+    /// coefficient. This is ignored if this body has a friction curve.
     #[inline]
     pub fn friction(&self) -> N {
         self.friction.clone()
     }
 
+    /// Gets this body's slip-speed-dependent friction coefficient, if any.
+    #[inline]
+    pub fn friction_curve(&self) -> Option<&FrictionCurve<N>> {
+        self.friction_curve.as_ref()
+    }
+
+    /// Sets this body's slip-speed-dependent friction coefficient.
+    ///
+    /// Set it to `None` to use the constant `self.friction()` coefficient instead.
+    #[inline]
+    pub fn set_friction_curve(&mut self, curve: Option<FrictionCurve<N>>) {
+        self.friction_curve = curve
+    }
+
+    /// This body's friction coefficient for a contact slipping at the speed `slip_speed`.
+    #[inline]
+    pub fn friction_at(&self, slip_speed: N) -> N {
+        match self.friction_curve {
+            Some(ref curve) => curve.sample(slip_speed),
+            None            => self.friction.clone()
+        }
+    }
+
+    /// The stiffness and damping of the contacts of this body, if it is soft.
+    #[inline]
+    pub fn contact_compliance(&self) -> Option<&ContactCompliance<N>> {
+        self.contact_compliance.as_ref()
+    }
+
+    /// Sets the stiffness and damping of the contacts of this body.
+    ///
+    /// The contacts between two soft bodies combine both compliances. Set it to `None` to resolve
+    /// the contacts of this body rigidly.
+    #[inline]
+    pub fn set_contact_compliance(&mut self, compliance: Option<ContactCompliance<N>>) {
+        self.contact_compliance = compliance
+    }
+
     /// Indicates whether or not this rigid body is active.
     ///
     /// An inactive rigid body is a body that did not move for some time. It is not longer
@@ -287,28 +457,28 @@ impl<N: Real> RigidBody<N> {
     ///
     /// Use this if the shape is shared by multiple rigid bodies.
     /// Set `mass_properties` to `None` if the rigid body is to be static.
+    ///
+    /// Invalid mass properties are corrected instead of letting them propagate NaNs to the
+    /// solver:
+    ///
+    /// * a body with a zero, negative, or non-finite mass (e.g. created with such a density, or
+    ///   from a shape without volume) becomes static.
+    /// * a singular angular inertia (e.g. of a flat or needle-like shape) is inflated by a small
+    ///   fraction of its largest diagonal element so that it can be inverted.
+    /// * a body with a zero or non-finite angular inertia cannot rotate.
+    ///
+    /// Use `RigidBody::can_move` to check whether the body ended up static.
     pub fn new(shape:           ShapeHandle<Point<N>, Isometry<N>>,
                mass_properties: Option<(N, Point<N>, AngularInertia<N>)>,
                restitution:     N,
                friction:        N)
                -> RigidBody<N> {
         let (inv_mass, center_of_mass, inv_inertia, active, state, groups) =
-            match mass_properties {
+            match mass_properties.and_then(sanitize_mass_properties) {
                 None => (na::zero(), na::origin(), na::zero(),
                          ActivationState::Inactive, RigidBodyState::Static,
                          RigidBodyCollisionGroups::new_static()),
-                Some((mass, com, inertia)) => {
-                    if mass.is_zero() {
-                        panic!("A dynamic body must not have a zero volume.")
-                    }
-
-                    let ii: AngularInertia<N>;
-
-                    match inertia.try_inverse() {
-                        Some(i) => ii = i,
-                        None    => ii = na::zero()
-                    }
-
+                Some((mass, com, ii)) => {
                     // Will be set to a sensible value later.
                     let active = ActivationState::Active(Bounded::max_value());
                     let groups = RigidBodyCollisionGroups::new_dynamic();
@@ -320,31 +490,50 @@ impl<N: Real> RigidBody<N> {
 
         let mut res =
             RigidBody {
-                state:             state,
-                shape:             shape,
-                local_to_world:    na::one(),
-                lin_vel:           na::zero(),
-                ang_vel:           na::zero(),
-                inv_mass:          inv_mass,
-                ls_inv_inertia:    inv_inertia.clone(),
-                inv_inertia:       inv_inertia,
-                ls_center_of_mass: center_of_mass,
-                center_of_mass:    na::origin(),
-                lin_acc:           na::zero(),
-                ang_acc:           na::zero(),
-                gravity:           na::zero(),
-                lin_force:         na::zero(),
-                ang_force:         na::zero(),
-                friction:          friction,
-                restitution:       restitution,
-                index:             0,
-                activation_state:  active,
-                sleep_threshold:   Some(na::convert(0.1f64)),
-                lin_acc_scale:     Vector::from_element(N::one()),
-                ang_acc_scale:     Orientation::from_element(N::one()),
-                margin:            na::convert(0.04f64), // FIXME: do not hard-code this.
-                collision_groups:  groups,
-                user_data:         None
+                state:                state,
+                shape:                shape,
+                local_to_world:       na::one(),
+                lin_vel:              na::zero(),
+                ang_vel:              na::zero(),
+                inv_mass:             inv_mass,
+                ls_inv_inertia:       inv_inertia.clone(),
+                inv_inertia:          inv_inertia,
+                ls_center_of_mass:    center_of_mass,
+                center_of_mass:       na::origin(),
+                lin_acc:              na::zero(),
+                ang_acc:              na::zero(),
+                gravity:              na::zero(),
+                ang_gravity:          na::zero(),
+                lin_force:            na::zero(),
+                ang_force:            na::zero(),
+                generated_lin_force:  na::zero(),
+                generated_ang_force:  na::zero(),
+                accumulated_force:    na::zero(),
+                accumulated_torque:   na::zero(),
+                friction:             friction,
+                friction_curve:       None,
+                contact_compliance:   None,
+                restitution:          restitution,
+                index:                0,
+                serial:               0,
+                activation_state:     active,
+                sleep_threshold:      Some(na::convert(0.1f64)),
+                lin_acc_scale:        Vector::from_element(N::one()),
+                ang_acc_scale:        Orientation::from_element(N::one()),
+                margin:               na::convert(0.04f64), // FIXME: do not hard-code this.
+                prediction:           None,
+                max_lin_vel:          None,
+                max_ang_vel:          None,
+                collision_groups:     groups,
+                speculative_contacts: false,
+                substepping:          false,
+                #[cfg(feature = "dim3")]
+                planar_depth:         None,
+                one_way_normal:       None,
+                ccd_threshold:        None,
+                kinematic:            false,
+                kinematic_position:   None,
+                user_data:            None
             };
 
         res.update_center_of_mass();
@@ -468,6 +657,26 @@ impl<N: Real> RigidBody<N> {
         self.update_lin_acc();
     }
 
+    /// Sets the angular acceleration applied to this RigidBody whatever its inertia. It's
+    /// internally called from BodyForceGenerator, don't use manually.
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_ang_gravity(&mut self, ang_gravity: Orientation<N>) {
+        self.ang_gravity = ang_gravity;
+        self.update_ang_acc();
+    }
+
+    /// Sets the force and torque applied by the force generators of the world. It's internally
+    /// called by the world, don't use manually.
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_generated_forces(&mut self, force: Vector<N>, torque: Orientation<N>) {
+        self.generated_lin_force = force;
+        self.generated_ang_force = torque;
+        self.update_lin_acc();
+        self.update_ang_acc();
+    }
+
     /// Resets linear and angular force.
     #[inline]
     pub fn clear_forces(&mut self) {
@@ -517,15 +726,72 @@ impl<N: Real> RigidBody<N> {
         self.append_ang_force(pnt_to_com.gcross(&force));
     }
 
+    /// Adds a force applied on the center of mass during the next step only.
+    ///
+    /// Unlike the forces appended with `append_lin_force`, the accumulated forces are cleared at
+    /// the end of each step, e.g., for the thrusters or the inputs of a player.
+    #[inline]
+    pub fn accumulate_force(&mut self, force: Vector<N>) {
+        self.accumulated_force = self.accumulated_force + force;
+        self.update_lin_acc();
+        self.wake_up();
+    }
+
+    /// Adds a torque applied during the next step only.
+    #[inline]
+    pub fn accumulate_torque(&mut self, torque: Orientation<N>) {
+        self.accumulated_torque = self.accumulated_torque + torque;
+        self.update_ang_acc();
+        self.wake_up();
+    }
+
+    /// Adds a force applied at `point`, in world-space, during the next step only.
+    #[inline]
+    pub fn accumulate_force_at_point(&mut self, force: Vector<N>, point: &Point<N>) {
+        let arm = *point - self.center_of_mass;
+
+        self.accumulate_force(force);
+        self.accumulate_torque(arm.gcross(&force));
+    }
+
+    /// The force accumulated for the next step.
+    #[inline]
+    pub fn accumulated_force(&self) -> Vector<N> {
+        self.accumulated_force
+    }
+
+    /// The torque accumulated for the next step.
+    #[inline]
+    pub fn accumulated_torque(&self) -> Orientation<N> {
+        self.accumulated_torque
+    }
+
+    /// Clears the accumulated force and torque. It's internally called by the world at the end of
+    /// each step.
+    #[doc(hidden)]
+    #[inline]
+    pub fn clear_accumulated_forces(&mut self) {
+        if self.accumulated_force != na::zero() || self.accumulated_torque != na::zero() {
+            self.accumulated_force  = na::zero();
+            self.accumulated_torque = na::zero();
+            self.update_lin_acc();
+            self.update_ang_acc();
+        }
+    }
+
     /// Update the linear acceleraction from the applied forces.
     #[inline]
     fn update_lin_acc(&mut self) {
-        self.lin_acc = (self.lin_force * self.inv_mass + self.gravity).component_mul(&self.lin_acc_scale);
+        let force = self.lin_force + self.generated_lin_force + self.accumulated_force;
+
+        self.lin_acc = (force * self.inv_mass + self.gravity).component_mul(&self.lin_acc_scale);
     }
     /// Update the angular acceleraction from the applied forces.
     #[inline]
     fn update_ang_acc(&mut self) {
-        self.ang_acc = (self.inv_inertia * self.ang_force).component_mul(&self.ang_acc_scale);
+        let torque = self.ang_force + self.generated_ang_force + self.accumulated_torque;
+
+        self.ang_acc = (self.inv_inertia * torque + self.ang_gravity).component_mul(&self.ang_acc_scale);
     }
 
     /// Forces the body to respond to any impulses before the next tick.
@@ -686,6 +952,166 @@ impl<N: Real> RigidBody<N> {
         self.collision_groups = new_groups;
     }
 
+    /// Whether or not speculative contacts are enabled for this rigid body.
+    #[inline]
+    pub fn speculative_contacts_enabled(&self) -> bool {
+        self.speculative_contacts
+    }
+
+    /// Enables speculative contacts for this rigid body.
+    ///
+    /// Contacts will then be generated within a margin proportional to this body maximum velocity,
+    /// before any actual penetration occurs. This prevents fast objects from tunnelling through
+    /// thin obstacles without the cost of continuous collision detection. The restitution
+    /// coefficient is ignored for contacts that are not in touch yet.
+    ///
+    /// The margin is computed from the maximum velocities of this body, or of the world, and
+    /// remains the same while the body speeds up. Without maximum velocities, the velocities of
+    /// this body at the next step are used as bounds instead.
+    #[inline]
+    pub fn enable_speculative_contacts(&mut self) {
+        self.speculative_contacts = true;
+    }
+
+    /// Disables speculative contacts for this rigid body.
+    #[inline]
+    pub fn disable_speculative_contacts(&mut self) {
+        self.speculative_contacts = false;
+    }
+
+    /// The depth of the plane this rigid body is constrained to, if any.
+    #[cfg(feature = "dim3")]
+    #[inline]
+    pub fn planar_depth(&self) -> Option<N> {
+        self.planar_depth
+    }
+
+    /// Constrains this rigid body to translate in the plane `z = depth`, and to rotate around the
+    /// `z` axis only, e.g., for a 2.5D game. Set to `None` to remove the constraint.
+    ///
+    /// The constraint is enforced by the `BodyPlanarIntegrator`, which must be set as the
+    /// integrator of the world.
+    #[cfg(feature = "dim3")]
+    #[inline]
+    pub fn set_planar_depth(&mut self, depth: Option<N>) {
+        self.planar_depth = depth;
+    }
+
+    /// Whether or not this rigid body is simulated with the world's internal substeps.
+    #[inline]
+    pub fn substepping_enabled(&self) -> bool {
+        self.substepping
+    }
+
+    /// Enables substepping for this rigid body.
+    ///
+    /// The body, and its contacts and joints, will then be simulated with the number of substeps
+    /// set by `World::set_num_substeps` while the rest of the world runs at the base rate. This is
+    /// meant for a few bodies requiring high-quality interactions, e.g., a player vehicle.
+    #[inline]
+    pub fn enable_substepping(&mut self) {
+        self.substepping = true;
+    }
+
+    /// Disables substepping for this rigid body.
+    #[inline]
+    pub fn disable_substepping(&mut self) {
+        self.substepping = false;
+    }
+
+    /// The normal, in local space, of the only side of this rigid body that can be collided with.
+    #[inline]
+    pub fn one_way_normal(&self) -> Option<&Vector<N>> {
+        self.one_way_normal.as_ref()
+    }
+
+    /// Makes this rigid body collide only with bodies coming from the side `normal` points to.
+    ///
+    /// The contacts of another body with this one are solved only if, when they start touching,
+    /// the contact normal agrees with `normal` (expressed in this body's local space) and the
+    /// other body is not moving away from this side. They are ignored otherwise until the two
+    /// bodies stop touching. This is typically used for jump-through platforms. Set it to `None`
+    /// to collide on all sides again.
+    #[inline]
+    pub fn set_one_way_normal(&mut self, normal: Option<Vector<N>>) {
+        self.one_way_normal = normal.map(|n| na::normalize(&n));
+    }
+
+    /// Whether or not continuous collision detection is enabled for this rigid body.
+    #[inline]
+    pub fn ccd_enabled(&self) -> bool {
+        self.ccd_threshold.is_some()
+    }
+
+    /// The motion threshold above which continuous collision detection is performed for this
+    /// rigid body, if it is enabled.
+    #[inline]
+    pub fn ccd_motion_threshold(&self) -> Option<N> {
+        self.ccd_threshold
+    }
+
+    /// Enables continuous collision detection for this rigid body.
+    ///
+    /// It will be performed only at the steps this body moves more than `motion_threshold`, so
+    /// that slow bodies do not pay its cost.
+    #[inline]
+    pub fn enable_ccd(&mut self, motion_threshold: N) {
+        self.ccd_threshold = Some(motion_threshold);
+    }
+
+    /// Disables continuous collision detection for this rigid body.
+    #[inline]
+    pub fn disable_ccd(&mut self) {
+        self.ccd_threshold = None;
+    }
+
+    /// Whether or not this rigid body is kinematic.
+    #[inline]
+    pub fn is_kinematic(&self) -> bool {
+        self.kinematic
+    }
+
+    /// Makes this static rigid body kinematic, or static again.
+    ///
+    /// A kinematic body is not moved by the physics world but by the application, e.g., from an
+    /// animation, by setting its position before each step. Its linear and angular velocities
+    /// are then derived at each step from its motion since the previous step, so that the
+    /// contacts with it, including friction, are solved as if it was moving instead of being
+    /// stationary. The bodies it pushes are woken up.
+    pub fn set_kinematic(&mut self, kinematic: bool) {
+        assert!(!kinematic || !self.can_move(), "Only a static rigid body can be kinematic.");
+
+        self.kinematic          = kinematic;
+        self.kinematic_position = None;
+
+        if !kinematic {
+            self.lin_vel = na::zero();
+            self.ang_vel = na::zero();
+        }
+    }
+
+    /// Derives the velocities of this kinematic body from its motion since the last call.
+    ///
+    /// Returns `true` if the body moved.
+    #[doc(hidden)]
+    pub fn update_kinematic_velocities(&mut self, dt: N) -> bool {
+        let moved = match self.kinematic_position {
+            Some(last) => {
+                let last_com = last * self.ls_center_of_mass;
+
+                self.lin_vel = (self.center_of_mass - last_com) / dt;
+                self.ang_vel = (self.local_to_world.rotation * last.rotation.inverse()).scaled_axis() / dt;
+
+                last != self.local_to_world
+            },
+            None => false
+        };
+
+        self.kinematic_position = Some(self.local_to_world.clone());
+
+        moved
+    }
+
     /// Reference to user-defined data attached to this rigid body.
     #[inline]
     pub fn user_data(&self) -> Option<&Box<Any>> {
@@ -719,3 +1145,48 @@ impl<N, M> HasBoundingVolume<M, AABB<Point<N>>> for RigidBody<N>
         bounding_volume::aabb(self.shape.as_ref(), &(*m * self.local_to_world)).loosened(self.margin())
     }
 }
+
+// Returns the mass, center of mass, and inverse angular inertia to be used by a dynamic body,
+// or `None` if it should be static.
+fn sanitize_mass_properties<N: Real>((mass, com, inertia): (N, Point<N>, AngularInertia<N>))
+                                     -> Option<(N, Point<N>, AngularInertia<N>)> {
+    if !is_finite(mass) || mass <= na::zero() || !com.coords.iter().all(|e| is_finite(*e)) {
+        return None;
+    }
+
+    if !inertia.iter().all(|e| is_finite(*e)) {
+        return Some((mass, com, na::zero()));
+    }
+
+    if let Some(ii) = invert_inertia(&inertia) {
+        return Some((mass, com, ii));
+    }
+
+    let max_diag = inertia.diagonal().iter().fold(N::zero(), |m, e| m.max(*e));
+
+    if max_diag > na::zero() {
+        let inflated = inertia + AngularInertia::identity() * (max_diag * na::convert(1.0e-3f64));
+
+        if let Some(ii) = invert_inertia(&inflated) {
+            return Some((mass, com, ii));
+        }
+    }
+
+    Some((mass, com, na::zero()))
+}
+
+fn invert_inertia<N: Real>(inertia: &AngularInertia<N>) -> Option<AngularInertia<N>> {
+    inertia.try_inverse().and_then(|ii| {
+        if ii.iter().all(|e| is_finite(*e)) {
+            Some(ii)
+        }
+        else {
+            None
+        }
+    })
+}
+
+#[inline]
+fn is_finite<N: Real>(x: N) -> bool {
+    x == x && x.abs() < N::max_value()
+}
@@ -6,7 +6,8 @@ use object::{STATIC_GROUP_ID, SENSOR_GROUP_ID};
 /// The `group 29` is reserved and you cannot use it.
 #[derive(Clone, Debug, Copy)]
 pub struct RigidBodyCollisionGroups {
-    collision_groups: CollisionGroups
+    collision_groups:   CollisionGroups,
+    visible_to_queries: bool
 }
 
 impl RigidBodyCollisionGroups {
@@ -24,7 +25,8 @@ impl RigidBodyCollisionGroups {
         groups.modify_whitelist(SENSOR_GROUP_ID, false);
 
         RigidBodyCollisionGroups{
-            collision_groups: groups
+            collision_groups:   groups,
+            visible_to_queries: true
         }
     }
 
@@ -44,7 +46,8 @@ impl RigidBodyCollisionGroups {
         groups.modify_blacklist(SENSOR_GROUP_ID, true);
 
         RigidBodyCollisionGroups{
-            collision_groups: groups
+            collision_groups:   groups,
+            visible_to_queries: true
         }
     }
 
@@ -0,0 +1,102 @@
+use std::ops::Deref;
+use std::cell::RefMut;
+use std::any::Any;
+
+use alga::general::Real;
+use object::RigidBody;
+use math::{Vector, Orientation};
+
+/// A mutable view of a rigid body restricted to its dynamics.
+///
+/// This is given to the callbacks run while the world iterates through its objects. Every
+/// property of the body can be read, but only its velocities, forces and user-data can be
+/// modified: changes that would invalidate the iteration (shape, collision groups, position,
+/// etc.) are statically prevented.
+pub struct RigidBodyDynamics<'a, N: Real + 'a> {
+    rb: RefMut<'a, RigidBody<N>>
+}
+
+impl<'a, N: Real> RigidBodyDynamics<'a, N> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(rb: RefMut<'a, RigidBody<N>>) -> RigidBodyDynamics<'a, N> {
+        RigidBodyDynamics {
+            rb: rb
+        }
+    }
+
+    /// Sets the linear velocity of this rigid body.
+    #[inline]
+    pub fn set_lin_vel(&mut self, lv: Vector<N>) {
+        self.rb.set_lin_vel(lv)
+    }
+
+    /// Sets the angular velocity of this rigid body.
+    #[inline]
+    pub fn set_ang_vel(&mut self, av: Orientation<N>) {
+        self.rb.set_ang_vel(av)
+    }
+
+    /// Applies a one-time central impulse.
+    #[inline]
+    pub fn apply_central_impulse(&mut self, impulse: Vector<N>) {
+        self.rb.apply_central_impulse(impulse)
+    }
+
+    /// Applies a one-time angular impulse.
+    #[inline]
+    pub fn apply_angular_momentum(&mut self, ang_moment: Orientation<N>) {
+        self.rb.apply_angular_momentum(ang_moment)
+    }
+
+    /// Applies a one-time impulse to a point relative to the center of mass.
+    #[inline]
+    pub fn apply_impulse_wrt_point(&mut self, impulse: Vector<N>, pnt_to_com: Vector<N>) {
+        self.rb.apply_impulse_wrt_point(impulse, pnt_to_com)
+    }
+
+    /// Adds an additional linear force.
+    #[inline]
+    pub fn append_lin_force(&mut self, force: Vector<N>) {
+        self.rb.append_lin_force(force)
+    }
+
+    /// Adds an additional angular force.
+    #[inline]
+    pub fn append_ang_force(&mut self, force: Orientation<N>) {
+        self.rb.append_ang_force(force)
+    }
+
+    /// Adds an additional force acting at a point different to the center of mass.
+    #[inline]
+    pub fn append_force_wrt_point(&mut self, force: Vector<N>, pnt_to_com: Vector<N>) {
+        self.rb.append_force_wrt_point(force, pnt_to_com)
+    }
+
+    /// Reset the linear and angular forces applied to this rigid body.
+    #[inline]
+    pub fn clear_forces(&mut self) {
+        self.rb.clear_forces()
+    }
+
+    /// Forces the body to respond to any impulses before the next tick.
+    #[inline]
+    pub fn wake_up(&mut self) {
+        self.rb.wake_up()
+    }
+
+    /// Mutable reference to the user-defined data of this rigid body.
+    #[inline]
+    pub fn user_data_mut(&mut self) -> Option<&mut Box<Any>> {
+        self.rb.user_data_mut()
+    }
+}
+
+impl<'a, N: Real> Deref for RigidBodyDynamics<'a, N> {
+    type Target = RigidBody<N>;
+
+    #[inline]
+    fn deref(&self) -> &RigidBody<N> {
+        &*self.rb
+    }
+}
@@ -6,7 +6,8 @@ use object::{STATIC_GROUP_ID, SENSOR_GROUP_ID};
 /// The `group 29` is reserved and you cannot use it.
 #[derive(Clone, Debug, Copy)]
 pub struct SensorCollisionGroups {
-    collision_groups: CollisionGroups
+    collision_groups:   CollisionGroups,
+    visible_to_queries: bool
 }
 
 impl SensorCollisionGroups {
@@ -26,7 +27,8 @@ impl SensorCollisionGroups {
         groups.modify_blacklist(SENSOR_GROUP_ID, true);
 
         SensorCollisionGroups{
-            collision_groups: groups
+            collision_groups:   groups,
+            visible_to_queries: true
         }
     }
 
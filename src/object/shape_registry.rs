@@ -0,0 +1,209 @@
+use alga::general::Real;
+use na;
+use ncollide::bounding_volume::AABB;
+use ncollide::shape::{Shape, ShapeHandle};
+use math::{Point, Vector, Isometry, Translation, AngularInertia};
+use volumetric::Volumetric;
+use object::{RigidBody, RigidBodyHandle, Sensor, SupportFunctionShape};
+
+/// The identifier of a shape registered into a `ShapeRegistry`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ShapeId(usize);
+
+impl ShapeId {
+    /// The index of this shape on the registry that generated this identifier.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+struct RegisteredShape<N: Real> {
+    shape:           ShapeHandle<Point<N>, Isometry<N>>,
+    // Mass properties for a unit density. `None` if the shape cannot be used by dynamic bodies.
+    mass_properties: Option<(N, Point<N>, AngularInertia<N>)>
+}
+
+/// A set of immutable shapes meant to be shared by many rigid bodies and sensors.
+///
+/// Instancing a registered shape does not duplicate it: every body created from the same
+/// `ShapeId` references the same shape, and its unit mass properties are computed only once at
+/// registration. The per-body properties (position, scale, density, restitution, friction,
+/// margin, etc.) remain independent.
+pub struct ShapeRegistry<N: Real> {
+    shapes: Vec<RegisteredShape<N>>
+}
+
+impl<N: Real> ShapeRegistry<N> {
+    /// Creates an empty shape registry.
+    pub fn new() -> ShapeRegistry<N> {
+        ShapeRegistry {
+            shapes: Vec::new()
+        }
+    }
+
+    /// Registers a shape that may be used by dynamic and static bodies alike.
+    pub fn add<G>(&mut self, shape: G) -> ShapeId
+        where G: Send + Sync + Shape<Point<N>, Isometry<N>> + Volumetric<N, Point<N>, AngularInertia<N>> {
+        let props = shape.mass_properties(N::one());
+
+        self.add_shared(ShapeHandle::new(shape), Some(props))
+    }
+
+    /// Registers a shape that may only be used by static bodies and sensors.
+    pub fn add_static<G>(&mut self, shape: G) -> ShapeId
+        where G: Send + Sync + Shape<Point<N>, Isometry<N>> {
+        self.add_shared(ShapeHandle::new(shape), None)
+    }
+
+    /// Registers an already shared shape.
+    ///
+    /// The mass properties must be given for a unit density. Set `unit_mass_properties` to
+    /// `None` if the shape is not to be used by dynamic bodies.
+    pub fn add_shared(&mut self,
+                      shape:                ShapeHandle<Point<N>, Isometry<N>>,
+                      unit_mass_properties: Option<(N, Point<N>, AngularInertia<N>)>)
+                      -> ShapeId {
+        let id = ShapeId(self.shapes.len());
+
+        self.shapes.push(RegisteredShape {
+            shape:           shape,
+            mass_properties: unit_mass_properties
+        });
+
+        id
+    }
+
+    /// The number of shapes registered so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// Whether this registry is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// The shape with the identifier `id`.
+    #[inline]
+    pub fn shape(&self, id: ShapeId) -> &ShapeHandle<Point<N>, Isometry<N>> {
+        &self.shapes[id.0].shape
+    }
+
+    /// The mass properties of the shape `id` with the given density, if it was registered with
+    /// mass properties.
+    ///
+    /// Those are the mass properties for a unit density scaled by `density`: the same as the ones
+    /// of a dynamic body built from the shape itself.
+    pub fn mass_properties(&self, id: ShapeId, density: N) -> Option<(N, Point<N>, AngularInertia<N>)> {
+        self.shapes[id.0].mass_properties.as_ref().map(|&(mass, ref com, ref inertia)| {
+            (mass * density, com.clone(), inertia.clone() * density)
+        })
+    }
+
+    /// The shape `id` uniformly scaled by `scale`.
+    ///
+    /// The scaled shape references the registered one instead of copying it, and has its mass
+    /// properties scaled accordingly. Panics if `scale` is not positive, or if the shape is not
+    /// convex, i.e., does not implement `SupportMap`, unless `scale` is one.
+    pub fn scaled_shape(&self, id: ShapeId, scale: N) -> ShapeHandle<Point<N>, Isometry<N>> {
+        assert!(scale > N::zero(), "The scale of a shape must be positive.");
+
+        if scale == N::one() {
+            return self.shape(id).clone();
+        }
+
+        let shape = self.shape(id).clone();
+        assert!(shape.as_support_map().is_some(), "Only the convex shapes can be scaled.");
+
+        let support = shape.clone();
+        let props   = self.shapes[id.0].mass_properties.clone();
+
+        let scaled = SupportFunctionShape::new(
+            move |dir: &Vector<N>| {
+                let pt = support.as_support_map().unwrap().support_point(&Isometry::identity(), dir);
+                Point::from_coordinates(pt.coords * scale)
+            },
+            // The shape scaled then transformed by `m` is the shape transformed by `m` with its
+            // translation divided by `scale`, then scaled.
+            move |m: &Isometry<N>| {
+                let m    = Isometry::from_parts(Translation::from_vector(m.translation.vector / scale), m.rotation);
+                let aabb = shape.aabb(&m);
+
+                AABB::new(Point::from_coordinates(aabb.mins().coords * scale),
+                          Point::from_coordinates(aabb.maxs().coords * scale))
+            },
+            move |density: N| {
+                match props {
+                    Some(ref props) => scale_mass_properties(props, scale, density),
+                    None            => panic!("Cannot compute the mass properties of a shape registered as static.")
+                }
+            });
+
+        ShapeHandle::new(scaled)
+    }
+
+    /// The mass properties of the shape `id` uniformly scaled by `scale`, with the given density,
+    /// if it was registered with mass properties.
+    pub fn scaled_mass_properties(&self, id: ShapeId, scale: N, density: N)
+                                  -> Option<(N, Point<N>, AngularInertia<N>)> {
+        self.shapes[id.0].mass_properties.as_ref().map(|props| scale_mass_properties(props, scale, density))
+    }
+
+    /// Creates a new dynamic rigid body that shares the shape `id`.
+    ///
+    /// Panics if the shape was registered without mass properties.
+    pub fn new_dynamic(&self, id: ShapeId, density: N, restitution: N, friction: N) -> RigidBody<N> {
+        let props = match self.mass_properties(id, density) {
+            Some(props) => props,
+            None        => panic!("Cannot create a dynamic body from a shape registered as static.")
+        };
+
+        RigidBody::new(self.shape(id).clone(), Some(props), restitution, friction)
+    }
+
+    /// Creates a new dynamic rigid body that shares the shape `id` uniformly scaled by `scale`.
+    ///
+    /// Panics if the shape was registered without mass properties, or cannot be scaled (see
+    /// `self.scaled_shape`).
+    pub fn new_dynamic_scaled(&self, id: ShapeId, scale: N, density: N, restitution: N, friction: N)
+                              -> RigidBody<N> {
+        let props = match self.scaled_mass_properties(id, scale, density) {
+            Some(props) => props,
+            None        => panic!("Cannot create a dynamic body from a shape registered as static.")
+        };
+
+        RigidBody::new(self.scaled_shape(id, scale), Some(props), restitution, friction)
+    }
+
+    /// Creates a new static rigid body that shares the shape `id` uniformly scaled by `scale`.
+    ///
+    /// Panics if the shape cannot be scaled (see `self.scaled_shape`).
+    pub fn new_static_scaled(&self, id: ShapeId, scale: N, restitution: N, friction: N) -> RigidBody<N> {
+        RigidBody::new(self.scaled_shape(id, scale), None, restitution, friction)
+    }
+
+    /// Creates a new static rigid body that shares the shape `id`.
+    pub fn new_static(&self, id: ShapeId, restitution: N, friction: N) -> RigidBody<N> {
+        RigidBody::new(self.shape(id).clone(), None, restitution, friction)
+    }
+
+    /// Creates a new sensor that shares the shape `id`.
+    pub fn new_sensor(&self, id: ShapeId, parent: Option<RigidBodyHandle<N>>) -> Sensor<N> {
+        Sensor::new_with_shared_shape(self.shape(id).clone(), parent)
+    }
+}
+
+// Scales the mass properties `props` of a unit density shape uniformly scaled by `scale`, and
+// multiplies them by `density`.
+fn scale_mass_properties<N: Real>(props: &(N, Point<N>, AngularInertia<N>), scale: N, density: N)
+                                  -> (N, Point<N>, AngularInertia<N>) {
+    let &(mass, ref com, ref inertia) = props;
+    let volume_scale = scale.powi(na::dimension::<Vector<N>>() as i32);
+
+    (mass * volume_scale * density,
+     Point::from_coordinates(com.coords * scale),
+     inertia.clone() * (volume_scale * scale * scale * density))
+}
@@ -0,0 +1,136 @@
+use alga::general::{Real, Id};
+use na;
+use ncollide::bounding_volume::AABB;
+use ncollide::shape::{Shape, SupportMap};
+use ncollide::query::{Ray, RayCast, RayIntersection, PointQuery, PointProjection};
+use ncollide::query::algorithms::gjk;
+use ncollide::query::algorithms::minkowski_sampling;
+use ncollide::query::algorithms::simplex::Simplex;
+use ncollide::query::algorithms::johnson_simplex::JohnsonSimplex;
+use ncollide::query::ray_internal;
+use ncollide::math::Isometry as IsometryOps;
+use volumetric::Volumetric;
+use math::{Point, Vector, Isometry, Translation, AngularInertia};
+
+/// A convex shape defined by user-provided functions.
+///
+/// This allows application-specific convex shapes, e.g., superellipsoids, to be simulated without
+/// being implemented by ncollide: contacts, proximities, continuous collision detection, ray
+/// casts, and point queries are all computed from the support function with the GJK algorithm.
+pub struct SupportFunctionShape<N: Real> {
+    support:         Box<Fn(&Vector<N>) -> Point<N> + Send + Sync>,
+    aabb:            Box<Fn(&Isometry<N>) -> AABB<Point<N>> + Send + Sync>,
+    mass_properties: Box<Fn(N) -> (N, Point<N>, AngularInertia<N>) + Send + Sync>
+}
+
+impl<N: Real> SupportFunctionShape<N> {
+    /// Creates a new convex shape from its functions.
+    ///
+    /// * `support` - the local-space point of the shape which maximizes its dot product with the
+    ///   given local-space direction. The direction is not necessarily normalized.
+    /// * `aabb` - the world-space bounding box of the shape transformed by the given isometry.
+    /// * `mass_properties` - the mass, local-space center of mass, and local-space angular
+    ///   inertia of the shape for the given density.
+    ///
+    /// The area of the shape is not known: `Volumetric::area` returns zero.
+    pub fn new<S, B, M>(support: S, aabb: B, mass_properties: M) -> SupportFunctionShape<N>
+        where S: Fn(&Vector<N>) -> Point<N> + Send + Sync + 'static,
+              B: Fn(&Isometry<N>) -> AABB<Point<N>> + Send + Sync + 'static,
+              M: Fn(N) -> (N, Point<N>, AngularInertia<N>) + Send + Sync + 'static {
+        SupportFunctionShape {
+            support:         Box::new(support),
+            aabb:            Box::new(aabb),
+            mass_properties: Box::new(mass_properties)
+        }
+    }
+}
+
+impl<N: Real, M: IsometryOps<Point<N>>> SupportMap<Point<N>, M> for SupportFunctionShape<N> {
+    #[inline]
+    fn support_point(&self, m: &M, dir: &Vector<N>) -> Point<N> {
+        m.transform_point(&(self.support)(&m.inverse_rotate_vector(dir)))
+    }
+}
+
+impl<N: Real> RayCast<Point<N>, Isometry<N>> for SupportFunctionShape<N> {
+    fn toi_and_normal_with_ray(&self, m: &Isometry<N>, ray: &Ray<Point<N>>, solid: bool)
+                               -> Option<RayIntersection<Vector<N>>> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        ray_internal::implicit_toi_and_normal_with_ray(&Id::new(), self, &mut JohnsonSimplex::new_w_tls(),
+                                                       &ls_ray, solid).map(|mut res| {
+            res.normal = m.rotation * res.normal;
+            res
+        })
+    }
+}
+
+impl<N: Real> PointQuery<Point<N>, Isometry<N>> for SupportFunctionShape<N> {
+    fn project_point(&self, m: &Isometry<N>, point: &Point<N>, solid: bool) -> PointProjection<Point<N>> {
+        // Project the origin on the shape translated by `-point`.
+        let m       = Translation::from_vector(-point.coords) * *m;
+        let simplex = &mut JohnsonSimplex::new_w_tls();
+
+        simplex.reset(self.support_point(&m, &-point.coords));
+
+        match gjk::project_origin(&m, self, simplex) {
+            Some(p) => PointProjection::new(false, p + point.coords),
+            None    => {
+                let proj = if solid {
+                    *point
+                }
+                else {
+                    minkowski_sampling::project_origin(&m, self, simplex).map_or(*point, |p| p + point.coords)
+                };
+
+                PointProjection::new(true, proj)
+            }
+        }
+    }
+}
+
+impl<N: Real> Shape<Point<N>, Isometry<N>> for SupportFunctionShape<N> {
+    #[inline]
+    fn aabb(&self, m: &Isometry<N>) -> AABB<Point<N>> {
+        (self.aabb)(m)
+    }
+
+    #[inline]
+    fn as_ray_cast(&self) -> Option<&RayCast<Point<N>, Isometry<N>>> {
+        Some(self)
+    }
+
+    #[inline]
+    fn as_point_query(&self) -> Option<&PointQuery<Point<N>, Isometry<N>>> {
+        Some(self)
+    }
+
+    #[inline]
+    fn as_support_map(&self) -> Option<&SupportMap<Point<N>, Isometry<N>>> {
+        Some(self)
+    }
+}
+
+impl<N: Real> Volumetric<N, Point<N>, AngularInertia<N>> for SupportFunctionShape<N> {
+    fn area(&self) -> N {
+        na::zero()
+    }
+
+    fn volume(&self) -> N {
+        (self.mass_properties)(na::one()).0
+    }
+
+    fn center_of_mass(&self) -> Point<N> {
+        (self.mass_properties)(na::one()).1
+    }
+
+    fn unit_angular_inertia(&self) -> AngularInertia<N> {
+        let (volume, _, inertia) = (self.mass_properties)(na::one());
+
+        inertia * (na::one::<N>() / volume)
+    }
+
+    fn mass_properties(&self, density: N) -> (N, Point<N>, AngularInertia<N>) {
+        (self.mass_properties)(density)
+    }
+}
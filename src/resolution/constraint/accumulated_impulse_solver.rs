@@ -1,33 +1,66 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::iter;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
 // use rand::RngUtil;
 use alga::general::Real;
 use na;
 use math::{Vector, Orientation, Rotation, Translation, Isometry};
-use detection::constraint::Constraint;
-use detection::joint::Joint;
-use object::RigidBody;
+use detection::constraint::{Constraint, ContactFlags};
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use object::{RigidBody, RigidBodyHandle};
 use resolution::constraint::velocity_constraint::VelocityConstraint;
 use resolution::constraint::contact_equation;
 use resolution::constraint::contact_equation::{CorrectionMode, CorrectionParameters};
 use resolution::constraint::ball_in_socket_equation;
 use resolution::constraint::fixed_equation;
+use resolution::constraint::hinge_equation;
+use resolution::constraint::prismatic_equation;
+use resolution::constraint::spring_equation;
+use resolution::constraint::distance_equation;
+use resolution::constraint::angular_motor_equation;
+use resolution::constraint::rack_and_pinion_equation;
+use resolution::constraint::pulley_equation;
+use resolution::constraint::gear_equation;
+#[cfg(feature = "dim3")]
+use resolution::constraint::universal_equation;
 use resolution::solver::Solver;
 use resolution::constraint::projected_gauss_seidel_solver as pgs;
-use resolution::constraint::projected_gauss_seidel_solver::Velocities;
-use resolution::constraint::impulse_cache::ImpulseCache;
+#[cfg(any(feature = "parallel", feature = "tracing"))]
+use resolution::constraint::parallel_solver;
+use resolution::constraint::projected_gauss_seidel_solver::{Velocities, FrictionModel, IterationScheme};
+use resolution::constraint::impulse_cache::{ImpulseCache, JointImpulseCache};
+use resolution::constraint::solver_statistics::SolverStatistics;
+use detection::joint::JointManager;
 
 
 /// Constraint solver using the projected gauss seidel algorithm and warm-starting.
 pub struct AccumulatedImpulseSolver<N: Real> {
     correction:              CorrectionParameters<N>,
     cache:                   ImpulseCache<N>,
+    joint_cache:             JointImpulseCache<N>,
     num_first_order_iter:    usize,
     num_second_order_iter:   usize,
+    block_solver:            bool,
+    contact_blocks:          Vec<(usize, usize)>,
+    contact_flags:           Vec<ContactFlags>,
+    friction_model:          FrictionModel,
+    iteration_scheme:        IterationScheme,
+    shock_propagation:       bool,
+    direct_solver_threshold: Option<usize>,
+    #[cfg(feature = "parallel")]
+    num_threads:             usize,
     restitution_constraints: Vec<VelocityConstraint<N>>,
     friction_constraints:    Vec<VelocityConstraint<N>>,
-    mj_lambda:               Vec<Velocities<N>>
+    mj_lambda:               Vec<Velocities<N>>,
+    joint_impulses:          Vec<(usize, Vector<N>, Orientation<N>)>,
+    joint_body_impulses:     Vec<(usize, Vector<N>, Orientation<N>, Vector<N>, Orientation<N>)>,
+    contact_impulses:        Vec<(usize, Vector<N>)>,
+    statistics:              SolverStatistics<N>,
+    #[cfg(feature = "tracing")]
+    island_spans:            Option<Vec<(Instant, Instant, usize)>>
 }
 
 impl<N: Real> AccumulatedImpulseSolver<N> {
@@ -42,15 +75,32 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
         AccumulatedImpulseSolver {
             num_first_order_iter:    num_first_order_iter,
             num_second_order_iter:   num_second_order_iter,
+            block_solver:            true,
+            contact_blocks:          Vec::new(),
+            contact_flags:           Vec::new(),
+            friction_model:          FrictionModel::Pyramid,
+            iteration_scheme:        IterationScheme::GaussSeidel,
+            shock_propagation:       false,
+            direct_solver_threshold: None,
+            #[cfg(feature = "parallel")]
+            num_threads:             1,
             restitution_constraints: Vec::new(),
             friction_constraints:    Vec::new(),
             mj_lambda:               Vec::new(),
+            joint_impulses:          Vec::new(),
+            joint_body_impulses:     Vec::new(),
+            contact_impulses:        Vec::new(),
+            statistics:              SolverStatistics::new(),
+            #[cfg(feature = "tracing")]
+            island_spans:            None,
             cache:                   ImpulseCache::new(step, na::dimension::<Vector<N>>()),
+            joint_cache:             JointImpulseCache::new(),
 
             correction: CorrectionParameters {
-                corr_mode:  correction_mode,
-                joint_corr: joint_corr_factor,
-                rest_eps:   rest_eps
+                corr_mode:      correction_mode,
+                joint_corr:     joint_corr_factor,
+                rest_eps:       rest_eps,
+                max_mass_ratio: None
             }
         }
     }
@@ -79,6 +129,308 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
         self.num_second_order_iter = num
     }
 
+    /// The method used to correct the penetrations, with its correction factors and depth limits.
+    #[inline]
+    pub fn correction_mode(&self) -> &CorrectionMode<N> {
+        &self.correction.corr_mode
+    }
+
+    /// Sets the method used to correct the penetrations, with its correction factors and depth
+    /// limits.
+    ///
+    /// Like the iteration counts, this can be changed between two steps, e.g., to scale the
+    /// quality of the simulation with the time available for each frame.
+    #[inline]
+    pub fn set_correction_mode(&mut self, mode: CorrectionMode<N>) {
+        self.correction.corr_mode = mode
+    }
+
+    /// The fraction of the joint errors corrected at each step.
+    #[inline]
+    pub fn joint_correction_factor(&self) -> N {
+        self.correction.joint_corr
+    }
+
+    /// Sets the fraction of the joint errors corrected at each step.
+    #[inline]
+    pub fn set_joint_correction_factor(&mut self, factor: N) {
+        self.correction.joint_corr = factor
+    }
+
+    /// The impact speed below which the contacts do not bounce.
+    #[inline]
+    pub fn restitution_threshold(&self) -> N {
+        self.correction.rest_eps
+    }
+
+    /// Sets the impact speed below which the contacts do not bounce.
+    ///
+    /// The restitution is ignored for slower impacts, so that the small bounces of a body coming
+    /// to rest die out instead of lasting forever.
+    #[inline]
+    pub fn set_restitution_threshold(&mut self, threshold: N) {
+        assert!(threshold >= na::zero(), "The restitution threshold must not be negative.");
+        self.correction.rest_eps = threshold
+    }
+
+    /// Whether the contacts between two bodies are solved simultaneously.
+    #[inline]
+    pub fn block_solver_enabled(&self) -> bool {
+        self.block_solver
+    }
+
+    /// Solves the contacts between two bodies simultaneously, by blocks of up to four contacts.
+    ///
+    /// A box resting on two or four contact points then no longer rocks with few iterations.
+    /// This is enabled by default.
+    #[inline]
+    pub fn enable_block_solver(&mut self) {
+        self.block_solver = true
+    }
+
+    /// Solves each contact one after the other.
+    #[inline]
+    pub fn disable_block_solver(&mut self) {
+        self.block_solver = false
+    }
+
+    /// The approximation of the friction cone used by this solver.
+    #[inline]
+    pub fn friction_model(&self) -> FrictionModel {
+        self.friction_model
+    }
+
+    /// Sets the approximation of the friction cone used by this solver.
+    ///
+    /// Defaults to `FrictionModel::Pyramid`. Both models are the same in 2D.
+    #[inline]
+    pub fn set_friction_model(&mut self, model: FrictionModel) {
+        self.friction_model = model
+    }
+
+    /// The order in which the constraints are solved at each iteration.
+    #[inline]
+    pub fn iteration_scheme(&self) -> IterationScheme {
+        self.iteration_scheme
+    }
+
+    /// Sets the order in which the constraints are solved at each iteration.
+    ///
+    /// Defaults to `IterationScheme::GaussSeidel`. The contacts are not solved by blocks with
+    /// `IterationScheme::Jacobi`, which usually needs more iterations.
+    #[inline]
+    pub fn set_iteration_scheme(&mut self, scheme: IterationScheme) {
+        self.iteration_scheme = scheme
+    }
+
+    /// Whether a shock propagation pass is performed after the iterations.
+    #[inline]
+    pub fn shock_propagation_enabled(&self) -> bool {
+        self.shock_propagation
+    }
+
+    /// Enables the shock propagation pass performed after the iterations.
+    ///
+    /// The contacts are then solved once more from the ground to the top of the stacks, without
+    /// friction, the lower body of each contact being considered infinitely massive. This stops
+    /// the tall stacks from slowly collapsing at low iteration counts, but the impulses of this
+    /// pass do not conserve the momentum.
+    #[inline]
+    pub fn enable_shock_propagation(&mut self) {
+        self.shock_propagation = true
+    }
+
+    /// Disables the shock propagation pass performed after the iterations.
+    #[inline]
+    pub fn disable_shock_propagation(&mut self) {
+        self.shock_propagation = false
+    }
+
+    /// The maximum number of bodies of the islands which equations are solved directly after the
+    /// iterations, if any.
+    #[inline]
+    pub fn direct_solver_threshold(&self) -> Option<usize> {
+        self.direct_solver_threshold
+    }
+
+    /// Sets the maximum number of bodies of the islands which equations are solved directly after
+    /// the iterations.
+    ///
+    /// The contacts and joints of these islands are then solved exactly, without friction, by
+    /// Gaussian elimination. This is much more accurate than the iterations for the mechanisms
+    /// with few bodies but many interdependent joints, e.g., robot arms, but the cost grows with
+    /// the cube of the number of equations of the island. Set it to `None` to solve every island
+    /// iteratively only.
+    #[inline]
+    pub fn set_direct_solver_threshold(&mut self, threshold: Option<usize>) {
+        self.direct_solver_threshold = threshold
+    }
+
+    /// The number of threads the islands are solved on.
+    #[cfg(feature = "parallel")]
+    #[inline]
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Sets the number of threads the islands are solved on, one by default.
+    ///
+    /// The constraints acting on a common body belong to the same island, and the islands are
+    /// distributed among the threads, the largest ones first. The result is the same whatever the
+    /// number of threads, so this only speeds up the scenes with several islands of comparable
+    /// sizes, e.g., many separate piles of objects.
+    #[cfg(feature = "parallel")]
+    #[inline]
+    pub fn set_num_threads(&mut self, num: usize) {
+        assert!(num > 0, "The number of threads must be at least one.");
+        self.num_threads = num
+    }
+
+    /// A copy of this solver for the copies `bodies` of the rigid bodies and the copies `joints`
+    /// of the joints, indexed by the identifiers of the originals, with the same parameters and
+    /// warm-starting impulses.
+    #[doc(hidden)]
+    pub fn clone_for_prediction(&self,
+                                bodies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>,
+                                joints: &HashMap<usize, usize, UintTWHash>)
+                                -> AccumulatedImpulseSolver<N> {
+        AccumulatedImpulseSolver {
+            correction:              self.correction.clone(),
+            cache:                   self.cache.clone_for_prediction(bodies),
+            joint_cache:             self.joint_cache.clone_for_prediction(joints),
+            num_first_order_iter:    self.num_first_order_iter,
+            num_second_order_iter:   self.num_second_order_iter,
+            block_solver:            self.block_solver,
+            contact_blocks:          Vec::new(),
+            contact_flags:           Vec::new(),
+            friction_model:          self.friction_model,
+            iteration_scheme:        self.iteration_scheme,
+            shock_propagation:       self.shock_propagation,
+            direct_solver_threshold: self.direct_solver_threshold,
+            #[cfg(feature = "parallel")]
+            num_threads:             self.num_threads,
+            restitution_constraints: Vec::new(),
+            friction_constraints:    Vec::new(),
+            mj_lambda:               Vec::new(),
+            joint_impulses:          Vec::new(),
+            joint_body_impulses:     Vec::new(),
+            contact_impulses:        Vec::new(),
+            statistics:              self.statistics.clone(),
+            #[cfg(feature = "tracing")]
+            island_spans:            self.island_spans.as_ref().map(|_| Vec::new())
+        }
+    }
+
+    /// Enables the measure of the time spent solving the velocity constraints of each island.
+    ///
+    /// The islands are then solved one after the other on the current thread, whatever the number
+    /// of threads. The impulses and velocities are unchanged.
+    #[doc(hidden)]
+    #[cfg(feature = "tracing")]
+    pub fn set_island_timing(&mut self, enabled: bool) {
+        if enabled != self.island_spans.is_some() {
+            self.island_spans = if enabled { Some(Vec::new()) } else { None };
+        }
+    }
+
+    /// The time interval spent solving the velocity constraints of each island during the last
+    /// resolution, with its number of equations, if the island timing is enabled.
+    #[doc(hidden)]
+    #[cfg(feature = "tracing")]
+    pub fn island_spans(&self) -> &[(Instant, Instant, usize)] {
+        match self.island_spans {
+            Some(ref spans) => &spans[..],
+            None            => &[]
+        }
+    }
+
+    /// Sets the flags of the contacts given to the next call to `solve`, indexed like the
+    /// constraints.
+    ///
+    /// The contacts without flags have both their friction and their restitution solved.
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_contact_flags(&mut self, flags: Vec<ContactFlags>) {
+        self.contact_flags = flags;
+    }
+
+    /// Forgets the impulses accumulated by the contacts and the joints during the last
+    /// resolution.
+    ///
+    /// The next resolution is then not warm-started.
+    #[inline]
+    pub fn clear_impulse_cache(&mut self) {
+        self.cache.clear();
+        self.joint_cache.clear();
+    }
+
+    /// The linear and angular impulses applied by each joint on its second body during the last
+    /// resolution.
+    ///
+    /// Each joint is identified by its index on the slice of constraints given to the last call
+    /// to `solve`. The angular impulses do not include the torque caused by the linear impulses
+    /// applied at the anchor.
+    #[doc(hidden)]
+    #[inline]
+    pub fn joint_impulses(&self) -> &[(usize, Vector<N>, Orientation<N>)] {
+        &self.joint_impulses[..]
+    }
+
+    /// The linear and angular impulses applied by each joint on its first and second bodies during
+    /// the last resolution.
+    ///
+    /// Each joint is identified by its index on the slice of constraints given to the last call
+    /// to `solve`. The angular impulses are expressed at the center of mass of each body, and
+    /// include the torque caused by the linear impulses applied at the anchors.
+    #[doc(hidden)]
+    #[inline]
+    pub fn joint_body_impulses(&self) -> &[(usize, Vector<N>, Orientation<N>, Vector<N>, Orientation<N>)] {
+        &self.joint_body_impulses[..]
+    }
+
+    /// The normal impulse applied by each contact on its second body during the last resolution.
+    ///
+    /// Each contact is identified by its index on the slice of constraints given to the last call
+    /// to `solve`. The friction impulses are not included.
+    #[doc(hidden)]
+    #[inline]
+    pub fn contact_impulses(&self) -> &[(usize, Vector<N>)] {
+        &self.contact_impulses[..]
+    }
+
+    /// The largest velocity error left on a contact or a joint by the last resolution.
+    ///
+    /// A large error means that the solver did not converge with its number of iterations, e.g.,
+    /// for stacks or chains of bodies with very different masses. The errors of the contacts and
+    /// joints which impulse reached its bounds, e.g., the maximum force of a motor, are ignored.
+    #[inline]
+    pub fn max_residual(&self) -> N {
+        self.statistics.max_residual
+    }
+
+    /// Metrics about the convergence of the last resolution.
+    #[inline]
+    pub fn statistics(&self) -> &SolverStatistics<N> {
+        &self.statistics
+    }
+
+    /// The maximum ratio between the masses of two bodies in contact seen by the solver, if any.
+    #[inline]
+    pub fn max_mass_ratio(&self) -> Option<N> {
+        self.correction.max_mass_ratio
+    }
+
+    /// Sets the maximum ratio between the masses of two bodies in contact seen by the solver.
+    ///
+    /// Beyond this ratio, the heaviest body is made lighter for the solver. It then reacts more to
+    /// the contact, but a very heavy body resting on a light one no longer sinks into it, e.g.,
+    /// with a ratio of `10`. Defaults to `None`, i.e., every contact is solved with the actual
+    /// masses.
+    #[inline]
+    pub fn set_max_mass_ratio(&mut self, ratio: Option<N>) {
+        self.correction.max_mass_ratio = ratio
+    }
+
     fn resize_buffers(&mut self, num_restitution_equations: usize, num_friction_equations: usize) {
         resize_buffer(&mut self.restitution_constraints,
                       num_restitution_equations,
@@ -89,6 +441,64 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
                       VelocityConstraint::new());
     }
 
+    // Solves the velocity equations iteratively.
+    fn solve_velocities(&mut self, num_bodies: usize) {
+        #[cfg(feature = "tracing")]
+        {
+            if self.island_spans.is_some() {
+                self.island_spans = Some(parallel_solver::solve_islands(
+                    &mut self.restitution_constraints[..],
+                    &mut self.friction_constraints[..],
+                    &self.contact_blocks[..],
+                    self.iteration_scheme,
+                    self.friction_model,
+                    &mut self.mj_lambda[..],
+                    num_bodies,
+                    self.num_second_order_iter,
+                    false));
+
+                return;
+            }
+        }
+
+        #[cfg(feature = "parallel")]
+        {
+            if self.num_threads > 1 {
+                return parallel_solver::solve(
+                    &mut self.restitution_constraints[..],
+                    &mut self.friction_constraints[..],
+                    &self.contact_blocks[..],
+                    self.iteration_scheme,
+                    self.friction_model,
+                    &mut self.mj_lambda[..],
+                    num_bodies,
+                    self.num_second_order_iter,
+                    false,
+                    self.num_threads);
+            }
+        }
+
+        match self.iteration_scheme {
+            IterationScheme::GaussSeidel => pgs::projected_gauss_seidel_block_solve(
+                &mut self.restitution_constraints[..],
+                &mut self.friction_constraints[..],
+                &self.contact_blocks[..],
+                self.friction_model,
+                &mut self.mj_lambda[..],
+                num_bodies,
+                self.num_second_order_iter,
+                false),
+            IterationScheme::Jacobi => pgs::projected_jacobi_solve(
+                &mut self.restitution_constraints[..],
+                &mut self.friction_constraints[..],
+                self.friction_model,
+                &mut self.mj_lambda[..],
+                num_bodies,
+                self.num_second_order_iter,
+                false)
+        }
+    }
+
     fn do_solve(&mut self,
                 dt:          N,
                 constraints: &[Constraint<N>],
@@ -100,14 +510,42 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
 
         for i in joints.iter() {
             match constraints[*i] {
-                Constraint::BallInSocket(_) => {
-                    num_joint_equations = num_joint_equations + na::dimension::<Vector<N>>()
+                Constraint::BallInSocket(ref bis) => {
+                    num_joint_equations = num_joint_equations + ball_in_socket_equation::num_equations(&*bis.borrow())
+                },
+                Constraint::Hinge(ref h) => {
+                    num_joint_equations = num_joint_equations + hinge_equation::num_equations(&*h.borrow())
+                },
+                Constraint::Prismatic(ref p) => {
+                    num_joint_equations = num_joint_equations + prismatic_equation::num_equations(&*p.borrow())
+                },
+                Constraint::Spring(ref s) => {
+                    num_joint_equations = num_joint_equations + spring_equation::num_equations(&*s.borrow())
+                },
+                Constraint::Distance(ref d) => {
+                    num_joint_equations = num_joint_equations + distance_equation::num_equations(&*d.borrow())
+                },
+                Constraint::AngularMotor(ref m) => {
+                    num_joint_equations = num_joint_equations + angular_motor_equation::num_equations(&*m.borrow())
+                },
+                Constraint::RackAndPinion(ref r) => {
+                    num_joint_equations = num_joint_equations + rack_and_pinion_equation::num_equations(&*r.borrow())
+                },
+                Constraint::Pulley(ref p) => {
+                    num_joint_equations = num_joint_equations + pulley_equation::num_equations(&*p.borrow())
+                },
+                Constraint::Gear(ref g) => {
+                    num_joint_equations = num_joint_equations + gear_equation::num_equations(&*g.borrow())
                 },
                 Constraint::Fixed(_) => {
                     num_joint_equations = num_joint_equations +
                                           na::dimension::<Vector<N>>() +
                                           na::dimension::<Orientation<N>>()
                 },
+                #[cfg(feature = "dim3")]
+                Constraint::Universal(ref u) => {
+                    num_joint_equations = num_joint_equations + universal_equation::num_equations(&*u.borrow())
+                },
                 Constraint::RBRB(_, _, _) => { }
             }
         }
@@ -116,12 +554,15 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
 
         let mut friction_offset = 0;
 
-        for (i, (_, &(ci, imp))) in self.cache.hash().iter().enumerate() {
+        for (i, &(_, ci, imp)) in self.cache.contacts().iter().enumerate() {
             match constraints[ci] {
                 Constraint::RBRB(ref rb1, ref rb2, ref c) => {
+                    let flags = self.contact_flags.get(ci).cloned().unwrap_or_else(ContactFlags::new);
+
                     contact_equation::fill_second_order_equation(
                         dt.clone(),
                         c,
+                        &flags,
                         &*rb1.borrow(), &*rb2.borrow(),
                         &mut self.restitution_constraints[i],
                         i,
@@ -136,9 +577,36 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
             friction_offset = friction_offset + na::dimension::<Vector<N>>() - 1;
         }
 
-        let mut joint_offset = num_restitution_equations;
+        // The contacts of a pair of bodies are consecutive: solve them by blocks. Only the contacts
+        // that persisted since the last resolution are grouped, so that the partition depends on
+        // the contacts and the impulse cache only, not on the order a manifold being rebuilt,
+        // e.g., by a copy of the world, generates its new contacts in.
+        self.contact_blocks.clear();
+
+        if self.block_solver {
+            let contacts = self.cache.contacts();
+            let mut begin = 0;
+
+            for end in 1 .. contacts.len() + 1 {
+                let new = |i: usize| contacts[i].2 == 0;
+
+                if end == contacts.len() || end - begin == pgs::MAX_BLOCK_SIZE ||
+                   contacts[end].0.pair() != contacts[begin].0.pair() || new(begin) || new(end) {
+                    if end - begin > 1 {
+                        self.contact_blocks.push((begin, end));
+                    }
+
+                    begin = end;
+                }
+            }
+        }
+
+        // The index of each joint, with the range of its equations.
+        let mut joint_equations = Vec::with_capacity(joints.len());
+        let mut joint_offset    = num_restitution_equations;
         for i in joints.iter() {
             let nconstraints = self.restitution_constraints.len();
+            let first        = joint_offset;
             match constraints[*i] {
                 Constraint::BallInSocket(ref bis) => {
                     ball_in_socket_equation::fill_second_order_equation(
@@ -148,7 +616,7 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
                         &self.correction
                     );
 
-                    joint_offset = joint_offset + na::dimension::<Vector<N>>();
+                    joint_offset = joint_offset + ball_in_socket_equation::num_equations(&*bis.borrow());
                 },
                 Constraint::Fixed(ref f) => {
                     fixed_equation::fill_second_order_equation(
@@ -160,20 +628,212 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
 
                     joint_offset = joint_offset + na::dimension::<Vector<N>>() + na::dimension::<Orientation<N>>();
                 },
+                Constraint::Hinge(ref h) => {
+                    hinge_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*h.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    // The angle limit equation is inactive if the limits are not reached.
+                    joint_offset = joint_offset + hinge_equation::num_equations(&*h.borrow());
+                },
+                Constraint::Prismatic(ref p) => {
+                    prismatic_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*p.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    // The translation limit equation is inactive if the limits are not reached.
+                    joint_offset = joint_offset + prismatic_equation::num_equations(&*p.borrow());
+                },
+                Constraint::Spring(ref s) => {
+                    spring_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*s.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints] // XXX
+                    );
+
+                    joint_offset = joint_offset + spring_equation::num_equations(&*s.borrow());
+                },
+                Constraint::Distance(ref d) => {
+                    distance_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*d.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    // The distance limit equation is inactive if the limits are not reached.
+                    joint_offset = joint_offset + distance_equation::num_equations(&*d.borrow());
+                },
+                Constraint::AngularMotor(ref m) => {
+                    angular_motor_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*m.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    joint_offset = joint_offset + angular_motor_equation::num_equations(&*m.borrow());
+                },
+                Constraint::RackAndPinion(ref r) => {
+                    rack_and_pinion_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*r.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    joint_offset = joint_offset + rack_and_pinion_equation::num_equations(&*r.borrow());
+                },
+                Constraint::Pulley(ref p) => {
+                    pulley_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*p.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    joint_offset = joint_offset + pulley_equation::num_equations(&*p.borrow());
+                },
+                Constraint::Gear(ref g) => {
+                    gear_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*g.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    joint_offset = joint_offset + gear_equation::num_equations(&*g.borrow());
+                },
+                #[cfg(feature = "dim3")]
+                Constraint::Universal(ref u) => {
+                    universal_equation::fill_second_order_equation(
+                        dt.clone(),
+                        &*u.borrow(),
+                        &mut self.restitution_constraints[joint_offset .. nconstraints], // XXX
+                        &self.correction
+                    );
+
+                    joint_offset = joint_offset + universal_equation::num_equations(&*u.borrow());
+                },
                 Constraint::RBRB(_, _, _) => { }
             }
+
+            // Warm-start the joint with its impulses of the last resolution, unless its equations
+            // changed.
+            if let Some(imps) = self.joint_cache.impulses(JointManager::joint_id(&constraints[*i])) {
+                if imps.len() == joint_offset - first {
+                    for (c, imp) in self.restitution_constraints[first .. joint_offset].iter_mut().zip(imps.iter()) {
+                        c.impulse = *na::clamp(imp, &c.lobound, &c.hibound);
+
+                        // A limit may be left during this resolution: its impulse never exceeds
+                        // the one that stops the current approach alone.
+                        if c.lobound.is_zero() != c.hibound.is_zero() {
+                            let stop = c.objective * c.inv_projected_mass;
+
+                            c.impulse = if c.impulse > na::zero() {
+                                c.impulse.min(stop.max(na::zero()))
+                            }
+                            else {
+                                c.impulse.max(stop.min(na::zero()))
+                            };
+                        }
+                    }
+                }
+            }
+
+            joint_equations.push((*i, first, joint_offset));
         }
 
         resize_buffer(&mut self.mj_lambda, bodies.len(), Velocities::new());
+        self.solve_velocities(bodies.len());
+
+        self.statistics.iterations = self.num_second_order_iter;
+
+        if let Some(max_bodies) = self.direct_solver_threshold {
+            for island in small_islands(&self.restitution_constraints[..], bodies.len(), max_bodies).iter() {
+                // The iterative solution is kept if the island cannot be solved directly.
+                let _ = pgs::direct_solve(&mut self.restitution_constraints[..], &island[..], &mut self.mj_lambda[..]);
+            }
+        }
+
+        let levels = if self.shock_propagation {
+            let levels = pgs::stack_levels(&self.restitution_constraints[.. num_restitution_equations], bodies.len());
+
+            pgs::shock_propagation_solve(
+                &self.restitution_constraints[.. num_restitution_equations],
+                &levels[..],
+                &mut self.mj_lambda[..]);
+
+            levels
+        }
+        else {
+            Vec::new()
+        };
+
+        for (i, &(_, ci, _)) in self.cache.contacts().iter().enumerate() {
+            let c = &self.restitution_constraints[i];
+            self.contact_impulses.push((ci, c.normal * c.impulse));
+        }
 
-        // FIXME: parametrize by the resolution algorithm?
-        pgs::projected_gauss_seidel_solve(
-            &mut self.restitution_constraints[..],
-            &mut self.friction_constraints[..],
-            &mut self.mj_lambda[..],
-            bodies.len(),
-            self.num_second_order_iter,
-            false);
+        for c in self.restitution_constraints.iter() {
+            let residual = pgs::velocity_residual(c, &self.mj_lambda[..]);
+
+            if residual > self.statistics.max_residual {
+                self.statistics.max_residual = residual;
+            }
+        }
+
+        let contacts = &self.restitution_constraints[.. num_restitution_equations];
+
+        self.statistics.contacts        = num_restitution_equations;
+        self.statistics.active_contacts = contacts.iter().filter(|c| c.impulse > na::zero()).count();
+        self.statistics.joint_equations = num_joint_equations;
+
+        // The angular equations are the ones with no linear component on the second body.
+        for &(i, first, last) in joint_equations.iter() {
+            let mut lin_impulse: Vector<N>      = na::zero();
+            let mut ang_impulse: Orientation<N> = na::zero();
+
+            for c in self.restitution_constraints[first .. last].iter() {
+                if c.normal2 == na::zero() {
+                    ang_impulse = ang_impulse + c.rot_axis2 * c.impulse;
+                }
+                else {
+                    lin_impulse = lin_impulse + c.normal2 * c.impulse;
+                }
+            }
+
+            self.joint_impulses.push((i, lin_impulse, ang_impulse));
+        }
+
+        for &(i, first, last) in joint_equations.iter() {
+            let imps = self.restitution_constraints[first .. last].iter()
+                                                                  .map(|c| c.impulse * na::convert::<f64, N>(0.85f64))
+                                                                  .collect();
+            self.joint_cache.insert(JointManager::joint_id(&constraints[i]), imps);
+        }
+
+        for &(i, first, last) in joint_equations.iter() {
+            let mut lin_impulse1: Vector<N>      = na::zero();
+            let mut ang_impulse1: Orientation<N> = na::zero();
+            let mut lin_impulse2: Vector<N>      = na::zero();
+            let mut ang_impulse2: Orientation<N> = na::zero();
+
+            for c in self.restitution_constraints[first .. last].iter() {
+                lin_impulse1 = lin_impulse1 - c.normal * c.impulse;
+                ang_impulse1 = ang_impulse1 + c.rot_axis1 * c.impulse;
+                lin_impulse2 = lin_impulse2 + c.normal2 * c.impulse;
+                ang_impulse2 = ang_impulse2 + c.rot_axis2 * c.impulse;
+            }
+
+            self.joint_body_impulses.push((i, lin_impulse1, ang_impulse1, lin_impulse2, ang_impulse2));
+        }
 
         // FIXME: this is _so_ ugly!
         self.resize_buffers(num_restitution_equations, num_friction_equations);
@@ -200,8 +860,8 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
         }
 
         let offset = self.cache.reserved_impulse_offset();
-        for (i, (_, kv)) in self.cache.hash_mut().iter_mut().enumerate() {
-            *kv = (kv.0, offset + i * na::dimension::<Vector<N>>());
+        for (i, contact) in self.cache.contacts_mut().iter_mut().enumerate() {
+            contact.2 = offset + i * na::dimension::<Vector<N>>();
         }
 
         /*
@@ -216,10 +876,17 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
             }
         });
 
+        for &(_, ci, _) in self.cache.contacts().iter() {
+            if let Constraint::RBRB(_, _, ref c) = constraints[ci] {
+                self.statistics.max_depth = self.statistics.max_depth.max(c.depth);
+            }
+        }
+
         if needs_correction {
+            self.statistics.iterations = self.statistics.iterations + self.num_first_order_iter;
             self.resize_buffers(num_restitution_equations, num_friction_equations);
 
-            for (i, (_, &(ci, _))) in self.cache.hash().iter().enumerate() {
+            for (i, &(_, ci, _)) in self.cache.contacts().iter().enumerate() {
                 match constraints[ci] {
                     Constraint::RBRB(_, _, ref c) => {
                         contact_equation::reinit_to_first_order_equation(
@@ -232,14 +899,44 @@ impl<N: Real> AccumulatedImpulseSolver<N> {
                 }
             }
 
-            // FIXME: parametrize by the resolution algorithm?
-            pgs::projected_gauss_seidel_solve(
-                &mut self.restitution_constraints[..],
-                &mut [][..],
-                &mut self.mj_lambda[..],
-                bodies.len(),
-                self.num_first_order_iter,
-                true);
+            match self.iteration_scheme {
+                IterationScheme::GaussSeidel => pgs::projected_gauss_seidel_block_solve(
+                    &mut self.restitution_constraints[..],
+                    &mut [][..],
+                    &self.contact_blocks[..],
+                    self.friction_model,
+                    &mut self.mj_lambda[..],
+                    bodies.len(),
+                    self.num_first_order_iter,
+                    true),
+                IterationScheme::Jacobi => pgs::projected_jacobi_solve(
+                    &mut self.restitution_constraints[..],
+                    &mut [][..],
+                    self.friction_model,
+                    &mut self.mj_lambda[..],
+                    bodies.len(),
+                    self.num_first_order_iter,
+                    true)
+            }
+
+            if self.shock_propagation {
+                pgs::shock_propagation_solve(
+                    &self.restitution_constraints[..],
+                    &levels[..],
+                    &mut self.mj_lambda[..]);
+            }
+
+            // The depths left once the bodies are moved apart by the correction.
+            self.statistics.max_depth = na::zero();
+
+            for (i, &(_, ci, _)) in self.cache.contacts().iter().enumerate() {
+                if let Constraint::RBRB(_, _, ref c) = constraints[ci] {
+                    let eq         = &self.restitution_constraints[i];
+                    let separation = eq.objective - eq.cfm * eq.impulse - pgs::velocity_error(eq, &self.mj_lambda[..]);
+
+                    self.statistics.max_depth = self.statistics.max_depth.max(c.depth - separation * dt);
+                }
+            }
 
             for b in bodies.iter() {
                 let mut rb = b.borrow_mut();
@@ -265,6 +962,18 @@ impl<N: Real> Solver<N, Constraint<N>> for AccumulatedImpulseSolver<N> {
         // FIXME: bodies index assignment is very ugly
         let mut bodies = Vec::new();
 
+        self.joint_impulses.clear();
+        self.joint_body_impulses.clear();
+        self.contact_impulses.clear();
+        self.statistics = SolverStatistics::new();
+
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(ref mut spans) = self.island_spans {
+                spans.clear();
+            }
+        }
+
         if constraints.len() != 0 {
             /*
              * Associate the constraints with the cached impulse.
@@ -277,11 +986,9 @@ impl<N: Real> Solver<N, Constraint<N>> for AccumulatedImpulseSolver<N> {
                                           &**b as *const RefCell<RigidBody<N>> as usize,
                                           na::center(&c.world1, &c.world2));
                     },
-                    Constraint::BallInSocket(_) => {
-                        // XXX: cache for ball in socket?
-                    },
-                    Constraint::Fixed(_) => {
-                        // XXX: cache for fixed?
+                    _ => {
+                        // The joints are warm-started by the joint impulse cache when their
+                        // equations are filled.
                     }
                 }
             }
@@ -293,41 +1000,16 @@ impl<N: Real> Solver<N, Constraint<N>> for AccumulatedImpulseSolver<N> {
             // This is not very good, but is the only way to do that without having a separate list
             // of all rigid bodies.
             for c in constraints.iter() {
-                match *c {
-                    Constraint::RBRB(ref a, ref b, _) => {
-                        a.borrow_mut().set_index(-2);
-                        b.borrow_mut().set_index(-2)
-                    },
-                    Constraint::BallInSocket(ref bis) => {
-                        let bbis = bis.borrow();
-                        match bbis.anchor1().body {
-                            Some(ref b) => {
-                                b.borrow_mut().set_index(-2)
-                            },
-                            None    => { }
-                        };
-
-                        match bbis.anchor2().body {
-                            Some(ref b) => {
-                                b.borrow_mut().set_index(-2)
-                            },
-                            None    => { }
+                match c.joint_info() {
+                    Some(joint) => {
+                        for b in joint.bodies() {
+                            b.borrow_mut().set_index(-2)
                         }
-                    }
-                    Constraint::Fixed(ref f) => { // FIXME: code duplication from BallInSocket
-                        let bf = f.borrow();
-                        match bf.anchor1().body {
-                            Some(ref b) => {
-                                b.borrow_mut().set_index(-2)
-                            },
-                            None    => { }
-                        };
-
-                        match bf.anchor2().body {
-                            Some(ref b) => {
-                                b.borrow_mut().set_index(-2)
-                            },
-                            None    => { }
+                    },
+                    None => {
+                        if let Constraint::RBRB(ref a, ref b, _) = *c {
+                            a.borrow_mut().set_index(-2);
+                            b.borrow_mut().set_index(-2)
                         }
                     }
                 }
@@ -354,35 +1036,18 @@ impl<N: Real> Solver<N, Constraint<N>> for AccumulatedImpulseSolver<N> {
             // FIXME: avoid allocation
             let mut joints = Vec::new();
             for (i, c) in constraints.iter().enumerate() {
-                match *c {
-                    Constraint::RBRB(ref a, ref b, _) => {
-                        set_body_index(a, &mut bodies, &mut id);
-                        set_body_index(b, &mut bodies, &mut id);
-                    },
-                    Constraint::BallInSocket(ref bis) => {
+                match c.joint_info() {
+                    Some(joint) => {
                         joints.push(i);
-                        let bbis = bis.borrow();
-                        match bbis.anchor1().body {
-                            Some(ref b) => set_body_index(b, &mut bodies, &mut id),
-                            None        => { }
-                        }
 
-                        match bbis.anchor2().body {
-                            Some(ref b) => set_body_index(b, &mut bodies, &mut id),
-                            None        => { }
+                        for b in joint.bodies() {
+                            set_body_index(b, &mut bodies, &mut id)
                         }
                     },
-                    Constraint::Fixed(ref f) => { // FIXME: code duplication from BallInSocket
-                        joints.push(i);
-                        let bf = f.borrow();
-                        match bf.anchor1().body {
-                            Some(ref b) => set_body_index(b, &mut bodies, &mut id),
-                            None        => { }
-                        }
-
-                        match bf.anchor2().body {
-                            Some(ref b) => set_body_index(b, &mut bodies, &mut id),
-                            None        => { }
+                    None => {
+                        if let Constraint::RBRB(ref a, ref b, _) = *c {
+                            set_body_index(a, &mut bodies, &mut id);
+                            set_body_index(b, &mut bodies, &mut id);
                         }
                     }
                 }
@@ -390,8 +1055,58 @@ impl<N: Real> Solver<N, Constraint<N>> for AccumulatedImpulseSolver<N> {
 
             self.do_solve(dt.clone(), constraints, &joints[..], &bodies[..]);
             self.cache.swap();
+            self.joint_cache.swap();
+        }
+
+        self.contact_flags.clear();
+    }
+}
+
+// The indices of the constraints of each island of at most `max_bodies` bodies, the islands being
+// the sets of bodies linked by the constraints `cs`.
+fn small_islands<N: Real>(cs: &[VelocityConstraint<N>], num_bodies: usize, max_bodies: usize) -> Vec<Vec<usize>> {
+    fn root(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i          = parents[i];
         }
+
+        i
     }
+
+    let mut parents: Vec<usize> = (0 .. num_bodies).collect();
+
+    for c in cs.iter() {
+        if c.id1 >= 0 && c.id2 >= 0 {
+            let root1 = root(&mut parents[..], c.id1 as usize);
+            let root2 = root(&mut parents[..], c.id2 as usize);
+
+            parents[root1] = root2;
+        }
+    }
+
+    let mut sizes = vec![0usize; num_bodies];
+
+    for i in 0 .. num_bodies {
+        let r    = root(&mut parents[..], i);
+        sizes[r] = sizes[r] + 1;
+    }
+
+    let mut islands = vec![Vec::new(); num_bodies];
+
+    for (i, c) in cs.iter().enumerate() {
+        let id = if c.id1 >= 0 { c.id1 } else { c.id2 };
+
+        if id >= 0 {
+            let r = root(&mut parents[..], id as usize);
+
+            if sizes[r] <= max_bodies {
+                islands[r].push(i);
+            }
+        }
+    }
+
+    islands.into_iter().filter(|island| !island.is_empty()).collect()
 }
 
 fn resize_buffer<A: Clone>(buff: &mut Vec<A>, size: usize, val: A) {
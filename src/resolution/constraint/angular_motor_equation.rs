@@ -0,0 +1,24 @@
+use alga::general::Real;
+use detection::joint::{AngularMotor, Joint};
+use resolution::constraint::hinge_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use math::Rotation;
+
+/// The number of equations of `joint`.
+pub fn num_equations<N: Real>(_: &AngularMotor<N>) -> usize {
+    1
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &AngularMotor<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           _:           &CorrectionParameters<N>) {
+    // The axis is expressed in world-space by conjugation with the first frame rotation.
+    let rot1    = joint.anchor1_pos().rotation;
+    let axis    = (rot1 * Rotation::from_scaled_axis(*joint.axis()) * rot1.inverse()).scaled_axis();
+    let impulse = joint.max_torque() * dt;
+
+    hinge_equation::fill_angular_equation(&axis, joint.target_velocity(), -impulse, impulse,
+                                          joint.anchor1(), joint.anchor2(), &mut constraints[0])
+}
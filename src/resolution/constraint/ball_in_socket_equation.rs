@@ -3,13 +3,48 @@ use num::Bounded;
 
 use alga::general::Real;
 use na::{self, U1};
-use math::{Point, Vector};
+use math::{Point, Vector, Orientation};
 use utils::GeneralizedCross;
 use object::RigidBody;
 use detection::joint::{Anchor, BallInSocket, Joint};
 use resolution::constraint::velocity_constraint::VelocityConstraint;
 use resolution::constraint::contact_equation::CorrectionParameters;
 use resolution::constraint::contact_equation;
+use resolution::constraint::hinge_equation;
+
+/// The number of equations of `joint`: those of its anchors, followed by those of its angular
+/// limits, and those of its angular damping.
+pub fn num_equations<N: Real>(joint: &BallInSocket<N>) -> usize {
+    num_limit_equations(joint) + num_damping_equations(joint)
+}
+
+fn num_limit_equations<N: Real>(joint: &BallInSocket<N>) -> usize {
+    if has_angular_limits(joint) {
+        na::dimension::<Vector<N>>() + 2
+    }
+    else {
+        na::dimension::<Vector<N>>()
+    }
+}
+
+fn num_damping_equations<N: Real>(joint: &BallInSocket<N>) -> usize {
+    if joint.angular_damping() == na::zero() {
+        0
+    }
+    else {
+        na::dimension::<Orientation<N>>()
+    }
+}
+
+#[cfg(feature = "dim3")]
+fn has_angular_limits<N: Real>(joint: &BallInSocket<N>) -> bool {
+    joint.angular_limits().is_some()
+}
+
+#[cfg(not(feature = "dim3"))]
+fn has_angular_limits<N: Real>(_: &BallInSocket<N>) -> bool {
+    false
+}
 
 pub fn fill_second_order_equation<N: Real>(dt:          N,
                                            joint:       &BallInSocket<N>,
@@ -23,6 +58,58 @@ pub fn fill_second_order_equation<N: Real>(dt:          N,
         joint.anchor2(),
         constraints,
         correction);
+
+    #[cfg(feature = "dim3")]
+    limit_relative_angular_motion(dt, joint, &mut constraints[na::dimension::<Vector<N>>() ..], correction);
+
+    // Damp the relative rotations around every axis.
+    let constraints = &mut constraints[num_limit_equations(joint) ..];
+
+    for i in 0 .. num_damping_equations(joint) {
+        let mut rot_axis: Orientation<N> = na::zero();
+        rot_axis[i] = na::one();
+
+        hinge_equation::damp_relative_angular_motion(dt, &rot_axis, joint.angular_damping(),
+                                                     joint.anchor1(), joint.anchor2(), &mut constraints[i]);
+    }
+}
+
+// Keeps the swing and twist angles within the joint limits. Each equation is inactive if its
+// limits are not reached.
+#[cfg(feature = "dim3")]
+fn limit_relative_angular_motion<N: Real>(dt:          N,
+                                          joint:       &BallInSocket<N>,
+                                          constraints: &mut [VelocityConstraint<N>],
+                                          correction:  &CorrectionParameters<N>) {
+    let limits = match joint.angular_limits() {
+        Some(limits) => limits.clone(),
+        None         => return
+    };
+    let (swing, swing_axis, twist, twist_axis) = joint.limit_axes().unwrap();
+    let _max: N = Bounded::max_value();
+
+    let (error, lobound, hibound) = if swing > limits.max_swing {
+        (limits.max_swing - swing, na::zero(), _max)
+    }
+    else {
+        (na::zero(), na::zero(), na::zero())
+    };
+
+    hinge_equation::fill_angular_equation(&swing_axis, error * correction.joint_corr / dt, lobound, hibound,
+                                          joint.anchor1(), joint.anchor2(), &mut constraints[0]);
+
+    let (error, lobound, hibound) = if twist < limits.min_twist {
+        (limits.min_twist - twist, -_max, na::zero())
+    }
+    else if twist > limits.max_twist {
+        (limits.max_twist - twist, na::zero(), _max)
+    }
+    else {
+        (na::zero(), na::zero(), na::zero())
+    };
+
+    hinge_equation::fill_angular_equation(&twist_axis, error * correction.joint_corr / dt, lobound, hibound,
+                                          joint.anchor1(), joint.anchor2(), &mut constraints[1]);
 }
 
 // FIXME: move this on another file. Something like "joint_equation_helper.rs"
@@ -71,7 +158,7 @@ pub fn cancel_relative_linear_motion<N: Real, P>(
         constraint.lobound   = -_max;
         constraint.hibound   = _max;
         constraint.objective = -dvel - error[i] / dt;
-        constraint.impulse   = na::zero(); // FIXME: cache
+        constraint.impulse   = na::zero(); // Warm-started by the solver once filled.
     }
 }
 
@@ -6,11 +6,13 @@ use na;
 use ncollide::query::Contact;
 use volumetric::InertiaTensor;
 use resolution::constraint::velocity_constraint::VelocityConstraint;
-use object::RigidBody;
+use object::{RigidBody, ContactCompliance};
+use detection::constraint::ContactFlags;
 use utils::GeneralizedCross;
 use math::{Point, Vector, Orientation};
 
 /// The correction coefficient used by the constraint solver.
+#[derive(Clone, Debug, PartialEq)]
 pub enum CorrectionMode<N: Real> {
     /// Penetration are solved by the penalty method.
     Velocity(N),
@@ -64,10 +66,12 @@ impl<N: Real> CorrectionMode<N> {
     }
 }
 
+#[derive(Clone)]
 pub struct CorrectionParameters<N: Real> {
-    pub corr_mode:  CorrectionMode<N>,
-    pub joint_corr: N,
-    pub rest_eps:   N
+    pub corr_mode:      CorrectionMode<N>,
+    pub joint_corr:     N,
+    pub rest_eps:       N,
+    pub max_mass_ratio: Option<N>
 }
 
 pub fn reinit_to_first_order_equation<N: Real>(dt:         N,
@@ -77,7 +81,8 @@ pub fn reinit_to_first_order_equation<N: Real>(dt:         N,
     /*
      * Fill b
      */
-    if coll.depth >= correction.corr_mode.min_depth_for_pos_corr() {
+    // The penetration of the soft contacts is what pushes the bodies apart: it is not corrected.
+    if coll.depth >= correction.corr_mode.min_depth_for_pos_corr() && constraint.cfm.is_zero() {
         constraint.objective = correction.corr_mode.pos_corr_factor() * coll.depth.max(na::zero()) / dt;
     }
     else {
@@ -92,6 +97,7 @@ pub fn reinit_to_first_order_equation<N: Real>(dt:         N,
 
 pub fn fill_second_order_equation<N: Real>(dt:           N,
                                            coll:         &Contact<Point<N>>,
+                                           flags:        &ContactFlags,
                                            rb1:          &RigidBody<N>,
                                            rb2:          &RigidBody<N>,
                                            rconstraint:  &mut VelocityConstraint<N>,
@@ -100,30 +106,45 @@ pub fn fill_second_order_equation<N: Real>(dt:           N,
                                            idf:          usize,
                                            cache:        &[N],
                                            correction:   &CorrectionParameters<N>) {
-    let restitution = rb1.restitution() * rb2.restitution();
+    let restitution = if flags.restitution { rb1.restitution() * rb2.restitution() } else { na::zero() };
+    let speculative = coll.depth < na::zero() &&
+                      (rb1.speculative_contacts_enabled() || rb2.speculative_contacts_enabled());
+
+    let compliance = match (rb1.contact_compliance(), rb2.contact_compliance()) {
+        (Some(c1), Some(c2)) => Some(c1.in_series(c2)),
+        (Some(c), None) | (None, Some(c)) => Some(*c),
+        (None, None) => None
+    };
 
     let center = na::center(&coll.world1, &coll.world2);
+    let scales = match correction.max_mass_ratio {
+        Some(ratio) => inv_mass_scales(rb1, rb2, ratio),
+        None        => (na::one(), na::one())
+    };
 
     fill_velocity_constraint(dt.clone(),
                              coll.normal.clone(),
                              center.clone(),
                              restitution,
                              coll.depth.clone(),
+                             speculative,
+                             compliance,
                              cache[0].clone(), // coll.impulses[0].clone(),
                              na::zero(),
                              Bounded::max_value(),
                              rb1,
                              rb2,
+                             scales,
                              rconstraint,
                              correction);
 
 
-    let friction  = rb1.friction() * rb2.friction();
     // To bound the friction we use the last frame normal impulse.
     // That means we have to make a special case for the first time the contact appears.
     // In that case, we estimate the impulse by the derired normal correction.
 
     let mut i = 0;
+    let mut sq_slip_speed: N = na::zero();
 
     Vector::orthonormal_subspace_basis(&[ coll.normal ], |friction_axis| {
         let constraint = &mut fconstraints[idf + i];
@@ -133,20 +154,64 @@ pub fn fill_second_order_equation<N: Real>(dt:           N,
                                  center.clone(),
                                  na::zero(),
                                  na::zero(),
+                                 false,
+                                 None,
                                  cache[i + 1].clone(), // coll.impulses[i].clone(),
                                  na::zero(), // dont setup the limit now
                                  na::zero(), // dont setup the limit now
                                  rb1,
                                  rb2,
+                                 scales,
                                  constraint,
                                  correction);
 
-        constraint.friction_coeff    = friction.clone();
+        // Without restitution nor penetration, the objective is the opposite of the tangential
+        // relative velocity along this axis.
+        sq_slip_speed = sq_slip_speed + constraint.objective * constraint.objective;
         constraint.friction_limit_id = idr;
         i = i + 1;
 
         true
-    })
+    });
+
+    let slip_speed = sq_slip_speed.sqrt();
+    let friction   = if flags.friction { rb1.friction_at(slip_speed) * rb2.friction_at(slip_speed) } else { na::zero() };
+
+    for constraint in fconstraints[idf .. idf + i].iter_mut() {
+        constraint.friction_coeff = friction;
+
+        // Frictionless rows are skipped by the solver: do not warm-start them either. The normal
+        // row keeps its warm-starting impulse.
+        if friction.is_zero() {
+            constraint.impulse = na::zero();
+        }
+    }
+}
+
+// The factors applied to the inverse masses of two bodies in contact so that the mass of the
+// heaviest does not exceed `max_mass_ratio` times the mass of the lightest.
+//
+// The heavy body is made lighter: it then reacts more to the contact, but the solver converges
+// much faster, e.g., a very heavy body resting on a light one no longer crushes it.
+fn inv_mass_scales<N: Real>(rb1: &RigidBody<N>, rb2: &RigidBody<N>, max_mass_ratio: N) -> (N, N) {
+    let _1: N = na::one();
+
+    if !rb1.can_move() || !rb2.can_move() || rb1.inv_mass().is_zero() || rb2.inv_mass().is_zero() {
+        return (_1, _1);
+    }
+
+    // Inverse masses are inversely proportional to the masses.
+    let ratio = rb1.inv_mass() / rb2.inv_mass();
+
+    if ratio > max_mass_ratio {
+        (_1, ratio / max_mass_ratio)
+    }
+    else if _1 / ratio > max_mass_ratio {
+        (_1 / (max_mass_ratio * ratio), _1)
+    }
+    else {
+        (_1, _1)
+    }
 }
 
 pub fn fill_constraint_geometry<N: Real>(normal:     Vector<N>,
@@ -155,16 +220,53 @@ pub fn fill_constraint_geometry<N: Real>(normal:     Vector<N>,
                                          rb1:        &Option<&RigidBody<N>>,
                                          rb2:        &Option<&RigidBody<N>>,
                                          constraint: &mut VelocityConstraint<N>) {
-    constraint.normal             = normal;
+    fill_scaled_constraint_geometry(normal, rot_axis1, rot_axis2, rb1, rb2, (na::one(), na::one()), constraint)
+}
+
+/// Same as `fill_constraint_geometry` but with the inverse mass and inertia of each body
+/// multiplied by the corresponding element of `scales`.
+pub fn fill_scaled_constraint_geometry<N: Real>(normal:     Vector<N>,
+                                                rot_axis1:  Orientation<N>,
+                                                rot_axis2:  Orientation<N>,
+                                                rb1:        &Option<&RigidBody<N>>,
+                                                rb2:        &Option<&RigidBody<N>>,
+                                                scales:     (N, N),
+                                                constraint: &mut VelocityConstraint<N>) {
+    fill_geometry(normal, normal, rot_axis1, rot_axis2, rb1, rb2, scales, constraint)
+}
+
+/// Same as `fill_constraint_geometry` but with the second body constrained along `normal2`
+/// instead of `normal1`.
+pub fn fill_coupled_constraint_geometry<N: Real>(normal1:    Vector<N>,
+                                                 normal2:    Vector<N>,
+                                                 rot_axis1:  Orientation<N>,
+                                                 rot_axis2:  Orientation<N>,
+                                                 rb1:        &Option<&RigidBody<N>>,
+                                                 rb2:        &Option<&RigidBody<N>>,
+                                                 constraint: &mut VelocityConstraint<N>) {
+    fill_geometry(normal1, normal2, rot_axis1, rot_axis2, rb1, rb2, (na::one(), na::one()), constraint)
+}
+
+fn fill_geometry<N: Real>(normal1:    Vector<N>,
+                          normal2:    Vector<N>,
+                          rot_axis1:  Orientation<N>,
+                          rot_axis2:  Orientation<N>,
+                          rb1:        &Option<&RigidBody<N>>,
+                          rb2:        &Option<&RigidBody<N>>,
+                          scales:     (N, N),
+                          constraint: &mut VelocityConstraint<N>) {
+    constraint.normal             = normal1;
+    constraint.normal2            = normal2;
     constraint.inv_projected_mass = na::zero();
+    constraint.cfm                = na::zero();
 
     match *rb1 {
         Some(ref rb) => {
             // rotation axis
-            constraint.weighted_normal1   = constraint.normal * rb.inv_mass();
+            constraint.weighted_normal1   = constraint.normal * (rb.inv_mass() * scales.0);
             constraint.rot_axis1          = rot_axis1;
 
-            constraint.weighted_rot_axis1 = rb.inv_inertia().apply(&constraint.rot_axis1);
+            constraint.weighted_rot_axis1 = rb.inv_inertia().apply(&constraint.rot_axis1) * scales.0;
 
             constraint.inv_projected_mass = constraint.inv_projected_mass +
                 na::dot(&constraint.normal, &constraint.weighted_normal1) +
@@ -176,13 +278,13 @@ pub fn fill_constraint_geometry<N: Real>(normal:     Vector<N>,
     match *rb2 {
         Some(ref rb) => {
             // rotation axis
-            constraint.weighted_normal2   = constraint.normal * rb.inv_mass();
+            constraint.weighted_normal2   = constraint.normal2 * (rb.inv_mass() * scales.1);
             constraint.rot_axis2          = rot_axis2;
 
-            constraint.weighted_rot_axis2 = rb.inv_inertia().apply(&constraint.rot_axis2);
+            constraint.weighted_rot_axis2 = rb.inv_inertia().apply(&constraint.rot_axis2) * scales.1;
 
             constraint.inv_projected_mass = constraint.inv_projected_mass +
-                na::dot(&constraint.normal, &constraint.weighted_normal2) +
+                na::dot(&constraint.normal2, &constraint.weighted_normal2) +
                 na::dot(&constraint.rot_axis2, &constraint.weighted_rot_axis2);
         },
         None => { }
@@ -197,11 +299,14 @@ fn fill_velocity_constraint<N: Real>(dt:              N,
                                      center:          Point<N>,
                                      restitution:     N,
                                      depth:           N,
+                                     speculative:     bool,
+                                     compliance:      Option<ContactCompliance<N>>,
                                      initial_impulse: N,
                                      lobound:         N,
                                      hibound:         N,
                                      rb1:             &RigidBody<N>,
                                      rb2:             &RigidBody<N>,
+                                     scales:          (N, N),
                                      constraint:      &mut VelocityConstraint<N>,
                                      correction:      &CorrectionParameters<N>) {
     let rot_axis1 = (center - *rb1.center_of_mass()).gcross(&-normal);
@@ -209,7 +314,7 @@ fn fill_velocity_constraint<N: Real>(dt:              N,
 
     let opt_rb1 = if rb1.can_move() { Some(rb1) } else { None };
     let opt_rb2 = if rb2.can_move() { Some(rb2) } else { None };
-    fill_constraint_geometry(normal, rot_axis1, rot_axis2, &opt_rb1, &opt_rb2, constraint);
+    fill_scaled_constraint_geometry(normal, rot_axis1, rot_axis2, &opt_rb1, &opt_rb2, scales, constraint);
 
     /*
      * Fill indice
@@ -226,19 +331,43 @@ fn fill_velocity_constraint<N: Real>(dt:              N,
         &constraint.normal,
         &constraint.rot_axis1,
         &constraint.rot_axis2,
-        &dt);
+        &dt) + kinematic_relative_velocity(rb1, rb2, &normal, &rot_axis1, &rot_axis2);
 
-    if constraint.objective < -correction.rest_eps {
-        constraint.objective = constraint.objective + restitution * constraint.objective
-    }
+    // The restitution is computed for each contact point from its own approach velocity, so
+    // that, e.g., only the corner of a tumbling box that hits the ground bounces.
+    // No bounce for speculative contacts as the bodies are not touching yet.
+    let approach = constraint.objective;
+    let bounce   = approach < -correction.rest_eps && !speculative;
+
+    constraint.objective = -approach;
 
-    constraint.objective = -constraint.objective;
+    if bounce {
+        constraint.objective = constraint.objective - restitution * approach
+    }
 
-    if false { // depth < na::zero() {
+    if speculative {
+        // Let the bodies close the gap between them, but not more, during this time step.
         constraint.objective = constraint.objective + depth / dt
     }
     else if depth < correction.corr_mode.max_depth_for_vel_corr() {
-        constraint.objective = constraint.objective + depth * correction.corr_mode.vel_corr_factor() / dt
+        let corrected = -approach + depth * correction.corr_mode.vel_corr_factor() / dt;
+
+        // The rebound already separates the bodies: adding the penetration correction on top of
+        // it would make the deepest points bounce higher than the others.
+        constraint.objective = if bounce { constraint.objective.max(corrected) } else { corrected }
+    }
+
+    match compliance {
+        Some(compliance) if !speculative => {
+            // Implicit spring-damper, as for the springs: the penetration is not corrected but
+            // pushes the bodies apart, and the normal impulse softens the constraint.
+            let gamma = na::one::<N>() / (dt * (compliance.damping + dt * compliance.stiffness));
+
+            constraint.objective          = -approach + depth * dt * compliance.stiffness * gamma;
+            constraint.cfm                = gamma;
+            constraint.inv_projected_mass = na::one::<N>() / (na::one::<N>() / constraint.inv_projected_mass + gamma);
+        },
+        _ => { }
     }
 
     // for warm-starting
@@ -251,6 +380,27 @@ fn fill_velocity_constraint<N: Real>(dt:              N,
     constraint.hibound = hibound;
 }
 
+// The part of the relative velocity due to the kinematic bodies: they are not moved by the
+// solver, but their animation still drives the contacts.
+fn kinematic_relative_velocity<N: Real>(rb1:       &RigidBody<N>,
+                                        rb2:       &RigidBody<N>,
+                                        normal:    &Vector<N>,
+                                        rot_axis1: &Orientation<N>,
+                                        rot_axis2: &Orientation<N>)
+                                        -> N {
+    let mut dvel: N = na::zero();
+
+    if rb1.is_kinematic() {
+        dvel = dvel - na::dot(&rb1.lin_vel(), normal) + na::dot(&rb1.ang_vel(), rot_axis1);
+    }
+
+    if rb2.is_kinematic() {
+        dvel = dvel + na::dot(&rb2.lin_vel(), normal) + na::dot(&rb2.ang_vel(), rot_axis2);
+    }
+
+    dvel
+}
+
 pub fn relative_velocity<N: Real>(rb1:       &Option<&RigidBody<N>>,
                                   rb2:       &Option<&RigidBody<N>>,
                                   normal:    &Vector<N>,
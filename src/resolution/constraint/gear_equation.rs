@@ -0,0 +1,63 @@
+use num::Bounded;
+use alga::general::Real;
+use na;
+use detection::joint::{Gear, Hinge, Joint};
+use resolution::constraint::ball_in_socket_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::contact_equation;
+use math::{Orientation, Rotation};
+
+/// The number of equations of `joint`.
+pub fn num_equations<N: Real>(_: &Gear<N>) -> usize {
+    1
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &Gear<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           correction:  &CorrectionParameters<N>) {
+    let hinge1 = joint.hinge1().borrow();
+    let hinge2 = joint.hinge2().borrow();
+    let axis1  = world_axis(&*hinge1);
+    let axis2  = world_axis(&*hinge2);
+    let ratio  = joint.ratio();
+    let _max: N = Bounded::max_value();
+
+    let constraint = &mut constraints[0];
+    let opt_rb1    = ball_in_socket_equation::write_anchor_id(joint.anchor1(), &mut constraint.id1);
+    let opt_rb2    = ball_in_socket_equation::write_anchor_id(joint.anchor2(), &mut constraint.id2);
+
+    // The impulse decreases the rotation speed of the second hinge relative to the one of the
+    // first hinge times the ratio.
+    contact_equation::fill_constraint_geometry(
+        na::zero(),
+        axis1 * ratio,
+        -axis2,
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        constraint
+    );
+
+    let error = -joint.error() * correction.joint_corr / dt;
+
+    constraint.lobound   = -_max;
+    constraint.hibound   = _max;
+    constraint.objective = angular_speed(&*hinge2, &axis2) - angular_speed(&*hinge1, &axis1) * ratio - error;
+    constraint.impulse   = na::zero(); // Warm-started by the solver once filled.
+}
+
+/// The axis of `hinge` in world-space.
+pub fn world_axis<N: Real>(hinge: &Hinge<N>) -> Orientation<N> {
+    let rot1 = hinge.anchor1_pos().rotation;
+
+    (rot1 * Rotation::from_scaled_axis(*hinge.axis()) * rot1.inverse()).scaled_axis()
+}
+
+// The relative angular velocity of the bodies attached to `hinge`, along its world-space `axis`.
+fn angular_speed<N: Real>(hinge: &Hinge<N>, axis: &Orientation<N>) -> N {
+    let ang_vel1 = match hinge.anchor1().body { Some(ref rb) => rb.borrow().ang_vel(), None => na::zero() };
+    let ang_vel2 = match hinge.anchor2().body { Some(ref rb) => rb.borrow().ang_vel(), None => na::zero() };
+
+    na::dot(&(ang_vel2 - ang_vel1), axis)
+}
@@ -0,0 +1,143 @@
+use num::Bounded;
+use alga::general::Real;
+use alga::linear::FiniteDimInnerSpace;
+use na;
+use detection::joint::{Hinge, Anchor, Joint};
+use resolution::constraint::ball_in_socket_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::contact_equation;
+use math::{Vector, Point, Orientation, Rotation};
+
+/// The number of equations of `joint`: those of its anchors and axis, followed by the one of its
+/// angular damping, and the one of its motor.
+pub fn num_equations<N: Real>(joint: &Hinge<N>) -> usize {
+    let mut num = na::dimension::<Vector<N>>() + na::dimension::<Orientation<N>>();
+
+    if joint.angular_damping() != na::zero() {
+        num = num + 1
+    }
+
+    if joint.motor().is_some() {
+        num = num + 1
+    }
+
+    num
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &Hinge<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           correction:  &CorrectionParameters<N>) {
+    let ref1 = joint.anchor1_pos();
+    let ref2 = joint.anchor2_pos();
+
+    ball_in_socket_equation::cancel_relative_linear_motion(
+        dt,
+        &Point::from_coordinates(ref1.translation.vector),
+        &Point::from_coordinates(ref2.translation.vector),
+        joint.anchor1(),
+        joint.anchor2(),
+        constraints,
+        correction);
+
+    let constraints = &mut constraints[na::dimension::<Vector<N>>() ..];
+    let _max: N     = Bounded::max_value();
+    // The axis and swing are expressed in world-space by conjugation with the first frame rotation.
+    let rot1        = ref1.rotation;
+    let axis        = (rot1 * Rotation::from_scaled_axis(*joint.axis()) * rot1.inverse()).scaled_axis();
+    let swing       = (rot1 * joint.swing() * rot1.inverse()).scaled_axis();
+
+    // Cancel the relative rotations around the axes orthogonal to the hinge axis.
+    let mut i = 0;
+    Orientation::orthonormal_subspace_basis(&[axis], |rot_axis| {
+        let error = -na::dot(&swing, rot_axis) * correction.joint_corr / dt;
+
+        fill_angular_equation(rot_axis, error, -_max, _max, joint.anchor1(), joint.anchor2(), &mut constraints[i]);
+        i = i + 1;
+
+        true
+    });
+
+    // Limit the rotation around the hinge axis. The equation is inactive if the limits are not
+    // reached.
+    let angle = joint.angle();
+    let limit = match joint.limits() {
+        Some((min, _)) if angle < min => Some((min - angle, -_max, na::zero())),
+        Some((_, max)) if angle > max => Some((max - angle, na::zero(), _max)),
+        _                             => None
+    };
+
+    match limit {
+        Some((error, lobound, hibound)) => {
+            let error = error * correction.joint_corr / dt;
+
+            fill_angular_equation(&axis, error, lobound, hibound, joint.anchor1(), joint.anchor2(), &mut constraints[i])
+        },
+        None => {
+            fill_angular_equation(&axis, na::zero(), na::zero(), na::zero(), joint.anchor1(), joint.anchor2(), &mut constraints[i])
+        }
+    }
+
+    i = i + 1;
+
+    if joint.angular_damping() != na::zero() {
+        damp_relative_angular_motion(dt, &axis, joint.angular_damping(), joint.anchor1(), joint.anchor2(),
+                                     &mut constraints[i]);
+        i = i + 1;
+    }
+
+    if let Some(motor) = joint.motor() {
+        let velocity = motor.target_angular_velocity(angle, dt);
+        let impulse  = motor.max_force() * dt;
+
+        fill_angular_equation(&axis, velocity, -impulse, impulse, joint.anchor1(), joint.anchor2(), &mut constraints[i])
+    }
+}
+
+// Sets up `constraint` so that it applies, along `rot_axis`, an angular impulse opposed to the
+// relative angular velocity of the anchored bodies and proportional to it. The impulse never
+// exceeds the one that stops the relative rotation.
+pub fn damp_relative_angular_motion<N: Real, P>(dt:         N,
+                                                rot_axis:   &Orientation<N>,
+                                                damping:    N,
+                                                anchor1:    &Anchor<N, P>,
+                                                anchor2:    &Anchor<N, P>,
+                                                constraint: &mut VelocityConstraint<N>) {
+    fill_angular_equation(rot_axis, na::zero(), na::zero(), na::zero(), anchor1, anchor2, constraint);
+
+    let bound = (constraint.objective * damping * dt).abs();
+
+    constraint.lobound = -bound;
+    constraint.hibound = bound;
+}
+
+// Sets up `constraint` so that the relative angular velocity of the anchored bodies along
+// `rot_axis` becomes `target`, with an impulse in `[lobound, hibound]`.
+pub fn fill_angular_equation<N: Real, P>(rot_axis:   &Orientation<N>,
+                                         target:     N,
+                                         lobound:    N,
+                                         hibound:    N,
+                                         anchor1:    &Anchor<N, P>,
+                                         anchor2:    &Anchor<N, P>,
+                                         constraint: &mut VelocityConstraint<N>) {
+    let opt_rb1 = ball_in_socket_equation::write_anchor_id(anchor1, &mut constraint.id1);
+    let opt_rb2 = ball_in_socket_equation::write_anchor_id(anchor2, &mut constraint.id2);
+
+    contact_equation::fill_constraint_geometry(
+        na::zero(),
+        rot_axis.clone(),
+        -*rot_axis,
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        constraint
+    );
+
+    let ang_vel1 = match opt_rb1 { Some(rb) => rb.ang_vel(), None => na::zero() };
+    let ang_vel2 = match opt_rb2 { Some(rb) => rb.ang_vel(), None => na::zero() };
+
+    constraint.lobound   = lobound;
+    constraint.hibound   = hibound;
+    constraint.objective = na::dot(&(ang_vel2 - ang_vel1), rot_axis) - target;
+    constraint.impulse   = na::zero(); // Warm-started by the solver once filled.
+}
@@ -2,16 +2,17 @@
 
 use std::iter;
 use std::mem;
-use std::hash::{Hash, Hasher};
 use std::collections::HashMap;
 
 use alga::general::Real;
 use na;
+use ncollide::utils::data::hash_map::HashMap as UidMap;
+use ncollide::utils::data::hash::UintTWHash;
+use object::{RigidBodyHandle, WorldObject};
 use math::Point;
-use ncollide::utils::AsBytes;
 use utils::DeterministicState;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Debug)]
 /// The identifier of a contact stored in the impulse cache.
 pub struct ContactIdentifier<N: Real> {
     obj1:    usize,
@@ -19,47 +20,53 @@ pub struct ContactIdentifier<N: Real> {
     ccenter: Point<N>
 }
 
-impl<N: Real> Eq for ContactIdentifier<N> { } // NOTE: this is  wrong because of floats, but we dont care
-
-impl<N: Real> Hash for ContactIdentifier<N> {
-    #[inline]
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write(self.ccenter.as_bytes())
-    }
-}
-
 impl<N: Real> ContactIdentifier<N> {
-    pub fn new(obj1: usize, obj2: usize, center: Point<N>, step: &N) -> ContactIdentifier<N> {
-        let mut cell = center / *step;
-
-        for x in cell.coords.iter_mut() {
-            *x = x.trunc()
-        }
-
+    pub fn new(obj1: usize, obj2: usize, center: Point<N>) -> ContactIdentifier<N> {
         ContactIdentifier {
             obj1:    obj1,
             obj2:    obj2,
-            ccenter: cell
+            ccenter: center
         }
     }
+
+    /// The identifiers of the two bodies involved in this contact.
+    #[inline]
+    pub fn pair(&self) -> (usize, usize) {
+        (self.obj1, self.obj2)
+    }
+
+    /// The contact center.
+    #[inline]
+    pub fn center(&self) -> &Point<N> {
+        &self.ccenter
+    }
 }
 
+/// Cache of the impulses applied at each contact during the last update.
+///
+/// Contacts are persisted from one frame to the next by matching them, on a per-body-pair basis,
+/// with the closest contact of the previous frame. This lets the solver warm-start each contact
+/// with the impulse it accumulated last time.
 pub struct ImpulseCache<N: Real> {
-    // XXX: simulations won't be reproductible because of the randomized HashMap.
-    hash_prev:           HashMap<ContactIdentifier<N>, (usize, usize), DeterministicState>,
+    // Contact centers and impulse offsets of the last update, grouped by pair of bodies.
+    hash_prev:           HashMap<(usize, usize), Vec<(Point<N>, usize)>, DeterministicState>,
     cache_prev:          Vec<N>,
-    hash_next:           HashMap<ContactIdentifier<N>, (usize, usize), DeterministicState>,
+    // Contacts of the current update with their constraint index and impulse offset.
+    contacts_next:       Vec<(ContactIdentifier<N>, usize, usize)>,
     cache_next:          Vec<N>,
     step:                N,
     impulse_per_contact: usize
 }
 
 impl<N: Real> ImpulseCache<N> {
+    /// Creates a new impulse cache.
+    ///
+    /// Two contacts between the same bodies on two consecutive frames are considered to be the
+    /// same if their centers are closer than `step`.
     pub fn new(step: N, impulse_per_contact: usize) -> ImpulseCache<N> {
-
         ImpulseCache {
             hash_prev:           HashMap::with_capacity_and_hasher(32, DeterministicState::new()),
-            hash_next:           HashMap::with_capacity_and_hasher(32, DeterministicState::new()),
+            contacts_next:       Vec::with_capacity(32),
             cache_prev:          iter::repeat(na::zero()).take(impulse_per_contact).collect(),
             cache_next:          iter::repeat(na::zero()).take(impulse_per_contact).collect(),
             step:                step,
@@ -67,23 +74,41 @@ impl<N: Real> ImpulseCache<N> {
         }
     }
 
+    /// Registers the contact with index `cid` and finds its impulse from the last update.
     pub fn insert(&mut self, cid: usize, obj1: usize, obj2: usize, center: Point<N>) {
-        let id = ContactIdentifier::new(obj1, obj2, center, &self.step);
-        let imp =
-            match self.hash_prev.get(&id).cloned() {
-                Some((_, i)) => i,
-                None         => 0
-            };
+        let mut imp = 0; // The reserved, zero, impulses.
+
+        if let Some(prev) = self.hash_prev.get_mut(&(obj1, obj2)) {
+            let mut best        = None;
+            let mut best_sqdist = self.step * self.step;
+
+            for (i, &(ref prev_center, _)) in prev.iter().enumerate() {
+                let sqdist = na::distance_squared(prev_center, &center);
+
+                if sqdist < best_sqdist {
+                    best_sqdist = sqdist;
+                    best        = Some(i);
+                }
+            }
 
-        let _ = self.hash_next.insert(id, (cid, imp));
+            // Each contact of the last frame warm-starts at most one contact.
+            if let Some(i) = best {
+                imp = prev.swap_remove(i).1;
+            }
+        }
+
+        self.contacts_next.push((ContactIdentifier::new(obj1, obj2, center), cid, imp));
     }
 
-    pub fn hash(&self) -> &HashMap<ContactIdentifier<N>, (usize, usize), DeterministicState> {
-        &self.hash_next
+    /// The contacts registered since the last swap, with their constraint index and impulse
+    /// offset.
+    pub fn contacts(&self) -> &[(ContactIdentifier<N>, usize, usize)] {
+        &self.contacts_next[..]
     }
 
-    pub fn hash_mut(&mut self) -> &mut HashMap<ContactIdentifier<N>, (usize, usize), DeterministicState> {
-        &mut self.hash_next
+    /// Mutable reference to the contacts registered since the last swap.
+    pub fn contacts_mut(&mut self) -> &mut [(ContactIdentifier<N>, usize, usize)] {
+        &mut self.contacts_next[..]
     }
 
     pub fn push_impulsions(&mut self) -> &mut [N] {
@@ -107,23 +132,115 @@ impl<N: Real> ImpulseCache<N> {
     }
 
     pub fn len(&self) -> usize {
-        self.hash_next.len()
+        self.contacts_next.len()
     }
 
     pub fn clear(&mut self) {
         self.cache_prev.clear();
         self.hash_prev.clear();
         self.cache_next.clear();
-        self.hash_next.clear();
+        self.contacts_next.clear();
 
         self.cache_prev.extend(iter::repeat(na::zero::<N>()).take(self.impulse_per_contact));
         self.cache_next.extend(iter::repeat(na::zero::<N>()).take(self.impulse_per_contact));
     }
 
+    /// A copy of this cache for the copies `bodies` of the rigid bodies, indexed by the
+    /// identifiers of the originals.
+    ///
+    /// The impulses of the pairs involving a body that has no copy are forgotten.
+    pub fn clone_for_prediction(&self, bodies: &UidMap<usize, RigidBodyHandle<N>, UintTWHash>) -> ImpulseCache<N> {
+        let mut hash_prev = HashMap::with_capacity_and_hasher(self.hash_prev.len(), DeterministicState::new());
+
+        for (&(obj1, obj2), prev) in self.hash_prev.iter() {
+            if let (Some(rb1), Some(rb2)) = (bodies.find(&obj1), bodies.find(&obj2)) {
+                let key = (WorldObject::rigid_body_uid(rb1), WorldObject::rigid_body_uid(rb2));
+                let _   = hash_prev.insert(key, prev.clone());
+            }
+        }
+
+        ImpulseCache {
+            hash_prev:           hash_prev,
+            cache_prev:          self.cache_prev.clone(),
+            contacts_next:       Vec::with_capacity(32),
+            cache_next:          iter::repeat(na::zero()).take(self.impulse_per_contact).collect(),
+            step:                self.step,
+            impulse_per_contact: self.impulse_per_contact
+        }
+    }
+
     pub fn swap(&mut self) {
-        mem::swap(&mut self.hash_prev, &mut self.hash_next);
-        mem::swap(&mut self.cache_prev,&mut self.cache_next);
-        self.hash_next.clear();
+        // Reuse the per-pair buffers, but forget about pairs that are no longer in contact.
+        for prev in self.hash_prev.values_mut() {
+            prev.clear();
+        }
+
+        for (id, _, imp) in self.contacts_next.drain(..) {
+            self.hash_prev.entry(id.pair()).or_insert_with(Vec::new).push((id.ccenter, imp));
+        }
+
+        self.hash_prev.retain(|_, prev| !prev.is_empty());
+
+        mem::swap(&mut self.cache_prev, &mut self.cache_next);
         self.cache_next.truncate(self.impulse_per_contact);
     }
 }
+
+/// Cache of the impulses applied by the equations of each joint during the last update.
+///
+/// Each joint is identified by its key on the joint manager. The impulses of a joint warm-start
+/// its equations at the next update if their number did not change, e.g., because a limit was
+/// reached.
+pub struct JointImpulseCache<N: Real> {
+    prev: HashMap<usize, Vec<N>, DeterministicState>,
+    next: HashMap<usize, Vec<N>, DeterministicState>
+}
+
+impl<N: Real> JointImpulseCache<N> {
+    /// Creates a new empty joint impulse cache.
+    pub fn new() -> JointImpulseCache<N> {
+        JointImpulseCache {
+            prev: HashMap::with_capacity_and_hasher(32, DeterministicState::new()),
+            next: HashMap::with_capacity_and_hasher(32, DeterministicState::new())
+        }
+    }
+
+    /// The impulses of the equations of `joint` during the last update, if it was solved.
+    pub fn impulses(&self, joint: usize) -> Option<&[N]> {
+        self.prev.get(&joint).map(|imps| &imps[..])
+    }
+
+    /// Records the impulses of the equations of `joint` during the current update.
+    pub fn insert(&mut self, joint: usize, impulses: Vec<N>) {
+        let _ = self.next.insert(joint, impulses);
+    }
+
+    pub fn clear(&mut self) {
+        self.prev.clear();
+        self.next.clear();
+    }
+
+    /// A copy of this cache for the copies of the joints, indexed by the keys of the originals.
+    ///
+    /// The impulses of the joints that have no copy are forgotten.
+    pub fn clone_for_prediction(&self, joints: &UidMap<usize, usize, UintTWHash>) -> JointImpulseCache<N> {
+        let mut prev = HashMap::with_capacity_and_hasher(self.prev.len(), DeterministicState::new());
+
+        for (joint, imps) in self.prev.iter() {
+            if let Some(copy) = joints.find(joint) {
+                let _ = prev.insert(*copy, imps.clone());
+            }
+        }
+
+        JointImpulseCache {
+            prev: prev,
+            next: HashMap::with_capacity_and_hasher(32, DeterministicState::new())
+        }
+    }
+
+    /// Forgets the joints that were not solved during the current update.
+    pub fn swap(&mut self) {
+        mem::swap(&mut self.prev, &mut self.next);
+        self.next.clear();
+    }
+}
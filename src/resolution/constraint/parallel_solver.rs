@@ -0,0 +1,261 @@
+//! Resolution of the independent islands of velocity constraints, on several threads or one
+//! after the other.
+
+#[cfg(feature = "parallel")]
+use std::thread;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+use alga::general::Real;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::projected_gauss_seidel_solver as pgs;
+use resolution::constraint::projected_gauss_seidel_solver::{Velocities, FrictionModel, IterationScheme};
+
+// The constraints of some islands, with the bodies as indexed by their constraints.
+struct Batch<N: Real> {
+    bodies:       Vec<usize>,
+    restitution:  Vec<usize>,
+    friction:     Vec<usize>,
+    blocks:       Vec<(usize, usize)>,
+    rconstraints: Vec<VelocityConstraint<N>>,
+    fconstraints: Vec<VelocityConstraint<N>>,
+    result:       Vec<Velocities<N>>
+}
+
+/// Solves the velocity constraints like `projected_gauss_seidel_block_solve` or
+/// `projected_jacobi_solve`, depending on `scheme`, the islands of constraints being distributed
+/// among `num_threads` threads.
+///
+/// Two constraints are in the same island if they act on a common body. The islands are solved
+/// independently, each one in the order of its constraints in `restitution` and `friction`, so
+/// the impulses and velocities are the same as with a single thread.
+#[cfg(feature = "parallel")]
+pub fn solve<N: Real>(restitution:    &mut [VelocityConstraint<N>],
+                      friction:       &mut [VelocityConstraint<N>],
+                      blocks:         &[(usize, usize)],
+                      scheme:         IterationScheme,
+                      friction_model: FrictionModel,
+                      result:         &mut [Velocities<N>],
+                      num_bodies:     usize,
+                      num_iterations: usize,
+                      is_lambda_zero: bool,
+                      num_threads:    usize) {
+    assert!(num_threads > 0, "The number of threads must be at least one.");
+    assert!(result.len() == num_bodies);
+
+    let mut batches = partition(restitution, friction, blocks, num_bodies, num_threads);
+
+    // Every batch but the first one is solved by its own thread.
+    let mut first       = batches.remove(0);
+    let handles: Vec<_> = batches.into_iter().map(|mut batch| {
+        thread::spawn(move || {
+            solve_batch(&mut batch, scheme, friction_model, num_iterations, is_lambda_zero);
+            batch
+        })
+    }).collect();
+
+    solve_batch(&mut first, scheme, friction_model, num_iterations, is_lambda_zero);
+
+    let solved = Some(first).into_iter().chain(handles.into_iter().map(|h| h.join().unwrap()));
+    gather(solved, restitution, friction, result)
+}
+
+/// Solves the velocity constraints like `solve`, but on the current thread, one island after the
+/// other.
+///
+/// Returns the time interval spent on each island, with its number of equations.
+#[cfg(feature = "tracing")]
+pub fn solve_islands<N: Real>(restitution:    &mut [VelocityConstraint<N>],
+                              friction:       &mut [VelocityConstraint<N>],
+                              blocks:         &[(usize, usize)],
+                              scheme:         IterationScheme,
+                              friction_model: FrictionModel,
+                              result:         &mut [Velocities<N>],
+                              num_bodies:     usize,
+                              num_iterations: usize,
+                              is_lambda_zero: bool)
+                              -> Vec<(Instant, Instant, usize)> {
+    assert!(result.len() == num_bodies);
+
+    // Each island has its own batch.
+    let mut batches = partition(restitution, friction, blocks, num_bodies, usize::max_value());
+    let mut spans   = Vec::with_capacity(batches.len());
+
+    for batch in batches.iter_mut() {
+        let num_equations = batch.rconstraints.len() + batch.fconstraints.len();
+
+        if num_equations != 0 {
+            let begin = Instant::now();
+            solve_batch(batch, scheme, friction_model, num_iterations, is_lambda_zero);
+            spans.push((begin, Instant::now(), num_equations));
+        }
+    }
+
+    gather(batches.into_iter(), restitution, friction, result);
+
+    spans
+}
+
+// Copies the constraints and velocities of the solved batches back to their original slices.
+fn gather<N: Real, I>(solved:      I,
+                      restitution: &mut [VelocityConstraint<N>],
+                      friction:    &mut [VelocityConstraint<N>],
+                      result:      &mut [Velocities<N>])
+    where I: Iterator<Item = Batch<N>> {
+    for v in result.iter_mut() {
+        v.reset();
+    }
+
+    for batch in solved {
+        for (i, c) in batch.restitution.iter().zip(batch.rconstraints.into_iter()) {
+            restitution[*i] = c;
+        }
+
+        for (i, c) in batch.friction.iter().zip(batch.fconstraints.into_iter()) {
+            friction[*i] = c;
+        }
+
+        for (b, v) in batch.bodies.iter().zip(batch.result.into_iter()) {
+            result[*b] = v;
+        }
+    }
+}
+
+fn solve_batch<N: Real>(batch:          &mut Batch<N>,
+                        scheme:         IterationScheme,
+                        friction_model: FrictionModel,
+                        num_iterations: usize,
+                        is_lambda_zero: bool) {
+    let num_bodies = batch.bodies.len();
+
+    match scheme {
+        IterationScheme::GaussSeidel => pgs::projected_gauss_seidel_block_solve(
+            &mut batch.rconstraints[..], &mut batch.fconstraints[..], &batch.blocks[..], friction_model,
+            &mut batch.result[..], num_bodies, num_iterations, is_lambda_zero),
+        IterationScheme::Jacobi => pgs::projected_jacobi_solve(
+            &mut batch.rconstraints[..], &mut batch.fconstraints[..], friction_model,
+            &mut batch.result[..], num_bodies, num_iterations, is_lambda_zero)
+    }
+}
+
+// Distributes the islands of constraints among at most `num_batches` batches, the constraints
+// and bodies of each batch being re-indexed in their original order.
+fn partition<N: Real>(restitution: &[VelocityConstraint<N>],
+                      friction:    &[VelocityConstraint<N>],
+                      blocks:      &[(usize, usize)],
+                      num_bodies:  usize,
+                      num_batches: usize)
+                      -> Vec<Batch<N>> {
+    fn root(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i          = parents[i];
+        }
+
+        i
+    }
+
+    // The constraints acting on no body are put with the fictitious body `num_bodies`.
+    let body = |c: &VelocityConstraint<N>| {
+        if c.id1 >= 0 { c.id1 as usize } else if c.id2 >= 0 { c.id2 as usize } else { num_bodies }
+    };
+
+    let mut parents: Vec<usize> = (0 .. num_bodies + 1).collect();
+
+    for c in restitution.iter().chain(friction.iter()) {
+        if c.id1 >= 0 && c.id2 >= 0 {
+            let r1 = root(&mut parents[..], c.id1 as usize);
+            let r2 = root(&mut parents[..], c.id2 as usize);
+            parents[r1.max(r2)] = r1.min(r2);
+        }
+    }
+
+    // The number of equations of each island, indexed by its root.
+    let mut sizes = vec![0usize; num_bodies + 1];
+
+    for c in restitution.iter().chain(friction.iter()) {
+        let r    = root(&mut parents[..], body(c));
+        sizes[r] = sizes[r] + 1;
+    }
+
+    // The largest islands are assigned first, each one to the least loaded batch.
+    let mut roots: Vec<usize> = (0 .. num_bodies + 1).filter(|r| sizes[*r] != 0).collect();
+    roots.sort_by(|r1, r2| sizes[*r2].cmp(&sizes[*r1]));
+
+    let num_batches  = num_batches.min(roots.len()).max(1);
+    let mut loads    = vec![0usize; num_batches];
+    let mut batch_of = vec![usize::max_value(); num_bodies + 1];
+
+    for r in roots.into_iter() {
+        let best = (0 .. num_batches).min_by_key(|b| loads[*b]).unwrap();
+
+        loads[best] = loads[best] + sizes[r];
+        batch_of[r] = best;
+    }
+
+    let mut batches: Vec<Batch<N>> = (0 .. num_batches).map(|_| {
+        Batch {
+            bodies:       Vec::new(),
+            restitution:  Vec::new(),
+            friction:     Vec::new(),
+            blocks:       Vec::new(),
+            rconstraints: Vec::new(),
+            fconstraints: Vec::new(),
+            result:       Vec::new()
+        }
+    }).collect();
+
+    // The index of each body, and of each restitution constraint, in its batch.
+    let mut body_ids        = vec![-1isize; num_bodies];
+    let mut restitution_ids = vec![0usize; restitution.len()];
+
+    for b in 0 .. num_bodies {
+        let r = root(&mut parents[..], b);
+
+        if sizes[r] != 0 {
+            let batch   = &mut batches[batch_of[r]];
+            body_ids[b] = batch.bodies.len() as isize;
+            batch.bodies.push(b);
+            batch.result.push(Velocities::new());
+        }
+    }
+
+    let local = |c: &VelocityConstraint<N>| {
+        let mut c = c.clone();
+
+        if c.id1 >= 0 {
+            c.id1 = body_ids[c.id1 as usize];
+        }
+
+        if c.id2 >= 0 {
+            c.id2 = body_ids[c.id2 as usize];
+        }
+
+        c
+    };
+
+    for (i, c) in restitution.iter().enumerate() {
+        let batch          = &mut batches[batch_of[root(&mut parents[..], body(c))]];
+        restitution_ids[i] = batch.restitution.len();
+        batch.restitution.push(i);
+        batch.rconstraints.push(local(c));
+    }
+
+    for (i, c) in friction.iter().enumerate() {
+        let batch = &mut batches[batch_of[root(&mut parents[..], body(c))]];
+        let mut c = local(c);
+
+        c.friction_limit_id = restitution_ids[c.friction_limit_id];
+        batch.friction.push(i);
+        batch.fconstraints.push(c);
+    }
+
+    // The constraints of a block act on the same two bodies, thus are consecutive in their batch.
+    for &(begin, end) in blocks.iter() {
+        let batch = &mut batches[batch_of[root(&mut parents[..], body(&restitution[begin]))]];
+        let first = restitution_ids[begin];
+
+        batch.blocks.push((first, first + end - begin));
+    }
+
+    batches
+}
@@ -0,0 +1,131 @@
+use num::Bounded;
+use alga::general::Real;
+use alga::linear::FiniteDimInnerSpace;
+use na;
+use utils::GeneralizedCross;
+use detection::joint::{Prismatic, Anchor, Joint};
+use resolution::constraint::ball_in_socket_equation;
+use resolution::constraint::fixed_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::contact_equation;
+use math::{Vector, Point, Orientation};
+
+/// The number of equations of `joint`: those of its axis and orientation, followed by the one of
+/// its motor.
+pub fn num_equations<N: Real>(joint: &Prismatic<N>) -> usize {
+    let num = na::dimension::<Vector<N>>() + na::dimension::<Orientation<N>>();
+
+    if joint.motor().is_some() {
+        num + 1
+    }
+    else {
+        num
+    }
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &Prismatic<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           correction:  &CorrectionParameters<N>) {
+    let ref1    = joint.anchor1_pos();
+    let ref2    = joint.anchor2_pos();
+    let global1 = Point::from_coordinates(ref1.translation.vector);
+    let global2 = Point::from_coordinates(ref2.translation.vector);
+    let delta   = global2 - global1;
+    let axis    = ref1.rotation * *joint.axis();
+    let _max: N = Bounded::max_value();
+
+    // Cancel the relative translations orthogonal to the slider axis.
+    let mut i = 0;
+    Vector::orthonormal_subspace_basis(&[axis], |lin_axis| {
+        let error = -na::dot(&delta, lin_axis) * correction.joint_corr / dt;
+
+        fill_linear_equation(dt, &global1, &global2, lin_axis, error, -_max, _max,
+                             joint.anchor1(), joint.anchor2(), &mut constraints[i]);
+        i = i + 1;
+
+        true
+    });
+
+    // Limit the translation along the slider axis. The equation is inactive if the limits are not
+    // reached.
+    let translation = na::dot(&delta, &axis);
+    let limit = match joint.limits() {
+        Some((min, _)) if translation < min => Some((min - translation, na::zero(), _max)),
+        Some((_, max)) if translation > max => Some((max - translation, -_max, na::zero())),
+        _                                   => None
+    };
+
+    match limit {
+        Some((error, lobound, hibound)) => {
+            let error = error * correction.joint_corr / dt;
+
+            fill_linear_equation(dt, &global1, &global2, &axis, error, lobound, hibound,
+                                 joint.anchor1(), joint.anchor2(), &mut constraints[i])
+        },
+        None => {
+            fill_linear_equation(dt, &global1, &global2, &axis, na::zero(), na::zero(), na::zero(),
+                                 joint.anchor1(), joint.anchor2(), &mut constraints[i])
+        }
+    }
+
+    fixed_equation::cancel_relative_angular_motion(
+        dt,
+        &ref1,
+        &ref2,
+        joint.anchor1(),
+        joint.anchor2(),
+        &mut constraints[na::dimension::<Vector<N>>() ..],
+        correction);
+
+    if let Some(motor) = joint.motor() {
+        let velocity = motor.target_velocity(translation, dt);
+        let impulse  = motor.max_force() * dt;
+
+        fill_linear_equation(dt, &global1, &global2, &axis, velocity, -impulse, impulse,
+                             joint.anchor1(), joint.anchor2(),
+                             &mut constraints[na::dimension::<Vector<N>>() + na::dimension::<Orientation<N>>()])
+    }
+}
+
+/// Sets up `constraint` so that the relative linear velocity of the anchor points `global1` and
+/// `global2` along `lin_axis` becomes `target`, with an impulse in `[lobound, hibound]`.
+pub fn fill_linear_equation<N: Real, P>(dt:         N,
+                                        global1:    &Point<N>,
+                                        global2:    &Point<N>,
+                                        lin_axis:   &Vector<N>,
+                                        target:     N,
+                                        lobound:    N,
+                                        hibound:    N,
+                                        anchor1:    &Anchor<N, P>,
+                                        anchor2:    &Anchor<N, P>,
+                                        constraint: &mut VelocityConstraint<N>) {
+    let opt_rb1 = ball_in_socket_equation::write_anchor_id(anchor1, &mut constraint.id1);
+    let opt_rb2 = ball_in_socket_equation::write_anchor_id(anchor2, &mut constraint.id2);
+
+    let rot_axis1 = -(*global1 - anchor1.center_of_mass()).gcross(lin_axis);
+    let rot_axis2 =  (*global2 - anchor2.center_of_mass()).gcross(lin_axis);
+
+    let dvel = contact_equation::relative_velocity(
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        lin_axis,
+        &rot_axis1,
+        &rot_axis2,
+        &dt);
+
+    contact_equation::fill_constraint_geometry(
+        lin_axis.clone(),
+        rot_axis1,
+        rot_axis2,
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        constraint
+    );
+
+    constraint.lobound   = lobound;
+    constraint.hibound   = hibound;
+    constraint.objective = target - dvel;
+    constraint.impulse   = na::zero(); // Warm-started by the solver once filled.
+}
@@ -1,8 +1,38 @@
+use std::usize;
+use num::Bounded;
 use alga::general::Real;
 use na;
 use math::{Vector, Orientation};
 use resolution::constraint::velocity_constraint::VelocityConstraint;
 
+/// The approximation of the Coulomb friction cone used by the projected gauss seidel solver.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum FrictionModel {
+    /// Each friction direction is bounded independently: the friction cone is approximated by a
+    /// pyramid.
+    ///
+    /// This is cheaper, but a body sliding along a diagonal of the pyramid undergoes more
+    /// friction than along its axes, and its sliding direction drifts towards them.
+    Pyramid,
+    /// The friction impulse of each contact is projected on the exact Coulomb cone: friction does
+    /// not depend on the sliding direction.
+    Cone
+}
+
+/// The order in which the constraints are solved at each iteration.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum IterationScheme {
+    /// Each constraint is solved using the velocities corrected by the constraints solved before
+    /// it during the same iteration.
+    GaussSeidel,
+    /// Every constraint is solved from the velocities of the previous iteration, then all the
+    /// impulses are applied at once.
+    ///
+    /// This needs more iterations, but the result does not depend on the order of the
+    /// constraints, and each constraint of an iteration can be solved independently.
+    Jacobi
+}
+
 /// Structure holding the result of the projected gauss seidel solver.
 #[derive(PartialEq, Debug, Clone)]
 pub struct Velocities<N: Real> {
@@ -46,6 +76,34 @@ pub fn projected_gauss_seidel_solve<N: Real>(restitution:    &mut [VelocityConst
                                              num_bodies:     usize,
                                              num_iterations: usize,
                                              is_lambda_zero: bool) {
+    projected_gauss_seidel_block_solve(restitution, friction, &[], FrictionModel::Pyramid, result,
+                                       num_bodies, num_iterations, is_lambda_zero)
+}
+
+/// The maximum number of restitution constraints solved simultaneously by
+/// `projected_gauss_seidel_block_solve`.
+pub const MAX_BLOCK_SIZE: usize = 4;
+
+/// Solve a set of velocity constraints using the projected gauss seidel solver, where some
+/// restitution constraints are solved simultaneously.
+///
+/// Each element of `blocks` is the range `(begin, end)` of at most `MAX_BLOCK_SIZE` restitution
+/// constraints which impulses are solved exactly together, e.g., the contacts between two bodies.
+/// The ranges must be sorted and disjoint, and the constraints of a range must involve the same
+/// two bodies. This removes the rocking of boxes resting on two or four contact points at low
+/// iteration counts.
+///
+/// The friction constraints of a contact, i.e., with the same `friction_limit_id`, must be
+/// consecutive. They are bounded following `friction_model`. The other arguments are the same as
+/// for `projected_gauss_seidel_solve`.
+pub fn projected_gauss_seidel_block_solve<N: Real>(restitution:    &mut [VelocityConstraint<N>],
+                                                   friction:       &mut [VelocityConstraint<N>],
+                                                   blocks:         &[(usize, usize)],
+                                                   friction_model: FrictionModel,
+                                                   result:         &mut [Velocities<N>],
+                                                   num_bodies:     usize,
+                                                   num_iterations: usize,
+                                                   is_lambda_zero: bool) {
     // initialize the solution with zeros...
     // mj_lambda is result
     assert!(result.len() == num_bodies);
@@ -69,58 +127,607 @@ pub fn projected_gauss_seidel_solve<N: Real>(restitution:    &mut [VelocityConst
      * solve the system
      */
     for _ in 0 .. num_iterations {
-        for c in restitution.iter_mut() {
-            solve_velocity_constraint(c, result);
+        let mut i      = 0;
+        let mut blocks = blocks.iter().peekable();
+
+        while i < restitution.len() {
+            match blocks.peek().cloned() {
+                Some(&(begin, end)) if begin == i => {
+                    solve_velocity_constraint_block(&mut restitution[begin .. end], result);
+                    let _ = blocks.next();
+                    i = end;
+                },
+                _ => {
+                    solve_velocity_constraint(&mut restitution[i], result);
+                    i = i + 1;
+                }
+            }
         }
 
+        match friction_model {
+            FrictionModel::Pyramid => {
+                for c in friction.iter_mut() {
+                    let impulse = restitution[c.friction_limit_id].impulse.clone();
+
+                    if impulse > na::zero() && !c.friction_coeff.is_zero() {
+                        let bound = c.friction_coeff * impulse;
+                        c.lobound = -bound;
+                        c.hibound = bound;
+
+                        solve_velocity_constraint(c, result);
+                    }
+                }
+            },
+            FrictionModel::Cone => {
+                let mut begin = 0;
+
+                while begin < friction.len() {
+                    let id      = friction[begin].friction_limit_id;
+                    let mut end = begin + 1;
+
+                    while end < friction.len() && friction[end].friction_limit_id == id {
+                        end = end + 1;
+                    }
+
+                    solve_friction_cone(&mut friction[begin .. end], restitution[id].impulse, result);
+                    begin = end;
+                }
+            }
+        }
+    }
+}
+
+/// Solve a set of velocity constraints using the projected Jacobi solver.
+///
+/// Each iteration solves every restitution constraint from the velocities of the previous
+/// iteration, then applies their impulses at once, and does the same for the friction
+/// constraints. The correction of each constraint is divided by the largest number of
+/// constraints acting on one of its bodies to ensure convergence. The arguments are the same as
+/// for `projected_gauss_seidel_block_solve`, without blocks.
+pub fn projected_jacobi_solve<N: Real>(restitution:    &mut [VelocityConstraint<N>],
+                                       friction:       &mut [VelocityConstraint<N>],
+                                       friction_model: FrictionModel,
+                                       result:         &mut [Velocities<N>],
+                                       num_bodies:     usize,
+                                       num_iterations: usize,
+                                       is_lambda_zero: bool) {
+    assert!(result.len() == num_bodies);
+
+    for v in result.iter_mut() {
+        v.reset();
+    }
+
+    if !is_lambda_zero {
+        for c in restitution.iter() {
+            setup_warmstart_for_constraint(c, result);
+        }
+
+        for c in friction.iter() {
+            setup_warmstart_for_constraint(c, result);
+        }
+    }
+
+    let restitution_weights = jacobi_weights(restitution, num_bodies);
+    let friction_weights    = jacobi_weights(friction, num_bodies);
+    let mut d_lambdas       = Vec::with_capacity(restitution.len().max(friction.len()));
+
+    for _ in 0 .. num_iterations {
+        jacobi_sweep(restitution, &restitution_weights[..], result, &mut d_lambdas, |_| true);
+
         for c in friction.iter_mut() {
-            let impulse = restitution[c.friction_limit_id].impulse.clone();
+            let bound = c.friction_coeff * restitution[c.friction_limit_id].impulse;
+            c.lobound = -bound;
+            c.hibound = bound;
+        }
+
+        jacobi_sweep(friction, &friction_weights[..], result, &mut d_lambdas,
+                     |c| restitution[c.friction_limit_id].impulse > na::zero() && !c.friction_coeff.is_zero());
+
+        if friction_model == FrictionModel::Cone {
+            let mut begin = 0;
+
+            while begin < friction.len() {
+                let id      = friction[begin].friction_limit_id;
+                let mut end = begin + 1;
+
+                while end < friction.len() && friction[end].friction_limit_id == id {
+                    end = end + 1;
+                }
+
+                let bound = friction[begin].friction_coeff * restitution[id].impulse;
+
+                if bound > na::zero() {
+                    project_on_friction_cone(&mut friction[begin .. end], bound, result);
+                }
+
+                begin = end;
+            }
+        }
+    }
+}
+
+/// The level of each body in the stacks formed by the constraints `cs`: one for the bodies
+/// touching a static body, i.e., with an id of -1, two for the bodies they support, etc.
+///
+/// The bodies not connected to a static body have the level `usize::MAX`.
+pub fn stack_levels<N: Real>(cs: &[VelocityConstraint<N>], num_bodies: usize) -> Vec<usize> {
+    let mut levels = vec![usize::MAX; num_bodies];
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for c in cs.iter() {
+            let level1 = if c.id1 >= 0 { levels[c.id1 as usize] } else { 0 };
+            let level2 = if c.id2 >= 0 { levels[c.id2 as usize] } else { 0 };
+
+            if c.id1 >= 0 && level2 < usize::MAX && level2 + 1 < level1 {
+                levels[c.id1 as usize] = level2 + 1;
+                changed = true;
+            }
+
+            if c.id2 >= 0 && level1 < usize::MAX && level1 + 1 < level2 {
+                levels[c.id2 as usize] = level1 + 1;
+                changed = true;
+            }
+        }
+    }
+
+    levels
+}
+
+/// Solves once the restitution constraints from the bottom to the top of the stacks, the body with
+/// the lowest of the `levels` computed by `stack_levels` being considered infinitely massive.
+///
+/// The weight of the upper bodies is then entirely transmitted to the ground instead of being
+/// distributed over the iterations, which stops the tall stacks from slowly collapsing at low
+/// iteration counts. This is meant to be performed after the normal iterations, with the same
+/// `result`. The consecutive constraints between the same two bodies are solved simultaneously,
+/// and the constraints between bodies of the same level are solved normally.
+///
+/// The friction is not solved by this pass: making each body follow the tilt of the body below
+/// it would amplify the tilt towards the top of the stacks. The accumulated impulses of the
+/// constraints are left unchanged, so that the next steps are not warm-started with the impulses
+/// of this pass.
+pub fn shock_propagation_solve<N: Real>(restitution: &[VelocityConstraint<N>],
+                                        levels:      &[usize],
+                                        result:      &mut [Velocities<N>]) {
+    let level = |c: &VelocityConstraint<N>| {
+        let level1 = if c.id1 >= 0 { levels[c.id1 as usize] } else { 0 };
+        let level2 = if c.id2 >= 0 { levels[c.id2 as usize] } else { 0 };
+
+        level1.min(level2)
+    };
 
-            if impulse > na::zero() {
-                let bound = c.friction_coeff * impulse;
-                c.lobound = -bound;
-                c.hibound = bound;
+    // The blocks of constraints between the same two bodies, from the bottom to the top.
+    let mut blocks = Vec::new();
+    let mut begin  = 0;
 
-                solve_velocity_constraint(c, result);
+    for end in 1 .. restitution.len() + 1 {
+        if end == restitution.len() || end - begin == MAX_BLOCK_SIZE ||
+           restitution[end].id1 != restitution[begin].id1 || restitution[end].id2 != restitution[begin].id2 {
+            blocks.push((begin, end));
+            begin = end;
+        }
+    }
+
+    blocks.sort_by_key(|&(begin, _)| level(&restitution[begin]));
+
+    let mut block = Vec::with_capacity(MAX_BLOCK_SIZE);
+
+    for (begin, end) in blocks.into_iter() {
+        block.clear();
+        block.extend(restitution[begin .. end].iter().map(|c| supported_by_lower_body(c, levels)));
+
+        if block.len() == 1 {
+            solve_velocity_constraint(&mut block[0], result);
+        }
+        else {
+            solve_velocity_constraint_block(&mut block[..], result);
+        }
+    }
+}
+
+/// Solves exactly the constraints of `cs` which indices are listed in `ids`, the impulses of the
+/// other constraints being those already applied on `result`.
+///
+/// The constraints are solved simultaneously by Gaussian elimination, the impulses reaching their
+/// bounds being clamped and released until a solution satisfying the bounds is found. This is
+/// costly for large systems, but the solution does not depend on the number of iterations: this
+/// is meant for the mechanisms with few bodies and many interdependent joints, e.g., robot arms,
+/// on which the iterative solvers converge slowly. The friction bounds are not updated.
+///
+/// Returns `false` and leaves the impulses unchanged if no solution was found, e.g., because
+/// some constraints are redundant.
+pub fn direct_solve<N: Real>(cs: &mut [VelocityConstraint<N>], ids: &[usize], result: &mut [Velocities<N>]) -> bool {
+    let n = ids.len();
+
+    // The error is `b - k * impulses`.
+    let mut k = vec![na::zero::<N>(); n * n];
+    let mut b = vec![na::zero::<N>(); n];
+
+    for i in 0 .. n {
+        let ci = &cs[ids[i]];
+
+        for j in 0 .. n {
+            k[i * n + j] = coupling(ci, &cs[ids[j]]);
+        }
+
+        k[i * n + i] = k[i * n + i] + ci.cfm;
+        b[i]         = velocity_error(ci, result);
+
+        for j in 0 .. n {
+            b[i] = b[i] + k[i * n + j] * cs[ids[j]].impulse;
+        }
+    }
+
+    let mut states   = vec![State::Free; n];
+    let mut impulses = vec![na::zero::<N>(); n];
+    let mut solved   = false;
+
+    for _ in 0 .. 4 * n + 1 {
+        for i in 0 .. n {
+            impulses[i] = match states[i] {
+                State::Free      => na::zero(),
+                State::AtLobound => cs[ids[i]].lobound,
+                State::AtHibound => cs[ids[i]].hibound
+            };
+        }
+
+        if !solve_free_impulses(&k, &b, &states, n, &mut impulses) {
+            return false;
+        }
+
+        // Clamp the free impulse the most out of its bounds...
+        let mut worst     = None;
+        let mut worst_err = na::zero::<N>();
+
+        for i in 0 .. n {
+            let c = &cs[ids[i]];
+
+            if states[i] == State::Free {
+                if c.lobound - impulses[i] > worst_err {
+                    worst     = Some((i, State::AtLobound));
+                    worst_err = c.lobound - impulses[i];
+                }
+                else if impulses[i] - c.hibound > worst_err {
+                    worst     = Some((i, State::AtHibound));
+                    worst_err = impulses[i] - c.hibound;
+                }
+            }
+        }
+
+        // ... or release the clamped impulse which error pushes it the most inside its bounds.
+        if worst.is_none() {
+            for i in 0 .. n {
+                if states[i] != State::Free {
+                    let mut error = b[i];
+
+                    for j in 0 .. n {
+                        error = error - k[i * n + j] * impulses[j];
+                    }
+
+                    let err = if states[i] == State::AtLobound { error } else { -error };
+
+                    if err > worst_err {
+                        worst     = Some((i, State::Free));
+                        worst_err = err;
+                    }
+                }
+            }
+        }
+
+        match worst {
+            Some((i, state)) => states[i] = state,
+            None             => {
+                solved = true;
+                break;
             }
         }
     }
+
+    if solved {
+        for (i, impulse) in ids.iter().zip(impulses.iter()) {
+            let c        = &mut cs[*i];
+            let d_lambda = *impulse - c.impulse;
+            c.impulse    = *impulse;
+
+            apply_impulse(c, d_lambda, result);
+        }
+    }
+
+    solved
+}
+
+// Whether the impulse of a constraint solved by `direct_solve` is clamped to one of its bounds.
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Free,
+    AtLobound,
+    AtHibound
+}
+
+// Sets the free impulses so that they zero the error `b - k * impulses` of their rows, the
+// clamped impulses being fixed. Returns `false` if the free rows are redundant.
+fn solve_free_impulses<N: Real>(k:        &[N],
+                                b:        &[N],
+                                states:   &[State],
+                                n:        usize,
+                                impulses: &mut [N])
+                                -> bool {
+    let ids: Vec<usize> = (0 .. n).filter(|i| states[*i] == State::Free).collect();
+    let m = ids.len();
+
+    // Gaussian elimination with partial pivoting on the free rows.
+    let mut a   = vec![na::zero::<N>(); m * m];
+    let mut x   = vec![na::zero::<N>(); m];
+    let mut eps = na::zero::<N>();
+
+    for r in 0 .. m {
+        x[r] = b[ids[r]];
+
+        for j in 0 .. n {
+            if states[j] != State::Free {
+                x[r] = x[r] - k[ids[r] * n + j] * impulses[j];
+            }
+        }
+
+        for c in 0 .. m {
+            a[r * m + c] = k[ids[r] * n + ids[c]];
+        }
+
+        eps = eps.max(a[r * m + r]);
+    }
+
+    eps = eps * na::convert(1.0e-9f64);
+
+    for col in 0 .. m {
+        let mut pivot = col;
+
+        for r in col + 1 .. m {
+            if a[r * m + col].abs() > a[pivot * m + col].abs() {
+                pivot = r;
+            }
+        }
+
+        if a[pivot * m + col].abs() <= eps {
+            return false;
+        }
+
+        for c in 0 .. m {
+            a.swap(col * m + c, pivot * m + c);
+        }
+
+        x.swap(col, pivot);
+
+        for r in col + 1 .. m {
+            let factor = a[r * m + col] / a[col * m + col];
+
+            for c in col .. m {
+                a[r * m + c] = a[r * m + c] - factor * a[col * m + c];
+            }
+
+            x[r] = x[r] - factor * x[col];
+        }
+    }
+
+    for col in (0 .. m).rev() {
+        for c in col + 1 .. m {
+            x[col] = x[col] - a[col * m + c] * x[c];
+        }
+
+        x[col] = x[col] / a[col * m + col];
+    }
+
+    for (r, i) in ids.iter().enumerate() {
+        impulses[*i] = x[r];
+    }
+
+    true
+}
+
+// A copy of `c` where the body with the lowest of the `levels` is infinitely massive: it is not
+// moved by the impulses, but its velocity is still taken into account.
+fn supported_by_lower_body<N: Real>(c: &VelocityConstraint<N>, levels: &[usize]) -> VelocityConstraint<N> {
+    let level1 = if c.id1 >= 0 { levels[c.id1 as usize] } else { 0 };
+    let level2 = if c.id2 >= 0 { levels[c.id2 as usize] } else { 0 };
+    let mut c  = c.clone();
+
+    if level1 != level2 {
+        if level1 < level2 {
+            c.weighted_normal1   = na::zero();
+            c.weighted_rot_axis1 = na::zero();
+        }
+        else {
+            c.weighted_normal2   = na::zero();
+            c.weighted_rot_axis2 = na::zero();
+        }
+
+        let inv_projected_mass = na::dot(&c.normal, &c.weighted_normal1) +
+                                 na::dot(&c.rot_axis1, &c.weighted_rot_axis1) +
+                                 na::dot(&c.normal2, &c.weighted_normal2) +
+                                 na::dot(&c.rot_axis2, &c.weighted_rot_axis2) +
+                                 c.cfm;
+
+        let _1: N = na::one();
+        c.inv_projected_mass = _1 / inv_projected_mass;
+    }
+
+    c
+}
+
+// The factor applied to the correction of each constraint by the Jacobi solver: the inverse of the
+// largest number of constraints acting on one of its bodies.
+fn jacobi_weights<N: Real>(cs: &[VelocityConstraint<N>], num_bodies: usize) -> Vec<N> {
+    let mut counts = vec![0usize; num_bodies];
+
+    for c in cs.iter() {
+        if c.id1 >= 0 {
+            counts[c.id1 as usize] = counts[c.id1 as usize] + 1;
+        }
+
+        if c.id2 >= 0 {
+            counts[c.id2 as usize] = counts[c.id2 as usize] + 1;
+        }
+    }
+
+    cs.iter().map(|c| {
+        let count1 = if c.id1 >= 0 { counts[c.id1 as usize] } else { 1 };
+        let count2 = if c.id2 >= 0 { counts[c.id2 as usize] } else { 1 };
+
+        na::one::<N>() / na::convert(count1.max(count2) as f64)
+    }).collect()
+}
+
+// Solves the constraints `cs` for which `active` is true from the same velocities, then applies
+// their impulses.
+fn jacobi_sweep<N: Real, F>(cs:        &mut [VelocityConstraint<N>],
+                            weights:   &[N],
+                            mj_lambda: &mut [Velocities<N>],
+                            d_lambdas: &mut Vec<N>,
+                            active:    F)
+    where F: Fn(&VelocityConstraint<N>) -> bool {
+    d_lambdas.clear();
+
+    for (c, weight) in cs.iter().zip(weights.iter()) {
+        if active(c) {
+            let d_lambda = velocity_error(c, mj_lambda) * c.inv_projected_mass * *weight;
+            let impulse  = *na::clamp(&(c.impulse + d_lambda), &c.lobound, &c.hibound);
+
+            d_lambdas.push(impulse - c.impulse);
+        }
+        else {
+            d_lambdas.push(na::zero());
+        }
+    }
+
+    for (c, d_lambda) in cs.iter_mut().zip(d_lambdas.iter()) {
+        c.impulse = c.impulse + *d_lambda;
+        apply_impulse(c, *d_lambda, mj_lambda);
+    }
+}
+
+// Solves the friction constraints `cs` of a contact with the normal impulse `impulse`, and
+// projects their impulses on the Coulomb cone.
+fn solve_friction_cone<N: Real>(cs: &mut [VelocityConstraint<N>], impulse: N, mj_lambda: &mut [Velocities<N>]) {
+    let friction_coeff = cs[0].friction_coeff;
+
+    if impulse <= na::zero() || friction_coeff.is_zero() {
+        return;
+    }
+
+    let bound = friction_coeff * impulse;
+
+    for c in cs.iter_mut() {
+        let d_lambda = velocity_error(c, mj_lambda) * c.inv_projected_mass;
+        c.impulse    = c.impulse + d_lambda;
+        c.lobound    = -bound;
+        c.hibound    = bound;
+
+        apply_impulse(c, d_lambda, mj_lambda);
+    }
+
+    project_on_friction_cone(cs, bound, mj_lambda)
+}
+
+// Scales the impulses of the friction constraints `cs` of a contact so that their norm does not
+// exceed `bound`.
+fn project_on_friction_cone<N: Real>(cs: &mut [VelocityConstraint<N>], bound: N, mj_lambda: &mut [Velocities<N>]) {
+    let mut sq_norm: N = na::zero();
+
+    for c in cs.iter() {
+        sq_norm = sq_norm + c.impulse * c.impulse;
+    }
+
+    if sq_norm > bound * bound {
+        let scale = bound / sq_norm.sqrt();
+
+        for c in cs.iter_mut() {
+            let impulse  = c.impulse * scale;
+            let d_lambda = impulse - c.impulse;
+            c.impulse    = impulse;
+
+            apply_impulse(c, d_lambda, mj_lambda);
+        }
+    }
 }
 
 #[inline(always)]
 fn setup_warmstart_for_constraint<N: Real>(c: &VelocityConstraint<N>, mj_lambda: &mut [Velocities<N>]) {
+    apply_impulse(c, c.impulse, mj_lambda)
+}
+
+#[inline(always)]
+fn apply_impulse<N: Real>(c: &VelocityConstraint<N>, impulse: N, mj_lambda: &mut [Velocities<N>]) {
     let id1 = c.id1;
     let id2 = c.id2;
 
     if id1 >= 0 {
-        mj_lambda[id1 as usize].lv = mj_lambda[id1 as usize].lv - c.weighted_normal1 * c.impulse;
-        mj_lambda[id1 as usize].av = mj_lambda[id1 as usize].av + c.weighted_rot_axis1 * c.impulse;
+        mj_lambda[id1 as usize].lv = mj_lambda[id1 as usize].lv - c.weighted_normal1 * impulse;
+        mj_lambda[id1 as usize].av = mj_lambda[id1 as usize].av + c.weighted_rot_axis1 * impulse;
     }
 
     if id2 >= 0 {
-        mj_lambda[id2 as usize].lv = mj_lambda[id2 as usize].lv + c.weighted_normal2 * c.impulse;
-        mj_lambda[id2 as usize].av = mj_lambda[id2 as usize].av + c.weighted_rot_axis2 * c.impulse;
+        mj_lambda[id2 as usize].lv = mj_lambda[id2 as usize].lv + c.weighted_normal2 * impulse;
+        mj_lambda[id2 as usize].av = mj_lambda[id2 as usize].av + c.weighted_rot_axis2 * impulse;
     }
 }
 
+/// The velocity error on `c` left by the solution `mj_lambda`: positive if its impulse must
+/// increase.
 #[inline(always)]
-fn solve_velocity_constraint<N: Real>(c: &mut VelocityConstraint<N>, mj_lambda: &mut [Velocities<N>]) {
+pub fn velocity_error<N: Real>(c: &VelocityConstraint<N>, mj_lambda: &[Velocities<N>]) -> N {
     let id1 = c.id1;
     let id2 = c.id2;
 
-    let mut d_lambda_i = c.objective.clone();
+    let mut error = c.objective - c.cfm * c.impulse;
 
     if id1 >= 0 {
-        d_lambda_i = d_lambda_i + na::dot(&c.normal, &mj_lambda[id1 as usize].lv)
-                                - na::dot(&c.rot_axis1, &mj_lambda[id1 as usize].av);
+        error = error + na::dot(&c.normal, &mj_lambda[id1 as usize].lv)
+                      - na::dot(&c.rot_axis1, &mj_lambda[id1 as usize].av);
     }
 
     if id2 >= 0 {
-        d_lambda_i = d_lambda_i - na::dot(&c.normal, &mj_lambda[id2 as usize].lv)
-                                - na::dot(&c.rot_axis2, &mj_lambda[id2 as usize].av);
+        error = error - na::dot(&c.normal2, &mj_lambda[id2 as usize].lv)
+                      - na::dot(&c.rot_axis2, &mj_lambda[id2 as usize].av);
     }
 
-    d_lambda_i = d_lambda_i * c.inv_projected_mass;
+    error
+}
+
+// The decrease of the velocity error on `ci` due to a unit impulse applied by `cj`.
+#[inline(always)]
+fn coupling<N: Real>(ci: &VelocityConstraint<N>, cj: &VelocityConstraint<N>) -> N {
+    let mut coupling = na::zero();
+
+    if ci.id1 >= 0 && ci.id1 == cj.id1 {
+        coupling = coupling + na::dot(&ci.normal, &cj.weighted_normal1)
+                            + na::dot(&ci.rot_axis1, &cj.weighted_rot_axis1);
+    }
+
+    if ci.id2 >= 0 && ci.id2 == cj.id2 {
+        coupling = coupling + na::dot(&ci.normal2, &cj.weighted_normal2)
+                            + na::dot(&ci.rot_axis2, &cj.weighted_rot_axis2);
+    }
+
+    // The body may also be the first of one constraint and the second of the other, e.g., along
+    // a chain of joints.
+    if ci.id1 >= 0 && ci.id1 == cj.id2 {
+        coupling = coupling - na::dot(&ci.normal, &cj.weighted_normal2)
+                            + na::dot(&ci.rot_axis1, &cj.weighted_rot_axis2);
+    }
+
+    if ci.id2 >= 0 && ci.id2 == cj.id1 {
+        coupling = coupling - na::dot(&ci.normal2, &cj.weighted_normal1)
+                            + na::dot(&ci.rot_axis2, &cj.weighted_rot_axis1);
+    }
+
+    coupling
+}
+
+#[inline(always)]
+fn solve_velocity_constraint<N: Real>(c: &mut VelocityConstraint<N>, mj_lambda: &mut [Velocities<N>]) {
+    let mut d_lambda_i = velocity_error(c, mj_lambda) * c.inv_projected_mass;
 
     // clamp the value such that: lambda- <= lambda <= lambda+
     // (this is the ``projected'' flavour of Gauss-Seidel
@@ -130,14 +737,163 @@ fn solve_velocity_constraint<N: Real>(c: &mut VelocityConstraint<N>, mj_lambda:
 
     d_lambda_i = c.impulse - lambda_i_0;
 
+    apply_impulse(c, d_lambda_i, mj_lambda)
+}
+
+// Solves exactly the linear complementarity problem of a block of unilateral constraints, i.e.,
+// with impulses bounded by zero and infinity, by enumerating which constraints are active.
+//
+// The block is solved one constraint after the other if it is not unilateral, or if there is no
+// solution because its constraints are redundant, e.g., two contacts at the same point.
+fn solve_velocity_constraint_block<N: Real>(cs: &mut [VelocityConstraint<N>], mj_lambda: &mut [Velocities<N>]) {
+    let n = cs.len();
+    assert!(n <= MAX_BLOCK_SIZE, "A block cannot have more than {} constraints.", MAX_BLOCK_SIZE);
 
-    if id1 >= 0 {
-        mj_lambda[id1 as usize].lv = mj_lambda[id1 as usize].lv - c.weighted_normal1 * d_lambda_i;
-        mj_lambda[id1 as usize].av = mj_lambda[id1 as usize].av + c.weighted_rot_axis1 * d_lambda_i;
+    let unilateral = cs.iter().all(|c| c.lobound.is_zero() && c.hibound == Bounded::max_value());
+
+    if unilateral {
+        // The error is `b - k * impulses`.
+        let mut k = [[na::zero::<N>(); MAX_BLOCK_SIZE]; MAX_BLOCK_SIZE];
+        let mut b = [na::zero::<N>(); MAX_BLOCK_SIZE];
+
+        for i in 0 .. n {
+            for j in 0 .. n {
+                k[i][j] = coupling(&cs[i], &cs[j]);
+            }
+
+            k[i][i] = k[i][i] + cs[i].cfm;
+            b[i]    = velocity_error(&cs[i], mj_lambda);
+
+            for j in 0 .. n {
+                b[i] = b[i] + k[i][j] * cs[j].impulse;
+            }
+        }
+
+        // Try the configurations with the most active constraints first.
+        for active in (0usize .. 1 << n).rev() {
+            if let Some(impulses) = solve_active_set(&k, &b, n, active) {
+                for (c, impulse) in cs.iter_mut().zip(impulses.iter()) {
+                    let d_lambda = *impulse - c.impulse;
+                    c.impulse    = *impulse;
+                    apply_impulse(c, d_lambda, mj_lambda);
+                }
+
+                return;
+            }
+        }
     }
 
-    if id2 >= 0 {
-        mj_lambda[id2 as usize].lv = mj_lambda[id2 as usize].lv + c.weighted_normal2 * d_lambda_i;
-        mj_lambda[id2 as usize].av = mj_lambda[id2 as usize].av + c.weighted_rot_axis2 * d_lambda_i;
+    for c in cs.iter_mut() {
+        solve_velocity_constraint(c, mj_lambda);
+    }
+}
+
+// The impulses zeroing the error `b - k * impulses` on the constraints of the bitmask `active`,
+// with zero impulses on the other constraints. `None` if an impulse is negative, if the error
+// on an inactive constraint is positive, or if the active constraints are redundant.
+fn solve_active_set<N: Real>(k:      &[[N; MAX_BLOCK_SIZE]; MAX_BLOCK_SIZE],
+                             b:      &[N; MAX_BLOCK_SIZE],
+                             n:      usize,
+                             active: usize)
+                             -> Option<[N; MAX_BLOCK_SIZE]> {
+    let mut ids  = [0usize; MAX_BLOCK_SIZE];
+    let mut nact = 0;
+
+    for i in 0 .. n {
+        if active & (1 << i) != 0 {
+            ids[nact] = i;
+            nact      = nact + 1;
+        }
+    }
+
+    // Gaussian elimination with partial pivoting on the active rows.
+    let mut a   = [[na::zero::<N>(); MAX_BLOCK_SIZE]; MAX_BLOCK_SIZE];
+    let mut x   = [na::zero::<N>(); MAX_BLOCK_SIZE];
+    let mut eps = na::zero::<N>();
+
+    for r in 0 .. nact {
+        for c in 0 .. nact {
+            a[r][c] = k[ids[r]][ids[c]];
+        }
+
+        x[r] = b[ids[r]];
+        eps  = eps.max(a[r][r]);
+    }
+
+    eps = eps * na::convert(1.0e-6f64);
+
+    for col in 0 .. nact {
+        let mut pivot = col;
+
+        for r in col + 1 .. nact {
+            if a[r][col].abs() > a[pivot][col].abs() {
+                pivot = r;
+            }
+        }
+
+        if a[pivot][col].abs() <= eps {
+            return None;
+        }
+
+        a.swap(col, pivot);
+        x.swap(col, pivot);
+
+        for r in col + 1 .. nact {
+            let factor = a[r][col] / a[col][col];
+
+            for c in col .. nact {
+                a[r][c] = a[r][c] - factor * a[col][c];
+            }
+
+            x[r] = x[r] - factor * x[col];
+        }
+    }
+
+    for col in (0 .. nact).rev() {
+        for c in col + 1 .. nact {
+            x[col] = x[col] - a[col][c] * x[c];
+        }
+
+        x[col] = x[col] / a[col][col];
+
+        if x[col] < na::zero() {
+            return None;
+        }
+    }
+
+    let mut impulses = [na::zero::<N>(); MAX_BLOCK_SIZE];
+
+    for r in 0 .. nact {
+        impulses[ids[r]] = x[r];
+    }
+
+    for i in 0 .. n {
+        if active & (1 << i) == 0 {
+            let mut error = b[i];
+
+            for j in 0 .. n {
+                error = error - k[i][j] * impulses[j];
+            }
+
+            if error > na::zero() {
+                return None;
+            }
+        }
+    }
+
+    Some(impulses)
+}
+
+/// The velocity error left on the constraint `c` by the solution `mj_lambda`.
+///
+/// The error is zero if the impulse of `c` reached the bound preventing it from being corrected.
+pub fn velocity_residual<N: Real>(c: &VelocityConstraint<N>, mj_lambda: &[Velocities<N>]) -> N {
+    let error = velocity_error(c, mj_lambda);
+
+    if (error < na::zero() && c.impulse <= c.lobound) || (error > na::zero() && c.impulse >= c.hibound) {
+        na::zero()
+    }
+    else {
+        error.abs()
     }
 }
@@ -0,0 +1,82 @@
+use num::Bounded;
+use alga::general::Real;
+use na;
+use utils::GeneralizedCross;
+use detection::joint::{Pulley, Joint};
+use resolution::constraint::ball_in_socket_equation;
+use resolution::constraint::prismatic_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::contact_equation;
+use object::RigidBody;
+use math::{Vector, Orientation};
+
+/// The number of equations of `joint`.
+pub fn num_equations<N: Real>(_: &Pulley<N>) -> usize {
+    1
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &Pulley<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           correction:  &CorrectionParameters<N>) {
+    let global1 = joint.anchor1_pos();
+    let global2 = joint.anchor2_pos();
+    let (length1, length2) = joint.lengths();
+    let constraint = &mut constraints[0];
+
+    // The equation is inactive while an anchor is on its pulley since the rope has no direction.
+    if length1 == na::zero() || length2 == na::zero() {
+        let mut axis: Vector<N> = na::zero();
+        axis[0] = na::one();
+
+        return prismatic_equation::fill_linear_equation(dt, &global1, &global2, &axis, na::zero(), na::zero(),
+                                                        na::zero(), joint.anchor1(), joint.anchor2(), constraint);
+    }
+
+    let ratio = joint.ratio();
+    let dir1  = (*joint.pulley1() - global1) / length1;
+    let dir2  = (*joint.pulley2() - global2) / length2;
+
+    // The relative velocity is the one at which the rope lengthens: a positive impulse pushes the
+    // anchors away from their pulleys.
+    let normal1   = dir1;
+    let normal2   = -dir2 * ratio;
+    let rot_axis1 = -(global1 - joint.anchor1().center_of_mass()).gcross(&dir1);
+    let rot_axis2 = -(global2 - joint.anchor2().center_of_mass()).gcross(&dir2) * ratio;
+
+    let opt_rb1 = ball_in_socket_equation::write_anchor_id(joint.anchor1(), &mut constraint.id1);
+    let opt_rb2 = ball_in_socket_equation::write_anchor_id(joint.anchor2(), &mut constraint.id2);
+
+    let dvel = velocity(&opt_rb1.as_ref().map(|r| &**r), &-normal1, &rot_axis1, dt) +
+               velocity(&opt_rb2.as_ref().map(|r| &**r), &normal2, &rot_axis2, dt);
+
+    contact_equation::fill_coupled_constraint_geometry(
+        normal1,
+        normal2,
+        rot_axis1,
+        rot_axis2,
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        constraint
+    );
+
+    // A slack rope lets the anchors move away from their pulleys until it is taut.
+    let error  = length1 + length2 * ratio - joint.length();
+    let target = if error < na::zero() { -error / dt } else { -error * correction.joint_corr / dt };
+    let _max: N = Bounded::max_value();
+
+    constraint.lobound   = -_max;
+    constraint.hibound   = na::zero();
+    constraint.objective = target - dvel;
+    constraint.impulse   = na::zero(); // Warm-started by the solver once filled.
+}
+
+// The velocity of `rb` along `normal` and `rot_axis`, including the external forces.
+fn velocity<N: Real>(rb: &Option<&RigidBody<N>>, normal: &Vector<N>, rot_axis: &Orientation<N>, dt: N) -> N {
+    match *rb {
+        Some(ref rb) => na::dot(&(rb.lin_vel() + rb.lin_acc() * dt), normal) +
+                        na::dot(&(rb.ang_vel() + rb.ang_acc() * dt), rot_axis),
+        None         => na::zero()
+    }
+}
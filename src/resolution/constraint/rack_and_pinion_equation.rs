@@ -0,0 +1,82 @@
+use num::Bounded;
+use alga::general::Real;
+use na;
+use detection::joint::{RackAndPinion, Anchor, Joint};
+use resolution::constraint::ball_in_socket_equation;
+use resolution::constraint::gear_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::contact_equation;
+use math::{Vector, Orientation, Isometry};
+
+/// The number of equations of `joint`.
+pub fn num_equations<N: Real>(_: &RackAndPinion<N>) -> usize {
+    1
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &RackAndPinion<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           correction:  &CorrectionParameters<N>) {
+    let hinge     = joint.hinge().borrow();
+    let prismatic = joint.prismatic().borrow();
+    let ang_axis  = gear_equation::world_axis(&*hinge);
+    let lin_axis  = prismatic.anchor1_pos().rotation * *prismatic.axis();
+    let ratio     = joint.ratio();
+    let _max: N   = Bounded::max_value();
+
+    let constraint = &mut constraints[0];
+    let opt_rb1    = ball_in_socket_equation::write_anchor_id(joint.anchor1(), &mut constraint.id1);
+    let opt_rb2    = ball_in_socket_equation::write_anchor_id(joint.anchor2(), &mut constraint.id2);
+
+    // The impulse increases the translation speed of the rack relative to the rotation speed of
+    // the pinion times the ratio.
+    contact_equation::fill_coupled_constraint_geometry(
+        na::zero(),
+        lin_axis,
+        -ang_axis * ratio,
+        na::zero(),
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        constraint
+    );
+
+    let dvel = relative_lin_vel(prismatic.anchor1(), prismatic.anchor2(), &lin_axis, dt) -
+               relative_ang_vel(hinge.anchor1(), hinge.anchor2(), &ang_axis, dt) * ratio;
+    let error = -joint.error() * correction.joint_corr / dt;
+
+    constraint.lobound   = -_max;
+    constraint.hibound   = _max;
+    constraint.objective = error - dvel;
+    constraint.impulse   = na::zero(); // Warm-started by the solver once filled.
+}
+
+// The relative linear velocity of the bodies of `anchor1` and `anchor2` along `axis`, including the
+// external forces.
+fn relative_lin_vel<N: Real>(anchor1: &Anchor<N, Isometry<N>>,
+                             anchor2: &Anchor<N, Isometry<N>>,
+                             axis:    &Vector<N>,
+                             dt:      N)
+                             -> N {
+    let vel = |anchor: &Anchor<N, Isometry<N>>| match anchor.body {
+        Some(ref rb) => { let rb = rb.borrow(); rb.lin_vel() + rb.lin_acc() * dt },
+        None         => na::zero()
+    };
+
+    na::dot(&(vel(anchor2) - vel(anchor1)), axis)
+}
+
+// The relative angular velocity of the bodies of `anchor1` and `anchor2` along `axis`, including
+// the external forces.
+fn relative_ang_vel<N: Real>(anchor1: &Anchor<N, Isometry<N>>,
+                             anchor2: &Anchor<N, Isometry<N>>,
+                             axis:    &Orientation<N>,
+                             dt:      N)
+                             -> N {
+    let vel = |anchor: &Anchor<N, Isometry<N>>| match anchor.body {
+        Some(ref rb) => { let rb = rb.borrow(); rb.ang_vel() + rb.ang_acc() * dt },
+        None         => na::zero()
+    };
+
+    na::dot(&(vel(anchor2) - vel(anchor1)), axis)
+}
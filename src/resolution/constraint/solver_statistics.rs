@@ -0,0 +1,37 @@
+use alga::general::Real;
+use na;
+
+/// Metrics about the convergence of the constraint solver during its last resolution.
+///
+/// Those are meant to help tuning the number of iterations: a large residual or a large
+/// remaining depth means the solver needs more iterations, or a smaller time step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolverStatistics<N: Real> {
+    /// The largest velocity error left on a contact or a joint after the last iteration.
+    pub max_residual:    N,
+    /// The number of iterations performed, including those of the position correction.
+    pub iterations:      usize,
+    /// The number of contacts solved.
+    pub contacts:        usize,
+    /// The number of contacts which applied an impulse, i.e., that were not separating.
+    pub active_contacts: usize,
+    /// The number of joint equations solved.
+    pub joint_equations: usize,
+    /// The largest penetration depth left after the position correction, including the margins
+    /// of the bodies.
+    pub max_depth:       N
+}
+
+impl<N: Real> SolverStatistics<N> {
+    /// Creates statistics with all metrics set to zero.
+    pub fn new() -> SolverStatistics<N> {
+        SolverStatistics {
+            max_residual:    na::zero(),
+            iterations:      0,
+            contacts:        0,
+            active_contacts: 0,
+            joint_equations: 0,
+            max_depth:       na::zero()
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use num::Bounded;
+use alga::general::Real;
+use na;
+use detection::joint::{Spring, Joint};
+use resolution::constraint::prismatic_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use math::Vector;
+
+/// The number of equations of `joint`.
+pub fn num_equations<N: Real>(_: &Spring<N>) -> usize {
+    1
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &Spring<N>,
+                                           constraints: &mut [VelocityConstraint<N>]) {
+    let global1 = joint.anchor1_pos();
+    let global2 = joint.anchor2_pos();
+    let length  = na::distance(&global1, &global2);
+    let _max: N = Bounded::max_value();
+
+    // The spring has no direction while both anchors coincide.
+    if length == na::zero() {
+        let mut axis: Vector<N> = na::zero();
+        axis[0] = na::one();
+
+        prismatic_equation::fill_linear_equation(dt, &global1, &global2, &axis, na::zero(), na::zero(), na::zero(),
+                                                 joint.anchor1(), joint.anchor2(), &mut constraints[0]);
+        return;
+    }
+
+    let axis    = (global2 - global1) / length;
+    let hibound = if joint.pulls_only() { na::zero() } else { _max };
+
+    // Implicit spring: the elongation velocity at the end of the step is the one the spring and
+    // damping forces, evaluated at the end of the step, lead to.
+    let gamma = na::one::<N>() / (dt * (joint.damping() + dt * joint.stiffness()));
+    let bias  = (length - joint.rest_length()) * dt * joint.stiffness() * gamma;
+
+    let constraint = &mut constraints[0];
+
+    prismatic_equation::fill_linear_equation(dt, &global1, &global2, &axis, -bias, -_max, hibound,
+                                             joint.anchor1(), joint.anchor2(), constraint);
+
+    constraint.cfm                = gamma;
+    constraint.inv_projected_mass = na::one::<N>() / (na::one::<N>() / constraint.inv_projected_mass + gamma);
+}
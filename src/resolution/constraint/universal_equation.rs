@@ -0,0 +1,53 @@
+use num::Bounded;
+use alga::general::Real;
+use na;
+use detection::joint::{Universal, Joint};
+use resolution::constraint::ball_in_socket_equation;
+use resolution::constraint::hinge_equation;
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use math::{Vector, Point};
+
+/// The number of equations of `joint`: those of its anchors, followed by the one locking the
+/// rotation orthogonal to its axes.
+pub fn num_equations<N: Real>(_: &Universal<N>) -> usize {
+    na::dimension::<Vector<N>>() + 1
+}
+
+pub fn fill_second_order_equation<N: Real>(dt:          N,
+                                           joint:       &Universal<N>,
+                                           constraints: &mut [VelocityConstraint<N>],
+                                           correction:  &CorrectionParameters<N>) {
+    let ref1 = joint.anchor1_pos();
+    let ref2 = joint.anchor2_pos();
+
+    ball_in_socket_equation::cancel_relative_linear_motion(
+        dt,
+        &Point::from_coordinates(ref1.translation.vector),
+        &Point::from_coordinates(ref2.translation.vector),
+        joint.anchor1(),
+        joint.anchor2(),
+        constraints,
+        correction);
+
+    // Cancel the relative rotation around the axis orthogonal to both joint axes. The time
+    // derivative of `dot(axis1, axis2)` is the opposite of the relative angular velocity along
+    // this axis, so the error is corrected by a relative rotation along it. The equation is
+    // inactive if the axes became parallel.
+    let (axis1, axis2) = joint.global_axes();
+    let locked         = axis1.cross(&axis2);
+    let norm           = na::norm(&locked);
+    let constraint     = &mut constraints[na::dimension::<Vector<N>>()];
+    let _max: N        = Bounded::max_value();
+
+    if norm > N::default_epsilon() {
+        let error = na::dot(&axis1, &axis2) * correction.joint_corr / dt;
+
+        hinge_equation::fill_angular_equation(&(locked / norm), error, -_max, _max,
+                                              joint.anchor1(), joint.anchor2(), constraint)
+    }
+    else {
+        hinge_equation::fill_angular_equation(&axis1, na::zero(), na::zero(), na::zero(),
+                                              joint.anchor1(), joint.anchor2(), constraint)
+    }
+}
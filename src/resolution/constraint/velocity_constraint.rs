@@ -7,6 +7,9 @@ use math::{Vector, Orientation};
 pub struct VelocityConstraint<N: Real> {
     /// The contact normal.
     pub normal:             Vector<N>,
+    /// The normal along which the second body is constrained. Equal to `normal` except for
+    /// constraints coupling motions along different directions, e.g., pulleys.
+    pub normal2:            Vector<N>,
 
     /// The contact normal multiplied by the first body's inverse mass.
     pub weighted_normal1:   Vector<N>,
@@ -34,6 +37,9 @@ pub struct VelocityConstraint<N: Real> {
     pub hibound:            N,
     /// The target delta velocity.
     pub objective:          N,
+    /// The softness of this constraint: the target delta velocity decreases by `cfm` times the
+    /// impulse applied. Zero for rigid constraints.
+    pub cfm:                N,
     /// The id of the first body.
     pub id1:                isize,
     /// The id of the second body.
@@ -49,6 +55,7 @@ impl<N: Real> VelocityConstraint<N> {
     pub fn new() -> VelocityConstraint<N> {
         VelocityConstraint {
             normal:             na::zero(),
+            normal2:            na::zero(),
 
             weighted_normal1:   na::zero(),
             weighted_normal2:   na::zero(),
@@ -65,6 +72,7 @@ impl<N: Real> VelocityConstraint<N> {
             hibound:            na::zero(),
             lobound:            na::zero(),
             objective:          na::zero(),
+            cfm:                na::zero(),
             id1:                -1,
             id2:                -1,
             friction_limit_id:  0,
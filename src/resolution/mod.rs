@@ -3,9 +3,14 @@
 pub use resolution::solver::Solver;
 pub use resolution::constraint::accumulated_impulse_solver::AccumulatedImpulseSolver;
 pub use resolution::constraint::contact_equation::CorrectionMode;
-pub use resolution::constraint::projected_gauss_seidel_solver::{Velocities, projected_gauss_seidel_solve};
-pub use resolution::constraint::impulse_cache::{ImpulseCache, ContactIdentifier};
+pub use resolution::constraint::projected_gauss_seidel_solver::{Velocities, FrictionModel, IterationScheme,
+                                                               MAX_BLOCK_SIZE, direct_solve, projected_gauss_seidel_solve,
+                                                               projected_gauss_seidel_block_solve,
+                                                               projected_jacobi_solve, shock_propagation_solve,
+                                                               stack_levels, velocity_residual};
+pub use resolution::constraint::impulse_cache::{ImpulseCache, JointImpulseCache, ContactIdentifier};
 pub use resolution::constraint::velocity_constraint::VelocityConstraint;
+pub use resolution::constraint::solver_statistics::SolverStatistics;
 
 
 // XXX: `pub` due to rust#18241
@@ -15,9 +20,22 @@ pub mod solver;
 mod constraint {
     pub mod impulse_cache;
     pub mod accumulated_impulse_solver;
+    pub mod solver_statistics;
     pub mod projected_gauss_seidel_solver;
+    #[cfg(any(feature = "parallel", feature = "tracing"))]
+    pub mod parallel_solver;
     pub mod velocity_constraint;
     pub mod contact_equation;
     pub mod ball_in_socket_equation;
     pub mod fixed_equation;
+    pub mod hinge_equation;
+    pub mod prismatic_equation;
+    pub mod spring_equation;
+    pub mod distance_equation;
+    pub mod angular_motor_equation;
+    pub mod pulley_equation;
+    pub mod gear_equation;
+    pub mod rack_and_pinion_equation;
+    #[cfg(feature = "dim3")]
+    pub mod universal_equation;
 }
@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+use std::fmt::Write;
+use trace::{Span, TraceSink};
+
+/// A trace sink accumulating spans in the Chrome trace event format.
+///
+/// The result can be loaded into `chrome://tracing`, Perfetto, Speedscope, or other flame-graph
+/// tools.
+pub struct ChromeTrace {
+    origin: Instant,
+    pid:    usize,
+    tid:    usize,
+    events: String
+}
+
+impl ChromeTrace {
+    /// Creates a trace which timestamps are relative to now.
+    ///
+    /// The events are attributed to the process `pid` and thread `tid` so that they may be
+    /// merged with the traces of other parts of the application.
+    pub fn new(pid: usize, tid: usize) -> ChromeTrace {
+        ChromeTrace {
+            origin: Instant::now(),
+            pid:    pid,
+            tid:    tid,
+            events: String::new()
+        }
+    }
+
+    /// Removes all the events recorded so far.
+    pub fn clear(&mut self) {
+        self.events.clear()
+    }
+
+    /// The recorded events, as a JSON document.
+    pub fn to_json(&self) -> String {
+        format!("{{\"traceEvents\":[{}]}}", self.events)
+    }
+
+    fn micros(&self, t: Instant) -> f64 {
+        let d = if t > self.origin { t - self.origin } else { Duration::new(0, 0) };
+
+        d.as_secs() as f64 * 1.0e6 + d.subsec_nanos() as f64 * 1.0e-3
+    }
+}
+
+impl TraceSink for ChromeTrace {
+    fn record(&mut self, span: &Span) {
+        let begin    = self.micros(span.begin);
+        let duration = self.micros(span.end) - begin;
+
+        if !self.events.is_empty() {
+            self.events.push(',');
+        }
+
+        let _ = write!(self.events,
+                       "{{\"name\":\"{}\",\"cat\":\"nphysics\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":{},\"tid\":{},\"args\":{{\"step\":{}",
+                       span.stage.name(), begin, duration, self.pid, self.tid, span.step);
+
+        if let Some(count) = span.count {
+            let _ = write!(self.events, ",\"count\":{}", count);
+        }
+
+        self.events.push_str("}}");
+    }
+}
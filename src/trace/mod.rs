@@ -0,0 +1,9 @@
+//! Structured tracing of the simulation steps.
+//!
+//! This is only available with the `tracing` feature.
+
+pub use self::trace_sink::{Stage, Span, TraceSink};
+pub use self::chrome_trace::ChromeTrace;
+
+mod trace_sink;
+mod chrome_trace;
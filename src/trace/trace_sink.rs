@@ -0,0 +1,69 @@
+use std::time::Instant;
+
+/// A stage of the physics pipeline.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Stage {
+    /// A whole call to `World::step`.
+    Step,
+    /// The application of forces and the integration of the active rigid bodies.
+    Integration,
+    /// The update of the sensors and collision objects positions.
+    PositionUpdate,
+    /// The broad phase.
+    BroadPhase,
+    /// The continuous collision detection and the narrow phase.
+    NarrowPhase,
+    /// The update of the joints and of the activation states.
+    Activation,
+    /// The collection of the contact and joint constraints.
+    ConstraintCollection,
+    /// The resolution of every constraint, including the `Island` stages.
+    Solver,
+    /// The resolution of the velocity constraints of one island.
+    Island,
+    /// The generation of the debug frame.
+    Debug
+}
+
+impl Stage {
+    /// A human-readable name for this stage.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Stage::Step                 => "step",
+            Stage::Integration          => "integration",
+            Stage::PositionUpdate       => "position update",
+            Stage::BroadPhase           => "broad phase",
+            Stage::NarrowPhase          => "narrow phase",
+            Stage::Activation           => "activation",
+            Stage::ConstraintCollection => "constraint collection",
+            Stage::Solver               => "solver",
+            Stage::Island               => "island",
+            Stage::Debug                => "debug"
+        }
+    }
+}
+
+/// A time interval spent on one stage of the physics pipeline.
+#[derive(Clone, Debug)]
+pub struct Span {
+    /// The stage executed.
+    pub stage: Stage,
+    /// The number of steps performed before this one.
+    pub step:  usize,
+    /// The time at which the stage started.
+    pub begin: Instant,
+    /// The time at which the stage ended.
+    pub end:   Instant,
+    /// The number of elements processed by this stage, if relevant, e.g., the number of
+    /// constraints solved.
+    pub count: Option<usize>
+}
+
+/// The destination of the spans measured by the physics world.
+pub trait TraceSink {
+    /// Called once a stage completes.
+    ///
+    /// Spans are emitted in the order they end, i.e., the `Stage::Step` span of each step is
+    /// emitted after the other stages it contains.
+    fn record(&mut self, span: &Span);
+}
@@ -14,8 +14,17 @@ use ncollide::math::Point;
 pub fn ball_volume<N: Real>(dimension: usize, radius: N) -> N {
     assert!(dimension == 2 || dimension == 3);
 
-    let _pi = N::pi();
-    _pi * radius.powi(dimension as i32)
+    match dimension {
+        2 => {
+            let _pi = N::pi();
+            _pi * radius * radius
+        }
+        3 => {
+            let _pi = N::pi();
+            _pi * radius * radius * radius * na::convert(4.0f64 / 3.0)
+        }
+        _ => unreachable!()
+    }
 }
 
 /// The area of a ball.
@@ -1,5 +1,6 @@
 use alga::general::Real;
 use ncollide::shape::{Shape, Ball, Cone, Cylinder, ConvexHull, Compound, Cuboid};
+use object::SupportFunctionShape;
 use volumetric::Volumetric;
 use math::{Point, Vector, Isometry, AngularInertia};
 
@@ -24,6 +25,9 @@ macro_rules! dispatch(
             else if let Some(c) = $sself.as_shape::<Cylinder<N>>() {
                 (c as &Volumetric<N, $p, $i>).$name($($argN,)*)
             }
+            else if let Some(c) = $sself.as_shape::<SupportFunctionShape<N>>() {
+                c.$name($($argN,)*)
+            }
             else {
                 /*
                  * XXX: dispatch by custom type.
@@ -0,0 +1,98 @@
+use std::mem;
+use std::collections::HashMap;
+
+use alga::general::Real;
+use na;
+use ncollide::utils::data::hash_map::HashMap as UidMap;
+use ncollide::utils::data::hash::UintTWHash;
+use detection::constraint::Constraint;
+use object::{RigidBodyHandle, WorldObject};
+use math::Vector;
+use utils::DeterministicState;
+
+/// The normal forces applied by the contacts during the last step, grouped by pair of bodies.
+///
+/// The forces between two bodies which are both asleep, or static, are not solved any more: the
+/// forces they applied last are kept until one of them wakes up.
+pub struct ContactForces<N: Real> {
+    // The force applied on the second body of each pair.
+    prev: HashMap<(usize, usize), (RigidBodyHandle<N>, RigidBodyHandle<N>, Vector<N>), DeterministicState>,
+    // The impulses accumulated during the current step.
+    next: HashMap<(usize, usize), (RigidBodyHandle<N>, RigidBodyHandle<N>, Vector<N>), DeterministicState>
+}
+
+impl<N: Real> ContactForces<N> {
+    /// Creates an empty set of contact forces.
+    pub fn new() -> ContactForces<N> {
+        ContactForces {
+            prev: HashMap::with_hasher(DeterministicState::new()),
+            next: HashMap::with_hasher(DeterministicState::new())
+        }
+    }
+
+    /// Accumulates the normal impulses applied by the contacts of `constraints` during one
+    /// resolution, as given by `AccumulatedImpulseSolver::contact_impulses`.
+    pub fn record(&mut self, constraints: &[Constraint<N>], impulses: &[(usize, Vector<N>)]) {
+        for &(i, impulse) in impulses.iter() {
+            if let Constraint::RBRB(ref rb1, ref rb2, _) = constraints[i] {
+                let key   = (WorldObject::rigid_body_uid(rb1), WorldObject::rigid_body_uid(rb2));
+                let entry = self.next.entry(key).or_insert_with(|| (rb1.clone(), rb2.clone(), na::zero()));
+
+                entry.2 = entry.2 + impulse;
+            }
+        }
+    }
+
+    /// Converts the impulses accumulated during a step of length `dt` to forces.
+    pub fn end_step(&mut self, dt: N) {
+        for (_, &mut (_, _, ref mut force)) in self.next.iter_mut() {
+            *force = *force / dt;
+        }
+
+        for (key, value) in self.prev.drain() {
+            if !self.next.contains_key(&key) && !value.0.borrow().is_active() && !value.1.borrow().is_active() {
+                let _ = self.next.insert(key, value);
+            }
+        }
+
+        mem::swap(&mut self.prev, &mut self.next);
+    }
+
+    /// The total normal force applied by the contacts on the body identified by `uid` during the
+    /// last step.
+    pub fn force(&self, uid: usize) -> Vector<N> {
+        let mut res = na::zero();
+
+        for (&(uid1, uid2), &(_, _, force)) in self.prev.iter() {
+            if uid2 == uid {
+                res = res + force;
+            }
+
+            if uid1 == uid {
+                res = res - force;
+            }
+        }
+
+        res
+    }
+
+    /// Forgets the forces applied on the body identified by `uid`.
+    pub fn forget(&mut self, uid: usize) {
+        self.prev.retain(|&(uid1, uid2), _| uid1 != uid && uid2 != uid);
+    }
+
+    /// A copy of these forces for the copies `bodies` of the rigid bodies, indexed by the
+    /// identifiers of the originals.
+    pub fn clone_for_prediction(&self, bodies: &UidMap<usize, RigidBodyHandle<N>, UintTWHash>) -> ContactForces<N> {
+        let mut res = ContactForces::new();
+
+        for (&(uid1, uid2), &(_, _, force)) in self.prev.iter() {
+            if let (Some(rb1), Some(rb2)) = (bodies.find(&uid1), bodies.find(&uid2)) {
+                let key = (WorldObject::rigid_body_uid(rb1), WorldObject::rigid_body_uid(rb2));
+                let _   = res.prev.insert(key, (rb1.clone(), rb2.clone(), force));
+            }
+        }
+
+        res
+    }
+}
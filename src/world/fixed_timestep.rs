@@ -0,0 +1,128 @@
+use alga::general::Real;
+use na;
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use object::{WorldObject, RigidBodyHandle};
+use world::World;
+use math::{Translation, Rotation, Isometry};
+
+/// Steps a world at a fixed rate from the time elapsed between the frames of the application.
+///
+/// The elapsed time is accumulated, and the world is stepped with a constant time step as long as
+/// a whole step fits in the accumulated time. The leftover, which is less than a step, is carried
+/// over to the next frame. The rendered positions lag one step behind the simulation: they are
+/// interpolated between the positions before and after the last step with the leftover ratio
+/// `alpha`, so that the motions look smooth even if the framerate is not a multiple of the
+/// simulation rate.
+pub struct FixedTimestep<N: Real> {
+    dt:          N,
+    max_steps:   usize,
+    accumulator: N,
+    // The position of each rigid body before the last step.
+    previous:    HashMap<usize, Isometry<N>, UintTWHash>
+}
+
+impl<N: Real> FixedTimestep<N> {
+    /// Creates a stepper advancing worlds with time steps of length `dt`.
+    pub fn new(dt: N) -> FixedTimestep<N> {
+        assert!(dt > na::zero(), "The fixed time step must be positive.");
+
+        FixedTimestep {
+            dt:          dt,
+            max_steps:   5,
+            accumulator: na::zero(),
+            previous:    HashMap::new(UintTWHash::new())
+        }
+    }
+
+    /// The fixed time step.
+    #[inline]
+    pub fn dt(&self) -> N {
+        self.dt
+    }
+
+    /// The maximum number of steps performed by a single call to `advance`.
+    #[inline]
+    pub fn max_steps(&self) -> usize {
+        self.max_steps
+    }
+
+    /// Sets the maximum number of steps performed by a single call to `advance`.
+    ///
+    /// The time that could not be simulated with so many steps is dropped, so that a slow frame
+    /// does not lead to even slower ones. The default is 5.
+    #[inline]
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        assert!(max_steps > 0, "The maximum number of steps must be at least 1.");
+        self.max_steps = max_steps
+    }
+
+    /// The accumulated time not simulated yet, which is always less than a step.
+    #[inline]
+    pub fn accumulator(&self) -> N {
+        self.accumulator
+    }
+
+    /// The ratio of the accumulated time to the time step, in `[0, 1[`.
+    ///
+    /// This is the weight of the current positions of the bodies relative to their positions
+    /// before the last step.
+    #[inline]
+    pub fn alpha(&self) -> N {
+        self.accumulator / self.dt
+    }
+
+    /// Accumulates the time `elapsed` since the last frame and steps `world` accordingly.
+    ///
+    /// Returns the number of steps performed, which may be zero on fast frames.
+    pub fn advance(&mut self, world: &mut World<N>, elapsed: N) -> usize {
+        self.accumulator = self.accumulator + elapsed;
+
+        let mut num_steps = 0;
+
+        while self.accumulator >= self.dt && num_steps < self.max_steps {
+            self.record(world);
+            let _ = world.step(self.dt);
+
+            self.accumulator = self.accumulator - self.dt;
+            num_steps = num_steps + 1;
+        }
+
+        if self.accumulator >= self.dt {
+            self.accumulator = self.accumulator - (self.accumulator / self.dt).floor() * self.dt;
+        }
+
+        num_steps
+    }
+
+    /// The position of `rb` to render at this frame.
+    ///
+    /// This interpolates the position of `rb` before the last step and its current position with
+    /// `alpha`. A body added to the world after the last step is rendered at its current position.
+    pub fn interpolated_position(&self, rb: &RigidBodyHandle<N>) -> Isometry<N> {
+        let current = rb.borrow().position().clone();
+
+        match self.previous.find(&WorldObject::rigid_body_uid(rb)) {
+            Some(previous) => {
+                let alpha       = self.alpha();
+                let translation = previous.translation.vector +
+                                  (current.translation.vector - previous.translation.vector) * alpha;
+                let rotation    = (current.rotation * previous.rotation.inverse()).scaled_axis() * alpha;
+
+                Isometry::from_parts(Translation::from_vector(translation),
+                                     Rotation::from_scaled_axis(rotation) * previous.rotation)
+            },
+            None => current
+        }
+    }
+
+    // Records the positions of the bodies of `world` before a step, forgetting the removed ones.
+    fn record(&mut self, world: &World<N>) {
+        self.previous.clear();
+
+        for rb in world.rigid_bodies() {
+            let position = rb.borrow().position().clone();
+            let _        = self.previous.insert(WorldObject::rigid_body_uid(rb), position);
+        }
+    }
+}
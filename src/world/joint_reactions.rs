@@ -0,0 +1,103 @@
+use std::mem;
+use std::collections::HashMap;
+
+use alga::general::Real;
+use na;
+use detection::constraint::Constraint;
+use detection::joint::JointManager;
+use math::{Vector, Orientation};
+use utils::DeterministicState;
+
+/// The force and torque applied by a joint on each of the bodies it attaches during the last step.
+///
+/// The torques are expressed at the center of mass of each body, and include the torque caused by
+/// the force applied at the anchor.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct JointReaction<N: Real> {
+    /// The force applied on the first body.
+    pub force1:  Vector<N>,
+    /// The torque applied on the first body.
+    pub torque1: Orientation<N>,
+    /// The force applied on the second body.
+    pub force2:  Vector<N>,
+    /// The torque applied on the second body.
+    pub torque2: Orientation<N>
+}
+
+impl<N: Real> JointReaction<N> {
+    fn zero() -> JointReaction<N> {
+        JointReaction {
+            force1:  na::zero(),
+            torque1: na::zero(),
+            force2:  na::zero(),
+            torque2: na::zero()
+        }
+    }
+}
+
+/// The reactions of the joints during the last step, indexed by their keys on the joint manager.
+///
+/// The joints between bodies which are both asleep, or static, are not solved any more: the
+/// reactions they applied last are kept until one of the bodies wakes up.
+pub struct JointReactions<N: Real> {
+    prev: HashMap<usize, (Constraint<N>, JointReaction<N>), DeterministicState>,
+    // The impulses accumulated during the current step.
+    next: HashMap<usize, (Constraint<N>, JointReaction<N>), DeterministicState>
+}
+
+impl<N: Real> JointReactions<N> {
+    /// Creates an empty set of joint reactions.
+    pub fn new() -> JointReactions<N> {
+        JointReactions {
+            prev: HashMap::with_hasher(DeterministicState::new()),
+            next: HashMap::with_hasher(DeterministicState::new())
+        }
+    }
+
+    /// Accumulates the impulses applied by the joints of `constraints` during one resolution, as
+    /// given by `AccumulatedImpulseSolver::joint_body_impulses`.
+    pub fn record(&mut self,
+                  constraints: &[Constraint<N>],
+                  impulses:    &[(usize, Vector<N>, Orientation<N>, Vector<N>, Orientation<N>)]) {
+        for &(i, lin1, ang1, lin2, ang2) in impulses.iter() {
+            let key   = JointManager::joint_id(&constraints[i]);
+            let entry = self.next.entry(key).or_insert_with(|| (constraints[i].clone(), JointReaction::zero()));
+
+            entry.1.force1  = entry.1.force1 + lin1;
+            entry.1.torque1 = entry.1.torque1 + ang1;
+            entry.1.force2  = entry.1.force2 + lin2;
+            entry.1.torque2 = entry.1.torque2 + ang2;
+        }
+    }
+
+    /// Converts the impulses accumulated during a step of length `dt` to forces.
+    ///
+    /// The joints which were not solved during the step keep their last reaction if they are
+    /// still handled by `joints`.
+    pub fn end_step(&mut self, dt: N, joints: &JointManager<N>) {
+        for (_, &mut (_, ref mut reaction)) in self.next.iter_mut() {
+            reaction.force1  = reaction.force1 / dt;
+            reaction.torque1 = reaction.torque1 / dt;
+            reaction.force2  = reaction.force2 / dt;
+            reaction.torque2 = reaction.torque2 / dt;
+        }
+
+        for (key, value) in self.prev.drain() {
+            if !self.next.contains_key(&key) && joints.joints().find(&key).is_some() {
+                let _ = self.next.insert(key, value);
+            }
+        }
+
+        mem::swap(&mut self.prev, &mut self.next);
+    }
+
+    /// The reaction of the joint identified by `key` during the last step.
+    pub fn reaction(&self, key: usize) -> Option<JointReaction<N>> {
+        self.prev.get(&key).map(|&(_, reaction)| reaction)
+    }
+
+    /// The reactions of all the joints solved during the last step.
+    pub fn reactions(&self) -> Vec<(Constraint<N>, JointReaction<N>)> {
+        self.prev.values().cloned().collect()
+    }
+}
@@ -1,6 +1,25 @@
 //! The physics world.
 
-pub use world::world::{World, WorldBroadPhase, RigidBodies, Sensors, RigidBodyCollisionWorld,
-                       WorldCollisionObject};
+pub use world::world::{World, WorldStage, WorldBroadPhase, RigidBodies, Sensors,
+                       RigidBodyCollisionWorld, WorldCollisionObject};
+pub use world::queries::{ShapeCastHit, RayHit, RayCastOptions, ClosestPoints};
+pub use world::summary::SceneSummary;
+pub use world::step_report::StepReport;
+pub use world::timestep_suggestion::{TimestepSuggestion, TimestepLimit};
+pub use world::fixed_timestep::FixedTimestep;
+pub use world::ragdoll::{RagdollBuilder, Ragdoll, RagdollJoint};
+pub use world::joint_reactions::JointReaction;
+pub use world::world_save::{WorldSave, BodySave};
+pub use world::transform_change_monitor::{TransformChangeMonitor, TransformChangeHandler, TransformChange};
 
 mod world;
+mod queries;
+mod summary;
+mod step_report;
+mod timestep_suggestion;
+mod fixed_timestep;
+mod ragdoll;
+mod world_save;
+mod transform_change_monitor;
+mod contact_forces;
+mod joint_reactions;
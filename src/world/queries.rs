@@ -0,0 +1,379 @@
+use alga::general::Real;
+use na;
+use ncollide::bounding_volume::{self, BoundingVolume, AABB};
+use ncollide::query::{self, Ray, RayCast, RayIntersection};
+use ncollide::shape::{Shape, Compound};
+use ncollide::partitioning::{BVT, BVTVisitor, BVTCostFn};
+use ncollide::world::CollisionGroups;
+use object::{RigidBodyHandle, WorldObject};
+use math::{Point, Vector, Isometry, Translation};
+use world::{World, WorldCollisionObject, RigidBodyCollisionWorld};
+
+/// The bounding volume tree of the world objects visible to ray and shape casts, which leaves are
+/// the identifiers of the collision objects.
+pub type QueryTree<N> = BVT<usize, AABB<Point<N>>>;
+
+/// The first rigid body hit by a shape cast.
+pub struct ShapeCastHit<N: Real> {
+    /// The rigid body hit.
+    pub body:   RigidBodyHandle<N>,
+    /// The time of impact: the shape touches `body` once translated by `dir * toi`.
+    pub toi:    N,
+    /// The contact point, on the surface of `body`.
+    pub point:  Point<N>,
+    /// The normal of `body` at the contact point, pointing toward the cast shape.
+    pub normal: Vector<N>
+}
+
+/// An intersection between a ray and a world object.
+pub struct RayHit<N: Real> {
+    /// The rigid body or sensor hit.
+    pub object:    WorldObject<N>,
+    /// The time of impact: the ray hits `object` at `ray.origin + ray.dir * toi`.
+    pub toi:       N,
+    /// The world-space intersection point.
+    pub point:     Point<N>,
+    /// The world-space normal of `object` at the intersection point.
+    pub normal:    Vector<N>,
+    /// If `object` has a compound shape, the index of the child shape hit.
+    pub sub_shape: Option<usize>
+}
+
+/// Filters the objects a ray cast may hit.
+pub struct RayCastOptions<'a, N: Real> {
+    /// The maximum time of impact, in multiples of the ray direction. Defaults to
+    /// `N::max_value()`, i.e., an infinite ray.
+    pub max_toi: N,
+    /// Only objects which collision groups can interact with these groups can be hit.
+    pub groups:  &'a CollisionGroups,
+    /// Objects that will not be hit, e.g., the body of the shooter.
+    pub ignored: &'a [WorldObject<N>]
+}
+
+impl<'a, N: Real> RayCastOptions<'a, N> {
+    /// Options for an infinite ray hitting every object that can interact with `groups`.
+    pub fn new(groups: &'a CollisionGroups) -> RayCastOptions<'a, N> {
+        RayCastOptions {
+            max_toi: N::max_value(),
+            groups:  groups,
+            ignored: &[]
+        }
+    }
+
+    /// Limits the ray length to `max_toi` times the ray direction.
+    pub fn with_max_toi(mut self, max_toi: N) -> RayCastOptions<'a, N> {
+        self.max_toi = max_toi;
+        self
+    }
+
+    /// Prevents the ray from hitting the objects of `ignored`.
+    pub fn with_ignored(mut self, ignored: &'a [WorldObject<N>]) -> RayCastOptions<'a, N> {
+        self.ignored = ignored;
+        self
+    }
+
+    // Whether `co` can be hit by the ray, before any exact intersection test.
+    fn accepts(&self, co: &WorldCollisionObject<N>) -> bool {
+        co.collision_groups.can_interact_with_groups(self.groups) &&
+        !self.ignored.iter().any(|o| o.uid() == co.uid)
+    }
+}
+
+/// The closest points between two rigid bodies.
+pub struct ClosestPoints<N: Real> {
+    /// The distance separating the two bodies. Zero if they are penetrating.
+    pub distance: N,
+    /// The point of the first body closest to the second one.
+    pub point1:   Point<N>,
+    /// The point of the second body closest to the first one.
+    pub point2:   Point<N>,
+    /// The unit vector pointing from the first body toward the second one.
+    pub normal:   Vector<N>
+}
+
+impl<N: Real> World<N> {
+    /// Sweeps a shape along a direction and finds the first rigid body it hits.
+    ///
+    /// The shape starts at the position `start` and is translated by `dir * t` for `t` in
+    /// `[0, max_toi]`. Only rigid bodies which collision groups can interact with `groups` are
+    /// considered, e.g., use `RigidBodyCollisionGroups::new_dynamic().as_collision_groups()` to
+    /// hit every body a dynamic rigid body could collide with. Sensors and bodies invisible to
+    /// queries are ignored.
+    ///
+    /// This is typically used to move character controllers.
+    pub fn cast_shape(&self,
+                      shape:   &Shape<Point<N>, Isometry<N>>,
+                      start:   &Isometry<N>,
+                      dir:     &Vector<N>,
+                      max_toi: N,
+                      groups:  &CollisionGroups)
+                      -> Option<ShapeCastHit<N>> {
+        let end        = Translation::from_vector(*dir * max_toi) * start;
+        let begin_aabb = bounding_volume::aabb(shape, start);
+        let end_aabb   = bounding_volume::aabb(shape, &end);
+        let swept_aabb = begin_aabb.merged(&end_aabb);
+
+        let mut candidates = AabbInterferences { aabb: &swept_aabb, found: Vec::new() };
+        self.query_tree().visit(&mut candidates);
+
+        let mut best: Option<(N, &WorldCollisionObject<N>)> = None;
+
+        for co in candidates.found.iter().filter_map(|uid| self.collision_world().collision_object(*uid)) {
+            if co.data.is_rigid_body() && co.collision_groups.can_interact_with_groups(groups) {
+                let toi = query::time_of_impact(start, dir, shape, &co.position, &na::zero(), co.shape.as_ref());
+
+                if let Some(toi) = toi {
+                    if toi <= max_toi && best.map_or(true, |(best_toi, _)| toi < best_toi) {
+                        best = Some((toi, co))
+                    }
+                }
+            }
+        }
+
+        best.and_then(|(toi, co)| {
+            let position = Translation::from_vector(*dir * toi) * start;
+
+            query::contact(&position, shape, &co.position, co.shape.as_ref(), self.prediction()).map(|c| {
+                ShapeCastHit {
+                    body:   co.data.clone().unwrap_rigid_body(),
+                    toi:    toi,
+                    point:  c.world2,
+                    normal: -c.normal
+                }
+            })
+        })
+    }
+
+    /// Collects every rigid body which shape contains the given world-space point.
+    ///
+    /// Only rigid bodies which collision groups can interact with `groups` are collected into
+    /// `out`. Sensors are ignored. This is typically used for mouse picking, or to check that a
+    /// spawn position is free.
+    pub fn bodies_containing_point(&self,
+                                   point:  &Point<N>,
+                                   groups: &CollisionGroups,
+                                   out:    &mut Vec<RigidBodyHandle<N>>) {
+        for co in self.collision_world().interferences_with_point(point, groups) {
+            if let WorldObject::RigidBody(ref rb) = co.data {
+                out.push(rb.clone())
+            }
+        }
+    }
+
+    /// The distance separating two rigid bodies. Zero if they are touching or penetrating.
+    pub fn distance(&self, rb1: &RigidBodyHandle<N>, rb2: &RigidBodyHandle<N>) -> N {
+        let rb1 = rb1.borrow();
+        let rb2 = rb2.borrow();
+
+        query::distance(rb1.position(), rb1.shape().as_ref(), rb2.position(), rb2.shape().as_ref())
+    }
+
+    /// Computes the closest points between two rigid bodies, if they are closer than
+    /// `max_distance`.
+    ///
+    /// If the bodies are penetrating, the points are the deepest points of each body inside of
+    /// the other. Unlike contacts, this does not require the bodies to be close enough for the
+    /// narrow phase to consider them, nor to be allowed to collide with each other.
+    pub fn closest_points(&self, rb1: &RigidBodyHandle<N>, rb2: &RigidBodyHandle<N>, max_distance: N)
+                          -> Option<ClosestPoints<N>> {
+        let rb1 = rb1.borrow();
+        let rb2 = rb2.borrow();
+
+        let contact = query::contact(rb1.position(), rb1.shape().as_ref(),
+                                     rb2.position(), rb2.shape().as_ref(),
+                                     max_distance);
+
+        contact.map(|c| {
+            ClosestPoints {
+                distance: (-c.depth).max(na::zero()),
+                point1:   c.world1,
+                point2:   c.world2,
+                normal:   c.normal
+            }
+        })
+    }
+
+    /// Computes the minimum translation vector separating two penetrating rigid bodies.
+    ///
+    /// Returns the unit normal pointing from `rb1` toward `rb2`, and the penetration depth:
+    /// translating `rb2` by `normal * depth` brings both bodies in touching contact. Returns `None`
+    /// if the bodies do not penetrate. Like `closest_points`, this is independent from the
+    /// contacts computed by the narrow phase, e.g., to validate a spawn position.
+    pub fn penetration(&self, rb1: &RigidBodyHandle<N>, rb2: &RigidBodyHandle<N>) -> Option<(Vector<N>, N)> {
+        let rb1 = rb1.borrow();
+        let rb2 = rb2.borrow();
+
+        let contact = query::contact(rb1.position(), rb1.shape().as_ref(),
+                                     rb2.position(), rb2.shape().as_ref(),
+                                     na::zero());
+
+        match contact {
+            Some(c) if c.depth > na::zero() => Some((c.normal, c.depth)),
+            _                               => None
+        }
+    }
+
+    /// Collects every intersection between a ray and the world objects.
+    ///
+    /// Only the objects accepted by `options` and visible to queries (see
+    /// `RigidBodyCollisionGroups::set_visible_to_queries`) are collected into `out`, in no
+    /// particular order. Rays starting inside of a shape hit it with a zero time of impact.
+    pub fn interferences_with_ray(&self,
+                                  ray:     &Ray<Point<N>>,
+                                  options: &RayCastOptions<N>,
+                                  out:     &mut Vec<RayHit<N>>) {
+        let mut visitor = RayInterferences {
+            cworld:  self.collision_world(),
+            ray:     ray,
+            options: options,
+            out:     out
+        };
+
+        self.query_tree().visit(&mut visitor)
+    }
+
+    /// Finds the first intersection between a ray and the world objects accepted by `options`.
+    ///
+    /// Unlike `self.interferences_with_ray`, this does not collect every hit. The bounding volume
+    /// tree of the world objects is traversed nearest bounding volume first, and the traversal
+    /// stops as soon as the remaining bounding volumes are hit farther than the closest hit found
+    /// so far.
+    pub fn cast_ray_closest(&self, ray: &Ray<Point<N>>, options: &RayCastOptions<N>) -> Option<RayHit<N>> {
+        let mut cost_fn = ClosestRayHit {
+            cworld:  self.collision_world(),
+            ray:     ray,
+            options: options
+        };
+
+        self.query_tree().best_first_search(&mut cost_fn).and_then(|(uid, inter)| {
+            self.collision_world().collision_object(uid).map(|co| ray_hit(co, ray, inter))
+        })
+    }
+}
+
+// The bounding volume tree of the objects of `cworld` visible to ray and shape casts.
+pub fn query_tree<N: Real>(cworld: &RigidBodyCollisionWorld<N>) -> QueryTree<N> {
+    let leaves = cworld.collision_objects().filter(|co| is_visible_to_queries(co)).map(|co| {
+        (co.uid, bounding_volume::aabb(co.shape.as_ref(), &co.position))
+    }).collect();
+
+    BVT::new_balanced(leaves)
+}
+
+fn is_visible_to_queries<N: Real>(co: &WorldCollisionObject<N>) -> bool {
+    match co.data {
+        WorldObject::RigidBody(ref rb) => rb.borrow().collision_groups().is_visible_to_queries(),
+        WorldObject::Sensor(ref s)     => s.borrow().collision_groups().is_visible_to_queries()
+    }
+}
+
+// The time of impact of `ray` with a bounding volume of the query tree, if it is hit before
+// `max_toi`.
+fn aabb_toi<N: Real>(aabb: &AABB<Point<N>>, ray: &Ray<Point<N>>, max_toi: N) -> Option<N> {
+    match RayCast::<Point<N>, Isometry<N>>::toi_with_ray(aabb, &na::one(), ray, true) {
+        Some(toi) if toi <= max_toi => Some(toi),
+        _                           => None
+    }
+}
+
+// Collects the objects which bounding box intersects `aabb`.
+struct AabbInterferences<'a, N: Real> {
+    aabb:  &'a AABB<Point<N>>,
+    found: Vec<usize>
+}
+
+impl<'a, N: Real> BVTVisitor<usize, AABB<Point<N>>> for AabbInterferences<'a, N> {
+    fn visit_internal(&mut self, bv: &AABB<Point<N>>) -> bool {
+        bv.intersects(self.aabb)
+    }
+
+    fn visit_leaf(&mut self, uid: &usize, bv: &AABB<Point<N>>) {
+        if bv.intersects(self.aabb) {
+            self.found.push(*uid)
+        }
+    }
+}
+
+// Collects the intersections between a ray and the objects accepted by `options`, which are
+// tested exactly only if their bounding box is hit.
+struct RayInterferences<'a, N: Real> {
+    cworld:  &'a RigidBodyCollisionWorld<N>,
+    ray:     &'a Ray<Point<N>>,
+    options: &'a RayCastOptions<'a, N>,
+    out:     &'a mut Vec<RayHit<N>>
+}
+
+impl<'a, N: Real> BVTVisitor<usize, AABB<Point<N>>> for RayInterferences<'a, N> {
+    fn visit_internal(&mut self, bv: &AABB<Point<N>>) -> bool {
+        aabb_toi(bv, self.ray, self.options.max_toi).is_some()
+    }
+
+    fn visit_leaf(&mut self, uid: &usize, bv: &AABB<Point<N>>) {
+        if aabb_toi(bv, self.ray, self.options.max_toi).is_none() {
+            return;
+        }
+
+        if let Some(co) = self.cworld.collision_object(*uid) {
+            if self.options.accepts(co) {
+                if let Some(inter) = co.shape.toi_and_normal_with_ray(&co.position, self.ray, true) {
+                    if inter.toi <= self.options.max_toi {
+                        self.out.push(ray_hit(co, self.ray, inter))
+                    }
+                }
+            }
+        }
+    }
+}
+
+// The cost of the best-first search of the closest intersection between a ray and the objects
+// accepted by `options`: the time of impact of the ray.
+struct ClosestRayHit<'a, N: Real> {
+    cworld:  &'a RigidBodyCollisionWorld<N>,
+    ray:     &'a Ray<Point<N>>,
+    options: &'a RayCastOptions<'a, N>
+}
+
+impl<'a, N: Real> BVTCostFn<N, usize, AABB<Point<N>>> for ClosestRayHit<'a, N> {
+    type UserData = (usize, RayIntersection<Vector<N>>);
+
+    fn compute_bv_cost(&mut self, bv: &AABB<Point<N>>) -> Option<N> {
+        aabb_toi(bv, self.ray, self.options.max_toi)
+    }
+
+    fn compute_b_cost(&mut self, uid: &usize) -> Option<(N, (usize, RayIntersection<Vector<N>>))> {
+        let co = match self.cworld.collision_object(*uid) {
+            Some(co) if self.options.accepts(co) => co,
+            _                                    => return None
+        };
+
+        match co.shape.toi_and_normal_with_ray(&co.position, self.ray, true) {
+            Some(inter) if inter.toi <= self.options.max_toi => Some((inter.toi, (*uid, inter))),
+            _                                                => None
+        }
+    }
+}
+
+fn ray_hit<N: Real>(co: &WorldCollisionObject<N>, ray: &Ray<Point<N>>, inter: RayIntersection<Vector<N>>)
+                    -> RayHit<N> {
+    let sub_shape = co.shape.as_shape::<Compound<Point<N>, Isometry<N>>>().and_then(|c| {
+        let mut best: Option<(N, usize)> = None;
+
+        for (i, &(ref m, ref child)) in c.shapes().iter().enumerate() {
+            if let Some(toi) = child.toi_with_ray(&(co.position * *m), ray, true) {
+                if best.map_or(true, |(best_toi, _)| toi < best_toi) {
+                    best = Some((toi, i))
+                }
+            }
+        }
+
+        best.map(|(_, i)| i)
+    });
+
+    RayHit {
+        object:    co.data.clone(),
+        toi:       inter.toi,
+        point:     ray.origin + ray.dir * inter.toi,
+        normal:    inter.normal,
+        sub_shape: sub_shape
+    }
+}
@@ -0,0 +1,228 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use alga::general::Real;
+use na;
+use ncollide::broad_phase::BroadPhasePairFilter;
+use math::{Point, Isometry, Orientation};
+use detection::joint::{Anchor, BallInSocket, Hinge};
+#[cfg(feature = "dim3")]
+use detection::joint::BallInSocketLimits;
+use object::{RigidBodyHandle, WorldObject};
+use world::{World, WorldCollisionObject};
+
+/// A joint between two limbs of a ragdoll.
+#[derive(Clone)]
+pub enum RagdollJoint<N: Real> {
+    /// A ball-in-socket joint, e.g., a shoulder or a hip.
+    BallInSocket(Rc<RefCell<BallInSocket<N>>>),
+    /// A hinge, e.g., an elbow or a knee.
+    Hinge(Rc<RefCell<Hinge<N>>>)
+}
+
+// The parent and child limbs, the world-space joint frame, and the joint specific parameters.
+enum JointDesc<N: Real> {
+    BallInSocket(usize, usize, Isometry<N>, N, N, N),
+    Hinge(usize, usize, Isometry<N>, Orientation<N>, N, N)
+}
+
+/// Assembles limbs into a ragdoll.
+///
+/// The joint frames are expressed in world-space, for the current positions of the limbs. Once
+/// built, adjacent limbs, i.e., limbs attached by a joint, do not collide with each other.
+pub struct RagdollBuilder<N: Real> {
+    limbs:  Vec<RigidBodyHandle<N>>,
+    joints: Vec<JointDesc<N>>
+}
+
+impl<N: Real> RagdollBuilder<N> {
+    /// Creates a builder of a ragdoll without limbs.
+    pub fn new() -> RagdollBuilder<N> {
+        RagdollBuilder {
+            limbs:  Vec::new(),
+            joints: Vec::new()
+        }
+    }
+
+    /// Adds a limb, already added to the world, and returns its index.
+    pub fn add_limb(&mut self, body: RigidBodyHandle<N>) -> usize {
+        self.limbs.push(body);
+
+        self.limbs.len() - 1
+    }
+
+    /// Attaches the limb `child` to the limb `parent` with a ball-in-socket joint at `frame`.
+    ///
+    /// In 3D, the `x` axis of `frame` is the twist axis. The swing is limited to `pi / 4` and the
+    /// twist to `[-pi / 4, pi / 4]`.
+    pub fn add_ball_in_socket(&mut self, parent: usize, child: usize, frame: Isometry<N>) {
+        self.add_ball_in_socket_with_limits(parent, child, frame, N::frac_pi_4(), -N::frac_pi_4(), N::frac_pi_4())
+    }
+
+    /// Attaches the limb `child` to the limb `parent` with a ball-in-socket joint at `frame`, with
+    /// the given limits.
+    ///
+    /// The limits are those of `BallInSocketLimits`, and are ignored in 2D.
+    pub fn add_ball_in_socket_with_limits(&mut self,
+                                          parent:    usize,
+                                          child:     usize,
+                                          frame:     Isometry<N>,
+                                          max_swing: N,
+                                          min_twist: N,
+                                          max_twist: N) {
+        self.check_limbs(parent, child);
+        self.joints.push(JointDesc::BallInSocket(parent, child, frame, max_swing, min_twist, max_twist))
+    }
+
+    /// Attaches the limb `child` to the limb `parent` with a hinge at `frame`, rotating around
+    /// `axis` expressed in `frame`.
+    ///
+    /// The child limb bends by up to `3 * pi / 4` in the positive direction around the hinge axis.
+    pub fn add_hinge(&mut self, parent: usize, child: usize, frame: Isometry<N>, axis: Orientation<N>) {
+        let max_angle = N::frac_pi_4() * na::convert(3.0f64);
+
+        self.add_hinge_with_limits(parent, child, frame, axis, na::zero(), max_angle)
+    }
+
+    /// Attaches the limb `child` to the limb `parent` with a hinge at `frame`, rotating around
+    /// `axis` expressed in `frame`, with an angle in `[min_angle, max_angle]`.
+    pub fn add_hinge_with_limits(&mut self,
+                                 parent:    usize,
+                                 child:     usize,
+                                 frame:     Isometry<N>,
+                                 axis:      Orientation<N>,
+                                 min_angle: N,
+                                 max_angle: N) {
+        self.check_limbs(parent, child);
+        self.joints.push(JointDesc::Hinge(parent, child, frame, axis, min_angle, max_angle))
+    }
+
+    fn check_limbs(&self, parent: usize, child: usize) {
+        assert!(parent < self.limbs.len() && child < self.limbs.len(), "Unknown ragdoll limb.");
+        assert!(parent != child, "A ragdoll limb cannot be attached to itself.");
+    }
+
+    /// Adds the joints of the ragdoll to `world`.
+    ///
+    /// The collisions between adjacent limbs are disabled by a broad phase pair filter registered
+    /// with the name `name`.
+    pub fn build(self, world: &mut World<N>, name: &str) -> Ragdoll<N> {
+        let mut joints = Vec::new();
+        let mut pairs  = Vec::new();
+
+        for desc in self.joints.into_iter() {
+            match desc {
+                JointDesc::BallInSocket(parent, child, frame, max_swing, min_twist, max_twist) => {
+                    let local1 = self.limbs[parent].borrow().position().inverse() * frame;
+                    let local2 = self.limbs[child].borrow().position().inverse() * frame;
+                    let anchor1 = Anchor::new(Some(self.limbs[parent].clone()),
+                                              Point::from_coordinates(local1.translation.vector));
+                    let anchor2 = Anchor::new(Some(self.limbs[child].clone()),
+                                              Point::from_coordinates(local2.translation.vector));
+
+                    let mut joint = BallInSocket::new(anchor1, anchor2);
+                    set_ball_in_socket_limits(&mut joint, &local1, &local2, max_swing, min_twist, max_twist);
+
+                    joints.push(RagdollJoint::BallInSocket(world.add_ball_in_socket(joint)));
+                    pairs.push(limb_pair(&self.limbs[parent], &self.limbs[child]));
+                },
+                JointDesc::Hinge(parent, child, frame, axis, min_angle, max_angle) => {
+                    let local1 = self.limbs[parent].borrow().position().inverse() * frame;
+                    let local2 = self.limbs[child].borrow().position().inverse() * frame;
+                    let anchor1 = Anchor::new(Some(self.limbs[parent].clone()), local1);
+                    let anchor2 = Anchor::new(Some(self.limbs[child].clone()), local2);
+
+                    let mut joint = Hinge::new(anchor1, anchor2, axis);
+                    joint.set_limits(Some((min_angle, max_angle)));
+
+                    joints.push(RagdollJoint::Hinge(world.add_hinge(joint)));
+                    pairs.push(limb_pair(&self.limbs[parent], &self.limbs[child]));
+                }
+            }
+        }
+
+        world.register_broad_phase_pair_filter(name, AdjacentLimbsPairFilter { pairs: pairs });
+
+        Ragdoll {
+            name:   name.to_string(),
+            limbs:  self.limbs,
+            joints: joints
+        }
+    }
+}
+
+/// Limbs and joints assembled by a `RagdollBuilder`.
+pub struct Ragdoll<N: Real> {
+    name:   String,
+    limbs:  Vec<RigidBodyHandle<N>>,
+    joints: Vec<RagdollJoint<N>>
+}
+
+impl<N: Real> Ragdoll<N> {
+    /// The name of the pair filter disabling the collisions between adjacent limbs.
+    pub fn name(&self) -> &str {
+        &self.name[..]
+    }
+
+    /// The limbs of this ragdoll, in the order they were added to the builder.
+    pub fn limbs(&self) -> &[RigidBodyHandle<N>] {
+        &self.limbs[..]
+    }
+
+    /// The joints of this ragdoll, in the order they were added to the builder.
+    pub fn joints(&self) -> &[RagdollJoint<N>] {
+        &self.joints[..]
+    }
+
+    /// Removes the joints and the pair filter of this ragdoll from `world`.
+    ///
+    /// The limbs are left in the world.
+    pub fn remove_joints(&self, world: &mut World<N>) {
+        for joint in self.joints.iter() {
+            match *joint {
+                RagdollJoint::BallInSocket(ref b) => world.remove_ball_in_socket(b),
+                RagdollJoint::Hinge(ref h)        => world.remove_hinge(h)
+            }
+        }
+
+        world.unregister_broad_phase_pair_filter(&self.name[..])
+    }
+}
+
+#[cfg(feature = "dim3")]
+fn set_ball_in_socket_limits<N: Real>(joint:     &mut BallInSocket<N>,
+                                      local1:    &Isometry<N>,
+                                      local2:    &Isometry<N>,
+                                      max_swing: N,
+                                      min_twist: N,
+                                      max_twist: N) {
+    let limits = BallInSocketLimits::new(local1.rotation, local2.rotation, max_swing, min_twist, max_twist);
+
+    joint.set_angular_limits(Some(limits))
+}
+
+#[cfg(feature = "dim2")]
+fn set_ball_in_socket_limits<N: Real>(_: &mut BallInSocket<N>, _: &Isometry<N>, _: &Isometry<N>, _: N, _: N, _: N) {
+}
+
+fn limb_pair<N: Real>(limb1: &RigidBodyHandle<N>, limb2: &RigidBodyHandle<N>) -> (usize, usize) {
+    let uid1 = WorldObject::rigid_body_uid(limb1);
+    let uid2 = WorldObject::rigid_body_uid(limb2);
+
+    if uid1 < uid2 { (uid1, uid2) } else { (uid2, uid1) }
+}
+
+struct AdjacentLimbsPairFilter {
+    pairs: Vec<(usize, usize)>
+}
+
+impl<N: Real> BroadPhasePairFilter<Point<N>, Isometry<N>, WorldObject<N>> for AdjacentLimbsPairFilter {
+    #[inline]
+    fn is_pair_valid(&self, b1: &WorldCollisionObject<N>, b2: &WorldCollisionObject<N>) -> bool {
+        match (&b1.data, &b2.data) {
+            (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) => {
+                !self.pairs.contains(&limb_pair(rb1, rb2))
+            },
+            _ => true
+        }
+    }
+}
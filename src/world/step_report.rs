@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+/// A summary of the work done by a call to `World::step`.
+///
+/// This is meant to display physics statistics, or to adapt the quality of the simulation to a
+/// time budget, e.g., by reducing the number of solver iterations when the solver takes too long.
+///
+/// If the step was split into several ones, e.g., by `set_max_substep_dt`, the integrated bodies,
+/// the solver iterations and the timings are accumulated over all of them, while the other
+/// counters are those of the last one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StepReport {
+    /// The number of steps performed.
+    pub num_steps:         usize,
+    /// The number of active bodies integrated, excluding the substeps of the substepped bodies.
+    pub bodies_integrated: usize,
+    /// The number of pairs of objects with intersecting bounding boxes.
+    pub pairs:             usize,
+    /// The number of contacts generated by the narrow phase.
+    pub contacts:          usize,
+    /// The number of islands of interacting bodies, including those made of a single body.
+    pub islands:           usize,
+    /// The number of iterations performed by the constraint solver, including those of the
+    /// position correction.
+    pub solver_iterations: usize,
+    /// The time spent integrating the forces and the velocities, including the substeps.
+    pub integration_time:  Duration,
+    /// The time spent on the broad phase, the continuous collision detection and the narrow
+    /// phase.
+    pub collision_time:    Duration,
+    /// The time spent building the islands and putting the bodies to sleep.
+    pub activation_time:   Duration,
+    /// The time spent collecting the contacts and joints, and solving them.
+    pub solver_time:       Duration,
+    /// The time spent on the whole step.
+    pub total_time:        Duration
+}
+
+impl StepReport {
+    /// Creates a report with all counters and timings set to zero.
+    pub fn new() -> StepReport {
+        StepReport::default()
+    }
+
+    /// Accumulates the report `other` of a subsequent step into this one.
+    #[doc(hidden)]
+    pub fn append(&mut self, other: StepReport) {
+        self.num_steps         = self.num_steps + other.num_steps;
+        self.bodies_integrated = self.bodies_integrated + other.bodies_integrated;
+        self.pairs             = other.pairs;
+        self.contacts          = other.contacts;
+        self.islands           = other.islands;
+        self.solver_iterations = self.solver_iterations + other.solver_iterations;
+        self.integration_time  = self.integration_time + other.integration_time;
+        self.collision_time    = self.collision_time + other.collision_time;
+        self.activation_time   = self.activation_time + other.activation_time;
+        self.solver_time       = self.solver_time + other.solver_time;
+        self.total_time        = self.total_time + other.total_time;
+    }
+}
@@ -0,0 +1,46 @@
+use alga::general::Real;
+use ncollide::bounding_volume::AABB;
+use math::Point;
+
+/// A snapshot of the content of the physics world, as returned by `World::summary`.
+#[derive(Clone, Debug)]
+pub struct SceneSummary<N: Real> {
+    /// The number of rigid bodies that cannot move.
+    pub num_static_bodies:   usize,
+    /// The number of movable rigid bodies currently active.
+    pub num_active_bodies:   usize,
+    /// The number of movable rigid bodies currently sleeping.
+    pub num_sleeping_bodies: usize,
+    /// The number of sensors, including trigger volumes.
+    pub num_sensors:         usize,
+    /// The sum of the masses of the movable rigid bodies.
+    pub total_mass:          N,
+    /// The bounding box of the rigid bodies and sensors, excluding those with an infinite
+    /// bounding box, e.g., planes. `None` if there is no such object.
+    pub bounds:              Option<AABB<Point<N>>>,
+    /// The number of ball-in-socket joints.
+    pub num_ball_in_sockets: usize,
+    /// The number of fixed joints.
+    pub num_fixed_joints:    usize,
+    /// The number of hinge joints.
+    pub num_hinges:          usize,
+    /// The number of prismatic joints.
+    pub num_prismatics:      usize,
+    /// The number of springs.
+    pub num_springs:         usize,
+    /// The number of distance joints.
+    pub num_distances:       usize,
+    /// The number of standalone angular motors.
+    pub num_angular_motors:  usize,
+    /// The number of rack-and-pinions.
+    pub num_racks:           usize,
+    /// The number of pulleys.
+    pub num_pulleys:         usize,
+    /// The number of gears.
+    pub num_gears:           usize,
+    /// The number of universal joints.
+    #[cfg(feature = "dim3")]
+    pub num_universals:      usize,
+    /// The number of bodies of the largest island built during the last step.
+    pub largest_island:      usize
+}
@@ -0,0 +1,25 @@
+use alga::general::Real;
+
+/// The factor limiting a time step suggested by `World::suggest_timestep`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimestepLimit {
+    /// The maximum time step is safe.
+    MaxTimestep,
+    /// The speed of the fastest body, relative to the thickness of the thinnest one.
+    Speed,
+    /// The velocity errors left by the solver during the last step.
+    Convergence,
+    /// The maximum number of substeps: the suggested time step is longer than the safe one.
+    MaxSubsteps
+}
+
+/// A time step suggested by `World::suggest_timestep`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TimestepSuggestion<N: Real> {
+    /// The suggested time step.
+    pub dt:           N,
+    /// The number of steps of length `dt` covering the maximum time step.
+    pub num_substeps: usize,
+    /// The factor limiting `dt`.
+    pub limit:        TimestepLimit
+}
@@ -0,0 +1,124 @@
+use alga::general::Real;
+use na;
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use object::RigidBodyHandle;
+use math::Isometry;
+
+/// A rigid body which moved noticeably since its last notification.
+pub struct TransformChange<N: Real> {
+    /// The rigid body.
+    pub body:     RigidBodyHandle<N>,
+    /// The current position of the rigid body.
+    pub position: Isometry<N>,
+    /// The motion of the rigid body since its last notification: `position` is equal to `delta`
+    /// multiplied by the position notified last.
+    pub delta:    Isometry<N>
+}
+
+/// Trait implemented by the receivers of rigid body transform changes.
+pub trait TransformChangeHandler<N: Real> {
+    /// Called at the end of each step with the rigid bodies that moved beyond the thresholds of
+    /// the monitor. Nothing is called if no body moved enough.
+    fn handle_transform_changes(&mut self, changes: &[TransformChange<N>]);
+}
+
+impl<N: Real, F: FnMut(&[TransformChange<N>])> TransformChangeHandler<N> for F {
+    #[inline]
+    fn handle_transform_changes(&mut self, changes: &[TransformChange<N>]) {
+        self(changes)
+    }
+}
+
+/// Notifies the application of the rigid bodies which moved noticeably during each step.
+///
+/// This lets an application syncing many scene-graph nodes from the simulation, e.g., meshes,
+/// lights, or cameras attached to bodies, update only the bodies that actually moved. The motion
+/// of a body is measured since its last notification, so that slow motions below the thresholds
+/// accumulate until they are notified. The position of a body is recorded, without
+/// notification, at the first step it is seen by the monitor.
+pub struct TransformChangeMonitor<N: Real> {
+    linear_threshold:  N,
+    angular_threshold: N,
+    notified:          HashMap<usize, Isometry<N>, UintTWHash>,
+    handler:           Box<TransformChangeHandler<N>>
+}
+
+impl<N: Real> TransformChangeMonitor<N> {
+    /// Creates a monitor calling `handler` with the bodies which translated by more than
+    /// `linear_threshold` or rotated by more than `angular_threshold` radians since their last
+    /// notification.
+    pub fn new<H>(linear_threshold: N, angular_threshold: N, handler: H) -> TransformChangeMonitor<N>
+        where H: TransformChangeHandler<N> + 'static {
+        TransformChangeMonitor {
+            linear_threshold:  linear_threshold,
+            angular_threshold: angular_threshold,
+            notified:          HashMap::new(UintTWHash::new()),
+            handler:           Box::new(handler)
+        }
+    }
+
+    /// The translation a body must exceed to be notified.
+    #[inline]
+    pub fn linear_threshold(&self) -> N {
+        self.linear_threshold
+    }
+
+    /// Sets the translation a body must exceed to be notified.
+    #[inline]
+    pub fn set_linear_threshold(&mut self, threshold: N) {
+        self.linear_threshold = threshold
+    }
+
+    /// The rotation angle, in radians, a body must exceed to be notified.
+    #[inline]
+    pub fn angular_threshold(&self) -> N {
+        self.angular_threshold
+    }
+
+    /// Sets the rotation angle, in radians, a body must exceed to be notified.
+    #[inline]
+    pub fn set_angular_threshold(&mut self, threshold: N) {
+        self.angular_threshold = threshold
+    }
+
+    /// The motion of the body identified by `uid` since its last notification, if it exceeds the
+    /// thresholds. The body is then considered notified at `position`.
+    #[doc(hidden)]
+    pub fn moved(&mut self, uid: usize, position: &Isometry<N>) -> Option<Isometry<N>> {
+        let delta = match self.notified.find(&uid) {
+            Some(last) => {
+                let translation = na::norm(&(position.translation.vector - last.translation.vector));
+                let rotation    = na::norm(&(position.rotation * last.rotation.inverse()).scaled_axis());
+
+                if translation <= self.linear_threshold && rotation <= self.angular_threshold {
+                    return None;
+                }
+
+                *position * last.inverse()
+            },
+            None => {
+                let _ = self.notified.insert(uid, *position);
+                return None;
+            }
+        };
+
+        let _ = self.notified.insert(uid, *position);
+
+        Some(delta)
+    }
+
+    /// Forgets the last notified position of the body identified by `uid`.
+    #[doc(hidden)]
+    #[inline]
+    pub fn forget(&mut self, uid: usize) {
+        let _ = self.notified.remove(&uid);
+    }
+
+    /// Sends the changes to the handler.
+    #[doc(hidden)]
+    #[inline]
+    pub fn emit(&mut self, changes: &[TransformChange<N>]) {
+        self.handler.handle_transform_changes(changes)
+    }
+}
@@ -1,26 +1,68 @@
 use std::slice::Iter;
 use std::iter::Map;
+use std::mem;
+use std::usize;
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::time::Instant;
 
 use alga::general::Real;
 use na;
-use ncollide::bounding_volume::AABB;
+use ncollide::bounding_volume::{self, AABB, BoundingVolume};
 use ncollide::utils::data::hash_map::{HashMap, Entry};
 use ncollide::utils::data::hash::UintTWHash;
 use ncollide::broad_phase::{DBVTBroadPhase, BroadPhasePairFilter};
 use ncollide::narrow_phase::{ContactHandler, ProximityHandler, DefaultNarrowPhase,
-                             DefaultContactDispatcher, DefaultProximityDispatcher,
-                             ContactAlgorithm};
+                             ContactDispatcher, ProximityDispatcher, ContactAlgorithm, ContactGenerator};
 use ncollide::world::{CollisionWorld, CollisionObject, GeometricQueryType};
-use integration::{Integrator, BodySmpEulerIntegrator, BodyForceGenerator,
-                  TranslationalCCDMotionClamping};
-use detection::ActivationManager;
-use detection::constraint::Constraint;
-use detection::joint::{JointManager, BallInSocket, Fixed};
-use resolution::{Solver, AccumulatedImpulseSolver, CorrectionMode};
-use object::{WorldObject, RigidBody, RigidBodyHandle, Sensor, SensorHandle, SensorProximityCollector};
-use math::{Point, Vector, Isometry};
+use ncollide::query::{self, Contact};
+use ncollide::shape::{Shape, ShapeHandle, Plane};
+use integration::{Integrator, CloneableIntegrator, BodySmpEulerIntegrator, BodyForceGenerator, ForceGenerator,
+                  CloneableForceGenerator, GravityZone, TranslationalCCDMotionClamping};
+use detection::{ActivationManager, IslandStatistics, IslandBridge, ContactJitter, ContactNormalSmoothing,
+                PipelineStatistics, CountingNarrowPhase, AabbGrowthMonitor, AabbGrowthEvent,
+                OneWayContactFilter, ContactCooldown, TriggerVolumes, TriggerVolumeCollector,
+                TriggerVolumeId, TriggerHandler, ExtensibleContactDispatcher, ExtensibleProximityDispatcher,
+                ContactDispatchers, ProximityDispatchers, ContactModifier, CollisionEvent,
+                StampedCollisionEvent, CollisionEventQueue, CollisionEventCollector, Cutters, CutHandler};
+use debug::{DebugChannel, DebugPrimitive, DebugColor};
+#[cfg(feature = "tracing")]
+use trace::{Stage, Span, TraceSink};
+use detection;
+use detection::constraint::{Constraint, ContactFlags};
+use detection::joint::{JointManager, Joints, Joint, BallInSocket, Fixed, Hinge, Prismatic, Spring, Distance,
+                       AngularMotor, RackAndPinion, Pulley, Gear, BrokenJoint,
+                       JointBreakHandler};
+#[cfg(feature = "dim3")]
+use detection::joint::Universal;
+use resolution::{Solver, AccumulatedImpulseSolver, CorrectionMode, SolverStatistics};
+use object::{WorldObject, RigidBody, RigidBodyHandle, RigidBodyDynamics, Sensor, SensorHandle,
+             SensorProximityCollector};
+use math::{Point, Vector, Orientation, Isometry, Translation};
+use world::queries::{self, QueryTree};
+use world::summary::SceneSummary;
+use world::step_report::StepReport;
+use world::timestep_suggestion::{TimestepSuggestion, TimestepLimit};
+use world::world_save::{WorldSave, BodySave};
+use world::transform_change_monitor::{TransformChangeMonitor, TransformChange};
+use world::contact_forces::ContactForces;
+use world::joint_reactions::{JointReactions, JointReaction};
+
+// The maximum number of contacts kept per pair of bodies by the manifold reduction.
+const MAX_MANIFOLD_CONTACTS: usize = 4;
+
+/// A stage of the world pipeline that can be disabled at runtime, e.g., for debugging.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum WorldStage {
+    /// The continuous collision detection of the CCD-enabled rigid bodies.
+    ContinuousCollisionDetection,
+    /// The deactivation of the islands with a low energy.
+    Sleeping,
+    /// The resolution of the contacts and joints, including the substeps.
+    Solver,
+    /// The generation of the frames sent to the debug channel.
+    Debug
+}
 
 /// The default broad phase.
 pub type WorldBroadPhase<N> = DBVTBroadPhase<Point<N>, WorldObject<N>, AABB<Point<N>>>;
@@ -43,20 +85,84 @@ pub type WorldCollisionObject<N> = CollisionObject<Point<N>, Isometry<N>, WorldO
 /// This is the main structure of the physics engine.
 pub struct World<N: Real> {
     cworld:       RigidBodyCollisionWorld<N>,
+    // The bounding volume tree of the world queries, built again after a change of `cworld`.
+    query_tree:   RefCell<Option<QueryTree<N>>>,
     rigid_bodies: HashMap<usize, RigidBodyHandle<N>, UintTWHash>,
     sensors:      HashMap<usize, SensorHandle<N>, UintTWHash>,
     forces:       BodyForceGenerator<N>,
+    generators:   Vec<(String, Box<CloneableForceGenerator<N>>)>,
     integrator:   BodySmpEulerIntegrator,
+    // The integrator used instead of `integrator`, if any.
+    custom_integrator: Option<Box<CloneableIntegrator<N>>>,
     sleep:        Rc<RefCell<ActivationManager<N>>>, // FIXME: avoid sharing (needed for the contact signal handler)
     ccd:          TranslationalCCDMotionClamping<N>,
     joints:       JointManager<N>,
+    non_colliding: Rc<RefCell<Vec<(usize, usize)>>>, // Shared with its broad phase pair filter.
     solver:       AccumulatedImpulseSolver<N>,
-    prediction:   N
+    // Solver of the internal substeps, with its own impulse cache.
+    sub_solver:   AccumulatedImpulseSolver<N>,
+    num_substeps: usize,
+    max_substep_dt: Option<N>,
+    prediction:   N,
+    max_lin_vel:  Option<N>,
+    max_ang_vel:  Option<N>,
+    // Velocity bounds and additional contact prediction registered for speculative rigid bodies.
+    speculative:  HashMap<usize, SpeculativeMargin<N>, UintTWHash>,
+    debug:        Option<DebugChannel<N>>,
+    jitter:       Option<ContactJitter<N>>,
+    smoothing:    Option<ContactNormalSmoothing<N>>,
+    // Smoother of the contacts of the substepped bodies, from one substep to the next.
+    sub_smoothing: Option<ContactNormalSmoothing<N>>,
+    aabb_growth:  Option<AabbGrowthMonitor<N>>,
+    transform_changes: Option<TransformChangeMonitor<N>>,
+    one_way:      OneWayContactFilter,
+    cutters:      Cutters<N>,
+    contact_forces: ContactForces<N>,
+    joint_reactions: JointReactions<N>,
+    triggers:     Rc<RefCell<TriggerVolumes<N>>>, // Shared with their proximity handler.
+    events:       Rc<RefCell<CollisionEventQueue<N>>>, // Shared with its contact and proximity handler.
+    // User-defined dispatchers, shared with the narrow phase.
+    contact_dispatchers:   ContactDispatchers<N>,
+    proximity_dispatchers: ProximityDispatchers<N>,
+    modifiers:    Vec<(String, Box<ContactModifier<N>>)>,
+    break_handlers: Vec<(String, Box<JointBreakHandler<N>>)>,
+    manifold_reduction: bool,
+    disabled_stages:    Vec<WorldStage>,
+    // The simulated time, shared with the contact handlers with a cooldown.
+    time:         Rc<Cell<N>>,
+    // Counters accumulated by the narrow phase since the last step, and those of the last step.
+    counters:     Rc<RefCell<PipelineStatistics>>,
+    stats:        PipelineStatistics,
+    num_steps:    usize,
+    // The number of rigid bodies added so far, giving their rank in the order of addition.
+    num_added_bodies: usize,
+    #[cfg(feature = "tracing")]
+    tracer:       Option<Box<TraceSink>>
+}
+
+// The speculative margin registered for a rigid body, and the velocity bounds it was computed from.
+#[derive(Copy, Clone)]
+struct SpeculativeMargin<N: Real> {
+    max_lin_vel: N,
+    max_ang_vel: N,
+    margin:      N
 }
 
+// The time a traced stage started at, if tracing is enabled.
+#[cfg(feature = "tracing")]
+#[derive(Copy, Clone)]
+struct TraceMark(Option<Instant>);
+
 impl<N: Real> World<N> {
     /// Creates a new physics world.
     pub fn new() -> World<N> {
+        World::new_with_dispatchers(ContactDispatchers::new(), ProximityDispatchers::new())
+    }
+
+    // Creates a new physics world querying the given user-defined dispatchers.
+    fn new_with_dispatchers(contact_dispatchers:   ContactDispatchers<N>,
+                            proximity_dispatchers: ProximityDispatchers<N>)
+                            -> World<N> {
         /*
          * Setup the physics world
          */
@@ -73,11 +179,15 @@ impl<N: Real> World<N> {
         // Collision world
         let mut cworld = CollisionWorld::new(prediction, false);
 
-        // Custom narrow phase.
-        let disp = DefaultContactDispatcher::new();
-        let prox = DefaultProximityDispatcher::new();
+        // Custom narrow phase, extensible with user-defined dispatchers.
+        let disp = ExtensibleContactDispatcher::new(contact_dispatchers.clone());
+        let prox = ExtensibleProximityDispatcher::new(proximity_dispatchers.clone());
         let nf   = DefaultNarrowPhase::new(Box::new(disp), Box::new(prox));
-        let _    = cworld.set_narrow_phase(Box::new(nf));
+
+        // Count the pairs handled by the narrow phase.
+        let counters = Rc::new(RefCell::new(PipelineStatistics::new()));
+        let nf       = CountingNarrowPhase::new(Box::new(nf), counters.clone());
+        let _        = cworld.set_narrow_phase(Box::new(nf));
 
         // CCD handler
         let ccd = TranslationalCCDMotionClamping::new();
@@ -95,51 +205,207 @@ impl<N: Real> World<N> {
         let filter_name = "__nphysics_internal_SensorsNotCollidingTheirParentPairFilter";
         cworld.register_broad_phase_pair_filter(filter_name, filter);
 
+        // Setup the broad phase pair filter that will prevent bodies from colliding with those
+        // they are attached to by a joint, as their contacts are usually fighting the joint.
+        let non_colliding = Rc::new(RefCell::new(Vec::new()));
+        let filter        = JointedBodiesPairFilter { pairs: non_colliding.clone() };
+        cworld.register_broad_phase_pair_filter(JOINTED_BODIES_PAIR_FILTER, filter);
+
         // Setup the proximity collector for sensors.
         let collector      = SensorProximityCollector;
         let collector_name = "__nphysics_internal_SensorProximityCollector";
         cworld.register_proximity_handler(collector_name, collector);
 
+        // Setup the proximity collector for trigger volumes.
+        let triggers       = Rc::new(RefCell::new(TriggerVolumes::new()));
+        let collector      = TriggerVolumeCollector::new(triggers.clone());
+        let collector_name = "__nphysics_internal_TriggerVolumeCollector";
+        cworld.register_proximity_handler(collector_name, collector);
+
+        // Setup the collectors of the collision event queue.
+        let events         = Rc::new(RefCell::new(CollisionEventQueue::new()));
+        let collector_name = "__nphysics_internal_CollisionEventCollector";
+        cworld.register_contact_handler(collector_name, CollisionEventCollector::new(events.clone()));
+        cworld.register_proximity_handler(collector_name, CollisionEventCollector::new(events.clone()));
+
         // Joints
         let joints = JointManager::new();
 
         /*
          * For constraints resolution
          */
-        let solver = AccumulatedImpulseSolver::new(
-            na::convert(0.1f64),
-            CorrectionMode::VelocityAndPosition(na::convert(0.2f64), na::convert(0.2f64), na::convert(0.08f64)),
-            na::convert(0.4f64),
-            na::convert(1.0f64),
-            10,
-            10);
+        let solver     = default_solver();
+        let sub_solver = default_solver();
 
         World {
             cworld:       cworld,
+            query_tree:   RefCell::new(None),
             rigid_bodies: HashMap::new(UintTWHash::new()),
             sensors:      HashMap::new(UintTWHash::new()),
             forces:       forces,
+            generators:   Vec::new(),
             integrator:   integrator,
+            custom_integrator: None,
             sleep:        sleep,
             ccd:          ccd,
             joints:       joints,
+            non_colliding: non_colliding,
             solver:       solver,
-            prediction:   prediction
+            sub_solver:   sub_solver,
+            num_substeps: 1,
+            max_substep_dt: None,
+            prediction:   prediction,
+            max_lin_vel:  None,
+            max_ang_vel:  None,
+            speculative:  HashMap::new(UintTWHash::new()),
+            debug:        None,
+            jitter:       None,
+            smoothing:    None,
+            sub_smoothing: None,
+            aabb_growth:  None,
+            transform_changes: None,
+            one_way:      OneWayContactFilter::new(),
+            cutters:      Cutters::new(),
+            contact_forces: ContactForces::new(),
+            joint_reactions: JointReactions::new(),
+            triggers:     triggers,
+            events:       events,
+            contact_dispatchers:   contact_dispatchers,
+            proximity_dispatchers: proximity_dispatchers,
+            modifiers:    Vec::new(),
+            break_handlers: Vec::new(),
+            manifold_reduction: false,
+            disabled_stages:    Vec::new(),
+            time:         Rc::new(Cell::new(na::zero())),
+            counters:     counters,
+            stats:        PipelineStatistics::new(),
+            num_steps:    0,
+            num_added_bodies: 0,
+            #[cfg(feature = "tracing")]
+            tracer:       None
         }
     }
 
     /// Updates the physics world.
-    pub fn step(&mut self, dt: N) {
+    ///
+    /// If `dt` exceeds the maximum substep length, this performs several shorter steps instead.
+    /// Returns a summary of the work done.
+    pub fn step(&mut self, dt: N) -> StepReport {
+        // The kinematic bodies move, and the accumulated forces apply, over the whole step.
+        self.update_kinematic_velocities(dt);
+
+        let report = match self.max_substep_dt {
+            Some(max_dt) if dt > max_dt => {
+                let num_steps: f64 = na::try_convert((dt / max_dt).ceil()).unwrap_or(1.0);
+                let num_steps      = if num_steps < 1.0 { 1 } else { num_steps as usize };
+                let step_dt        = dt / na::convert(num_steps as f64);
+                let mut report     = StepReport::new();
+
+                for _ in 0 .. num_steps {
+                    report.append(self.step_once(step_dt))
+                }
+
+                report
+            },
+            _ => self.step_once(dt)
+        };
+
+        for e in self.rigid_bodies.elements().iter() {
+            e.value.borrow_mut().clear_accumulated_forces();
+        }
+
+        report
+    }
+
+    // Derives the velocities of the kinematic bodies from their motion since the last step, and
+    // wakes up the bodies they push.
+    fn update_kinematic_velocities(&mut self, dt: N) {
+        let mut kinematic = Vec::new();
+
         for e in self.rigid_bodies.elements_mut().iter_mut() {
             let mut rb = e.value.borrow_mut();
 
-            if rb.is_active() {
-                self.forces.update(dt.clone(), &mut *rb);
-                self.integrator.update(dt.clone(), &mut *rb);
-                self.cworld.deferred_set_position(WorldObject::rigid_body_uid(&e.value), rb.position().clone());
+            if rb.is_kinematic() && rb.update_kinematic_velocities(dt) {
+                kinematic.push(e.key);
+            }
+        }
+
+        if !kinematic.is_empty() {
+            self.wake_up_pushed_bodies(&kinematic[..]);
+        }
+    }
+
+    // Performs a single step of length `dt`.
+    fn step_once(&mut self, dt: N) -> StepReport {
+        let mut report = StepReport::new();
+        let step_begin = Instant::now();
+
+        report.num_steps = 1;
+
+        self.time.set(self.time.get() + dt);
+        self.events.borrow_mut().set_step(self.num_steps);
+
+        #[cfg(feature = "tracing")]
+        let step_mark = self.trace_mark();
+        #[cfg(feature = "tracing")]
+        let mut mark = step_mark;
+
+        for e in self.rigid_bodies.elements().iter() {
+            self.ccd.track(&e.value);
+        }
+
+        // The substepped bodies perform all their substeps before the rest of the world moves.
+        let substepping = self.num_substeps > 1;
+
+        if substepping {
+            let sub_dt = dt / na::convert(self.num_substeps as f64);
+            self.perform_substeps(sub_dt);
+        }
+
+        let mut non_finite = Vec::new();
+
+        for e in self.rigid_bodies.elements_mut().iter_mut() {
+            let mut rb = e.value.borrow_mut();
+
+            if rb.is_kinematic() {
+                self.cworld.deferred_set_position(e.key, rb.position().clone());
+            }
+            else if rb.is_active() {
+                report.bodies_integrated = report.bodies_integrated + 1;
+
+                if !substepping || !rb.substepping_enabled() {
+                    let integrator: &mut Integrator<N, RigidBody<N>> = match self.custom_integrator {
+                        Some(ref mut integrator) => integrator.as_integrator(),
+                        None                     => &mut self.integrator
+                    };
+
+                    integrate(integrator, &mut self.forces, &mut self.generators[..], dt.clone(), &mut *rb);
+                }
+
+                // Leave the monitor a chance to fix non-finite positions before the collision
+                // world sees them.
+                if self.aabb_growth.is_some() && !is_finite_position(rb.position()) {
+                    non_finite.push(e.value.clone());
+                }
+                else {
+                    self.cworld.deferred_set_position(WorldObject::rigid_body_uid(&e.value), rb.position().clone());
+                }
             }
         }
 
+        let collision_begin     = Instant::now();
+        report.integration_time = collision_begin - step_begin;
+
+        #[cfg(feature = "tracing")]
+        { mark = self.trace(Stage::Integration, mark, None); }
+
+        self.update_contact_predictions(dt.clone());
+
+        // The cutters are usually moved by the application, even if they are static.
+        for rb in self.cutters.bodies().iter() {
+            self.cworld.deferred_set_position(WorldObject::rigid_body_uid(rb), rb.borrow().position().clone());
+        }
+
         for e in self.sensors.elements_mut().iter_mut() {
             let mut sensor = e.value.borrow_mut();
 
@@ -156,230 +422,2139 @@ impl<N: Real> World<N> {
             }
         }
 
+        if self.aabb_growth.is_some() {
+            self.check_aabb_growth(non_finite);
+        }
+
+        self.update_non_colliding_pairs();
         self.cworld.perform_position_update();
+        *self.query_tree.get_mut() = None;
+
+        #[cfg(feature = "tracing")]
+        { mark = self.trace(Stage::PositionUpdate, mark, None); }
+
         self.cworld.perform_broad_phase();
-        if !self.ccd.update(&mut self.cworld) {
+
+        #[cfg(feature = "tracing")]
+        { mark = self.trace(Stage::BroadPhase, mark, None); }
+
+        let clamped = self.stage_enabled(WorldStage::ContinuousCollisionDetection) &&
+                      self.ccd.update(&mut self.cworld);
+
+        if clamped && self.events.borrow().enabled() {
+            let mut events = self.events.borrow_mut();
+
+            for &(ref rb, ref obstacle, toi) in self.ccd.hits().iter() {
+                events.push(CollisionEvent::CcdHit(rb.clone(), obstacle.clone(), toi))
+            }
+        }
+
+        if !clamped {
             self.cworld.perform_narrow_phase();
         }
 
+        #[cfg(feature = "tracing")]
+        { mark = self.trace(Stage::NarrowPhase, mark, None); }
+
+        let activation_begin  = Instant::now();
+        report.collision_time = activation_begin - collision_begin;
+
+        let was_active = self.activation_snapshot();
+
         self.joints.update(&mut *self.sleep.borrow_mut());
-        self.sleep.borrow_mut().update(&mut self.cworld, &self.joints, &self.rigid_bodies);
+
+        if substepping {
+            self.sleep.borrow_mut().update_substepped(&mut self.cworld, &self.joints, &self.rigid_bodies, false);
+        }
+        else {
+            self.sleep.borrow_mut().update(&mut self.cworld, &self.joints, &self.rigid_bodies);
+        }
+
+        self.push_activation_events(was_active);
+
+        report.islands = self.sleep.borrow().island_statistics().num_islands;
+
+        let solver_begin       = Instant::now();
+        report.activation_time = solver_begin - activation_begin;
+
+        #[cfg(feature = "tracing")]
+        { mark = self.trace(Stage::Activation, mark, None); }
 
         // XXX: use `self.collector` instead to avoid allocation.
         let mut collector = Vec::new();
+        let mut flags     = Vec::new();
+
+        let mut contacts = Vec::new();
 
-        for (b1, b2, c) in self.cworld.contacts() {
+        for (b1, b2, generator) in self.cworld.contact_pairs() {
             if let (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) = (&b1.data, &b2.data) {
+                let (uid1, uid2) = (WorldObject::rigid_body_uid(rb1), WorldObject::rigid_body_uid(rb2));
+
+                // The contacts with cutters are reported instead of being solved, even for
+                // sleeping bodies.
+                if !self.cutters.is_empty() && (self.cutters.is_cutter(uid1) || self.cutters.is_cutter(uid2)) {
+                    contacts.clear();
+                    generator.contacts(&mut contacts);
+                    let _ = self.cutters.record(uid1, rb1, uid2, rb2, &contacts[..]);
+                    continue;
+                }
+
+                // The contacts of the substepped bodies were solved by the substeps.
+                if substepping && (is_substepped(&*rb1.borrow()) || is_substepped(&*rb2.borrow())) {
+                    continue;
+                }
+
                 if rb1.borrow().is_active() || rb2.borrow().is_active() {
-                    let m1 = rb1.borrow().margin();
-                    let m2 = rb2.borrow().margin();
+                    contacts.clear();
+                    generator.contacts(&mut contacts);
 
-                    let mut c = c.clone();
-                    c.depth = c.depth + m1 + m2;
+                    if self.manifold_reduction {
+                        detection::reduce_contact_manifold(&mut contacts, MAX_MANIFOLD_CONTACTS);
+                    }
+
+                    for c in contacts.iter() {
+                        if !self.one_way.accept(uid1, &*rb1.borrow(), uid2, &*rb2.borrow(), c) {
+                            continue;
+                        }
+
+                        let m1 = rb1.borrow().margin();
+                        let m2 = rb2.borrow().margin();
+
+                        let mut c = c.clone();
+                        c.depth = c.depth + m1 + m2;
+
+                        if let Some(ref mut smoothing) = self.smoothing {
+                            smoothing.smooth(uid1, uid2, &mut c);
+                        }
+
+                        if let Some(ref mut jitter) = self.jitter {
+                            jitter.perturb(&mut c);
+                        }
 
-                    collector.push(Constraint::RBRB(rb1.clone(), rb2.clone(), c));
+                        flags.push(modify_contact(&mut self.modifiers[..], &*rb1.borrow(), &*rb2.borrow(), &mut c));
+                        collector.push(Constraint::RBRB(rb1.clone(), rb2.clone(), c));
+                    }
                 }
             }
         }
 
-        self.joints.constraints(&mut collector);
+        if let Some(ref mut smoothing) = self.smoothing {
+            smoothing.end_frame();
+        }
 
-        self.solver.solve(dt, &collector[..]);
+        self.one_way.end_frame();
 
-        collector.clear();
-    }
+        sort_contacts(&mut collector, &mut flags);
+        self.joints.constraints(&mut collector);
 
-    /// Adds a rigid body to the physics world.
-    pub fn add_rigid_body(&mut self, rb: RigidBody<N>) -> RigidBodyHandle<N> {
-        let position = rb.position().clone();
-        let shape = rb.shape().clone();
-        let groups = rb.collision_groups().as_collision_groups().clone();
-        let collision_object_prediction = rb.margin() + self.prediction / na::convert(2.0f64);
-        let handle = Rc::new(RefCell::new(rb));
-        let uid = WorldObject::rigid_body_uid(&handle);
+        if substepping {
+            collector.retain(|c| !is_joint_substepped(c));
+        }
 
-        let _ = self.rigid_bodies.insert(uid, handle.clone());
-        self.cworld.deferred_add(uid, position, shape, groups,
-                                 GeometricQueryType::Contacts(collision_object_prediction),
-                                 WorldObject::RigidBody(handle.clone()));
-        self.cworld.perform_additions_removals_and_broad_phase();
+        #[cfg(feature = "tracing")]
+        { mark = self.trace(Stage::ConstraintCollection, mark, Some(collector.len())); }
 
-        handle
-    }
+        if self.stage_enabled(WorldStage::Solver) {
+            #[cfg(feature = "tracing")]
+            {
+                let timing = self.tracer.is_some();
+                self.solver.set_island_timing(timing);
+            }
 
-    /// Adds a sensor to the physics world.
-    pub fn add_sensor(&mut self, sensor: Sensor<N>) -> SensorHandle<N> {
-        let position = sensor.position().clone();
-        let shape    = sensor.shape().clone();
-        let groups   = sensor.collision_groups().as_collision_groups().clone();
-        let margin   = sensor.margin();
-        let handle   = Rc::new(RefCell::new(sensor));
-        let uid      = &*handle as *const RefCell<Sensor<N>> as usize;
+            self.solver.set_contact_flags(flags);
+            self.solver.solve(dt, &collector[..]);
 
-        let _ = self.sensors.insert(uid, handle.clone());
-        self.cworld.deferred_add(uid, position, shape, groups,
-                                 GeometricQueryType::Proximity(margin),
-                                 WorldObject::Sensor(handle.clone()));
-        self.cworld.perform_additions_removals_and_broad_phase();
+            #[cfg(feature = "tracing")]
+            { self.trace_islands(); }
 
-        handle
-    }
+            self.contact_forces.record(&collector[..], self.solver.contact_impulses());
+            self.joint_reactions.record(&collector[..], self.solver.joint_body_impulses());
+            self.break_joints(dt, &collector[..], false);
 
-    /// Remove a rigid body from the physics world.
-    pub fn remove_rigid_body(&mut self, rb: &RigidBodyHandle<N>) {
-        let uid = WorldObject::rigid_body_uid(rb);
-        self.cworld.deferred_remove(uid);
-        self.cworld.perform_additions_removals_and_broad_phase();
-        self.joints.remove(rb, &mut *self.sleep.borrow_mut());
-        self.ccd.remove_ccd_from(rb);
-        let _ = self.rigid_bodies.remove(&uid);
-        rb.borrow_mut().delete();
-    }
+            report.solver_iterations = self.solver.statistics().iterations;
+        }
 
-    /// Remove a sensor from the physics world.
-    pub fn remove_sensor(&mut self, sensor: &SensorHandle<N>) {
-        let uid = WorldObject::sensor_uid(sensor);
-        self.cworld.deferred_remove(uid);
-        self.cworld.perform_additions_removals_and_broad_phase();
-        let _ = self.sensors.remove(&uid);
-    }
+        self.contact_forces.end_step(dt);
+        self.joint_reactions.end_step(dt, &self.joints);
 
-    // XXX: keep this reference mutable?
-    /// Gets a mutable reference to the force generator.
-    pub fn forces_generator(&mut self) -> &mut BodyForceGenerator<N> {
-        &mut self.forces
-    }
+        report.solver_time = solver_begin.elapsed();
 
-    // XXX: keep this reference mutable?
-    /// Gets a mutable reference to the position and orientation integrator.
-    pub fn integrator(&mut self) -> &mut BodySmpEulerIntegrator {
-        &mut self.integrator
-    }
+        #[cfg(feature = "tracing")]
+        { mark = self.trace(Stage::Solver, mark, Some(collector.len())); }
 
-    // XXX: keep this reference mutable?
-    /// Gets a mutable reference to the CCD manager.
-    pub fn ccd_manager(&mut self) -> &mut TranslationalCCDMotionClamping<N> {
-        &mut self.ccd
-    }
+        collector.clear();
 
-    // XXX: keep this reference mutable?
-    /// Gets a mutable reference to the joint manager.
-    pub fn joint_manager(&mut self) -> &mut JointManager<N> {
-        &mut self.joints
-    }
+        for e in self.rigid_bodies.elements().iter() {
+            let mut rb = e.value.borrow_mut();
 
-    // XXX: keep this reference mutable?
-    /// Gets a mutable reference to the constraint solver.
-    pub fn constraints_solver(&mut self) -> &mut AccumulatedImpulseSolver<N> {
-        &mut self.solver
-    }
+            if rb.is_active() {
+                rb.clamp_velocities(self.max_lin_vel, self.max_ang_vel);
+            }
+        }
 
-    /// Gets the underlying collision world.
-    pub fn collision_world(&self) -> &RigidBodyCollisionWorld<N> {
-        &self.cworld
-    }
+        // The positions are final once clamped by the continuous collision detection and
+        // corrected by the solver.
+        if self.transform_changes.is_some() {
+            self.notify_transform_changes();
+        }
 
-    /// Sets the linear acceleration afecting every dynamic rigid body.
-    pub fn set_gravity(&mut self, gravity: Vector<N>) {
-        self.forces.set_lin_acc(gravity);
-    }
+        self.update_statistics();
 
-    /*/// Sets the angular acceleration afecting every dynamic rigid body.
-    pub fn set_angular_acceleration(&mut self, accel: Orientation) {
-        self.forces.set_ang_acc(accel)
-    }*/
+        report.pairs    = self.stats.active_pairs;
+        report.contacts = self.stats.contacts;
 
-    /// Gets the linear acceleration afecting every dynamic rigid body.
-    pub fn gravity(&self) -> Vector<N> {
-        self.forces.lin_acc()
-    }
+        self.triggers.borrow_mut().dispatch();
+        self.cutters.dispatch();
 
-    /*/// Gets the angular acceleration afecting every dynamic rigid body.
-    pub fn angular_acceleration(&self) -> Orientation {
-        self.forces.ang_acc()
-    }*/
+        if self.stage_enabled(WorldStage::Debug) {
+            if let Some(mut debug) = self.debug.take() {
+                let primitives = self.debug_primitives(debug.normal_length());
+                let _ = debug.send(primitives);
+                self.debug = Some(debug);
+            }
+        }
 
-    /// Adds continuous collision detection to the given rigid body.
-    ///
-    /// Set `trigger_sensor` to `true` if the rigid body should active the sensors that would have
-    /// been missed without CCD enabled.
-    pub fn add_ccd_to(&mut self, body: &RigidBodyHandle<N>, motion_thresold: N, trigger_sensors: bool) {
-        self.ccd.add_ccd_to(body.clone(), motion_thresold, trigger_sensors)
-    }
+        #[cfg(feature = "tracing")]
+        {
+            let _ = self.trace(Stage::Debug, mark, None);
+            let _ = self.trace(Stage::Step, step_mark, None);
+        }
 
-    /// Adds a ball-in-socket joint to the world.
-    pub fn add_ball_in_socket(&mut self, joint: BallInSocket<N>) -> Rc<RefCell<BallInSocket<N>>> {
-        let res = Rc::new(RefCell::new(joint));
+        self.num_steps = self.num_steps + 1;
 
-        self.joints.add_ball_in_socket(res.clone(), &mut *self.sleep.borrow_mut());
+        report.total_time = step_begin.elapsed();
+        report
+    }
 
-        res
+    /// The number of steps performed since the creation of this world.
+    ///
+    /// This is the index of the next step, as stamped on the collision events it will emit.
+    #[inline]
+    pub fn num_steps(&self) -> usize {
+        self.num_steps
     }
 
-    /// Removes a ball-in-socket joint from the world.
-    pub fn remove_ball_in_socket(&mut self, joint: &Rc<RefCell<BallInSocket<N>>>) {
-        self.joints.remove_ball_in_socket(joint, &mut *self.sleep.borrow_mut())
+    // Wakes up the bodies in contact with the moving kinematic bodies identified by `kinematic`.
+    fn wake_up_pushed_bodies(&mut self, kinematic: &[usize]) {
+        let mut sleep = self.sleep.borrow_mut();
+
+        for (b1, b2, _) in self.cworld.contact_pairs() {
+            if let (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) = (&b1.data, &b2.data) {
+                if kinematic.contains(&WorldObject::rigid_body_uid(rb1)) {
+                    sleep.deferred_activate(rb2)
+                }
+                else if kinematic.contains(&WorldObject::rigid_body_uid(rb2)) {
+                    sleep.deferred_activate(rb1)
+                }
+            }
+        }
     }
 
-    /// Adds a fixed joint to the world.
-    pub fn add_fixed(&mut self, joint: Fixed<N>) -> Rc<RefCell<Fixed<N>>> {
-        let res = Rc::new(RefCell::new(joint));
+    // Updates the pairs of bodies filtered out by the broad phase because of their joints. The
+    // broad phase examines every pair again if they changed.
+    fn update_non_colliding_pairs(&mut self) {
+        let mut pairs = Vec::new();
+        self.joints.non_colliding_pairs(&mut pairs);
 
-        self.joints.add_fixed(res.clone(), &mut *self.sleep.borrow_mut());
+        if pairs != *self.non_colliding.borrow() {
+            *self.non_colliding.borrow_mut() = pairs;
 
-        res
+            let filter = JointedBodiesPairFilter { pairs: self.non_colliding.clone() };
+            self.cworld.unregister_broad_phase_pair_filter(JOINTED_BODIES_PAIR_FILTER);
+            self.cworld.register_broad_phase_pair_filter(JOINTED_BODIES_PAIR_FILTER, filter);
+        }
     }
 
-    /// Removes a fixed joint from the world.
-    pub fn remove_fixed(&mut self, joint: &Rc<RefCell<Fixed<N>>>) {
-        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    // The activation status of the dynamic bodies, if the collision event queue is enabled.
+    fn activation_snapshot(&self) -> Vec<(RigidBodyHandle<N>, bool)> {
+        if !self.events.borrow().enabled() {
+            return Vec::new();
+        }
+
+        self.rigid_bodies.elements().iter().filter_map(|e| {
+            let rb = e.value.borrow();
+
+            if rb.can_move() { Some((e.value.clone(), rb.is_active())) } else { None }
+        }).collect()
     }
 
-    /// Collects every constraincts detected since the last update.
-    pub fn constraints(&mut self, out: &mut Vec<Constraint<N>>) {
-        // FIXME: ugly.
-        for (b1, b2, c) in self.cworld.contacts() {
-            if let (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) = (&b1.data, &b2.data) {
-                let m1 = rb1.borrow().margin();
-                let m2 = rb2.borrow().margin();
+    // Pushes an event for each body of `was_active` which activation status changed since.
+    fn push_activation_events(&self, was_active: Vec<(RigidBodyHandle<N>, bool)>) {
+        let mut events = self.events.borrow_mut();
 
-                let mut c = c.clone();
-                c.depth = c.depth + m1 + m2;
+        for (rb, was_active) in was_active.into_iter() {
+            let is_active = rb.borrow().is_active();
 
-                out.push(Constraint::RBRB(rb1.clone(), rb2.clone(), c));
+            if is_active && !was_active {
+                events.push(CollisionEvent::BodyActivated(rb))
+            }
+            else if !is_active && was_active {
+                events.push(CollisionEvent::BodyDeactivated(rb))
             }
         }
-
-        self.joints.constraints(out);
     }
 
-    /// An iterator visiting all rigid bodies on this world.
-    pub fn rigid_bodies(&self) -> RigidBodies<N> {
-        fn extract_value<N: Real>(e: &Entry<usize, RigidBodyHandle<N>>) -> &RigidBodyHandle<N> {
-            &e.value
+    // Simulates the substepped bodies during all their substeps.
+    //
+    // The other bodies are not moved, but the impulses of their contacts and joints with the
+    // substepped bodies are applied to their velocity, which is then integrated at the base rate.
+    // Those contacts and joints are thus solved by the substeps only.
+    fn perform_substeps(&mut self, sub_dt: N) {
+        let mut collector = Vec::new();
+
+        if self.sub_smoothing.is_none() {
+            self.sub_smoothing = self.smoothing.as_ref().map(|s| {
+                ContactNormalSmoothing::new(s.blend(), s.max_angle(), s.max_distance())
+            });
         }
 
-        let extract_value_fn: fn(_) -> _ = extract_value;
-        self.rigid_bodies.elements().iter().map(extract_value_fn)
-    }
+        for _ in 0 .. self.num_substeps {
+            let mut any_substepped = false;
 
-    /// An iterator visiting all sensors on this world.
-    pub fn sensors(&self) -> Sensors<N> {
-        fn extract_value<N: Real>(e: &Entry<usize, SensorHandle<N>>) -> &SensorHandle<N> {
-            &e.value
-        }
+            for e in self.rigid_bodies.elements_mut().iter_mut() {
+                let mut rb = e.value.borrow_mut();
 
-        let extract_value_fn: fn(_) -> _ = extract_value;
-        self.sensors.elements().iter().map(extract_value_fn)
-    }
+                if is_substepped(&*rb) {
+                    let integrator: &mut Integrator<N, RigidBody<N>> = match self.custom_integrator {
+                        Some(ref mut integrator) => integrator.as_integrator(),
+                        None                     => &mut self.integrator
+                    };
 
-    /// Adds a filter that tells if a potential collision pair should be ignored or not.
-    ///
-    /// The proximity filter returns `false` for a given pair of collision objects if they should
-    /// be ignored by the narrow phase. Keep in mind that modifying the proximity filter will have
-    /// a non-trivial overhead during the next update as it will force re-detection of all
-    /// collision pairs.
-    pub fn register_broad_phase_pair_filter<F>(&mut self, name: &str, filter: F)
+                    integrate(integrator, &mut self.forces, &mut self.generators[..], sub_dt.clone(), &mut *rb);
+
+                    // Non-finite positions are handled by the monitor at the end of the step.
+                    if self.aabb_growth.is_none() || is_finite_position(rb.position()) {
+                        self.cworld.deferred_set_position(WorldObject::rigid_body_uid(&e.value), rb.position().clone());
+                    }
+
+                    any_substepped = true;
+                }
+            }
+
+            if !any_substepped {
+                return;
+            }
+
+            self.cworld.perform_position_update();
+            *self.query_tree.get_mut() = None;
+            self.cworld.perform_broad_phase();
+            self.cworld.perform_narrow_phase();
+
+            // The bodies hit by the substepped ones are woken up before they are pushed.
+            let was_active = self.activation_snapshot();
+
+            self.joints.update(&mut *self.sleep.borrow_mut());
+            self.sleep.borrow_mut().update_substepped(&mut self.cworld, &self.joints, &self.rigid_bodies, true);
+
+            self.push_activation_events(was_active);
+
+            let mut flags    = Vec::new();
+            let mut contacts = Vec::new();
+
+            for (b1, b2, generator) in self.cworld.contact_pairs() {
+                if let (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) = (&b1.data, &b2.data) {
+                    let brb1 = rb1.borrow();
+                    let brb2 = rb2.borrow();
+
+                    if !is_substepped(&*brb1) && !is_substepped(&*brb2) {
+                        continue;
+                    }
+
+                    let (uid1, uid2) = (WorldObject::rigid_body_uid(rb1), WorldObject::rigid_body_uid(rb2));
+
+                    // The cuts are only reported once, at the end of the step.
+                    if self.cutters.is_cutter(uid1) || self.cutters.is_cutter(uid2) {
+                        continue;
+                    }
+
+                    contacts.clear();
+                    generator.contacts(&mut contacts);
+
+                    if self.manifold_reduction {
+                        detection::reduce_contact_manifold(&mut contacts, MAX_MANIFOLD_CONTACTS);
+                    }
+
+                    for c in contacts.iter() {
+                        if self.one_way.accept(uid1, &*brb1, uid2, &*brb2, c) {
+                            let mut c = c.clone();
+                            c.depth = c.depth + brb1.margin() + brb2.margin();
+
+                            if let Some(ref mut smoothing) = self.sub_smoothing {
+                                smoothing.smooth(uid1, uid2, &mut c);
+                            }
+
+                            if let Some(ref mut jitter) = self.jitter {
+                                jitter.perturb(&mut c);
+                            }
+
+                            flags.push(modify_contact(&mut self.modifiers[..], &*brb1, &*brb2, &mut c));
+                            collector.push(Constraint::RBRB(rb1.clone(), rb2.clone(), c));
+                        }
+                    }
+                }
+            }
+
+            if let Some(ref mut smoothing) = self.sub_smoothing {
+                smoothing.end_frame();
+            }
+
+            sort_contacts(&mut collector, &mut flags);
+
+            for joint in self.joints.joints().elements().iter() {
+                if is_joint_substepped(&joint.value) {
+                    collector.push(joint.value.clone())
+                }
+            }
+
+            if self.stage_enabled(WorldStage::Solver) {
+                self.sub_solver.set_contact_flags(flags);
+                self.sub_solver.solve(sub_dt.clone(), &collector[..]);
+                self.contact_forces.record(&collector[..], self.sub_solver.contact_impulses());
+                self.joint_reactions.record(&collector[..], self.sub_solver.joint_body_impulses());
+                self.break_joints(sub_dt, &collector[..], true);
+            }
+
+            for e in self.rigid_bodies.elements().iter() {
+                let mut rb = e.value.borrow_mut();
+
+                if is_substepped(&*rb) {
+                    rb.clamp_velocities(self.max_lin_vel, self.max_ang_vel);
+                }
+            }
+
+            collector.clear();
+        }
+    }
+
+    // Removes the joints which reaction exceeded their breaking forces during the last resolution
+    // of `constraints` by the main solver or the substep solver, and reports them to the joint
+    // break handlers.
+    fn break_joints(&mut self, dt: N, constraints: &[Constraint<N>], substep: bool) {
+        let mut broken = Vec::new();
+
+        {
+            let solver = if substep { &self.sub_solver } else { &self.solver };
+
+            for &(i, lin_impulse, ang_impulse) in solver.joint_impulses().iter() {
+                if let Some((max_force, max_torque)) = constraints[i].joint_info().and_then(|j| j.breaking_forces) {
+                    if na::norm(&lin_impulse) > max_force * dt || na::norm(&ang_impulse) > max_torque * dt {
+                        broken.push(BrokenJoint {
+                            joint:           constraints[i].clone(),
+                            linear_impulse:  lin_impulse,
+                            angular_impulse: ang_impulse
+                        })
+                    }
+                }
+            }
+        }
+
+        for b in broken.iter() {
+            self.joints.remove_constraint(&b.joint, &mut *self.sleep.borrow_mut());
+
+            for &mut (_, ref mut handler) in self.break_handlers.iter_mut() {
+                handler.handle_joint_broken(b)
+            }
+        }
+    }
+
+    /// Whether the given stage of the pipeline is performed at each step.
+    ///
+    /// Every stage is enabled by default.
+    #[inline]
+    pub fn stage_enabled(&self, stage: WorldStage) -> bool {
+        match stage {
+            // Also configurable through `self.activation_manager()`.
+            WorldStage::Sleeping => self.sleep.borrow().deactivation_enabled(),
+            _                    => !self.disabled_stages.contains(&stage)
+        }
+    }
+
+    /// Enables or disables a stage of the pipeline without rebuilding the world.
+    ///
+    /// Disabling the sleeping wakes every body up. Re-enabling a stage resets the state it
+    /// accumulated before being disabled: the CCD forgets the motion of the bodies since its last
+    /// update, and the solver is not warm-started with impulses of outdated contacts.
+    pub fn set_stage_enabled(&mut self, stage: WorldStage, enabled: bool) {
+        if self.stage_enabled(stage) == enabled {
+            return;
+        }
+
+        if enabled {
+            self.disabled_stages.retain(|s| *s != stage);
+        }
+        else if stage != WorldStage::Sleeping {
+            self.disabled_stages.push(stage);
+        }
+
+        match stage {
+            WorldStage::ContinuousCollisionDetection => {
+                if enabled {
+                    self.ccd.forget_motions();
+                }
+            },
+            WorldStage::Sleeping => {
+                let mut sleep = self.sleep.borrow_mut();
+
+                sleep.set_deactivation_enabled(enabled);
+
+                if !enabled {
+                    sleep.wake_all(&self.rigid_bodies);
+                }
+            },
+            WorldStage::Solver => {
+                if enabled {
+                    self.solver.clear_impulse_cache();
+                    self.sub_solver.clear_impulse_cache();
+                }
+            },
+            WorldStage::Debug => { }
+        }
+    }
+
+    /// The number of substeps performed by the substepped rigid bodies at each step.
+    #[inline]
+    pub fn num_substeps(&self) -> usize {
+        self.num_substeps
+    }
+
+    /// Sets the number of substeps performed by the substepped rigid bodies at each step.
+    ///
+    /// Only the rigid bodies with substepping enabled are affected: their motion, contacts and
+    /// joints are simulated `num_substeps` times per step with a time step divided accordingly,
+    /// while the rest of the world runs at the base rate.
+    pub fn set_num_substeps(&mut self, num_substeps: usize) {
+        assert!(num_substeps > 0, "The number of substeps must be at least 1.");
+        self.num_substeps = num_substeps;
+    }
+
+    /// The maximum length of the steps actually performed by `step`.
+    #[inline]
+    pub fn max_substep_dt(&self) -> Option<N> {
+        self.max_substep_dt
+    }
+
+    /// Sets the maximum length of the steps actually performed by `step`.
+    ///
+    /// A call to `step` with a longer time step, e.g., after a frame hitch, then performs the
+    /// smallest number of equal steps no longer than `max_substep_dt`, which keeps the stacks and
+    /// the joints stable. Unlike `set_num_substeps`, this affects every body, and each of these
+    /// steps counts in `num_steps`. Set it to `None` to perform a single step whatever its length,
+    /// which is the default.
+    pub fn set_max_substep_dt(&mut self, max_substep_dt: Option<N>) {
+        if let Some(max_dt) = max_substep_dt {
+            assert!(max_dt > na::zero(), "The maximum substep length must be positive.");
+        }
+
+        self.max_substep_dt = max_substep_dt;
+    }
+
+    /// Suggests a time step for the next steps of this world, no longer than `max_dt`.
+    ///
+    /// The suggested time step is short enough for the fastest body to move by less than half the
+    /// thickness of the thinnest body at each step, so that they do not tunnel through each
+    /// other. It also keeps the velocity errors left by the solver during the last step from
+    /// moving the bodies by more than this. The thickness of a body is the smallest side of its
+    /// local bounding box: flat and infinite shapes are ignored. The application either steps the
+    /// world with `dt`, or splits its fixed steps of length `max_dt` into `num_substeps` steps,
+    /// where `num_substeps` is at most `max_substeps`.
+    pub fn suggest_timestep(&self, max_dt: N, max_substeps: usize) -> TimestepSuggestion<N> {
+        assert!(max_dt > na::zero(), "The maximum time step must be positive.");
+        assert!(max_substeps > 0, "The maximum number of substeps must be at least 1.");
+
+        let _max: N = N::max_value();
+        let mut thickness = _max;
+        let mut speed: N  = na::zero();
+
+        for rb in self.rigid_bodies() {
+            let rb      = rb.borrow();
+            let aabb    = bounding_volume::aabb(rb.shape().as_ref(), &na::one::<Isometry<N>>());
+            let extents = *aabb.maxs() - *aabb.mins();
+
+            if !extents.iter().all(|x| *x == *x && *x < _max / na::convert(2.0f64)) {
+                continue;
+            }
+
+            let thinnest = extents.iter().fold(_max, |min, x| if *x < min { *x } else { min });
+
+            if thinnest > na::zero() && thinnest < thickness {
+                thickness = thinnest;
+            }
+
+            if rb.is_active() {
+                let radius   = na::norm(&extents) / na::convert(2.0f64);
+                let rb_speed = na::norm(&rb.lin_vel()) + na::norm(&rb.ang_vel()) * radius;
+
+                if rb_speed > speed {
+                    speed = rb_speed;
+                }
+            }
+        }
+
+        let mut dt    = max_dt;
+        let mut limit = TimestepLimit::MaxTimestep;
+
+        if thickness != _max {
+            let max_motion = thickness / na::convert(2.0f64);
+            let residual   = self.solver.max_residual().max(self.sub_solver.max_residual());
+
+            if speed * dt > max_motion {
+                dt    = max_motion / speed;
+                limit = TimestepLimit::Speed;
+            }
+
+            if residual * dt > max_motion {
+                dt    = max_motion / residual;
+                limit = TimestepLimit::Convergence;
+            }
+        }
+
+        let num_substeps: f64 = na::try_convert((max_dt / dt).ceil()).unwrap_or(max_substeps as f64);
+        let mut num_substeps  = num_substeps as usize;
+
+        if num_substeps > max_substeps {
+            num_substeps = max_substeps;
+            limit        = TimestepLimit::MaxSubsteps;
+        }
+        else if num_substeps == 0 {
+            num_substeps = 1;
+        }
+
+        TimestepSuggestion {
+            dt:           max_dt / na::convert(num_substeps as f64),
+            num_substeps: num_substeps,
+            limit:        limit
+        }
+    }
+
+    /// Counters gathered by the collision detection pipeline during the last step.
+    ///
+    /// Pairs created or destroyed by the addition or removal of objects since the previous step
+    /// are accounted for as well.
+    #[inline]
+    pub fn stats(&self) -> &PipelineStatistics {
+        &self.stats
+    }
+
+    /// Metrics about the convergence of the constraint solver during the last step.
+    ///
+    /// If the last step was split into several ones, e.g., by `set_max_substep_dt`, those are the
+    /// metrics of the last one. The contacts of the substepped bodies are not accounted for.
+    #[inline]
+    pub fn solver_statistics(&self) -> &SolverStatistics<N> {
+        self.solver.statistics()
+    }
+
+    fn update_statistics(&mut self) {
+        let mut stats = mem::replace(&mut *self.counters.borrow_mut(), PipelineStatistics::new());
+
+        for (_, _, c) in self.cworld.contact_pairs() {
+            let num_contacts = c.num_contacts();
+
+            stats.active_pairs = stats.active_pairs + 1;
+
+            if num_contacts != 0 {
+                stats.contact_pairs = stats.contact_pairs + 1;
+                stats.contacts      = stats.contacts + num_contacts;
+            }
+        }
+
+        stats.active_pairs = stats.active_pairs + self.cworld.proximity_pairs().count();
+        self.stats = stats;
+    }
+
+    /// Sets the sink receiving the time spent on each stage of the subsequent steps.
+    ///
+    /// Set it to `None` to disable tracing. This is disabled by default.
+    #[cfg(feature = "tracing")]
+    pub fn set_trace_sink(&mut self, sink: Option<Box<TraceSink>>) -> Option<Box<TraceSink>> {
+        mem::replace(&mut self.tracer, sink)
+    }
+
+    /// The sink receiving the time spent on each stage of the steps.
+    #[cfg(feature = "tracing")]
+    pub fn trace_sink_mut(&mut self) -> Option<&mut TraceSink> {
+        match self.tracer {
+            Some(ref mut sink) => Some(&mut **sink),
+            None               => None
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    fn trace_mark(&self) -> TraceMark {
+        TraceMark(if self.tracer.is_some() { Some(Instant::now()) } else { None })
+    }
+
+    // Records the span of the stage that started at `mark`, and returns the mark of the next one.
+    #[cfg(feature = "tracing")]
+    fn trace(&mut self, stage: Stage, mark: TraceMark, count: Option<usize>) -> TraceMark {
+        match (mark.0, self.tracer.as_mut()) {
+            (Some(begin), Some(sink)) => {
+                let end = Instant::now();
+
+                sink.record(&Span {
+                    stage: stage,
+                    step:  self.num_steps,
+                    begin: begin,
+                    end:   end,
+                    count: count
+                });
+
+                TraceMark(Some(end))
+            },
+            _ => TraceMark(None)
+        }
+    }
+
+    // Records the spans of the islands solved by the last resolution.
+    #[cfg(feature = "tracing")]
+    fn trace_islands(&mut self) {
+        if let Some(sink) = self.tracer.as_mut() {
+            for &(begin, end, count) in self.solver.island_spans().iter() {
+                sink.record(&Span {
+                    stage: Stage::Island,
+                    step:  self.num_steps,
+                    begin: begin,
+                    end:   end,
+                    count: Some(count)
+                });
+            }
+        }
+    }
+
+    /// Sets the smoother applied to the normals of persistent contacts before they are solved.
+    ///
+    /// Set it to `None` to disable contact normal smoothing. This is disabled by default.
+    pub fn set_contact_normal_smoothing(&mut self, smoothing: Option<ContactNormalSmoothing<N>>)
+                                        -> Option<ContactNormalSmoothing<N>> {
+        self.sub_smoothing = None;
+        mem::replace(&mut self.smoothing, smoothing)
+    }
+
+    /// Sets the monitor of the broad phase bounding boxes of the active rigid bodies.
+    ///
+    /// Set it to `None` to disable monitoring, which is the default. Returns the previous monitor.
+    pub fn set_aabb_growth_monitor(&mut self, monitor: Option<AabbGrowthMonitor<N>>)
+                                   -> Option<AabbGrowthMonitor<N>> {
+        mem::replace(&mut self.aabb_growth, monitor)
+    }
+
+    // Reports the active rigid bodies which bounding box is too large, or which position is not
+    // finite. The latter are given to the collision world only if the handler fixed them.
+    fn check_aabb_growth(&mut self, non_finite: Vec<RigidBodyHandle<N>>) {
+        if let Some(ref mut monitor) = self.aabb_growth {
+            let mut events = Vec::new();
+
+            for e in self.rigid_bodies.elements().iter() {
+                let rb = e.value.borrow();
+
+                if !rb.can_move() || !rb.is_active() {
+                    continue;
+                }
+
+                if let Some(co) = self.cworld.collision_object(e.key) {
+                    let limit = co.query_type.query_limit();
+
+                    if is_finite_position(rb.position()) {
+                        let aabb = bounding_volume::aabb(rb.shape().as_ref(), rb.position()).loosened(limit);
+
+                        if let Some(ratio) = monitor.abnormal_ratio(rb.shape(), &aabb) {
+                            events.push(AabbGrowthEvent {
+                                body:  e.value.clone(),
+                                aabb:  aabb,
+                                ratio: ratio
+                            })
+                        }
+                    }
+                    else {
+                        // Report the last valid bounding box.
+                        events.push(AabbGrowthEvent {
+                            body:  e.value.clone(),
+                            aabb:  bounding_volume::aabb(co.shape.as_ref(), &co.position).loosened(limit),
+                            ratio: N::zero() / N::zero()
+                        })
+                    }
+                }
+            }
+
+            // Emit once the bodies are no longer borrowed, so that the handler can modify them.
+            for event in events.iter() {
+                monitor.emit(event)
+            }
+        }
+
+        for handle in non_finite.iter() {
+            let rb = handle.borrow();
+
+            if is_finite_position(rb.position()) {
+                self.cworld.deferred_set_position(WorldObject::rigid_body_uid(handle), rb.position().clone());
+            }
+        }
+    }
+
+    /// Sets the monitor notified of the rigid bodies which moved noticeably during each step.
+    ///
+    /// Set it to `None` to disable notifications, which is the default. Returns the previous
+    /// monitor.
+    pub fn set_transform_change_monitor(&mut self, monitor: Option<TransformChangeMonitor<N>>)
+                                        -> Option<TransformChangeMonitor<N>> {
+        mem::replace(&mut self.transform_changes, monitor)
+    }
+
+    // Notifies the active rigid bodies which moved beyond the thresholds of the monitor.
+    fn notify_transform_changes(&mut self) {
+        if let Some(ref mut monitor) = self.transform_changes {
+            let mut changes = Vec::new();
+
+            for e in self.rigid_bodies.elements().iter() {
+                let rb = e.value.borrow();
+
+                if !rb.is_active() || !is_finite_position(rb.position()) {
+                    continue;
+                }
+
+                if let Some(delta) = monitor.moved(e.key, rb.position()) {
+                    changes.push(TransformChange {
+                        body:     e.value.clone(),
+                        position: rb.position().clone(),
+                        delta:    delta
+                    })
+                }
+            }
+
+            // Emit once the bodies are no longer borrowed, so that the handler can read them.
+            if !changes.is_empty() {
+                monitor.emit(&changes[..])
+            }
+        }
+    }
+
+    /// Sets the generator of the perturbations applied to every contact before they are solved.
+    ///
+    /// Set it to `None` to disable contact jittering. This is disabled by default.
+    pub fn set_contact_jitter(&mut self, jitter: Option<ContactJitter<N>>) -> Option<ContactJitter<N>> {
+        mem::replace(&mut self.jitter, jitter)
+    }
+
+    /// Sets the channel debug frames are sent to at the end of each step.
+    ///
+    /// Set it to `None` to stop generating debug frames.
+    pub fn set_debug_channel(&mut self, channel: Option<DebugChannel<N>>) -> Option<DebugChannel<N>> {
+        mem::replace(&mut self.debug, channel)
+    }
+
+    /// The channel debug frames are sent to at the end of each step.
+    pub fn debug_channel(&self) -> Option<&DebugChannel<N>> {
+        self.debug.as_ref()
+    }
+
+    /// Collects primitives depicting the contacts, joints, and rigid bodies of this world.
+    fn debug_primitives(&self, normal_length: N) -> Vec<DebugPrimitive<N>> {
+        const CONTACT_COLOR:  DebugColor = [1.0, 0.0, 0.0];
+        const JOINT_COLOR:    DebugColor = [0.0, 0.0, 1.0];
+        const ACTIVE_COLOR:   DebugColor = [0.0, 1.0, 0.0];
+        const SLEEPING_COLOR: DebugColor = [0.5, 0.5, 0.5];
+
+        let mut res = Vec::new();
+
+        for (_, _, c) in self.cworld.contacts() {
+            res.push(DebugPrimitive::Point(c.world1, CONTACT_COLOR));
+            res.push(DebugPrimitive::Line(c.world1, c.world1 + c.normal * normal_length, CONTACT_COLOR));
+        }
+
+        for e in self.joints.joints().elements().iter() {
+            match e.value {
+                Constraint::BallInSocket(ref b) => {
+                    let b = b.borrow();
+                    res.push(DebugPrimitive::Line(b.anchor1_pos(), b.anchor2_pos(), JOINT_COLOR));
+                },
+                Constraint::Fixed(ref f) => {
+                    let f  = f.borrow();
+                    let p1 = Point::from_coordinates(f.anchor1_pos().translation.vector);
+                    let p2 = Point::from_coordinates(f.anchor2_pos().translation.vector);
+                    res.push(DebugPrimitive::Line(p1, p2, JOINT_COLOR));
+                },
+                Constraint::Hinge(ref h) => {
+                    let h  = h.borrow();
+                    let p1 = Point::from_coordinates(h.anchor1_pos().translation.vector);
+                    let p2 = Point::from_coordinates(h.anchor2_pos().translation.vector);
+                    res.push(DebugPrimitive::Line(p1, p2, JOINT_COLOR));
+                },
+                Constraint::Prismatic(ref p) => {
+                    let p  = p.borrow();
+                    let p1 = Point::from_coordinates(p.anchor1_pos().translation.vector);
+                    let p2 = Point::from_coordinates(p.anchor2_pos().translation.vector);
+                    res.push(DebugPrimitive::Line(p1, p2, JOINT_COLOR));
+                },
+                Constraint::Spring(ref s) => {
+                    let s = s.borrow();
+                    res.push(DebugPrimitive::Line(s.anchor1_pos(), s.anchor2_pos(), JOINT_COLOR));
+                },
+                Constraint::Distance(ref d) => {
+                    let d = d.borrow();
+                    res.push(DebugPrimitive::Line(d.anchor1_pos(), d.anchor2_pos(), JOINT_COLOR));
+                },
+                Constraint::AngularMotor(ref m) => {
+                    let m  = m.borrow();
+                    let p1 = Point::from_coordinates(m.anchor1_pos().translation.vector);
+                    let p2 = Point::from_coordinates(m.anchor2_pos().translation.vector);
+                    res.push(DebugPrimitive::Line(p1, p2, JOINT_COLOR));
+                },
+                Constraint::RackAndPinion(ref r) => {
+                    let r  = r.borrow();
+                    let p1 = Point::from_coordinates(r.anchor1_pos().translation.vector);
+                    let p2 = Point::from_coordinates(r.anchor2_pos().translation.vector);
+                    res.push(DebugPrimitive::Line(p1, p2, JOINT_COLOR));
+                },
+                Constraint::Pulley(ref p) => {
+                    let p = p.borrow();
+                    res.push(DebugPrimitive::Line(p.anchor1_pos(), *p.pulley1(), JOINT_COLOR));
+                    res.push(DebugPrimitive::Line(*p.pulley1(), *p.pulley2(), JOINT_COLOR));
+                    res.push(DebugPrimitive::Line(*p.pulley2(), p.anchor2_pos(), JOINT_COLOR));
+                },
+                Constraint::Gear(ref g) => {
+                    let g  = g.borrow();
+                    let p1 = Point::from_coordinates(g.anchor1_pos().translation.vector);
+                    let p2 = Point::from_coordinates(g.anchor2_pos().translation.vector);
+                    res.push(DebugPrimitive::Line(p1, p2, JOINT_COLOR));
+                },
+                #[cfg(feature = "dim3")]
+                Constraint::Universal(ref u) => {
+                    let u  = u.borrow();
+                    let p1 = Point::from_coordinates(u.anchor1_pos().translation.vector);
+                    let p2 = Point::from_coordinates(u.anchor2_pos().translation.vector);
+                    res.push(DebugPrimitive::Line(p1, p2, JOINT_COLOR));
+                },
+                Constraint::RBRB(..) => { }
+            }
+        }
+
+        for e in self.rigid_bodies.elements().iter() {
+            let rb = e.value.borrow();
+
+            if rb.can_move() {
+                if rb.is_active() {
+                    res.push(DebugPrimitive::Point(*rb.center_of_mass(), ACTIVE_COLOR));
+                }
+                else {
+                    res.push(DebugPrimitive::Point(*rb.center_of_mass(), SLEEPING_COLOR));
+                    res.push(DebugPrimitive::Label(*rb.center_of_mass(), "sleeping".to_string(), SLEEPING_COLOR));
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Adds a rigid body to the physics world.
+    pub fn add_rigid_body(&mut self, rb: RigidBody<N>) -> RigidBodyHandle<N> {
+        let handle = self.deferred_add_rigid_body(rb);
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+
+        handle
+    }
+
+    /// Adds many rigid bodies to the physics world at once.
+    ///
+    /// Unlike successive calls to `add_rigid_body`, the broad phase is not updated after each
+    /// insertion: the bodies are inserted in bulk and their collision pairs are computed by a
+    /// single broad phase update. Sharing the shapes of many similar bodies with a
+    /// `ShapeRegistry` makes their creation cheap as well.
+    pub fn spawn_batch<I>(&mut self, bodies: I) -> Vec<RigidBodyHandle<N>>
+        where I: IntoIterator<Item = RigidBody<N>> {
+        let handles = bodies.into_iter().map(|rb| self.deferred_add_rigid_body(rb)).collect();
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+
+        handles
+    }
+
+    /// Predicts the trajectory of `body` during `num_steps` steps of length `dt`.
+    ///
+    /// The body is integrated alone, undergoing the gravity and the force generators of this
+    /// world and colliding with its static bodies only: the other bodies, the joints and the
+    /// sensors are ignored, and this world is left untouched. Returns the position of the body
+    /// after each step.
+    ///
+    /// At each step, the motion of the body is swept against the static bodies found by the broad
+    /// phase so that fast bodies do not tunnel through them. Its contacts with them, computed with
+    /// the user-defined dispatchers of this world, then remove its velocity toward them, with their
+    /// restitution and friction. Those contacts do not rotate the body, so the prediction diverges
+    /// from the simulation once the body touches the static geometry.
+    pub fn predict_trajectory(&self, body: &RigidBodyHandle<N>, dt: N, num_steps: usize) -> Vec<Isometry<N>> {
+        let mut forces            = self.forces.clone();
+        let mut generators        = self.generators.iter().map(|g| (g.0.clone(), g.1.clone_box())).collect::<Vec<_>>();
+        let mut integrator        = self.integrator.clone();
+        let mut custom_integrator = self.custom_integrator.as_ref().map(|i| i.clone_box());
+        let dispatcher            = ExtensibleContactDispatcher::new(self.contact_dispatchers.clone());
+        let uid                   = WorldObject::rigid_body_uid(body);
+
+        let mut rb = body.borrow().clone();
+        rb.wake_up();
+
+        let shape        = rb.shape().clone();
+        let groups       = rb.collision_groups().as_collision_groups().clone();
+        let prediction   = self.contact_prediction(&rb);
+        let mut contacts = Vec::new();
+        let mut res      = Vec::with_capacity(num_steps);
+
+        for _ in 0 .. num_steps {
+            let start = rb.position().clone();
+
+            {
+                let integrator: &mut Integrator<N, RigidBody<N>> = match custom_integrator {
+                    Some(ref mut integrator) => integrator.as_integrator(),
+                    None                     => &mut integrator
+                };
+
+                integrate(integrator, &mut forces, &mut generators[..], dt.clone(), &mut rb);
+            }
+
+            rb.clear_accumulated_forces();
+
+            let motion = rb.position().translation.vector - start.translation.vector;
+            let swept  = bounding_volume::aabb(shape.as_ref(), &start)
+                             .merged(&bounding_volume::aabb(shape.as_ref(), rb.position()))
+                             .loosened(prediction);
+            let statics: Vec<_> = self.cworld.interferences_with_aabb(&swept, &groups).filter(|co| {
+                co.uid != uid && !co.data.is_sensor() && !co.data.borrow_rigid_body().can_move()
+            }).collect();
+
+            // The static bodies closer than the contact prediction are handled by the contacts.
+            if is_castable(shape.as_ref()) {
+                let mut first_toi: N = na::one();
+
+                for co in statics.iter().filter(|co| is_castable(co.shape.as_ref())) {
+                    if query::distance(&start, shape.as_ref(), &co.position, co.shape.as_ref()) > prediction {
+                        let toi = query::time_of_impact(&start, &motion, shape.as_ref(),
+                                                        &co.position, &na::zero(), co.shape.as_ref());
+
+                        if let Some(toi) = toi {
+                            if toi < first_toi {
+                                first_toi = toi
+                            }
+                        }
+                    }
+                }
+
+                if first_toi < na::one() {
+                    rb.set_translation(Translation::from_vector(start.translation.vector + motion * first_toi));
+                }
+            }
+
+            for co in statics.iter() {
+                let static_rb = co.data.borrow_rigid_body();
+
+                if let Some(mut algorithm) = dispatcher.get_contact_algorithm(shape.as_ref(), co.shape.as_ref()) {
+                    let _ = algorithm.update(&dispatcher, rb.position(), shape.as_ref(),
+                                             &co.position, co.shape.as_ref(), prediction);
+                    contacts.clear();
+                    algorithm.contacts(&mut contacts);
+
+                    for c in contacts.iter() {
+                        resolve_predicted_contact(&mut rb, &static_rb, c, dt);
+                    }
+                }
+            }
+
+            rb.clamp_velocities(self.max_lin_vel, self.max_ang_vel);
+            res.push(rb.position().clone());
+        }
+
+        res
+    }
+
+    /// Creates an independent copy of this world, e.g., to simulate what would happen if a body
+    /// was pushed without affecting this world.
+    ///
+    /// The rigid bodies, without their user data, and the joints are copied, as well as the
+    /// parameters of the pipeline and the state cached by the activation manager, the CCD and the
+    /// solvers, including the contact and joint impulses warm-starting the next resolution, the
+    /// integrator and the force generators, so that the copy evolves like this world would. The
+    /// sensors, the trigger volumes, the cutters, the monitors, the handlers and the contact
+    /// modifiers are not copied, and the user-defined dispatchers are shared with this world.
+    /// Returns the copy, and the copy of each rigid body of this world indexed by the identifier of
+    /// the original, as given by `WorldObject::rigid_body_uid`.
+    ///
+    /// The contact manifolds are computed again by the copy, so that bodies resting on each other
+    /// may drift slightly away from their originals.
+    pub fn clone_for_prediction(&self) -> (World<N>, HashMap<usize, RigidBodyHandle<N>, UintTWHash>) {
+        let mut world = World::new_with_dispatchers(self.contact_dispatchers.clone(),
+                                                    self.proximity_dispatchers.clone());
+        let mut copies = HashMap::new(UintTWHash::new());
+
+        for e in self.rigid_bodies.elements().iter() {
+            let copy = world.deferred_add_rigid_body(e.value.borrow().clone());
+            copy.borrow_mut().set_serial(e.value.borrow().serial());
+            let _    = copies.insert(e.key, copy);
+        }
+
+        world.num_added_bodies = self.num_added_bodies;
+
+        world.cworld.perform_additions_removals_and_broad_phase();
+
+        fn copy_body<N: Real>(copies: &HashMap<usize, RigidBodyHandle<N>, UintTWHash>,
+                              body:   &Option<RigidBodyHandle<N>>)
+                              -> Option<RigidBodyHandle<N>> {
+            body.as_ref().map(|rb| copies.find(&WorldObject::rigid_body_uid(rb)).unwrap().clone())
+        }
+
+        fn joint_uid<J>(joint: &Rc<RefCell<J>>) -> usize {
+            &**joint as *const RefCell<J> as usize
+        }
+
+        // The joints do not wake their bodies up: the pending activations are copied instead.
+        let mut scratch = ActivationManager::new(na::zero());
+        // The gears and racks are copied last, once the joints they couple are.
+        let mut hinges     = HashMap::new(UintTWHash::new());
+        let mut prismatics = HashMap::new(UintTWHash::new());
+        let mut gears      = Vec::new();
+        let mut racks      = Vec::new();
+        // The keys of the copies of the joints, indexed by the keys of the originals.
+        let mut joint_copies = HashMap::new(UintTWHash::new());
+
+        for e in self.joints.joints().elements().iter() {
+            match e.value {
+                Constraint::BallInSocket(ref bis) => {
+                    let bis  = bis.borrow();
+                    let copy = bis.clone_with_bodies(copy_body(&copies, &bis.anchor1().body),
+                                                     copy_body(&copies, &bis.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_ball_in_socket(copy, &mut scratch)
+                },
+                Constraint::Fixed(ref f) => {
+                    let f    = f.borrow();
+                    let copy = f.clone_with_bodies(copy_body(&copies, &f.anchor1().body),
+                                                   copy_body(&copies, &f.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_fixed(copy, &mut scratch)
+                },
+                Constraint::Hinge(ref h) => {
+                    let h    = h.borrow();
+                    let copy = h.clone_with_bodies(copy_body(&copies, &h.anchor1().body),
+                                                   copy_body(&copies, &h.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = hinges.insert(e.key, copy.clone());
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_hinge(copy, &mut scratch)
+                },
+                Constraint::Prismatic(ref p) => {
+                    let p    = p.borrow();
+                    let copy = p.clone_with_bodies(copy_body(&copies, &p.anchor1().body),
+                                                   copy_body(&copies, &p.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = prismatics.insert(e.key, copy.clone());
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_prismatic(copy, &mut scratch)
+                },
+                Constraint::Spring(ref s) => {
+                    let s    = s.borrow();
+                    let copy = s.clone_with_bodies(copy_body(&copies, &s.anchor1().body),
+                                                   copy_body(&copies, &s.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_spring(copy, &mut scratch)
+                },
+                Constraint::Distance(ref d) => {
+                    let d    = d.borrow();
+                    let copy = d.clone_with_bodies(copy_body(&copies, &d.anchor1().body),
+                                                   copy_body(&copies, &d.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_distance(copy, &mut scratch)
+                },
+                Constraint::AngularMotor(ref m) => {
+                    let m    = m.borrow();
+                    let copy = m.clone_with_bodies(copy_body(&copies, &m.anchor1().body),
+                                                   copy_body(&copies, &m.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_angular_motor(copy, &mut scratch)
+                },
+                Constraint::RackAndPinion(ref r) => racks.push(r.clone()),
+                Constraint::Pulley(ref p) => {
+                    let p    = p.borrow();
+                    let copy = p.clone_with_bodies(copy_body(&copies, &p.anchor1().body),
+                                                   copy_body(&copies, &p.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_pulley(copy, &mut scratch)
+                },
+                Constraint::Gear(ref g) => gears.push(g.clone()),
+                #[cfg(feature = "dim3")]
+                Constraint::Universal(ref u) => {
+                    let u    = u.borrow();
+                    let copy = u.clone_with_bodies(copy_body(&copies, &u.anchor1().body),
+                                                   copy_body(&copies, &u.anchor2().body));
+                    let copy = Rc::new(RefCell::new(copy));
+                    let _    = joint_copies.insert(e.key, joint_uid(&copy));
+                    world.joints.add_universal(copy, &mut scratch)
+                },
+                Constraint::RBRB(_, _, _) => panic!("Internal error: a contact RBRB should not be here.")
+            }
+        }
+
+        // A joint removed from the world is copied along with its gear or rack.
+        let copy_hinge = |hinge: &Rc<RefCell<Hinge<N>>>| {
+            match hinges.find(&(&**hinge as *const RefCell<Hinge<N>> as usize)) {
+                Some(copy) => copy.clone(),
+                None       => {
+                    let h = hinge.borrow();
+                    Rc::new(RefCell::new(h.clone_with_bodies(copy_body(&copies, &h.anchor1().body),
+                                                             copy_body(&copies, &h.anchor2().body))))
+                }
+            }
+        };
+        let copy_prismatic = |prismatic: &Rc<RefCell<Prismatic<N>>>| {
+            match prismatics.find(&(&**prismatic as *const RefCell<Prismatic<N>> as usize)) {
+                Some(copy) => copy.clone(),
+                None       => {
+                    let p = prismatic.borrow();
+                    Rc::new(RefCell::new(p.clone_with_bodies(copy_body(&copies, &p.anchor1().body),
+                                                             copy_body(&copies, &p.anchor2().body))))
+                }
+            }
+        };
+
+        for gear in gears.iter() {
+            let g    = gear.borrow();
+            let copy = g.clone_with_hinges(copy_hinge(g.hinge1()), copy_hinge(g.hinge2()));
+            let copy = Rc::new(RefCell::new(copy));
+            let _    = joint_copies.insert(joint_uid(gear), joint_uid(&copy));
+            world.joints.add_gear(copy, &mut scratch)
+        }
+
+        for rack in racks.iter() {
+            let r    = rack.borrow();
+            let copy = r.clone_with_joints(copy_hinge(r.hinge()), copy_prismatic(r.prismatic()));
+            let copy = Rc::new(RefCell::new(copy));
+            let _    = joint_copies.insert(joint_uid(rack), joint_uid(&copy));
+            world.joints.add_rack_and_pinion(copy, &mut scratch)
+        }
+
+        *world.sleep.borrow_mut() = self.sleep.borrow().clone_for_prediction(&copies);
+
+        world.forces             = self.forces.clone();
+        world.generators         = self.generators.iter().map(|g| (g.0.clone(), g.1.clone_box())).collect();
+        world.integrator         = self.integrator.clone();
+        world.custom_integrator  = self.custom_integrator.as_ref().map(|i| i.clone_box());
+        world.ccd                = self.ccd.clone_for_prediction(&copies);
+        world.solver             = self.solver.clone_for_prediction(&copies, &joint_copies);
+        world.sub_solver         = self.sub_solver.clone_for_prediction(&copies, &joint_copies);
+        world.num_substeps       = self.num_substeps;
+        world.max_substep_dt     = self.max_substep_dt;
+        world.max_lin_vel        = self.max_lin_vel;
+        world.max_ang_vel        = self.max_ang_vel;
+        world.one_way            = self.one_way.clone_for_prediction(&copies);
+        world.contact_forces     = self.contact_forces.clone_for_prediction(&copies);
+        world.manifold_reduction = self.manifold_reduction;
+        world.disabled_stages    = self.disabled_stages.clone();
+        world.num_steps          = self.num_steps;
+        world.time.set(self.time.get());
+        world.events.borrow_mut().set_enabled(self.events.borrow().enabled());
+
+        (world, copies)
+    }
+
+    fn deferred_add_rigid_body(&mut self, mut rb: RigidBody<N>) -> RigidBodyHandle<N> {
+        rb.set_serial(self.num_added_bodies);
+        self.num_added_bodies = self.num_added_bodies + 1;
+
+        let position = rb.position().clone();
+        let shape = rb.shape().clone();
+        let groups = rb.collision_groups().as_collision_groups().clone();
+        let collision_object_prediction = self.contact_prediction(&rb);
+        let handle = Rc::new(RefCell::new(rb));
+        let uid = WorldObject::rigid_body_uid(&handle);
+
+        let _ = self.rigid_bodies.insert(uid, handle.clone());
+        self.cworld.deferred_add(uid, position, shape, groups,
+                                 GeometricQueryType::Contacts(collision_object_prediction),
+                                 WorldObject::RigidBody(handle.clone()));
+
+        handle
+    }
+
+    // The contact prediction of `rb`, without speculative margin.
+    fn contact_prediction(&self, rb: &RigidBody<N>) -> N {
+        rb.margin() + rb.prediction().unwrap_or(self.prediction / na::convert(2.0f64))
+    }
+
+    /// Re-registers the collision objects of rigid bodies which contact prediction does not match
+    /// their margin, prediction, or maximum velocities for speculative contacts, any more.
+    ///
+    /// Re-registering a collision object drops its contact manifolds, so the speculative margin
+    /// does not follow the current velocity of the body: it is bounded by the maximum velocities
+    /// of the body, or of the world, and changes only with them or with a longer time step.
+    fn update_contact_predictions(&mut self, dt: N) {
+        let _1_5: N = na::convert(1.5f64);
+        let _4:   N = na::convert(4.0f64);
+        let mut speculatives = Vec::new();
+        let mut to_update    = Vec::new();
+
+        for e in self.rigid_bodies.elements().iter() {
+            let rb          = e.value.borrow();
+            let previous    = self.speculative.find(&e.key).cloned();
+            let mut current = previous.map_or(na::zero(), |s| s.margin);
+
+            if rb.speculative_contacts_enabled() {
+                // Without maximum velocities, those of the body when its speculative contacts are
+                // first taken into account are kept as bounds.
+                let max_lin_vel = rb.max_lin_vel().or(self.max_lin_vel).unwrap_or_else(
+                    || previous.map_or(na::norm(&rb.lin_vel()), |s| s.max_lin_vel));
+                let max_ang_vel = rb.max_ang_vel().or(self.max_ang_vel).unwrap_or_else(
+                    || previous.map_or(na::norm(&rb.ang_vel()), |s| s.max_ang_vel));
+
+                // Bound the distance travelled by any point of the body during the next step.
+                let bs     = bounding_volume::bounding_sphere(rb.shape().as_ref(), rb.position());
+                let radius = bs.radius() + na::distance(bs.center(), rb.center_of_mass());
+                let needed = (max_lin_vel + max_ang_vel * radius) * dt;
+
+                // Grow with some slack, and shrink lazily, to avoid re-registering at each step.
+                if previous.is_none() || needed > current || needed * _4 < current {
+                    current = needed * _1_5;
+                }
+
+                let speculative = SpeculativeMargin {
+                    max_lin_vel: max_lin_vel,
+                    max_ang_vel: max_ang_vel,
+                    margin:      current
+                };
+
+                speculatives.push((e.key, Some(speculative)));
+            }
+            else if previous.is_some() {
+                current = na::zero();
+                speculatives.push((e.key, None));
+            }
+
+            if let Some(co) = self.cworld.collision_object(e.key) {
+                if co.query_type.query_limit() != self.contact_prediction(&*rb) + current {
+                    to_update.push((e.value.clone(), current));
+                }
+            }
+        }
+
+        for (uid, speculative) in speculatives.into_iter() {
+            match speculative {
+                Some(speculative) => { let _ = self.speculative.insert(uid, speculative); },
+                None              => { let _ = self.speculative.remove(&uid); }
+            }
+        }
+
+        if to_update.is_empty() {
+            return;
+        }
+
+        for &(ref rb, _) in to_update.iter() {
+            self.cworld.deferred_remove(WorldObject::rigid_body_uid(rb));
+        }
+
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+
+        for (handle, margin) in to_update.into_iter() {
+            let uid        = WorldObject::rigid_body_uid(&handle);
+            let rb         = handle.borrow();
+            let groups     = rb.collision_groups().as_collision_groups().clone();
+            let prediction = self.contact_prediction(&*rb) + margin;
+
+            self.cworld.deferred_add(uid, rb.position().clone(), rb.shape().clone(), groups,
+                                     GeometricQueryType::Contacts(prediction),
+                                     WorldObject::RigidBody(handle.clone()));
+        }
+
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+    }
+
+    /// Adds a sensor to the physics world.
+    pub fn add_sensor(&mut self, sensor: Sensor<N>) -> SensorHandle<N> {
+        let position = sensor.position().clone();
+        let shape    = sensor.shape().clone();
+        let groups   = sensor.collision_groups().as_collision_groups().clone();
+        let margin   = sensor.margin();
+        let handle   = Rc::new(RefCell::new(sensor));
+        let uid      = &*handle as *const RefCell<Sensor<N>> as usize;
+
+        let _ = self.sensors.insert(uid, handle.clone());
+        self.cworld.deferred_add(uid, position, shape, groups,
+                                 GeometricQueryType::Proximity(margin),
+                                 WorldObject::Sensor(handle.clone()));
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+
+        handle
+    }
+
+    /// Remove a rigid body from the physics world.
+    pub fn remove_rigid_body(&mut self, rb: &RigidBodyHandle<N>) {
+        let uid = WorldObject::rigid_body_uid(rb);
+        self.cworld.deferred_remove(uid);
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+        self.joints.remove(rb, &mut *self.sleep.borrow_mut());
+        self.ccd.remove_ccd_from(rb);
+        let _ = self.speculative.remove(&uid);
+        let _ = self.rigid_bodies.remove(&uid);
+        let _ = self.cutters.remove(uid);
+        self.contact_forces.forget(uid);
+
+        if let Some(ref mut monitor) = self.transform_changes {
+            monitor.forget(uid);
+        }
+
+        rb.borrow_mut().delete();
+    }
+
+    /// Remove a sensor from the physics world.
+    pub fn remove_sensor(&mut self, sensor: &SensorHandle<N>) {
+        let uid = WorldObject::sensor_uid(sensor);
+        self.cworld.deferred_remove(uid);
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+        let _ = self.sensors.remove(&uid);
+    }
+
+    /// Adds a static trigger volume to the physics world.
+    ///
+    /// The trigger volume tracks the rigid bodies intersecting `shape` at `position`, and reports
+    /// them entering and leaving it to the handlers registered with
+    /// `register_trigger_handler`, at the end of each step. Like sensors, trigger volumes never
+    /// generate contacts. They detect neither static bodies nor sensors.
+    pub fn add_trigger_volume(&mut self, shape: ShapeHandle<Point<N>, Isometry<N>>, position: Isometry<N>)
+                              -> TriggerVolumeId {
+        let mut sensor = Sensor::new_with_shared_shape(shape, None);
+        sensor.set_relative_position(position);
+        sensor.disable_interfering_bodies_collection();
+
+        let position = sensor.position();
+        let shape    = sensor.shape().clone();
+        let groups   = sensor.collision_groups().as_collision_groups().clone();
+        let margin   = sensor.margin();
+        let handle   = Rc::new(RefCell::new(sensor));
+        let uid      = WorldObject::sensor_uid(&handle);
+
+        let id = self.triggers.borrow_mut().add(handle.clone());
+        self.cworld.deferred_add(uid, position, shape, groups,
+                                 GeometricQueryType::Proximity(margin),
+                                 WorldObject::Sensor(handle));
+        self.cworld.perform_additions_removals_and_broad_phase();
+        *self.query_tree.get_mut() = None;
+
+        id
+    }
+
+    /// Removes a trigger volume from the physics world.
+    ///
+    /// No exit event is emitted for the bodies that were inside it. Returns `false` if the volume
+    /// had already been removed.
+    pub fn remove_trigger_volume(&mut self, volume: TriggerVolumeId) -> bool {
+        let removed = self.triggers.borrow_mut().remove(volume);
+
+        match removed {
+            Some(sensor) => {
+                self.cworld.deferred_remove(WorldObject::sensor_uid(&sensor));
+                self.cworld.perform_additions_removals_and_broad_phase();
+                *self.query_tree.get_mut() = None;
+                true
+            },
+            None => false
+        }
+    }
+
+    /// The rigid bodies currently inside a trigger volume, or `None` if it has been removed.
+    pub fn bodies_inside_trigger_volume(&self, volume: TriggerVolumeId) -> Option<Vec<RigidBodyHandle<N>>> {
+        self.triggers.borrow().bodies_inside(volume)
+    }
+
+    /// Registers a handler for the bodies entering and leaving the trigger volumes.
+    pub fn register_trigger_handler<H: TriggerHandler<N> + 'static>(&mut self, name: &str, handler: H) {
+        self.triggers.borrow_mut().register_handler(name, handler)
+    }
+
+    /// Unregisters a handler for the bodies entering and leaving the trigger volumes.
+    pub fn unregister_trigger_handler(&mut self, name: &str) {
+        self.triggers.borrow_mut().unregister_handler(name)
+    }
+
+    /// Makes `rb` a cutter, which intersections with the other rigid bodies are reported to
+    /// `handler` at the end of each step.
+    ///
+    /// The contacts of a cutter are never solved: it goes through the other bodies without
+    /// pushing them, like a blade, and they are cut instead. A cutter is typically a static body
+    /// moved by the application, which position is updated on the collision world at each step.
+    /// Its targets must be dynamic. This replaces the previous handler if `rb` was already a cutter.
+    pub fn add_cutter<H: CutHandler<N> + 'static>(&mut self, rb: &RigidBodyHandle<N>, handler: H) {
+        self.cutters.add(WorldObject::rigid_body_uid(rb), rb.clone(), handler)
+    }
+
+    /// Makes the cutter `rb` a regular rigid body. Returns `false` if it was not a cutter.
+    pub fn remove_cutter(&mut self, rb: &RigidBodyHandle<N>) -> bool {
+        self.cutters.remove(WorldObject::rigid_body_uid(rb))
+    }
+
+    /// Whether `rb` is a cutter.
+    pub fn is_cutter(&self, rb: &RigidBodyHandle<N>) -> bool {
+        self.cutters.is_cutter(WorldObject::rigid_body_uid(rb))
+    }
+
+    // XXX: keep this reference mutable?
+    /// Gets a mutable reference to the force generator.
+    pub fn forces_generator(&mut self) -> &mut BodyForceGenerator<N> {
+        &mut self.forces
+    }
+
+    /// Registers a generator of forces applied on the active dynamic rigid bodies at each step.
+    ///
+    /// The forces of all generators are added to the gravity and to the forces appended to each
+    /// body. A generator replaces the one previously registered with the same name. It is cloned
+    /// along with the world by `clone_for_prediction`.
+    pub fn register_force_generator<G>(&mut self, name: &str, generator: G)
+        where G: ForceGenerator<N> + Clone + 'static {
+        match self.generators.iter().position(|g| g.0 == name) {
+            Some(i) => self.generators[i].1 = Box::new(generator),
+            None    => self.generators.push((name.to_string(), Box::new(generator)))
+        }
+    }
+
+    /// Unregisters a force generator.
+    ///
+    /// Its forces stop applying at the next step.
+    pub fn unregister_force_generator(&mut self, name: &str) {
+        self.generators.retain(|g| g.0 != name)
+    }
+
+    // XXX: keep this reference mutable?
+    /// Gets a mutable reference to the default position and orientation integrator.
+    ///
+    /// It is used unless another integrator is set by `set_integrator`.
+    pub fn integrator(&mut self) -> &mut BodySmpEulerIntegrator {
+        &mut self.integrator
+    }
+
+    /// Gets a mutable reference to the position and orientation integrator actually used: the
+    /// one set by `set_integrator`, or the default one.
+    pub fn body_integrator(&mut self) -> &mut Integrator<N, RigidBody<N>> {
+        match self.custom_integrator {
+            Some(ref mut integrator) => integrator.as_integrator(),
+            None                     => &mut self.integrator
+        }
+    }
+
+    /// Sets the position and orientation integrator used instead of the default one.
+    ///
+    /// The default is a `BodySmpEulerIntegrator`, which keeps the energy of oscillating systems
+    /// bounded, unlike a `BodyExpEulerIntegrator`. A `BodyRk4Integrator` is more accurate for
+    /// ballistic and orbital trajectories and fast spinning bodies.
+    pub fn set_integrator<I>(&mut self, integrator: I)
+        where I: Integrator<N, RigidBody<N>> + Clone + 'static {
+        self.custom_integrator = Some(Box::new(integrator))
+    }
+
+    // XXX: keep this reference mutable?
+    /// Gets a mutable reference to the CCD manager.
+    pub fn ccd_manager(&mut self) -> &mut TranslationalCCDMotionClamping<N> {
+        &mut self.ccd
+    }
+
+    // XXX: keep this reference mutable?
+    /// Gets a mutable reference to the joint manager.
+    pub fn joint_manager(&mut self) -> &mut JointManager<N> {
+        &mut self.joints
+    }
+
+    // XXX: keep this reference mutable?
+    /// Gets a mutable reference to the constraint solver.
+    pub fn constraints_solver(&mut self) -> &mut AccumulatedImpulseSolver<N> {
+        &mut self.solver
+    }
+
+    /// Gets a mutable reference to the activation manager, to tune it at runtime.
+    pub fn activation_manager(&mut self) -> RefMut<ActivationManager<N>> {
+        self.sleep.borrow_mut()
+    }
+
+    /// Activates every sleeping rigid body of this world immediately.
+    pub fn wake_all(&mut self) {
+        self.sleep.borrow_mut().wake_all(&self.rigid_bodies)
+    }
+
+    /// Gets the underlying collision world.
+    pub fn collision_world(&self) -> &RigidBodyCollisionWorld<N> {
+        &self.cworld
+    }
+
+    /// The bounding volume tree of the objects visible to the world queries.
+    ///
+    /// It is built by the first query following a change of the collision world.
+    #[doc(hidden)]
+    pub fn query_tree(&self) -> Ref<QueryTree<N>> {
+        if self.query_tree.borrow().is_none() {
+            *self.query_tree.borrow_mut() = Some(queries::query_tree(&self.cworld));
+        }
+
+        Ref::map(self.query_tree.borrow(), |tree| tree.as_ref().unwrap())
+    }
+
+    /// The simulated time elapsed since the creation of this world.
+    #[inline]
+    pub fn time(&self) -> N {
+        self.time.get()
+    }
+
+    /// The distance within which the narrow phase generates contacts before any penetration.
+    pub fn prediction(&self) -> N {
+        self.prediction
+    }
+
+    /// The maximum norm of the linear velocity of the rigid bodies without their own maximum.
+    pub fn max_lin_vel(&self) -> Option<N> {
+        self.max_lin_vel
+    }
+
+    /// Sets the maximum norm of the linear velocity of the rigid bodies without their own maximum.
+    ///
+    /// The velocities are clamped at the end of each step, after the integration and the
+    /// constraints resolution. The default is `None`, i.e., unlimited.
+    pub fn set_max_lin_vel(&mut self, max_lin_vel: Option<N>) {
+        self.max_lin_vel = max_lin_vel
+    }
+
+    /// The maximum norm of the angular velocity of the rigid bodies without their own maximum.
+    pub fn max_ang_vel(&self) -> Option<N> {
+        self.max_ang_vel
+    }
+
+    /// Sets the maximum norm of the angular velocity of the rigid bodies without their own
+    /// maximum.
+    ///
+    /// The default is `None`, i.e., unlimited.
+    pub fn set_max_ang_vel(&mut self, max_ang_vel: Option<N>) {
+        self.max_ang_vel = max_ang_vel
+    }
+
+    /// Sets the linear acceleration afecting every dynamic rigid body.
+    ///
+    /// This can be changed at any time, e.g., to flip the gravity: every body is then woken up so
+    /// that the sleeping ones respond to the change at the next step.
+    pub fn set_gravity(&mut self, gravity: Vector<N>) {
+        if gravity != self.forces.lin_acc() {
+            self.forces.set_lin_acc(gravity);
+            self.wake_all();
+        }
+    }
+
+    /// Sets the angular acceleration afecting every dynamic rigid body, whatever its inertia.
+    ///
+    /// Every body is woken up if the acceleration changes.
+    pub fn set_angular_acceleration(&mut self, accel: Orientation<N>) {
+        if accel != self.forces.ang_acc() {
+            self.forces.set_ang_acc(accel);
+            self.wake_all();
+        }
+    }
+
+    /// Registers a zone overriding the gravity of the bodies which center of mass lies within it.
+    ///
+    /// Where several zones overlap, the last registered one applies. A zone replaces the one
+    /// previously registered with the same name. Every body is woken up.
+    pub fn register_gravity_zone(&mut self, name: &str, zone: GravityZone<N>) {
+        self.forces.register_gravity_zone(name, zone);
+        self.wake_all();
+    }
+
+    /// Unregisters a gravity zone, and wakes every body up.
+    pub fn unregister_gravity_zone(&mut self, name: &str) {
+        self.forces.unregister_gravity_zone(name);
+        self.wake_all();
+    }
+
+    /// Gets the linear acceleration afecting every dynamic rigid body.
+    pub fn gravity(&self) -> Vector<N> {
+        self.forces.lin_acc()
+    }
+
+    /// Gets the angular acceleration afecting every dynamic rigid body.
+    pub fn angular_acceleration(&self) -> Orientation<N> {
+        self.forces.ang_acc()
+    }
+
+    /// Adds continuous collision detection to the given rigid body.
+    ///
+    /// Set `trigger_sensor` to `true` if the rigid body should active the sensors that would have
+    /// been missed without CCD enabled. Otherwise, this is equivalent to enabling CCD with
+    /// `RigidBody::enable_ccd`.
+    pub fn add_ccd_to(&mut self, body: &RigidBodyHandle<N>, motion_thresold: N, trigger_sensors: bool) {
+        self.ccd.add_ccd_to(body.clone(), motion_thresold, trigger_sensors)
+    }
+
+    /// Computes the time at which two rigid bodies will touch if they keep moving at the given
+    /// constant linear velocities, without stepping the world.
+    ///
+    /// Returns `None` if they do not touch before `max_toi`. See
+    /// `TranslationalCCDMotionClamping::time_of_impact` for details.
+    pub fn time_of_impact(&self,
+                          rb1:     &RigidBodyHandle<N>,
+                          vel1:    &Vector<N>,
+                          rb2:     &RigidBodyHandle<N>,
+                          vel2:    &Vector<N>,
+                          max_toi: N)
+                          -> Option<N> {
+        self.ccd.time_of_impact(&*rb1.borrow(), vel1, &*rb2.borrow(), vel2, max_toi)
+    }
+
+    /// An iterator visiting all joints on this world.
+    pub fn joints(&self) -> Joints<N> {
+        self.joints.iter()
+    }
+
+    /// Removes a joint of any type from the world, e.g., one given by `self.joints()`.
+    pub fn remove_joint(&mut self, joint: &Constraint<N>) {
+        self.joints.remove_constraint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a ball-in-socket joint to the world.
+    pub fn add_ball_in_socket(&mut self, joint: BallInSocket<N>) -> Rc<RefCell<BallInSocket<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_ball_in_socket(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a ball-in-socket joint from the world.
+    pub fn remove_ball_in_socket(&mut self, joint: &Rc<RefCell<BallInSocket<N>>>) {
+        self.joints.remove_ball_in_socket(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a fixed joint to the world.
+    pub fn add_fixed(&mut self, joint: Fixed<N>) -> Rc<RefCell<Fixed<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_fixed(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a fixed joint from the world.
+    pub fn remove_fixed(&mut self, joint: &Rc<RefCell<Fixed<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a hinge joint to the world.
+    pub fn add_hinge(&mut self, joint: Hinge<N>) -> Rc<RefCell<Hinge<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_hinge(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a hinge joint from the world.
+    pub fn remove_hinge(&mut self, joint: &Rc<RefCell<Hinge<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a prismatic joint to the world.
+    pub fn add_prismatic(&mut self, joint: Prismatic<N>) -> Rc<RefCell<Prismatic<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_prismatic(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a prismatic joint from the world.
+    pub fn remove_prismatic(&mut self, joint: &Rc<RefCell<Prismatic<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a spring to the world.
+    pub fn add_spring(&mut self, joint: Spring<N>) -> Rc<RefCell<Spring<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_spring(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a spring from the world.
+    pub fn remove_spring(&mut self, joint: &Rc<RefCell<Spring<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a distance joint to the world.
+    pub fn add_distance(&mut self, joint: Distance<N>) -> Rc<RefCell<Distance<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_distance(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a distance joint from the world.
+    pub fn remove_distance(&mut self, joint: &Rc<RefCell<Distance<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a standalone angular motor to the world.
+    pub fn add_angular_motor(&mut self, joint: AngularMotor<N>) -> Rc<RefCell<AngularMotor<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_angular_motor(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a standalone angular motor from the world.
+    pub fn remove_angular_motor(&mut self, joint: &Rc<RefCell<AngularMotor<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a rack-and-pinion to the world.
+    pub fn add_rack_and_pinion(&mut self, joint: RackAndPinion<N>) -> Rc<RefCell<RackAndPinion<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_rack_and_pinion(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a rack-and-pinion from the world.
+    pub fn remove_rack_and_pinion(&mut self, joint: &Rc<RefCell<RackAndPinion<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a pulley to the world.
+    pub fn add_pulley(&mut self, joint: Pulley<N>) -> Rc<RefCell<Pulley<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_pulley(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a pulley from the world.
+    pub fn remove_pulley(&mut self, joint: &Rc<RefCell<Pulley<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a gear to the world.
+    pub fn add_gear(&mut self, joint: Gear<N>) -> Rc<RefCell<Gear<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_gear(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a gear from the world.
+    pub fn remove_gear(&mut self, joint: &Rc<RefCell<Gear<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Adds a universal joint to the world.
+    #[cfg(feature = "dim3")]
+    pub fn add_universal(&mut self, joint: Universal<N>) -> Rc<RefCell<Universal<N>>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_universal(res.clone(), &mut *self.sleep.borrow_mut());
+
+        res
+    }
+
+    /// Removes a universal joint from the world.
+    #[cfg(feature = "dim3")]
+    pub fn remove_universal(&mut self, joint: &Rc<RefCell<Universal<N>>>) {
+        self.joints.remove_joint(joint, &mut *self.sleep.borrow_mut())
+    }
+
+    /// Collects every constraincts detected since the last update.
+    pub fn constraints(&mut self, out: &mut Vec<Constraint<N>>) {
+        // FIXME: ugly.
+        let mut contacts = Vec::new();
+
+        for (b1, b2, generator) in self.cworld.contact_pairs() {
+            if let (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) = (&b1.data, &b2.data) {
+                let m1 = rb1.borrow().margin();
+                let m2 = rb2.borrow().margin();
+
+                contacts.clear();
+                generator.contacts(&mut contacts);
+
+                if self.manifold_reduction {
+                    detection::reduce_contact_manifold(&mut contacts, MAX_MANIFOLD_CONTACTS);
+                }
+
+                for c in contacts.iter() {
+                    let mut c = c.clone();
+                    c.depth = c.depth + m1 + m2;
+
+                    let _ = modify_contact(&mut self.modifiers[..], &*rb1.borrow(), &*rb2.borrow(), &mut c);
+                    out.push(Constraint::RBRB(rb1.clone(), rb2.clone(), c));
+                }
+            }
+        }
+
+        self.joints.constraints(out);
+    }
+
+    /// Calls `f` for each pair of rigid bodies in contact, with their contacts.
+    ///
+    /// Only the dynamics of the two bodies can be modified by `f`. Since the world remains
+    /// borrowed during the whole iteration, bodies cannot be added, removed or modified
+    /// structurally midway.
+    pub fn for_each_contact_pair_mut<F>(&mut self, mut f: F)
+        where F: FnMut(RigidBodyDynamics<N>, RigidBodyDynamics<N>, &[Contact<Point<N>>]) {
+        let mut contacts = Vec::new();
+
+        for (co1, co2, generator) in self.cworld.contact_pairs() {
+            if let (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) = (&co1.data, &co2.data) {
+                generator.contacts(&mut contacts);
+
+                if !contacts.is_empty() {
+                    f(RigidBodyDynamics::new(rb1.borrow_mut()), RigidBodyDynamics::new(rb2.borrow_mut()), &contacts[..]);
+                    contacts.clear();
+                }
+            }
+        }
+    }
+
+    /// The total normal force applied on `rb` by its contacts during the last step.
+    ///
+    /// This is the sum of the normal impulses of the contacts divided by the length of the step,
+    /// e.g., the weight of the bodies resting on a pressure plate or a scale, plus the reaction of
+    /// its own support. The friction forces are not included. The forces between sleeping bodies
+    /// are those of the last step they were awake at.
+    pub fn contact_force(&self, rb: &RigidBodyHandle<N>) -> Vector<N> {
+        self.contact_forces.force(WorldObject::rigid_body_uid(rb))
+    }
+
+    /// The force and torque applied by `joint` on each of the bodies it attaches during the last
+    /// step.
+    ///
+    /// This is the sum of the impulses of the joint divided by the length of the step, including
+    /// its limits and motors. Returns `None` if the joint has not been solved yet. The reactions of
+    /// joints between sleeping bodies are those of the last step they were awake at.
+    pub fn joint_reaction<T: Joint<N, M>, M>(&self, joint: &Rc<RefCell<T>>) -> Option<JointReaction<N>> {
+        self.joint_reactions.reaction(&**joint as *const RefCell<T> as usize)
+    }
+
+    /// The reactions of every joint solved during the last step.
+    ///
+    /// A joint broken during the last step is still included, with the reaction which broke it.
+    pub fn joint_reactions(&self) -> Vec<(Constraint<N>, JointReaction<N>)> {
+        self.joint_reactions.reactions()
+    }
+
+    /// An iterator visiting all rigid bodies on this world.
+    pub fn rigid_bodies(&self) -> RigidBodies<N> {
+        fn extract_value<N: Real>(e: &Entry<usize, RigidBodyHandle<N>>) -> &RigidBodyHandle<N> {
+            &e.value
+        }
+
+        let extract_value_fn: fn(_) -> _ = extract_value;
+        self.rigid_bodies.elements().iter().map(extract_value_fn)
+    }
+
+    /// An iterator visiting all sensors on this world.
+    pub fn sensors(&self) -> Sensors<N> {
+        fn extract_value<N: Real>(e: &Entry<usize, SensorHandle<N>>) -> &SensorHandle<N> {
+            &e.value
+        }
+
+        let extract_value_fn: fn(_) -> _ = extract_value;
+        self.sensors.elements().iter().map(extract_value_fn)
+    }
+
+    /// Statistics about the sizes of the islands of interacting bodies built during the last step.
+    pub fn island_statistics(&self) -> IslandStatistics {
+        self.sleep.borrow().island_statistics().clone()
+    }
+
+    /// Saves the state of every movable rigid body of the world.
+    pub fn save(&self) -> WorldSave<N> {
+        let mut res = WorldSave::new(None);
+
+        for e in self.rigid_bodies.elements().iter() {
+            let rb = e.value.borrow();
+
+            if rb.can_move() {
+                res.push(&e.value, BodySave::new(&*rb))
+            }
+        }
+
+        res
+    }
+
+    /// Saves the state of the movable rigid bodies which may differ from the full save `baseline`.
+    ///
+    /// Only the bodies of the awake islands, and the sleeping bodies which state changed since
+    /// `baseline`, are saved. Panics if `baseline` is itself an incremental save.
+    pub fn save_incremental(&self, baseline: &WorldSave<N>) -> WorldSave<N> {
+        let mut res = WorldSave::new(Some(baseline));
+
+        for e in self.rigid_bodies.elements().iter() {
+            let rb = e.value.borrow();
+
+            if !rb.can_move() {
+                continue;
+            }
+
+            let save = BodySave::new(&*rb);
+
+            if rb.is_active() || baseline.body(&e.value) != Some(&save) {
+                res.push(&e.value, save)
+            }
+        }
+
+        res
+    }
+
+    /// Restores the rigid bodies of this world to the state they had in `save`.
+    ///
+    /// If `save` is incremental, its `baseline` must be given: the bodies not saved by `save` are
+    /// restored from `baseline`. The bodies that are part of neither save are left unchanged.
+    /// Panics if `baseline` is not the baseline of `save`.
+    pub fn restore(&mut self, save: &WorldSave<N>, baseline: Option<&WorldSave<N>>) {
+        assert!(save.baseline() == baseline.map(|b| b.id()), "The baseline does not match the save.");
+
+        for e in self.rigid_bodies.elements().iter() {
+            let state = save.body(&e.value).or_else(|| baseline.and_then(|b| b.body(&e.value)));
+
+            if let Some(state) = state {
+                let mut rb = e.value.borrow_mut();
+
+                state.restore(&mut *rb);
+                self.cworld.deferred_set_position(e.key, rb.position().clone());
+            }
+        }
+
+        self.cworld.perform_position_update();
+        *self.query_tree.get_mut() = None;
+    }
+
+    /// A snapshot of the content of the world: bodies by state, total mass, bounds, joints, and
+    /// the size of the largest island of the last step.
+    ///
+    /// This iterates through all the objects of the world, but is cheap enough to be called at
+    /// each frame by an editor or a monitoring dashboard.
+    pub fn summary(&self) -> SceneSummary<N> {
+        let mut res = SceneSummary {
+            num_static_bodies:   0,
+            num_active_bodies:   0,
+            num_sleeping_bodies: 0,
+            num_sensors:         self.sensors.len(),
+            total_mass:          na::zero(),
+            bounds:              None,
+            num_ball_in_sockets: 0,
+            num_fixed_joints:    0,
+            num_hinges:          0,
+            num_prismatics:      0,
+            num_springs:         0,
+            num_distances:       0,
+            num_angular_motors:  0,
+            num_racks:           0,
+            num_pulleys:         0,
+            num_gears:           0,
+            #[cfg(feature = "dim3")]
+            num_universals:      0,
+            largest_island:      self.sleep.borrow().island_statistics().largest
+        };
+
+        fn merge_bounds<N: Real>(bounds: &mut Option<AABB<Point<N>>>, aabb: AABB<Point<N>>) {
+            // The infinite shapes are bounded by half of the largest representable value.
+            let max    = N::max_value() / na::convert(2.0f64);
+            let finite = aabb.mins().iter().chain(aabb.maxs().iter()).all(|x| *x == *x && x.abs() < max);
+
+            if finite {
+                *bounds = Some(match bounds.take() {
+                    Some(bounds) => bounds.merged(&aabb),
+                    None         => aabb
+                })
+            }
+        }
+
+        for rb in self.rigid_bodies() {
+            let rb = rb.borrow();
+
+            if !rb.can_move() {
+                res.num_static_bodies = res.num_static_bodies + 1;
+            }
+            else {
+                if rb.is_active() {
+                    res.num_active_bodies = res.num_active_bodies + 1;
+                }
+                else {
+                    res.num_sleeping_bodies = res.num_sleeping_bodies + 1;
+                }
+
+                res.total_mass = res.total_mass + rb.mass().unwrap_or(na::zero());
+            }
+
+            merge_bounds(&mut res.bounds, bounding_volume::aabb(rb.shape().as_ref(), rb.position()));
+        }
+
+        let triggers = self.triggers.borrow().sensors();
+        res.num_sensors = res.num_sensors + triggers.len();
+
+        for sensor in self.sensors().chain(triggers.iter()) {
+            let sensor = sensor.borrow();
+
+            merge_bounds(&mut res.bounds, bounding_volume::aabb(sensor.shape().as_ref(), &sensor.position()));
+        }
+
+        for e in self.joints.joints().elements().iter() {
+            match e.value {
+                Constraint::BallInSocket(_)  => res.num_ball_in_sockets = res.num_ball_in_sockets + 1,
+                Constraint::Fixed(_)         => res.num_fixed_joints    = res.num_fixed_joints + 1,
+                Constraint::Hinge(_)         => res.num_hinges          = res.num_hinges + 1,
+                Constraint::Prismatic(_)     => res.num_prismatics      = res.num_prismatics + 1,
+                Constraint::Spring(_)        => res.num_springs         = res.num_springs + 1,
+                Constraint::Distance(_)      => res.num_distances       = res.num_distances + 1,
+                Constraint::AngularMotor(_)  => res.num_angular_motors  = res.num_angular_motors + 1,
+                Constraint::RackAndPinion(_) => res.num_racks           = res.num_racks + 1,
+                Constraint::Pulley(_)        => res.num_pulleys         = res.num_pulleys + 1,
+                Constraint::Gear(_)          => res.num_gears           = res.num_gears + 1,
+                #[cfg(feature = "dim3")]
+                Constraint::Universal(_)     => res.num_universals      = res.num_universals + 1,
+                Constraint::RBRB(..)         => { }
+            }
+        }
+
+        res
+    }
+
+    /// The islands of interacting bodies built during the last step.
+    ///
+    /// Each island lists movable bodies interacting, directly or not, through contacts or joints.
+    /// Those may be processed as a single unit, e.g., a pile of crates, or independently from the
+    /// other islands. Sleeping islands and islands made of a single body are included. Like
+    /// `bridge_bodies`, this must be called before any rigid body is added or removed.
+    pub fn islands(&self) -> Vec<Vec<RigidBodyHandle<N>>> {
+        self.sleep.borrow().islands(&self.rigid_bodies)
+    }
+
+    /// Finds the bodies that, on their own, connect at least two parts of `min_part_size` bodies
+    /// or more of the same island.
+    ///
+    /// Such bodies prevent large islands from being split into smaller ones. This analyses the
+    /// islands of the last step, so it must be called before any rigid body is added or removed.
+    pub fn bridge_bodies(&self, min_part_size: usize) -> Vec<IslandBridge<N>> {
+        self.sleep.borrow().bridge_bodies(&self.rigid_bodies, min_part_size)
+    }
+
+    /// Adds a filter that tells if a potential collision pair should be ignored or not.
+    ///
+    /// The proximity filter returns `false` for a given pair of collision objects if they should
+    /// be ignored by the narrow phase. Keep in mind that modifying the proximity filter will have
+    /// a non-trivial overhead during the next update as it will force re-detection of all
+    /// collision pairs.
+    pub fn register_broad_phase_pair_filter<F>(&mut self, name: &str, filter: F)
         where F: BroadPhasePairFilter<Point<N>, Isometry<N>, WorldObject<N>> + 'static {
         self.cworld.register_broad_phase_pair_filter(name, filter)
     }
@@ -395,11 +2570,110 @@ impl<N: Real> World<N> {
         self.cworld.register_contact_handler(name, handler)
     }
 
+    /// Registers a handler receiving at most one contact start event per pair of objects every
+    /// `cooldown` units of simulated time.
+    ///
+    /// This avoids flooding the handler, e.g., to play collision sounds, with the many contacts
+    /// starting and stopping while bodies settle. A contact stop event is sent only for the pairs
+    /// which contact start event was sent.
+    pub fn register_contact_handler_with_cooldown<H>(&mut self, name: &str, handler: H, cooldown: N)
+        where H: ContactHandler<Point<N>, Isometry<N>, WorldObject<N>> + 'static {
+        let handler = ContactCooldown::new(handler, cooldown, self.time.clone());
+        self.cworld.register_contact_handler(name, handler)
+    }
+
     /// Unregisters a handler for contact start/stop events.
     pub fn unregister_contact_handler(&mut self, name: &str) {
         self.cworld.unregister_contact_handler(name)
     }
 
+    /// Registers a dispatcher providing the contact algorithms of pairs of shapes unsupported by
+    /// the default narrow phase, e.g., user-defined shapes.
+    ///
+    /// The dispatchers are queried in registration order before the default one, which is used
+    /// for the pairs they all return `None` for. A dispatcher replaces the one previously
+    /// registered with the same name. Only the pairs of objects starting to interact after the
+    /// registration are affected.
+    pub fn register_contact_dispatcher<D>(&mut self, name: &str, dispatcher: D)
+        where D: ContactDispatcher<Point<N>, Isometry<N>> + 'static {
+        self.contact_dispatchers.register(name, Box::new(dispatcher))
+    }
+
+    /// Unregisters a contact dispatcher.
+    pub fn unregister_contact_dispatcher(&mut self, name: &str) {
+        self.contact_dispatchers.unregister(name)
+    }
+
+    /// Registers a dispatcher providing the proximity algorithms of pairs of shapes unsupported by
+    /// the default narrow phase, e.g., between sensors and user-defined shapes.
+    ///
+    /// See `register_contact_dispatcher` for the dispatch rules.
+    pub fn register_proximity_dispatcher<D>(&mut self, name: &str, dispatcher: D)
+        where D: ProximityDispatcher<Point<N>, Isometry<N>> + 'static {
+        self.proximity_dispatchers.register(name, Box::new(dispatcher))
+    }
+
+    /// Unregisters a proximity dispatcher.
+    pub fn unregister_proximity_dispatcher(&mut self, name: &str) {
+        self.proximity_dispatchers.unregister(name)
+    }
+
+    /// Whether the contacts between each pair of bodies are reduced before being solved.
+    #[inline]
+    pub fn contact_manifold_reduction_enabled(&self) -> bool {
+        self.manifold_reduction
+    }
+
+    /// Reduces the contacts between each pair of bodies to at most four well-spread points
+    /// before they are solved.
+    ///
+    /// This is disabled by default. Feeding fewer contacts to the solver speeds it up and
+    /// stabilizes large stacks of flat shapes.
+    #[inline]
+    pub fn enable_contact_manifold_reduction(&mut self) {
+        self.manifold_reduction = true
+    }
+
+    /// Solves every contact generated by the narrow phase. This is the default.
+    #[inline]
+    pub fn disable_contact_manifold_reduction(&mut self) {
+        self.manifold_reduction = false
+    }
+
+    /// Registers a callback modifying the contacts between rigid bodies before they are solved.
+    ///
+    /// The modifiers are called in registration order on each contact, and may disable its
+    /// friction or restitution. A modifier replaces the one previously registered with the same
+    /// name.
+    pub fn register_contact_modifier<M>(&mut self, name: &str, modifier: M)
+        where M: ContactModifier<N> + 'static {
+        match self.modifiers.iter().position(|m| m.0 == name) {
+            Some(i) => self.modifiers[i].1 = Box::new(modifier),
+            None    => self.modifiers.push((name.to_string(), Box::new(modifier)))
+        }
+    }
+
+    /// Unregisters a contact modifier.
+    pub fn unregister_contact_modifier(&mut self, name: &str) {
+        self.modifiers.retain(|m| m.0 != name)
+    }
+
+    /// Registers a handler for the joints broken by a reaction exceeding their breaking forces.
+    ///
+    /// A handler replaces the one previously registered with the same name.
+    pub fn register_joint_break_handler<H>(&mut self, name: &str, handler: H)
+        where H: JointBreakHandler<N> + 'static {
+        match self.break_handlers.iter().position(|h| h.0 == name) {
+            Some(i) => self.break_handlers[i].1 = Box::new(handler),
+            None    => self.break_handlers.push((name.to_string(), Box::new(handler)))
+        }
+    }
+
+    /// Unregisters a handler for the broken joints.
+    pub fn unregister_joint_break_handler(&mut self, name: &str) {
+        self.break_handlers.retain(|h| h.0 != name)
+    }
+
     /// Registers a handler for proximity status change events.
     pub fn register_proximity_handler<H>(&mut self, name: &str, handler: H)
         where H: ProximityHandler<Point<N>, Isometry<N>, WorldObject<N>> + 'static {
@@ -410,6 +2684,182 @@ impl<N: Real> World<N> {
     pub fn unregister_proximity_handler(&mut self, name: &str) {
         self.cworld.unregister_proximity_handler(name);
     }
+
+    /// Whether the collision events are collected into the queue drained by
+    /// `drain_collision_events`. Disabled by default.
+    pub fn collision_event_queue_enabled(&self) -> bool {
+        self.events.borrow().enabled()
+    }
+
+    /// Starts collecting the contact, proximity, and activation events into a queue.
+    ///
+    /// This is an alternative to the contact and proximity handlers: the events are kept until
+    /// they are drained with `drain_collision_events`, typically after `step` returns, so that
+    /// they can be processed with no part of the physics world borrowed.
+    pub fn enable_collision_event_queue(&mut self) {
+        self.events.borrow_mut().set_enabled(true)
+    }
+
+    /// Stops collecting the collision events, discarding those not drained yet.
+    pub fn disable_collision_event_queue(&mut self) {
+        self.events.borrow_mut().set_enabled(false)
+    }
+
+    /// Removes from the queue all the events collected since the last call to this method, in
+    /// the order they occurred.
+    ///
+    /// The activation events only report the status changes of the dynamic bodies decided by the
+    /// world during `step`, not those requested by the user.
+    pub fn drain_collision_events(&mut self) -> Vec<CollisionEvent<N>> {
+        self.events.borrow_mut().drain()
+    }
+
+    /// Removes from the queue all the events collected since the last drain, stamped with the
+    /// index of the step they occurred at and a unique identifier increasing in the order they
+    /// occurred.
+    ///
+    /// This lets the applications which batch or defer the processing of the events, possibly
+    /// over several steps, order and deduplicate them.
+    pub fn drain_stamped_collision_events(&mut self) -> Vec<StampedCollisionEvent<N>> {
+        self.events.borrow_mut().drain_stamped()
+    }
+}
+
+// Orders the contacts by the ranks of their bodies in the order of addition to the world, the
+// first body of each contact being the one added first.
+//
+// The contacts are otherwise ordered following the pairs of the broad phase, which depend on the
+// addresses of the bodies: the order of resolution, and thus the simulation, would change from one
+// run to another. The flags of the contacts are reordered along with them.
+fn sort_contacts<N: Real>(contacts: &mut Vec<Constraint<N>>, flags: &mut Vec<ContactFlags>) {
+    for constraint in contacts.iter_mut() {
+        if let Constraint::RBRB(ref mut rb1, ref mut rb2, ref mut c) = *constraint {
+            if rb1.borrow().serial() > rb2.borrow().serial() {
+                mem::swap(rb1, rb2);
+                c.flip();
+            }
+        }
+    }
+
+    let mut sorted: Vec<_> = contacts.drain(..).zip(flags.drain(..)).collect();
+
+    // The sort is stable: the contacts of a pair stay in the order they were generated in.
+    sorted.sort_by_key(|&(ref constraint, _)| {
+        match *constraint {
+            Constraint::RBRB(ref rb1, ref rb2, _) => (rb1.borrow().serial(), rb2.borrow().serial()),
+            _                                     => (usize::MAX, usize::MAX)
+        }
+    });
+
+    for (constraint, f) in sorted.into_iter() {
+        contacts.push(constraint);
+        flags.push(f);
+    }
+}
+
+fn default_solver<N: Real>() -> AccumulatedImpulseSolver<N> {
+    AccumulatedImpulseSolver::new(
+        na::convert(0.1f64),
+        CorrectionMode::VelocityAndPosition(na::convert(0.2f64), na::convert(0.2f64), na::convert(0.08f64)),
+        na::convert(0.4f64),
+        na::convert(1.0f64),
+        10,
+        10)
+}
+
+// Integrates `rb` during `dt` with `integrator`, the forces applied on it being set by `forces`
+// and `generators`.
+fn integrate<N: Real>(integrator: &mut Integrator<N, RigidBody<N>>,
+                      forces:     &mut BodyForceGenerator<N>,
+                      generators: &mut [(String, Box<CloneableForceGenerator<N>>)],
+                      dt:         N,
+                      rb:         &mut RigidBody<N>) {
+    integrator.update_with_forces(dt, rb, &mut |dt, rb| {
+        forces.update(dt.clone(), rb);
+        generate_forces(&mut generators[..], dt, rb)
+    })
+}
+
+// Sets the sum of the forces applied by `generators` on `rb` during the next time step of length
+// `dt`.
+fn generate_forces<N: Real>(generators: &mut [(String, Box<CloneableForceGenerator<N>>)], dt: N, rb: &mut RigidBody<N>) {
+    let mut force  = na::zero();
+    let mut torque = na::zero();
+
+    for generator in generators.iter_mut() {
+        let (f, t) = generator.1.forces(dt, rb);
+
+        force  = force + f;
+        torque = torque + t;
+    }
+
+    rb.set_generated_forces(force, torque)
+}
+
+// Runs the contact modifiers on `c`, returning its flags.
+fn modify_contact<N: Real>(modifiers: &mut [(String, Box<ContactModifier<N>>)],
+                           rb1:       &RigidBody<N>,
+                           rb2:       &RigidBody<N>,
+                           c:         &mut Contact<Point<N>>)
+                           -> ContactFlags {
+    let mut flags = ContactFlags::new();
+
+    for &mut (_, ref mut modifier) in modifiers.iter_mut() {
+        modifier.modify_contact(rb1, rb2, c, &mut flags);
+    }
+
+    flags
+}
+
+// Removes the velocity of `rb` toward `static_rb` along the normal of their contact `c`, bouncing
+// and slowing it down with their restitution and friction, and pushes it out of `static_rb`.
+fn resolve_predicted_contact<N: Real>(rb: &mut RigidBody<N>, static_rb: &RigidBody<N>, c: &Contact<Point<N>>, dt: N) {
+    if c.depth > na::zero() {
+        let position = rb.position().translation.vector - c.normal * c.depth;
+        rb.set_translation(Translation::from_vector(position));
+    }
+
+    let lin_vel    = rb.lin_vel();
+    let normal_vel = na::dot(&lin_vel, &c.normal);
+
+    // Separating, or not reaching the static body before the next step.
+    if normal_vel <= na::zero() || normal_vel * dt < -c.depth {
+        return;
+    }
+
+    let restitution  = rb.restitution() * static_rb.restitution();
+    let normal_delta = normal_vel * (na::one::<N>() + restitution);
+    let tangent_vel  = lin_vel - c.normal * normal_vel;
+    let slip_speed   = na::norm(&tangent_vel);
+    let friction     = rb.friction_at(slip_speed) * static_rb.friction_at(slip_speed);
+
+    let tangent_vel = if slip_speed <= friction * normal_delta {
+        na::zero()
+    }
+    else {
+        tangent_vel * ((slip_speed - friction * normal_delta) / slip_speed)
+    };
+
+    rb.set_lin_vel(tangent_vel - c.normal * (normal_vel * restitution));
+}
+
+// Whether the time of impact and the distance of `shape` can be computed without a user-defined
+// algorithm.
+fn is_castable<N: Real>(shape: &Shape<Point<N>, Isometry<N>>) -> bool {
+    shape.as_support_map().is_some() || shape.as_composite_shape().is_some() || shape.is_shape::<Plane<Vector<N>>>()
+}
+
+fn is_finite_position<N: Real>(position: &Isometry<N>) -> bool {
+    position.to_homogeneous().iter().all(|x| *x == *x && x.abs() < N::max_value())
+}
+
+fn is_substepped<N: Real>(rb: &RigidBody<N>) -> bool {
+    rb.substepping_enabled() && rb.is_active()
+}
+
+// Whether a joint is attached to a substepped body. The contacts are not joints.
+fn is_joint_substepped<N: Real>(joint: &Constraint<N>) -> bool {
+    joint.joint_info().map_or(false, |j| j.bodies().any(|rb| is_substepped(&*rb.borrow())))
 }
 
 struct ObjectActivationOnContactHandler<N: Real> {
@@ -437,6 +2887,35 @@ for ObjectActivationOnContactHandler<N> {
     }
 }
 
+const JOINTED_BODIES_PAIR_FILTER: &'static str = "__nphysics_internal_JointedBodiesPairFilter";
+
+struct JointedBodiesPairFilter {
+    // The sorted pairs of bodies attached by a joint disabling their collisions.
+    pairs: Rc<RefCell<Vec<(usize, usize)>>>
+}
+
+impl<N: Real> BroadPhasePairFilter<Point<N>, Isometry<N>, WorldObject<N>>
+for JointedBodiesPairFilter {
+    #[inline]
+    fn is_pair_valid(&self, b1: &WorldCollisionObject<N>, b2: &WorldCollisionObject<N>) -> bool {
+        match (&b1.data, &b2.data) {
+            (&WorldObject::RigidBody(ref rb1), &WorldObject::RigidBody(ref rb2)) => {
+                let pairs = self.pairs.borrow();
+
+                if pairs.is_empty() {
+                    return true;
+                }
+
+                let uid1 = WorldObject::rigid_body_uid(rb1);
+                let uid2 = WorldObject::rigid_body_uid(rb2);
+
+                pairs.binary_search(&(uid1.min(uid2), uid1.max(uid2))).is_err()
+            },
+            _ => true
+        }
+    }
+}
+
 struct SensorsNotCollidingTheirParentPairFilter;
 
 impl<N: Real> BroadPhasePairFilter<Point<N>, Isometry<N>, WorldObject<N>>
@@ -0,0 +1,114 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use alga::general::Real;
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use object::{RigidBody, RigidBodyHandle, ActivationState, WorldObject};
+use math::{Vector, Orientation, Isometry};
+
+// The identifier of the next world save.
+static NEXT_SAVE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The saved state of a rigid body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BodySave<N: Real> {
+    /// The position of the rigid body.
+    pub position:   Isometry<N>,
+    /// The linear velocity of the rigid body.
+    pub lin_vel:    Vector<N>,
+    /// The angular velocity of the rigid body.
+    pub ang_vel:    Orientation<N>,
+    /// The activation state of the rigid body.
+    pub activation: ActivationState<N>
+}
+
+impl<N: Real> BodySave<N> {
+    /// Saves the state of `rb`.
+    pub fn new(rb: &RigidBody<N>) -> BodySave<N> {
+        BodySave {
+            position:   rb.position().clone(),
+            lin_vel:    rb.lin_vel(),
+            ang_vel:    rb.ang_vel(),
+            activation: rb.activation_state().clone()
+        }
+    }
+
+    /// Restores the state of `rb`.
+    pub fn restore(&self, rb: &mut RigidBody<N>) {
+        rb.set_transformation(self.position.clone());
+        rb.set_lin_vel_internal(self.lin_vel.clone());
+        rb.set_ang_vel_internal(self.ang_vel.clone());
+
+        match self.activation {
+            ActivationState::Active(energy) => rb.activate(energy),
+            _                               => rb.deactivate()
+        }
+    }
+}
+
+/// The saved states of the movable rigid bodies of a world, as returned by `World::save` and
+/// `World::save_incremental`.
+///
+/// A full save contains every movable rigid body. An incremental save only contains the rigid
+/// bodies which state may differ from a previous full save, its baseline: the bodies of the awake
+/// islands, and the sleeping bodies which moved since the baseline. In a large world mostly
+/// asleep, incremental saves are much smaller than full ones. The saved bodies are referenced by
+/// their handles, so that the application can map them to its own identifiers when writing the
+/// save to a file.
+pub struct WorldSave<N: Real> {
+    id:       usize,
+    baseline: Option<usize>,
+    bodies:   Vec<(RigidBodyHandle<N>, BodySave<N>)>,
+    indices:  HashMap<usize, usize, UintTWHash>
+}
+
+impl<N: Real> WorldSave<N> {
+    #[doc(hidden)]
+    pub fn new(baseline: Option<&WorldSave<N>>) -> WorldSave<N> {
+        if let Some(baseline) = baseline {
+            assert!(baseline.baseline.is_none(), "The baseline of an incremental save must be a full save.");
+        }
+
+        WorldSave {
+            id:       NEXT_SAVE_ID.fetch_add(1, Ordering::Relaxed),
+            baseline: baseline.map(|b| b.id),
+            bodies:   Vec::new(),
+            indices:  HashMap::new(UintTWHash::new())
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn push(&mut self, rb: &RigidBodyHandle<N>, save: BodySave<N>) {
+        let _ = self.indices.insert(WorldObject::rigid_body_uid(rb), self.bodies.len());
+        self.bodies.push((rb.clone(), save))
+    }
+
+    /// The unique identifier of this save.
+    #[inline]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The identifier of the full save this incremental save is based on, `None` for full saves.
+    #[inline]
+    pub fn baseline(&self) -> Option<usize> {
+        self.baseline
+    }
+
+    /// Whether this save only contains the rigid bodies which may differ from its baseline.
+    #[inline]
+    pub fn is_incremental(&self) -> bool {
+        self.baseline.is_some()
+    }
+
+    /// The saved rigid bodies and their states.
+    #[inline]
+    pub fn bodies(&self) -> &[(RigidBodyHandle<N>, BodySave<N>)] {
+        &self.bodies[..]
+    }
+
+    /// The saved state of `rb`, if it is part of this save.
+    #[inline]
+    pub fn body(&self, rb: &RigidBodyHandle<N>) -> Option<&BodySave<N>> {
+        self.indices.find(&WorldObject::rigid_body_uid(rb)).map(|i| &self.bodies[*i].1)
+    }
+}